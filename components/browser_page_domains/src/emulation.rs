@@ -9,9 +9,31 @@ use protocol_handler::DomainHandler;
 use serde_json::{json, Value};
 use std::sync::Arc;
 
+/// Maximum supported number of emulated touch points
+pub const MAX_TOUCH_POINTS: u32 = 16;
+
+/// Emulated idle state reported to the Idle Detection API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdleState {
+    /// Whether the user is interacting with the device
+    pub is_user_active: bool,
+    /// Whether the screen is unlocked
+    pub is_screen_unlocked: bool,
+}
+
+impl Default for IdleState {
+    fn default() -> Self {
+        Self {
+            is_user_active: true,
+            is_screen_unlocked: true,
+        }
+    }
+}
+
 /// Emulation domain handler
 ///
-/// Provides methods for device metrics emulation, user agent override, and geolocation override.
+/// Provides methods for device metrics emulation, user agent override, geolocation override,
+/// touch emulation, and idle state override.
 #[derive(Debug, Clone)]
 pub struct EmulationDomain {
     state: Arc<RwLock<EmulationState>>,
@@ -22,6 +44,12 @@ struct EmulationState {
     device_metrics: Option<DeviceMetrics>,
     user_agent: Option<String>,
     geolocation: Option<Geolocation>,
+    /// Max touch points while touch emulation is enabled
+    touch_points: Option<u32>,
+    /// Configuration ("mobile" or "desktop") while mouse events emit touch events
+    touch_events_for_mouse: Option<String>,
+    /// Idle state override, if set
+    idle_override: Option<IdleState>,
 }
 
 #[derive(Debug, Clone)]
@@ -136,6 +164,101 @@ impl EmulationDomain {
         self.state.write().geolocation = None;
         Ok(json!({}))
     }
+
+    /// Enable or disable touch emulation
+    fn set_touch_emulation_enabled(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+
+        let enabled = params["enabled"]
+            .as_bool()
+            .ok_or_else(|| CdpError::invalid_params("Missing 'enabled' parameter"))?;
+
+        if !enabled {
+            self.state.write().touch_points = None;
+            return Ok(json!({}));
+        }
+
+        let max_touch_points = params["maxTouchPoints"].as_u64().unwrap_or(1);
+        if !(1..=MAX_TOUCH_POINTS as u64).contains(&max_touch_points) {
+            return Err(CdpError::invalid_params(format!(
+                "'maxTouchPoints' must be between 1 and {}",
+                MAX_TOUCH_POINTS
+            )));
+        }
+
+        self.state.write().touch_points = Some(max_touch_points as u32);
+        Ok(json!({}))
+    }
+
+    /// Enable or disable emitting touch events for mouse input
+    fn set_emit_touch_events_for_mouse(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+
+        let enabled = params["enabled"]
+            .as_bool()
+            .ok_or_else(|| CdpError::invalid_params("Missing 'enabled' parameter"))?;
+
+        if !enabled {
+            self.state.write().touch_events_for_mouse = None;
+            return Ok(json!({}));
+        }
+
+        let configuration = params["configuration"].as_str().unwrap_or("mobile");
+        if configuration != "mobile" && configuration != "desktop" {
+            return Err(CdpError::invalid_params(
+                "'configuration' must be 'mobile' or 'desktop'",
+            ));
+        }
+
+        self.state.write().touch_events_for_mouse = Some(configuration.to_string());
+        Ok(json!({}))
+    }
+
+    /// Override the idle state reported to the Idle Detection API
+    fn set_idle_override(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+
+        let is_user_active = params["isUserActive"]
+            .as_bool()
+            .ok_or_else(|| CdpError::invalid_params("Missing 'isUserActive' parameter"))?;
+
+        let is_screen_unlocked = params["isScreenUnlocked"]
+            .as_bool()
+            .ok_or_else(|| CdpError::invalid_params("Missing 'isScreenUnlocked' parameter"))?;
+
+        self.state.write().idle_override = Some(IdleState {
+            is_user_active,
+            is_screen_unlocked,
+        });
+
+        Ok(json!({}))
+    }
+
+    /// Clear idle state override
+    fn clear_idle_override(&self) -> Result<Value, CdpError> {
+        self.state.write().idle_override = None;
+        Ok(json!({}))
+    }
+
+    /// Whether touch emulation is enabled
+    pub fn is_touch_emulation_enabled(&self) -> bool {
+        self.state.read().touch_points.is_some()
+    }
+
+    /// Max touch points reported while touch emulation is enabled
+    pub fn max_touch_points(&self) -> Option<u32> {
+        self.state.read().touch_points
+    }
+
+    /// Configuration used when mouse input emits touch events, if enabled
+    pub fn touch_events_for_mouse(&self) -> Option<String> {
+        self.state.read().touch_events_for_mouse.clone()
+    }
+
+    /// Current idle state: the override if set, otherwise active and unlocked
+    pub fn idle_state(&self) -> IdleState {
+        self.state.read().idle_override.unwrap_or_default()
+    }
 }
 
 impl Default for EmulationDomain {
@@ -157,6 +280,10 @@ impl DomainHandler for EmulationDomain {
             "setUserAgentOverride" => self.set_user_agent_override(params),
             "setGeolocationOverride" => self.set_geolocation_override(params),
             "clearGeolocationOverride" => self.clear_geolocation_override(),
+            "setTouchEmulationEnabled" => self.set_touch_emulation_enabled(params),
+            "setEmitTouchEventsForMouse" => self.set_emit_touch_events_for_mouse(params),
+            "setIdleOverride" => self.set_idle_override(params),
+            "clearIdleOverride" => self.clear_idle_override(),
             _ => Err(CdpError::method_not_found(format!("Emulation.{}", method))),
         }
     }
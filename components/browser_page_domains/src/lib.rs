@@ -7,7 +7,7 @@
 //! - **BrowserDomain**: Browser information and control
 //! - **PageDomain**: Page navigation and screenshot capture
 //! - **SecurityDomain**: Security state tracking and certificate handling
-//! - **EmulationDomain**: Device emulation (viewport, user agent, etc.)
+//! - **EmulationDomain**: Device emulation (viewport, user agent, touch, etc.)
 
 mod browser;
 mod emulation;
@@ -15,8 +15,8 @@ mod page;
 mod security;
 
 pub use browser::BrowserDomain;
-pub use emulation::EmulationDomain;
-pub use page::PageDomain;
+pub use emulation::{EmulationDomain, IdleState, MAX_TOUCH_POINTS};
+pub use page::{NavigationCallback, PageDomain};
 pub use security::{
     CertificateDetails, CertificateError, CertificateErrorAction, CertificateSecurityState,
     InsecureContentStatus, MixedContentType, SafeBrowsingState, SecurityDomain, SecurityState,
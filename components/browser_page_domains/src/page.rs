@@ -7,14 +7,27 @@ use cdp_types::CdpError;
 use parking_lot::RwLock;
 use protocol_handler::DomainHandler;
 use serde_json::{json, Value};
+use std::fmt;
 use std::sync::Arc;
 
+/// Callback run after `navigate` replaces the page's document
+pub type NavigationCallback = Arc<dyn Fn() + Send + Sync>;
+
 /// Page domain handler
 ///
 /// Provides methods for page navigation, reloading, and screenshot capture.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct PageDomain {
     state: Arc<RwLock<PageState>>,
+    navigation_callback: Arc<RwLock<Option<NavigationCallback>>>,
+}
+
+impl fmt::Debug for PageDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PageDomain")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug)]
@@ -40,9 +53,18 @@ impl PageDomain {
                 current_url: None,
                 frame_id: "main_frame".to_string(),
             })),
+            navigation_callback: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Register the callback run after each navigation
+    ///
+    /// Used to release state tied to the old document, such as the remote
+    /// objects of its execution context.
+    pub fn set_navigation_callback(&self, callback: NavigationCallback) {
+        *self.navigation_callback.write() = Some(callback);
+    }
+
     /// Check if the domain is enabled
     pub fn is_enabled(&self) -> bool {
         self.state.read().enabled
@@ -71,6 +93,11 @@ impl PageDomain {
         // Update state
         self.state.write().current_url = Some(url.to_string());
 
+        let callback = self.navigation_callback.read().clone();
+        if let Some(callback) = callback {
+            callback();
+        }
+
         Ok(json!({
             "frameId": self.state.read().frame_id,
             "loaderId": "loader_1"
@@ -171,6 +198,26 @@ mod tests {
         assert!(value["frameId"].is_string());
     }
 
+    #[tokio::test]
+    async fn test_navigate_runs_navigation_callback() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let domain = PageDomain::new();
+        let navigations = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&navigations);
+        domain.set_navigation_callback(Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        let params = json!({ "url": "https://example.com" });
+        domain
+            .handle_method("navigate", Some(params))
+            .await
+            .unwrap();
+        assert!(domain.handle_method("navigate", None).await.is_err());
+        assert_eq!(navigations.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_get_frame_tree() {
         let domain = PageDomain::new();
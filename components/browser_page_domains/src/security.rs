@@ -53,6 +53,7 @@ impl SecurityState {
     }
 
     /// Creates a SecurityState from a string
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "secure" => SecurityState::Secure,
@@ -689,12 +690,11 @@ impl SecurityDomain {
             || state.insecure_content.ran_content_with_cert_errors
         {
             state.security_state = SecurityState::Insecure;
-        } else if state.insecure_content.displayed_insecure_content
-            || state.insecure_content.displayed_content_with_cert_errors
+        } else if (state.insecure_content.displayed_insecure_content
+            || state.insecure_content.displayed_content_with_cert_errors)
+            && state.security_state == SecurityState::Secure
         {
-            if state.security_state == SecurityState::Secure {
-                state.security_state = SecurityState::Neutral;
-            }
+            state.security_state = SecurityState::Neutral;
         }
 
         drop(state);
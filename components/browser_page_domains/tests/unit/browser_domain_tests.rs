@@ -2,7 +2,6 @@
 
 use browser_page_domains::BrowserDomain;
 use protocol_handler::DomainHandler;
-use serde_json::json;
 
 #[tokio::test]
 async fn test_browser_domain_name() {
@@ -1,6 +1,6 @@
 // Unit tests for EmulationDomain
 
-use browser_page_domains::EmulationDomain;
+use browser_page_domains::{EmulationDomain, IdleState};
 use protocol_handler::DomainHandler;
 use serde_json::json;
 
@@ -132,3 +132,127 @@ async fn test_unknown_method() {
 
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_touch_emulation_enable_and_disable() {
+    let domain = EmulationDomain::new();
+    assert!(!domain.is_touch_emulation_enabled());
+
+    let params = json!({"enabled": true, "maxTouchPoints": 5});
+    domain
+        .handle_method("setTouchEmulationEnabled", Some(params))
+        .await
+        .unwrap();
+    assert!(domain.is_touch_emulation_enabled());
+    assert_eq!(domain.max_touch_points(), Some(5));
+
+    let params = json!({"enabled": false});
+    domain
+        .handle_method("setTouchEmulationEnabled", Some(params))
+        .await
+        .unwrap();
+    assert!(!domain.is_touch_emulation_enabled());
+    assert_eq!(domain.max_touch_points(), None);
+}
+
+#[tokio::test]
+async fn test_touch_emulation_rejects_out_of_range_points() {
+    let domain = EmulationDomain::new();
+
+    for points in [0, 20] {
+        let params = json!({"enabled": true, "maxTouchPoints": points});
+        let result = domain
+            .handle_method("setTouchEmulationEnabled", Some(params))
+            .await;
+        assert_eq!(result.unwrap_err().code, -32602);
+    }
+    assert!(!domain.is_touch_emulation_enabled());
+}
+
+#[tokio::test]
+async fn test_set_emit_touch_events_for_mouse() {
+    let domain = EmulationDomain::new();
+
+    let params = json!({"enabled": true, "configuration": "desktop"});
+    domain
+        .handle_method("setEmitTouchEventsForMouse", Some(params))
+        .await
+        .unwrap();
+    assert_eq!(domain.touch_events_for_mouse().as_deref(), Some("desktop"));
+
+    let params = json!({"enabled": true, "configuration": "tablet"});
+    assert!(domain
+        .handle_method("setEmitTouchEventsForMouse", Some(params))
+        .await
+        .is_err());
+
+    let params = json!({"enabled": false});
+    domain
+        .handle_method("setEmitTouchEventsForMouse", Some(params))
+        .await
+        .unwrap();
+    assert_eq!(domain.touch_events_for_mouse(), None);
+}
+
+#[tokio::test]
+async fn test_set_idle_override() {
+    let domain = EmulationDomain::new();
+    assert_eq!(domain.idle_state(), IdleState::default());
+
+    let params = json!({"isUserActive": false, "isScreenUnlocked": false});
+    domain
+        .handle_method("setIdleOverride", Some(params))
+        .await
+        .unwrap();
+    assert_eq!(
+        domain.idle_state(),
+        IdleState {
+            is_user_active: false,
+            is_screen_unlocked: false,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_idle_override_intermediate_states_and_clear() {
+    let domain = EmulationDomain::new();
+
+    let params = json!({"isUserActive": false, "isScreenUnlocked": true});
+    domain
+        .handle_method("setIdleOverride", Some(params))
+        .await
+        .unwrap();
+    let state = domain.idle_state();
+    assert!(!state.is_user_active);
+    assert!(state.is_screen_unlocked);
+
+    let params = json!({"isUserActive": true, "isScreenUnlocked": false});
+    domain
+        .handle_method("setIdleOverride", Some(params))
+        .await
+        .unwrap();
+    let state = domain.idle_state();
+    assert!(state.is_user_active);
+    assert!(!state.is_screen_unlocked);
+
+    domain
+        .handle_method("clearIdleOverride", None)
+        .await
+        .unwrap();
+    assert_eq!(
+        domain.idle_state(),
+        IdleState {
+            is_user_active: true,
+            is_screen_unlocked: true,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_set_idle_override_requires_both_flags() {
+    let domain = EmulationDomain::new();
+    let params = json!({"isUserActive": false});
+    let result = domain.handle_method("setIdleOverride", Some(params)).await;
+    assert_eq!(result.unwrap_err().code, -32602);
+    assert_eq!(domain.idle_state(), IdleState::default());
+}
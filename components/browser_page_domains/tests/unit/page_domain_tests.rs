@@ -21,7 +21,7 @@ async fn test_page_enable() {
 
 #[tokio::test]
 async fn test_page_disable() {
-    let mut domain = PageDomain::new();
+    let domain = PageDomain::new();
     domain.handle_method("enable", None).await.unwrap();
 
     let result = domain.handle_method("disable", None).await;
@@ -1,6 +1,10 @@
 //! Server configuration
 
 use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use crate::error::{CdpServerError, Result};
 
 /// Configuration for the CDP WebSocket server
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,7 +19,18 @@ pub struct ServerConfig {
     pub allowed_origins: Vec<String>,
 
     /// Bind address (default 127.0.0.1 for localhost only)
+    ///
+    /// IPv6 addresses may be bracketed or bare, e.g. `[::1]` or `::`.
     pub bind_address: String,
+
+    /// Time allowed for a client to complete the WebSocket upgrade, in
+    /// milliseconds (default 10s)
+    #[serde(default = "default_handshake_timeout_ms")]
+    pub handshake_timeout_ms: u64,
+}
+
+fn default_handshake_timeout_ms() -> u64 {
+    10_000
 }
 
 impl Default for ServerConfig {
@@ -25,6 +40,7 @@ impl Default for ServerConfig {
             max_message_size: 100 * 1024 * 1024, // 100MB
             allowed_origins: vec!["http://localhost:*".to_string()],
             bind_address: "127.0.0.1".to_string(),
+            handshake_timeout_ms: default_handshake_timeout_ms(),
         }
     }
 }
@@ -55,6 +71,34 @@ impl ServerConfig {
         self.bind_address = address;
         self
     }
+
+    /// Set the WebSocket upgrade timeout
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+
+    /// Time allowed for a client to complete the WebSocket upgrade
+    pub fn handshake_timeout(&self) -> Duration {
+        Duration::from_millis(self.handshake_timeout_ms)
+    }
+
+    /// Socket address to listen on, built from `bind_address` and `port`
+    pub fn socket_addr(&self) -> Result<SocketAddr> {
+        let host = self.bind_address.trim();
+        let host = host
+            .strip_prefix('[')
+            .and_then(|h| h.strip_suffix(']'))
+            .unwrap_or(host);
+        let ip: IpAddr = host.parse().map_err(|e| {
+            CdpServerError::Other(anyhow::anyhow!(
+                "Invalid address {}: {}",
+                self.bind_address,
+                e
+            ))
+        })?;
+        Ok(SocketAddr::new(ip, self.port))
+    }
 }
 
 #[cfg(test)]
@@ -67,6 +111,7 @@ mod tests {
         assert_eq!(config.port, 9222);
         assert_eq!(config.max_message_size, 100 * 1024 * 1024);
         assert_eq!(config.bind_address, "127.0.0.1");
+        assert_eq!(config.handshake_timeout(), Duration::from_secs(10));
     }
 
     #[test]
@@ -74,11 +119,36 @@ mod tests {
         let config = ServerConfig::new(8080)
             .with_max_message_size(1024)
             .with_allowed_origins(vec!["https://example.com".to_string()])
-            .with_bind_address("0.0.0.0".to_string());
+            .with_bind_address("0.0.0.0".to_string())
+            .with_handshake_timeout(Duration::from_millis(250));
 
         assert_eq!(config.port, 8080);
         assert_eq!(config.max_message_size, 1024);
         assert_eq!(config.allowed_origins[0], "https://example.com");
         assert_eq!(config.bind_address, "0.0.0.0");
+        assert_eq!(config.handshake_timeout_ms, 250);
+    }
+
+    #[test]
+    fn test_socket_addr_ipv6() {
+        for address in ["[::1]", "::1"] {
+            let addr = ServerConfig::new(9222)
+                .with_bind_address(address.to_string())
+                .socket_addr()
+                .unwrap();
+            assert_eq!(addr.to_string(), "[::1]:9222");
+        }
+
+        let addr = ServerConfig::new(0)
+            .with_bind_address("[::]".to_string())
+            .socket_addr()
+            .unwrap();
+        assert!(addr.ip().is_unspecified());
+        assert!(addr.is_ipv6());
+
+        let result = ServerConfig::new(0)
+            .with_bind_address("[::1".to_string())
+            .socket_addr();
+        assert!(result.is_err());
     }
 }
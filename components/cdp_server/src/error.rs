@@ -1,5 +1,6 @@
 //! Error types for CDP server
 
+use cdp_types::CdpError;
 use thiserror::Error;
 
 /// Errors that can occur in the CDP server
@@ -46,5 +47,15 @@ pub enum CdpServerError {
     Other(#[from] anyhow::Error),
 }
 
+impl CdpServerError {
+    /// CDP error reported to the client for this failure
+    pub fn to_cdp_error(&self) -> CdpError {
+        match self {
+            Self::MessageTooLarge(_, limit) => CdpError::message_too_large(*limit),
+            other => CdpError::internal_error(other.to_string()),
+        }
+    }
+}
+
 /// Result type for CDP server operations
 pub type Result<T> = std::result::Result<T, CdpServerError>;
@@ -29,7 +29,7 @@ pub mod validation;
 pub use config::ServerConfig;
 pub use error::{CdpServerError, Result};
 pub use server::CdpWebSocketServer;
-pub use session::{Session, SessionId, SessionState};
+pub use session::{Session, SessionId, SessionMetadata, SessionState};
 pub use transport::{
     parse_cdp_message, serialize_cdp_message, validate_message_size, validate_origin,
 };
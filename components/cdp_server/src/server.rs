@@ -2,16 +2,19 @@
 
 use dashmap::DashMap;
 use futures::{SinkExt, StreamExt};
-use std::net::SocketAddr;
+use socket2::SockRef;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::net::{TcpListener, TcpStream};
+use tokio::net::{TcpListener, TcpSocket, TcpStream};
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
 use tokio_tungstenite::tungstenite::{handshake::server::Request, Message};
 use tokio_tungstenite::{accept_hdr_async, WebSocketStream};
 use tracing::{debug, error, info, warn};
 
 use crate::config::ServerConfig;
-use crate::error::{CdpServerError, Result};
-use crate::session::{Session, SessionId, SessionState};
+use crate::error::Result;
+use crate::session::{Session, SessionId, SessionMetadata, SessionState};
 use crate::transport::{
     parse_cdp_message, serialize_cdp_message, validate_message_size, validate_origin,
 };
@@ -23,6 +26,9 @@ pub struct CdpWebSocketServer {
 
     /// Active sessions
     sessions: Arc<DashMap<SessionId, Arc<parking_lot::RwLock<Session>>>>,
+
+    /// Connections dropped for not completing the upgrade in time
+    handshake_timeouts: Arc<AtomicU64>,
 }
 
 impl CdpWebSocketServer {
@@ -31,32 +37,72 @@ impl CdpWebSocketServer {
         Ok(Self {
             config,
             sessions: Arc::new(DashMap::new()),
+            handshake_timeouts: Arc::new(AtomicU64::new(0)),
         })
     }
 
+    /// Get the server configuration
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
     /// Get reference to sessions map
     pub fn get_sessions(&self) -> Arc<DashMap<SessionId, Arc<parking_lot::RwLock<Session>>>> {
         Arc::clone(&self.sessions)
     }
 
+    /// Number of connections dropped because the upgrade handshake timed out
+    pub fn handshake_timeouts(&self) -> u64 {
+        self.handshake_timeouts.load(Ordering::Relaxed)
+    }
+
     /// Start the WebSocket server
     pub async fn start(&self) -> Result<()> {
-        let addr: SocketAddr = format!("{}:{}", self.config.bind_address, self.config.port)
-            .parse()
-            .map_err(|e| CdpServerError::Other(anyhow::anyhow!("Invalid address: {}", e)))?;
+        let listener = self.bind().await?;
+        self.serve(listener).await
+    }
 
-        let listener = TcpListener::bind(&addr).await?;
-        info!("CDP WebSocket server listening on {}", addr);
+    /// Bind the configured address without accepting connections yet
+    ///
+    /// Accepts IPv4, IPv6 and dual-stack (`[::]`) addresses. `IPV6_V6ONLY` is
+    /// set explicitly rather than left to the platform default: off for `[::]`,
+    /// on for any other IPv6 address. With port 0 the OS picks a port,
+    /// available from the listener's `local_addr`.
+    pub async fn bind(&self) -> Result<TcpListener> {
+        let addr = self.config.socket_addr()?;
+        let socket = if addr.is_ipv6() {
+            let socket = TcpSocket::new_v6()?;
+            SockRef::from(&socket).set_only_v6(!addr.ip().is_unspecified())?;
+            socket
+        } else {
+            TcpSocket::new_v4()?
+        };
+        #[cfg(not(windows))]
+        socket.set_reuseaddr(true)?;
+        socket.bind(addr)?;
+        let listener = socket.listen(1024)?;
+        info!(
+            "CDP WebSocket server listening on {}",
+            listener.local_addr()?
+        );
+        Ok(listener)
+    }
 
+    /// Accept connections on a listener returned by `bind`
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
         loop {
             match listener.accept().await {
                 Ok((stream, peer_addr)) => {
                     debug!("New connection from {}", peer_addr);
                     let sessions = Arc::clone(&self.sessions);
                     let config = self.config.clone();
+                    let handshake_timeouts = Arc::clone(&self.handshake_timeouts);
 
                     tokio::spawn(async move {
-                        if let Err(e) = Self::handle_connection(stream, sessions, config).await {
+                        let result =
+                            Self::handle_connection(stream, sessions, config, handshake_timeouts)
+                                .await;
+                        if let Err(e) = result {
                             error!("Connection error: {}", e);
                         }
                     });
@@ -69,14 +115,19 @@ impl CdpWebSocketServer {
     }
 
     /// Handle a single WebSocket connection
+    // The handshake callback's error type is fixed by tungstenite.
+    #[allow(clippy::result_large_err)]
     async fn handle_connection(
         stream: TcpStream,
         sessions: Arc<DashMap<SessionId, Arc<parking_lot::RwLock<Session>>>>,
         config: ServerConfig,
+        handshake_timeouts: Arc<AtomicU64>,
     ) -> Result<()> {
         // Accept WebSocket connection with header validation
         let allowed_origins = config.allowed_origins.clone();
-        let callback = move |req: &Request, response: http::Response<()>| {
+        let captured = Arc::new(parking_lot::Mutex::new(SessionMetadata::default()));
+        let handshake_metadata = Arc::clone(&captured);
+        let callback = move |req: &Request, mut response: http::Response<()>| {
             // Validate Origin header
             if let Some(origin) = req.headers().get("Origin") {
                 if let Ok(origin_str) = origin.to_str() {
@@ -96,18 +147,68 @@ impl CdpWebSocketServer {
                 }
             }
 
+            let header = |name: &str| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+            // Accept the first subprotocol the client offers
+            let subprotocol = header("Sec-WebSocket-Protocol").and_then(|offered| {
+                offered
+                    .split(',')
+                    .map(str::trim)
+                    .find(|protocol| !protocol.is_empty())
+                    .map(str::to_string)
+            });
+            if let Some(protocol) = &subprotocol {
+                if let Ok(value) = http::HeaderValue::from_str(protocol) {
+                    response
+                        .headers_mut()
+                        .insert("Sec-WebSocket-Protocol", value);
+                }
+            }
+            *handshake_metadata.lock() = SessionMetadata {
+                user_agent: header("User-Agent"),
+                subprotocol,
+                connected_at: std::time::SystemTime::now(),
+            };
+
             Ok(response)
         };
 
-        let ws_stream = accept_hdr_async(stream, callback).await.map_err(Box::new)?;
+        let handshake = accept_hdr_async(stream, callback);
+        let ws_stream = match tokio::time::timeout(config.handshake_timeout(), handshake).await {
+            Ok(result) => result.map_err(Box::new)?,
+            Err(_) => {
+                handshake_timeouts.fetch_add(1, Ordering::Relaxed);
+                warn!(
+                    "Dropped connection: upgrade not completed within {:?}",
+                    config.handshake_timeout()
+                );
+                return Ok(());
+            }
+        };
         debug!("WebSocket connection established");
 
         // Create session
         let session_id = SessionId::new();
-        let session = Arc::new(parking_lot::RwLock::new(Session::new(session_id)));
+        let metadata = captured.lock().clone();
+        let session = Arc::new(parking_lot::RwLock::new(Session::with_metadata(
+            session_id, metadata,
+        )));
         sessions.insert(session_id, Arc::clone(&session));
 
-        info!("Session created: {}", session_id);
+        info!(
+            "Session created: {} (user agent: {})",
+            session_id,
+            session
+                .read()
+                .metadata()
+                .user_agent
+                .as_deref()
+                .unwrap_or("unknown")
+        );
 
         // Handle messages
         if let Err(e) = Self::handle_messages(ws_stream, Arc::clone(&session), &config).await {
@@ -139,7 +240,11 @@ impl CdpWebSocketServer {
                             // Validate message size
                             if let Err(e) = validate_message_size(&text, config.max_message_size) {
                                 error!("Message too large: {}", e);
-                                write.send(Message::Close(None))
+                                let frame = CloseFrame {
+                                    code: CloseCode::Size,
+                                    reason: e.to_cdp_error().message.into(),
+                                };
+                                write.send(Message::Close(Some(frame)))
                                     .await
                                     .map_err(Box::new)?;
                                 break;
@@ -50,6 +50,32 @@ pub enum SessionState {
     Closed,
 }
 
+/// Details about the client captured during the WebSocket handshake
+///
+/// Useful for telling connected tools apart (Chrome, Puppeteer, VS Code)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMetadata {
+    /// Value of the client's `User-Agent` header
+    pub user_agent: Option<String>,
+
+    /// WebSocket subprotocol selected for the connection
+    pub subprotocol: Option<String>,
+
+    /// When the connection was established
+    pub connected_at: SystemTime,
+}
+
+impl Default for SessionMetadata {
+    fn default() -> Self {
+        Self {
+            user_agent: None,
+            subprotocol: None,
+            connected_at: SystemTime::now(),
+        }
+    }
+}
+
 /// A CDP session representing a connected client
 pub struct Session {
     /// Unique session ID
@@ -61,6 +87,9 @@ pub struct Session {
     /// Creation timestamp
     created_at: SystemTime,
 
+    /// Client details captured at connection time
+    metadata: SessionMetadata,
+
     /// Message queue for outgoing messages
     message_tx: mpsc::UnboundedSender<String>,
 
@@ -71,12 +100,18 @@ pub struct Session {
 impl Session {
     /// Create a new session
     pub fn new(id: SessionId) -> Self {
+        Self::with_metadata(id, SessionMetadata::default())
+    }
+
+    /// Create a new session for a client described by `metadata`
+    pub fn with_metadata(id: SessionId, metadata: SessionMetadata) -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
 
         Self {
             id,
             state: Arc::new(RwLock::new(SessionState::Active)),
             created_at: SystemTime::now(),
+            metadata,
             message_tx: tx,
             message_rx: Arc::new(RwLock::new(Some(rx))),
         }
@@ -97,6 +132,11 @@ impl Session {
         self.created_at
     }
 
+    /// Get the client details captured at connection time
+    pub fn metadata(&self) -> &SessionMetadata {
+        &self.metadata
+    }
+
     /// Pause the session
     pub fn pause(&mut self) {
         let mut state = self.state.write();
@@ -191,6 +231,20 @@ mod tests {
         assert_eq!(session.state(), SessionState::Active);
     }
 
+    #[test]
+    fn test_session_with_metadata() {
+        let metadata = SessionMetadata {
+            user_agent: Some("Puppeteer".to_string()),
+            ..Default::default()
+        };
+        let session = Session::with_metadata(SessionId::new(), metadata.clone());
+        assert_eq!(session.metadata(), &metadata);
+        assert!(Session::new(SessionId::new())
+            .metadata()
+            .user_agent
+            .is_none());
+    }
+
     #[test]
     fn test_session_state_transitions() {
         let mut session = Session::new(SessionId::new());
@@ -1,6 +1,7 @@
 //! Message transport layer for CDP protocol
 
 use crate::error::{CdpServerError, Result};
+use crate::validation::normalize_ipv6_origin;
 use cdp_types::CdpMessage;
 
 /// Parse a CDP message from JSON string
@@ -25,8 +26,12 @@ pub fn validate_message_size(message: &str, max_size: usize) -> Result<()> {
 }
 
 /// Validate origin header against allowed origins
+///
+/// Bracketed IPv6 hosts are compared in canonical form on both sides.
 pub fn validate_origin(origin: &str, allowed_origins: &[String]) -> bool {
+    let origin = normalize_ipv6_origin(origin);
     allowed_origins.iter().any(|allowed| {
+        let allowed = normalize_ipv6_origin(allowed);
         if allowed.ends_with('*') {
             // Wildcard match
             let prefix = &allowed[..allowed.len() - 1];
@@ -77,7 +82,11 @@ mod tests {
     #[test]
     fn test_validate_size_too_large() {
         let msg = "a".repeat(2000);
-        assert!(validate_message_size(&msg, 1024).is_err());
+        let err = validate_message_size(&msg, 1024).unwrap_err();
+
+        let cdp_error = err.to_cdp_error();
+        assert_eq!(cdp_error.code, cdp_types::errors::MESSAGE_TOO_LARGE_CODE);
+        assert!(cdp_error.message.contains("1024"));
     }
 
     #[test]
@@ -87,6 +96,14 @@ mod tests {
         assert!(!validate_origin("http://localhost:4000", &origins));
     }
 
+    #[test]
+    fn test_validate_origin_ipv6() {
+        let origins = vec!["http://[::1]:9222".to_string(), "http://[::]:*".to_string()];
+        assert!(validate_origin("http://[0::1]:9222", &origins));
+        assert!(validate_origin("http://[0:0:0:0:0:0:0:0]:3000", &origins));
+        assert!(!validate_origin("http://[::1]:3000", &origins));
+    }
+
     #[test]
     fn test_validate_origin_wildcard() {
         let origins = vec!["http://localhost:*".to_string()];
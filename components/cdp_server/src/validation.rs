@@ -4,7 +4,7 @@
 //! including origin validation and security checks.
 
 use crate::error::{CdpServerError, Result};
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv6Addr};
 
 /// Origin validation configuration
 #[derive(Debug, Clone)]
@@ -94,12 +94,10 @@ impl OriginValidator {
         }
 
         // Check if it's an IP address
-        if parsed.is_ip_address {
-            if !self.config.allow_ip_addresses {
-                return Err(CdpServerError::InvalidOrigin(
-                    "IP address origins are not allowed".to_string(),
-                ));
-            }
+        if parsed.is_ip_address && !self.config.allow_ip_addresses {
+            return Err(CdpServerError::InvalidOrigin(
+                "IP address origins are not allowed".to_string(),
+            ));
         }
 
         // Check HTTPS requirement for non-localhost
@@ -160,8 +158,7 @@ impl OriginValidator {
             let host = &rest[1..bracket_end];
             let after_bracket = &rest[bracket_end + 1..];
 
-            let port = if after_bracket.starts_with(':') {
-                let port_str = &after_bracket[1..];
+            let port = if let Some(port_str) = after_bracket.strip_prefix(':') {
                 Some(port_str.parse::<u16>().map_err(|_| {
                     CdpServerError::InvalidOrigin("Invalid port number".to_string())
                 })?)
@@ -220,8 +217,9 @@ impl OriginValidator {
 
     /// Check if the origin matches any allowed pattern
     fn check_allowed_patterns(&self, origin: &str) -> Result<()> {
+        let normalized = normalize_ipv6_origin(origin);
         for pattern in &self.config.allowed_origins {
-            if self.matches_pattern(origin, pattern) {
+            if self.matches_pattern(&normalized, &normalize_ipv6_origin(pattern)) {
                 return Ok(());
             }
         }
@@ -282,6 +280,30 @@ impl OriginValidator {
     }
 }
 
+/// Rewrite a bracketed IPv6 host into its canonical compressed form
+///
+/// `http://[0:0:0:0:0:0:0:1]:9222` becomes `http://[::1]:9222`, so origins and
+/// allow entries spelling the same address differently still compare equal.
+/// Anything without a parseable bracketed IPv6 host is returned unchanged.
+pub(crate) fn normalize_ipv6_origin(origin: &str) -> String {
+    let host_start = match origin.find("://") {
+        Some(pos) => pos + 3,
+        None => return origin.to_string(),
+    };
+    let rest = &origin[host_start..];
+    if !rest.starts_with('[') {
+        return origin.to_string();
+    }
+    let bracket_end = match rest.find(']') {
+        Some(pos) => pos,
+        None => return origin.to_string(),
+    };
+    match rest[1..bracket_end].parse::<Ipv6Addr>() {
+        Ok(ip) => format!("{}[{}]{}", &origin[..host_start], ip, &rest[bracket_end + 1..]),
+        Err(_) => origin.to_string(),
+    }
+}
+
 /// Parsed origin components
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -479,6 +501,17 @@ mod tests {
         assert!(validator.validate("http://[::1]:3000").is_ok());
     }
 
+    #[test]
+    fn test_ipv6_address_spellings_match() {
+        let origins = vec!["http://[0:0:0:0:0:0:0:1]:9222".to_string()];
+        assert!(validate_origin("http://[::1]:9222", &origins));
+        assert!(!validate_origin("http://[::2]:9222", &origins));
+
+        let origins = vec!["http://[2001:DB8::1]:*".to_string()];
+        assert!(validate_origin("http://[2001:db8:0::1]:3000", &origins));
+        assert!(!validate_origin("http://[2001:db8::1]", &origins));
+    }
+
     #[test]
     fn test_ip_addresses_denied_when_configured() {
         let config = OriginValidatorConfig {
@@ -18,6 +18,7 @@ fn test_server_config_custom() {
         max_message_size: 1024,
         allowed_origins: vec!["https://example.com".to_string()],
         bind_address: "127.0.0.1".to_string(),
+        handshake_timeout_ms: 10_000,
     };
 
     assert_eq!(config.port, 8080);
@@ -18,6 +18,7 @@ fn test_server_config_custom() {
         max_message_size: 1024,
         allowed_origins: vec!["https://example.com".to_string()],
         bind_address: "127.0.0.1".to_string(),
+        handshake_timeout_ms: 10_000,
     };
 
     assert_eq!(config.port, 8080);
@@ -253,3 +254,147 @@ fn test_serialize_cdp_event() {
     let json_str = json.unwrap();
     assert!(json_str.contains("\"method\":\"DOM.documentUpdated\""));
 }
+
+#[tokio::test]
+async fn test_server_binds_ipv6_loopback() {
+    if std::net::TcpListener::bind("[::1]:0").is_err() {
+        eprintln!("skipping: IPv6 loopback is unavailable");
+        return;
+    }
+
+    let config = ServerConfig::new(0).with_bind_address("[::1]".to_string());
+    let server = CdpWebSocketServer::new(config).unwrap();
+
+    let listener = server.bind().await.expect("IPv6 loopback should bind");
+    let addr = listener.local_addr().unwrap();
+    assert!(addr.is_ipv6());
+    assert_ne!(addr.port(), 0);
+    assert!(socket2::SockRef::from(&listener).only_v6().unwrap());
+
+    let server_handle = tokio::spawn(async move { server.serve(listener).await });
+
+    let url = format!("ws://[::1]:{}", addr.port());
+    let result = tokio_tungstenite::connect_async(&url).await;
+    assert!(result.is_ok(), "Should be able to connect over IPv6");
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_server_binds_ipv6_unspecified_as_dual_stack() {
+    if std::net::TcpListener::bind("[::]:0").is_err() {
+        eprintln!("skipping: IPv6 is unavailable");
+        return;
+    }
+
+    let config = ServerConfig::new(0).with_bind_address("[::]".to_string());
+    let server = CdpWebSocketServer::new(config).unwrap();
+
+    let listener = server.bind().await.expect("IPv6 unspecified should bind");
+    assert!(!socket2::SockRef::from(&listener).only_v6().unwrap());
+}
+
+#[test]
+fn test_origin_validation_bracketed_ipv6() {
+    let allowed = vec!["http://[::1]:*".to_string()];
+
+    assert!(validate_origin("http://[::1]:3000", &allowed));
+    assert!(validate_origin("http://[0:0:0:0:0:0:0:1]:3000", &allowed));
+    assert!(!validate_origin("http://[::2]:3000", &allowed));
+}
+
+#[tokio::test]
+async fn test_stalled_handshake_is_dropped() {
+    use futures::{SinkExt, StreamExt};
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+
+    let config = ServerConfig::new(0).with_handshake_timeout(Duration::from_millis(100));
+    let server = std::sync::Arc::new(CdpWebSocketServer::new(config).unwrap());
+    let listener = server.bind().await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let serving = std::sync::Arc::clone(&server);
+    let server_handle = tokio::spawn(async move { serving.serve(listener).await });
+
+    // A well-behaved client connects alongside the stalled one
+    let url = format!("ws://127.0.0.1:{}", port);
+    let (mut client, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+
+    // Connect but never send an upgrade request
+    let mut stalled = tokio::net::TcpStream::connect(("127.0.0.1", port))
+        .await
+        .unwrap();
+    let mut buf = [0u8; 16];
+    let read = tokio::time::timeout(Duration::from_secs(2), stalled.read(&mut buf))
+        .await
+        .expect("Server should drop the stalled connection");
+    assert_eq!(
+        read.unwrap(),
+        0,
+        "Connection should be closed without a response"
+    );
+    assert_eq!(server.handshake_timeouts(), 1);
+
+    // The established session is unaffected
+    client
+        .send(tokio_tungstenite::tungstenite::Message::Text(
+            r#"{"id": 1, "method": "Browser.getVersion"}"#.to_string(),
+        ))
+        .await
+        .unwrap();
+    let reply = client.next().await.unwrap().unwrap();
+    assert!(reply.to_text().unwrap().contains("\"id\":1"));
+    assert_eq!(server.get_sessions().len(), 1);
+
+    server_handle.abort();
+}
+
+#[tokio::test]
+async fn test_session_metadata_captures_user_agent() {
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    let server = std::sync::Arc::new(CdpWebSocketServer::new(ServerConfig::new(0)).unwrap());
+    let listener = server.bind().await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let serving = std::sync::Arc::clone(&server);
+    let server_handle = tokio::spawn(async move { serving.serve(listener).await });
+
+    let before = std::time::SystemTime::now();
+    let mut request = format!("ws://127.0.0.1:{}", port)
+        .into_client_request()
+        .unwrap();
+    request
+        .headers_mut()
+        .insert("User-Agent", "Puppeteer/21.0".parse().unwrap());
+    request
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", "cdp, json".parse().unwrap());
+    let (_client, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+    assert_eq!(
+        response.headers().get("Sec-WebSocket-Protocol").unwrap(),
+        "cdp"
+    );
+
+    // The session is registered right after the handshake completes
+    let sessions = server.get_sessions();
+    for _ in 0..50 {
+        if !sessions.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let session = sessions
+        .iter()
+        .next()
+        .expect("session should be registered");
+    let session = session.value().read();
+    let metadata = session.metadata();
+    assert_eq!(metadata.user_agent.as_deref(), Some("Puppeteer/21.0"));
+    assert_eq!(metadata.subprotocol.as_deref(), Some("cdp"));
+    assert!(metadata.connected_at >= before);
+    assert!(metadata.connected_at <= std::time::SystemTime::now());
+
+    server_handle.abort();
+}
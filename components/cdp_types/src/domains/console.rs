@@ -52,6 +52,12 @@ pub struct ConsoleMessage {
     /// Column number in the resource (0-based)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column: Option<u32>,
+    /// Time the message was recorded (ms since epoch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<f64>,
+    /// Nesting depth of console.group() calls when the message was recorded
+    #[serde(default)]
+    pub group_depth: u32,
 }
 
 #[cfg(test)]
@@ -81,6 +87,8 @@ mod tests {
             url: Some("http://example.com".to_string()),
             line: Some(10),
             column: Some(5),
+            timestamp: None,
+            group_depth: 0,
         };
 
         let json = serde_json::to_string(&msg).unwrap();
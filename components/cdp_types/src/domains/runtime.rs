@@ -94,6 +94,20 @@ pub struct ObjectPreview {
     pub overflow: bool,
     /// Preview properties
     pub properties: Vec<PropertyPreview>,
+    /// Entry previews for Map and Set objects
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entries: Option<Vec<EntryPreview>>,
+}
+
+/// Preview of a Map or Set entry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryPreview {
+    /// Entry key (Map entries only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<ObjectPreview>,
+    /// Entry value
+    pub value: ObjectPreview,
 }
 
 /// Property preview
@@ -111,6 +125,9 @@ pub struct PropertyPreview {
     /// Property subtype
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subtype: Option<RemoteObjectSubtype>,
+    /// Nested preview of an object-valued property
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_preview: Option<Box<ObjectPreview>>,
 }
 
 /// Response for Runtime.evaluate
@@ -177,6 +194,45 @@ pub struct CallFrame {
     pub column_number: u32,
 }
 
+/// Description of an isolated world (execution context)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionContextDescription {
+    /// Context identifier, unique within the target
+    pub id: ExecutionContextId,
+    /// Execution context origin
+    pub origin: String,
+    /// Human readable name describing the context
+    pub name: String,
+    /// Globally unique context identifier
+    pub unique_id: String,
+    /// Embedder-specific auxiliary data (e.g. frameId, isDefault)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aux_data: Option<serde_json::Value>,
+}
+
+/// Event: Runtime.executionContextCreated
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionContextCreatedEvent {
+    /// Newly created execution context
+    pub context: ExecutionContextDescription,
+}
+
+/// Event: Runtime.executionContextDestroyed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionContextDestroyedEvent {
+    /// Id of the destroyed context
+    pub execution_context_id: ExecutionContextId,
+    /// Unique id of the destroyed context
+    pub execution_context_unique_id: String,
+}
+
+/// Event: Runtime.executionContextsCleared
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ExecutionContextsClearedEvent {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,4 +261,65 @@ mod tests {
         let json = serde_json::to_string(&num).unwrap();
         assert_eq!(json, "\"number\"");
     }
+
+    #[test]
+    fn test_execution_context_description_serialization() {
+        let context = ExecutionContextDescription {
+            id: ExecutionContextId(1),
+            origin: "https://example.com".to_string(),
+            name: "top".to_string(),
+            unique_id: "ctx-unique-1".to_string(),
+            aux_data: Some(serde_json::json!({ "frameId": "frame-1", "isDefault": true })),
+        };
+        let json = serde_json::to_value(&context).unwrap();
+        assert_eq!(json["id"], 1);
+        assert_eq!(json["origin"], "https://example.com");
+        assert_eq!(json["name"], "top");
+        assert_eq!(json["uniqueId"], "ctx-unique-1");
+        assert_eq!(json["auxData"]["frameId"], "frame-1");
+        assert_eq!(json["auxData"]["isDefault"], true);
+
+        let parsed: ExecutionContextDescription = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, context);
+    }
+
+    #[test]
+    fn test_execution_context_description_without_aux_data() {
+        let context = ExecutionContextDescription {
+            id: ExecutionContextId(2),
+            origin: String::new(),
+            name: "worker".to_string(),
+            unique_id: "ctx-unique-2".to_string(),
+            aux_data: None,
+        };
+        let json = serde_json::to_value(&context).unwrap();
+        assert!(json.get("auxData").is_none());
+    }
+
+    #[test]
+    fn test_execution_context_lifecycle_events() {
+        let created = ExecutionContextCreatedEvent {
+            context: ExecutionContextDescription {
+                id: ExecutionContextId(3),
+                origin: "https://example.com".to_string(),
+                name: String::new(),
+                unique_id: "ctx-unique-3".to_string(),
+                aux_data: None,
+            },
+        };
+        let json = serde_json::to_value(&created).unwrap();
+        assert_eq!(json["context"]["id"], 3);
+        assert_eq!(json["context"]["uniqueId"], "ctx-unique-3");
+
+        let destroyed = ExecutionContextDestroyedEvent {
+            execution_context_id: ExecutionContextId(3),
+            execution_context_unique_id: "ctx-unique-3".to_string(),
+        };
+        let json = serde_json::to_value(&destroyed).unwrap();
+        assert_eq!(json["executionContextId"], 3);
+        assert_eq!(json["executionContextUniqueId"], "ctx-unique-3");
+
+        let cleared = serde_json::to_string(&ExecutionContextsClearedEvent {}).unwrap();
+        assert_eq!(cleared, "{}");
+    }
 }
@@ -5,6 +5,18 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use thiserror::Error;
 
+/// Server error code for methods rejected by the method policy
+pub const METHOD_BLOCKED_CODE: i32 = -32001;
+
+/// Server error code for requests rejected by rate limiting
+pub const RATE_LIMITED_CODE: i32 = -32010;
+
+/// Server error code for requests that did not complete in time
+pub const TIMED_OUT_CODE: i32 = -32011;
+
+/// Server error code for messages exceeding the size limit
+pub const MESSAGE_TOO_LARGE_CODE: i32 = -32012;
+
 /// CDP Error following JSON-RPC 2.0 error specification
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CdpError {
@@ -98,6 +110,39 @@ impl CdpError {
         );
         Self::new(code, message)
     }
+
+    /// Method blocked (-32001)
+    /// The method is not callable under the configured method policy
+    pub fn method_blocked(method: impl Into<String>) -> Self {
+        let method = method.into();
+        Self::with_data(
+            METHOD_BLOCKED_CODE,
+            "Method blocked by policy",
+            serde_json::json!({ "method": method }),
+        )
+    }
+
+    /// Rate limited (-32010)
+    /// The client sent too many requests
+    pub fn rate_limited() -> Self {
+        Self::server_error(RATE_LIMITED_CODE, "Rate limit exceeded, retry later")
+    }
+
+    /// Timed out (-32011)
+    /// The request did not complete in time
+    pub fn timed_out() -> Self {
+        Self::server_error(TIMED_OUT_CODE, "Request timed out")
+    }
+
+    /// Message too large (-32012)
+    /// The message exceeds the size limit in bytes
+    pub fn message_too_large(limit: usize) -> Self {
+        Self::with_data(
+            MESSAGE_TOO_LARGE_CODE,
+            format!("Message too large: limit is {} bytes", limit),
+            serde_json::json!({ "limit": limit }),
+        )
+    }
 }
 
 /// CDP-specific error type using thiserror
@@ -176,6 +221,27 @@ mod tests {
         assert_eq!(error.message, "Custom error");
     }
 
+    #[test]
+    fn test_limit_error_codes() {
+        let error = CdpError::rate_limited();
+        assert_eq!(error.code, RATE_LIMITED_CODE);
+        assert!(error.message.contains("Rate limit"));
+
+        let error = CdpError::timed_out();
+        assert_eq!(error.code, TIMED_OUT_CODE);
+        assert!(error.message.contains("timed out"));
+
+        let error = CdpError::method_blocked("Runtime.evaluate");
+        assert_eq!(error.code, METHOD_BLOCKED_CODE);
+        assert_eq!(error.message, "Method blocked by policy");
+        assert_eq!(error.data.unwrap()["method"], "Runtime.evaluate");
+
+        let error = CdpError::message_too_large(1024);
+        assert_eq!(error.code, MESSAGE_TOO_LARGE_CODE);
+        assert!(error.message.contains("1024"));
+        assert_eq!(error.data.unwrap()["limit"], 1024);
+    }
+
     #[test]
     fn test_error_serialization() {
         let error = CdpError::new(-32601, "Method not found");
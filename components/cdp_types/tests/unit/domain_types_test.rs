@@ -417,6 +417,8 @@ mod console_domain {
             url: Some("https://example.com".to_string()),
             line: Some(10),
             column: Some(5),
+            timestamp: None,
+            group_depth: 0,
         };
 
         let json_str = serde_json::to_string(&message).unwrap();
@@ -0,0 +1,146 @@
+//! Shared cookie store
+//!
+//! A single cookie jar that can be injected into the Storage domain, the
+//! Network domain and the inspector `StorageBridge`, so a cookie set through
+//! one of them is visible to the others.
+
+use parking_lot::RwLock;
+
+use crate::storage_types::Cookie;
+
+/// Thread-safe cookie jar shared between domains via `Arc<CookieStore>`
+#[derive(Debug, Default)]
+pub struct CookieStore {
+    /// Stored cookies, in insertion order
+    cookies: RwLock<Vec<Cookie>>,
+}
+
+impl CookieStore {
+    /// Create an empty cookie store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get all cookies
+    pub fn all(&self) -> Vec<Cookie> {
+        self.cookies.read().clone()
+    }
+
+    /// Add or update a cookie, replacing one with the same name, domain and path
+    ///
+    /// `size` is recomputed from the cookie's contents.
+    pub fn set(&self, mut cookie: Cookie) {
+        cookie.size = cookie.computed_size();
+        let mut cookies = self.cookies.write();
+        cookies.retain(|c| {
+            !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path)
+        });
+        cookies.push(cookie);
+    }
+
+    /// Delete the cookies with the given name and domain, on any path
+    pub fn delete(&self, name: &str, domain: &str) {
+        self.cookies
+            .write()
+            .retain(|c| !(c.name == name && c.domain == domain));
+    }
+
+    /// Keep only the cookies matching a predicate
+    pub fn retain(&self, f: impl FnMut(&Cookie) -> bool) {
+        self.cookies.write().retain(f);
+    }
+
+    /// Remove all cookies
+    pub fn clear(&self) {
+        self.cookies.write().clear();
+    }
+
+    /// Number of stored cookies
+    pub fn len(&self) -> usize {
+        self.cookies.read().len()
+    }
+
+    /// Whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.cookies.read().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cookie(name: &str, domain: &str, value: &str) -> Cookie {
+        cookie_on_path(name, domain, "/", value)
+    }
+
+    fn cookie_on_path(name: &str, domain: &str, path: &str, value: &str) -> Cookie {
+        Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: domain.to_string(),
+            path: path.to_string(),
+            expires: None,
+            size: 0,
+            http_only: None,
+            secure: None,
+            session: None,
+            same_site: None,
+        }
+    }
+
+    #[test]
+    fn test_set_replaces_same_name_and_domain() {
+        let store = CookieStore::new();
+        store.set(cookie("id", "example.com", "1"));
+        store.set(cookie("id", "other.com", "2"));
+        store.set(cookie("id", "example.com", "3"));
+
+        let cookies = store.all();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[1].value, "3");
+    }
+
+    #[test]
+    fn test_same_name_and_domain_on_different_paths_coexist() {
+        let store = CookieStore::new();
+        store.set(cookie_on_path("id", "example.com", "/", "1"));
+        store.set(cookie_on_path("id", "example.com", "/admin", "2"));
+
+        let cookies = store.all();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].path, "/");
+        assert_eq!(cookies[1].path, "/admin");
+    }
+
+    #[test]
+    fn test_set_recomputes_size_on_replace() {
+        let store = CookieStore::new();
+        store.set(cookie("id", "example.com", "1"));
+        // "id" + "1" + "example.com" + "/"
+        assert_eq!(store.all()[0].size, 15);
+
+        store.set(cookie("id", "example.com", "12345"));
+        let cookies = store.all();
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].value, "12345");
+        assert_eq!(cookies[0].size, 19);
+    }
+
+    #[test]
+    fn test_delete_retain_and_clear() {
+        let store = CookieStore::new();
+        store.set(cookie("a", "example.com", "1"));
+        store.set(cookie("b", "example.com", "2"));
+        store.set(cookie("c", "other.com", "3"));
+
+        store.delete("a", "example.com");
+        assert_eq!(store.len(), 2);
+
+        store.retain(|c| c.domain != "other.com");
+        assert_eq!(store.all()[0].name, "b");
+
+        store.clear();
+        assert!(store.is_empty());
+    }
+}
@@ -3,21 +3,28 @@
 //! This module implements the Console and Storage domains for the Chrome DevTools Protocol.
 //! It provides console message management, logging, and storage inspection capabilities.
 
+pub mod cookie_store;
 pub mod storage_types;
 
 // Re-export main types
+pub use cookie_store::CookieStore;
 pub use storage_types::{Cookie, CookieSameSite, StorageType};
 
 use async_trait::async_trait;
-use cdp_types::domains::console::ConsoleMessage;
+use cdp_types::domains::console::{ConsoleMessage, ConsoleMessageLevel, ConsoleMessageSource};
 use cdp_types::CdpError;
 use parking_lot::RwLock;
 use protocol_handler::DomainHandler;
 use serde_json::{json, Value};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
 
+/// Default number of console messages kept before the oldest are evicted
+pub const DEFAULT_CONSOLE_CAPACITY: usize = 1000;
+
 /// Console domain handler
 ///
 /// Implements the Chrome DevTools Protocol Console domain for managing console messages,
@@ -25,35 +32,181 @@ use tracing::{debug, warn};
 pub struct ConsoleDomain {
     /// Whether console monitoring is enabled
     enabled: Arc<AtomicBool>,
-    /// Stored console messages
-    messages: Arc<RwLock<Vec<ConsoleMessage>>>,
+    /// Stored console messages, oldest first
+    messages: Arc<RwLock<VecDeque<ConsoleMessage>>>,
+    /// Maximum number of stored messages
+    capacity: usize,
+    /// Messages evicted since the last clear
+    dropped: Arc<AtomicU64>,
+    /// Per-label counters for console.count()
+    counters: Arc<RwLock<HashMap<String, u64>>>,
+    /// Current console.group() nesting depth
+    group_depth: Arc<AtomicU32>,
 }
 
 impl ConsoleDomain {
     /// Create a new ConsoleDomain instance
     pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CONSOLE_CAPACITY)
+    }
+
+    /// Create a ConsoleDomain keeping at most `capacity` messages
+    ///
+    /// A capacity of zero is treated as one.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
         Self {
             enabled: Arc::new(AtomicBool::new(false)),
-            messages: Arc::new(RwLock::new(Vec::new())),
+            messages: Arc::new(RwLock::new(VecDeque::with_capacity(capacity))),
+            capacity,
+            dropped: Arc::new(AtomicU64::new(0)),
+            counters: Arc::new(RwLock::new(HashMap::new())),
+            group_depth: Arc::new(AtomicU32::new(0)),
         }
     }
 
-    /// Add a console message
-    fn add_message(&self, message: ConsoleMessage) {
-        self.messages.write().push(message);
+    /// Add a console message, stamping it with the current time and group depth
+    ///
+    /// The timestamp is taken while holding the write lock and never goes
+    /// backwards, so concurrent callers still produce a total order.
+    fn add_message(&self, mut message: ConsoleMessage) {
+        let mut messages = self.messages.write();
+        message.group_depth = self.group_depth.load(Ordering::SeqCst);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0);
+        let last = messages.back().and_then(|m| m.timestamp).unwrap_or(0.0);
+        message.timestamp = Some(now.max(last));
+        while messages.len() >= self.capacity {
+            messages.pop_front();
+            self.dropped.fetch_add(1, Ordering::SeqCst);
+        }
+        messages.push_back(message);
     }
 
-    /// Clear all console messages
-    fn clear(&self) {
-        self.messages.write().clear();
+    /// Clear all console messages and the dropped counter
+    pub fn clear(&self) {
+        let mut messages = self.messages.write();
+        messages.clear();
+        self.dropped.store(0, Ordering::SeqCst);
     }
 
-    /// Get all console messages
+    /// Get all console messages, oldest first
     fn get_messages(&self) -> Vec<ConsoleMessage> {
-        self.messages.read().clone()
+        self.messages.read().iter().cloned().collect()
+    }
+
+    /// Increment the counter for `label` and record a `label: N` message
+    fn count(&self, label: &str) -> u64 {
+        let count = {
+            let mut counters = self.counters.write();
+            let count = counters.entry(label.to_string()).or_insert(0);
+            *count += 1;
+            *count
+        };
+        self.add_message(console_api_message(format!("{}: {}", label, count)));
+        count
+    }
+
+    /// Record an error-level `Assertion failed` message when `condition` is false
+    ///
+    /// Returns whether a message was added; a true condition is a no-op.
+    pub fn assert(&self, condition: bool, message: &str) -> bool {
+        if condition {
+            return false;
+        }
+        let text = if message.is_empty() {
+            "Assertion failed".to_string()
+        } else {
+            format!("Assertion failed: {}", message)
+        };
+        self.add_message(ConsoleMessage {
+            level: ConsoleMessageLevel::Error,
+            ..console_api_message(text)
+        });
+        true
+    }
+
+    /// Record a group header at the current depth, then nest one level deeper
+    fn group(&self, label: &str) -> u32 {
+        self.add_message(console_api_message(label.to_string()));
+        self.group_depth.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Leave the innermost group; the depth never goes below zero
+    fn group_end(&self) -> u32 {
+        let previous = self
+            .group_depth
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |d| Some(d.saturating_sub(1)))
+            .unwrap_or(0);
+        previous.saturating_sub(1)
+    }
+
+    /// Number of messages evicted since the last clear
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::SeqCst)
+    }
+
+    /// Get all console messages, newest first
+    fn get_messages_descending(&self) -> Vec<ConsoleMessage> {
+        let mut messages = self.get_messages();
+        messages.reverse();
+        messages
+    }
+
+    /// Number of stored messages per level, including levels with no messages
+    fn message_counts(&self) -> HashMap<String, usize> {
+        let mut counts: HashMap<String, usize> = [
+            ConsoleMessageLevel::Log,
+            ConsoleMessageLevel::Warning,
+            ConsoleMessageLevel::Error,
+            ConsoleMessageLevel::Debug,
+            ConsoleMessageLevel::Info,
+        ]
+        .iter()
+        .map(|level| (wire_name(level), 0))
+        .collect();
+
+        for message in self.messages.read().iter() {
+            *counts.entry(wire_name(&message.level)).or_insert(0) += 1;
+        }
+        counts
     }
 }
 
+/// Log-level message attributed to the console API
+fn console_api_message(text: String) -> ConsoleMessage {
+    ConsoleMessage {
+        source: ConsoleMessageSource::ConsoleApi,
+        level: ConsoleMessageLevel::Log,
+        text,
+        url: None,
+        line: None,
+        column: None,
+        timestamp: None,
+        group_depth: 0,
+    }
+}
+
+/// Optional `label` param, defaulting as console.count() and console.group() do
+fn label_param(params: &Option<Value>, default: &str) -> String {
+    params
+        .as_ref()
+        .and_then(|p| p.get("label"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(default)
+        .to_string()
+}
+
+/// Protocol string for a serde enum such as a message level or source
+fn wire_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
 impl Default for ConsoleDomain {
     fn default() -> Self {
         Self::new()
@@ -66,6 +219,20 @@ impl DomainHandler for ConsoleDomain {
         "Console"
     }
 
+    fn supported_methods(&self) -> Vec<&'static str> {
+        vec![
+            "enable",
+            "disable",
+            "clearMessages",
+            "messageAdded",
+            "count",
+            "assert",
+            "group",
+            "groupEnd",
+            "getMessages",
+        ]
+    }
+
     async fn handle_method(&self, method: &str, params: Option<Value>) -> Result<Value, CdpError> {
         debug!("Console.{} called", method);
 
@@ -97,10 +264,64 @@ impl DomainHandler for ConsoleDomain {
                 self.add_message(message);
                 Ok(json!({}))
             }
+            "count" => {
+                let label = label_param(&params, "default");
+                let count = self.count(&label);
+                Ok(json!({ "label": label, "count": count }))
+            }
+            "assert" => {
+                // A missing condition is `undefined`, which fails the assertion
+                let condition = params
+                    .as_ref()
+                    .and_then(|p| p.get("condition"))
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let message = params
+                    .as_ref()
+                    .and_then(|p| p.get("message"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                let failed = self.assert(condition, message);
+                Ok(json!({ "failed": failed }))
+            }
+            "group" => {
+                let label = label_param(&params, "console.group");
+                let depth = self.group(&label);
+                Ok(json!({ "groupDepth": depth }))
+            }
+            "groupEnd" => Ok(json!({ "groupDepth": self.group_end() })),
             "getMessages" => {
-                let messages = self.get_messages();
+                let descending = params
+                    .as_ref()
+                    .and_then(|p| p.get("descending"))
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let level = params
+                    .as_ref()
+                    .and_then(|p| p.get("level"))
+                    .and_then(|v| v.as_str());
+                let source = params
+                    .as_ref()
+                    .and_then(|p| p.get("source"))
+                    .and_then(|v| v.as_str());
+
+                let mut messages = if descending {
+                    self.get_messages_descending()
+                } else {
+                    self.get_messages()
+                };
+                // Unknown levels or sources simply match nothing
+                if let Some(level) = level {
+                    messages.retain(|m| wire_name(&m.level) == level);
+                }
+                if let Some(source) = source {
+                    messages.retain(|m| wire_name(&m.source) == source);
+                }
+
                 Ok(json!({
-                    "messages": messages
+                    "messages": messages,
+                    "counts": self.message_counts(),
+                    "dropped": self.dropped_count()
                 }))
             }
             _ => {
@@ -111,49 +332,185 @@ impl DomainHandler for ConsoleDomain {
     }
 }
 
+/// DOM storage area key: (security origin, storage type)
+type DomStorageKey = (String, StorageType);
+
+/// DOM storage items as (key, value) pairs in insertion order
+type DomStorageItems = Vec<(String, String)>;
+
 /// Storage domain handler
 ///
 /// Implements the Chrome DevTools Protocol Storage domain for inspecting cookies,
 /// localStorage, sessionStorage, and other storage mechanisms.
 pub struct StorageDomain {
-    /// Mock cookie storage for testing
-    cookies: Arc<RwLock<Vec<Cookie>>>,
+    /// Cookie jar (may be shared with other domains)
+    cookies: Arc<CookieStore>,
+    /// DOM storage areas
+    dom_storage: Arc<RwLock<HashMap<DomStorageKey, DomStorageItems>>>,
 }
 
 impl StorageDomain {
     /// Create a new StorageDomain instance
     pub fn new() -> Self {
+        Self::with_cookie_store(Arc::new(CookieStore::new()))
+    }
+
+    /// Create a StorageDomain backed by a shared cookie store
+    pub fn with_cookie_store(cookies: Arc<CookieStore>) -> Self {
         Self {
-            cookies: Arc::new(RwLock::new(Vec::new())),
+            cookies,
+            dom_storage: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Get the cookie store backing this domain
+    pub fn cookie_store(&self) -> Arc<CookieStore> {
+        Arc::clone(&self.cookies)
+    }
+
     /// Get all cookies
     fn get_cookies(&self) -> Vec<Cookie> {
-        self.cookies.read().clone()
+        self.cookies.all()
     }
 
     /// Add or update a cookie
     fn set_cookie(&self, cookie: Cookie) {
-        let mut cookies = self.cookies.write();
-
-        // Remove existing cookie with same name and domain
-        cookies.retain(|c| !(c.name == cookie.name && c.domain == cookie.domain));
-
-        // Add new cookie
-        cookies.push(cookie);
+        self.cookies.set(cookie);
     }
 
     /// Clear all cookies
     fn clear_cookies(&self) {
-        self.cookies.write().clear();
+        self.cookies.clear();
     }
 
     /// Delete a specific cookie
     fn delete_cookie(&self, name: &str, domain: &str) {
-        let mut cookies = self.cookies.write();
-        cookies.retain(|c| !(c.name == name && c.domain == domain));
+        self.cookies.delete(name, domain);
+    }
+
+    /// Get all DOM storage items for an origin as `[key, value]` pairs
+    fn get_dom_storage_items(&self, origin: &str, storage_type: StorageType) -> Vec<[String; 2]> {
+        self.dom_storage
+            .read()
+            .get(&(origin.to_string(), storage_type))
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|(key, value)| [key.clone(), value.clone()])
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Add or update a DOM storage item
+    fn set_dom_storage_item(&self, origin: &str, storage_type: StorageType, key: &str, value: &str) {
+        let mut storage = self.dom_storage.write();
+        let items = storage.entry((origin.to_string(), storage_type)).or_default();
+
+        match items.iter_mut().find(|(k, _)| k == key) {
+            Some(item) => item.1 = value.to_string(),
+            None => items.push((key.to_string(), value.to_string())),
+        }
     }
+
+    /// Remove a DOM storage item
+    fn remove_dom_storage_item(&self, origin: &str, storage_type: StorageType, key: &str) {
+        if let Some(items) = self
+            .dom_storage
+            .write()
+            .get_mut(&(origin.to_string(), storage_type))
+        {
+            items.retain(|(k, _)| k != key);
+        }
+    }
+
+    /// Clear all DOM storage items for an origin
+    fn clear_dom_storage(&self, origin: &str, storage_type: StorageType) {
+        self.dom_storage
+            .write()
+            .remove(&(origin.to_string(), storage_type));
+    }
+}
+
+/// Parse a `DOMStorage.StorageId` (`securityOrigin` + `isLocalStorage`) from params
+fn parse_storage_id(params: &Value) -> Result<(String, StorageType), CdpError> {
+    let storage_id = params
+        .get("storageId")
+        .ok_or_else(|| CdpError::invalid_params("Missing 'storageId' field"))?;
+
+    let origin = storage_id
+        .get("securityOrigin")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| CdpError::invalid_params("Missing 'storageId.securityOrigin' field"))?
+        .to_string();
+
+    let is_local = storage_id
+        .get("isLocalStorage")
+        .and_then(|v| v.as_bool())
+        .ok_or_else(|| CdpError::invalid_params("Missing 'storageId.isLocalStorage' field"))?;
+
+    let storage_type = if is_local {
+        StorageType::LocalStorage
+    } else {
+        StorageType::SessionStorage
+    };
+
+    Ok((origin, storage_type))
+}
+
+/// Check whether a cookie would be sent to a URL (domain-suffix and path-prefix match)
+fn cookie_matches_url(cookie: &Cookie, url: &str) -> bool {
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest,
+        None => return false,
+    };
+    let (authority, path) = match rest.find(['/', '?', '#']) {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+    let host = authority
+        .rsplit('@')
+        .next()
+        .unwrap_or(authority)
+        .split(':')
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let path = match path.split(['?', '#']).next() {
+        Some(p) if p.starts_with('/') => p,
+        _ => "/",
+    };
+
+    let domain = cookie.domain.trim_start_matches('.').to_ascii_lowercase();
+    let domain_matches = host == domain || host.ends_with(&format!(".{}", domain));
+
+    let cookie_path = if cookie.path.is_empty() { "/" } else { cookie.path.as_str() };
+    let path_matches = path == cookie_path
+        || (path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || path[cookie_path.len()..].starts_with('/')));
+
+    domain_matches && path_matches
+}
+
+/// Parse a CDP `CookieSameSite` value
+fn parse_same_site(value: &str) -> Result<CookieSameSite, CdpError> {
+    match value {
+        "Strict" => Ok(CookieSameSite::Strict),
+        "Lax" => Ok(CookieSameSite::Lax),
+        "None" => Ok(CookieSameSite::None),
+        other => Err(CdpError::invalid_params(format!(
+            "Invalid sameSite value: {}",
+            other
+        ))),
+    }
+}
+
+/// Extract a required string field from params
+fn required_str<'a>(params: &'a Value, field: &str) -> Result<&'a str, CdpError> {
+    params
+        .get(field)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| CdpError::invalid_params(format!("Missing '{}' field", field)))
 }
 
 impl Default for StorageDomain {
@@ -173,7 +530,26 @@ impl DomainHandler for StorageDomain {
 
         match method {
             "getCookies" => {
-                let cookies = self.get_cookies();
+                let urls: Option<Vec<String>> = params
+                    .as_ref()
+                    .and_then(|p| p.get("urls"))
+                    .map(|urls| {
+                        urls.as_array()
+                            .ok_or_else(|| CdpError::invalid_params("'urls' must be an array"))?
+                            .iter()
+                            .map(|url| {
+                                url.as_str().map(str::to_string).ok_or_else(|| {
+                                    CdpError::invalid_params("'urls' must contain strings")
+                                })
+                            })
+                            .collect()
+                    })
+                    .transpose()?;
+
+                let mut cookies = self.get_cookies();
+                if let Some(urls) = urls {
+                    cookies.retain(|cookie| urls.iter().any(|url| cookie_matches_url(cookie, url)));
+                }
                 Ok(json!({
                     "cookies": cookies
                 }))
@@ -207,8 +583,18 @@ impl DomainHandler for StorageDomain {
                     .unwrap_or("/")
                     .to_string();
 
-                // Calculate size
-                let size = (name.len() + value.len()) as u32;
+                let secure = params.get("secure").and_then(|v| v.as_bool());
+                let same_site = match params.get("sameSite").and_then(|v| v.as_str()) {
+                    Some(value) => Some(parse_same_site(value)?),
+                    None => None,
+                };
+
+                // Browsers drop SameSite=None cookies that are not Secure
+                if same_site == Some(CookieSameSite::None) && secure != Some(true) {
+                    return Err(CdpError::invalid_params(
+                        "sameSite 'None' requires secure: true",
+                    ));
+                }
 
                 let cookie = Cookie {
                     name,
@@ -216,11 +602,12 @@ impl DomainHandler for StorageDomain {
                     domain,
                     path,
                     expires: params.get("expires").and_then(|v| v.as_f64()),
-                    size,
+                    // Recomputed by the cookie store
+                    size: 0,
                     http_only: params.get("httpOnly").and_then(|v| v.as_bool()),
-                    secure: params.get("secure").and_then(|v| v.as_bool()),
+                    secure,
                     session: params.get("session").and_then(|v| v.as_bool()),
-                    same_site: None, // Simplified for now
+                    same_site,
                 };
 
                 self.set_cookie(cookie);
@@ -247,6 +634,44 @@ impl DomainHandler for StorageDomain {
                 self.delete_cookie(name, domain);
                 Ok(json!({}))
             }
+            "getDOMStorageItems" => {
+                let params = params
+                    .ok_or_else(|| CdpError::invalid_params("getDOMStorageItems requires params"))?;
+                let (origin, storage_type) = parse_storage_id(&params)?;
+
+                let entries = self.get_dom_storage_items(&origin, storage_type);
+                Ok(json!({
+                    "entries": entries
+                }))
+            }
+            "setDOMStorageItem" => {
+                let params = params
+                    .ok_or_else(|| CdpError::invalid_params("setDOMStorageItem requires params"))?;
+                let (origin, storage_type) = parse_storage_id(&params)?;
+                let key = required_str(&params, "key")?;
+                let value = required_str(&params, "value")?;
+
+                self.set_dom_storage_item(&origin, storage_type, key, value);
+                Ok(json!({}))
+            }
+            "removeDOMStorageItem" => {
+                let params = params.ok_or_else(|| {
+                    CdpError::invalid_params("removeDOMStorageItem requires params")
+                })?;
+                let (origin, storage_type) = parse_storage_id(&params)?;
+                let key = required_str(&params, "key")?;
+
+                self.remove_dom_storage_item(&origin, storage_type, key);
+                Ok(json!({}))
+            }
+            "clear" => {
+                let params =
+                    params.ok_or_else(|| CdpError::invalid_params("clear requires params"))?;
+                let (origin, storage_type) = parse_storage_id(&params)?;
+
+                self.clear_dom_storage(&origin, storage_type);
+                Ok(json!({}))
+            }
             _ => {
                 warn!("Unknown Storage method: {}", method);
                 Err(CdpError::method_not_found(format!("Storage.{}", method)))
@@ -301,13 +726,15 @@ mod tests {
         let console = ConsoleDomain::new();
 
         // Add some messages first
-        console.messages.write().push(ConsoleMessage {
+        console.messages.write().push_back(ConsoleMessage {
             source: ConsoleMessageSource::Console,
             level: ConsoleMessageLevel::Log,
             text: "Test message".to_string(),
             url: None,
             line: None,
             column: None,
+            timestamp: None,
+            group_depth: 0,
         });
 
         assert_eq!(console.messages.read().len(), 1);
@@ -349,22 +776,26 @@ mod tests {
         let console = ConsoleDomain::new();
 
         // Add test messages
-        console.messages.write().push(ConsoleMessage {
+        console.messages.write().push_back(ConsoleMessage {
             source: ConsoleMessageSource::Console,
             level: ConsoleMessageLevel::Log,
             text: "Message 1".to_string(),
             url: None,
             line: None,
             column: None,
+            timestamp: None,
+            group_depth: 0,
         });
 
-        console.messages.write().push(ConsoleMessage {
+        console.messages.write().push_back(ConsoleMessage {
             source: ConsoleMessageSource::Console,
             level: ConsoleMessageLevel::Error,
             text: "Message 2".to_string(),
             url: None,
             line: None,
             column: None,
+            timestamp: None,
+            group_depth: 0,
         });
 
         let result = console.handle_method("getMessages", None).await;
@@ -376,6 +807,236 @@ mod tests {
         assert_eq!(response["messages"].as_array().unwrap().len(), 2);
     }
 
+    fn log_message(text: &str) -> ConsoleMessage {
+        ConsoleMessage {
+            source: ConsoleMessageSource::Console,
+            level: ConsoleMessageLevel::Log,
+            text: text.to_string(),
+            url: None,
+            line: None,
+            column: None,
+            timestamp: None,
+            group_depth: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_console_messages_are_timestamped_in_order() {
+        let console = Arc::new(ConsoleDomain::new());
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let console = Arc::clone(&console);
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        console.add_message(log_message(&format!("{}-{}", t, i)));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let messages = console.get_messages();
+        assert_eq!(messages.len(), 100);
+        let timestamps: Vec<f64> = messages.iter().map(|m| m.timestamp.unwrap()).collect();
+        assert!(timestamps[0] > 0.0);
+        assert!(timestamps.windows(2).all(|w| w[0] <= w[1]));
+
+        let response = console.handle_method("getMessages", None).await.unwrap();
+        assert!(response["messages"][0]["timestamp"].as_f64().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_console_get_messages_descending() {
+        let console = ConsoleDomain::new();
+        for text in ["first", "second", "third"] {
+            console
+                .handle_method(
+                    "messageAdded",
+                    Some(json!({"message": {"source": "console", "level": "log", "text": text}})),
+                )
+                .await
+                .unwrap();
+        }
+
+        let response = console
+            .handle_method("getMessages", Some(json!({"descending": true})))
+            .await
+            .unwrap();
+        let texts: Vec<&str> = response["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(texts, vec!["third", "second", "first"]);
+
+        let response = console.handle_method("getMessages", None).await.unwrap();
+        assert_eq!(response["messages"][0]["text"], "first");
+    }
+
+    #[tokio::test]
+    async fn test_console_get_messages_filter_and_counts() {
+        let console = ConsoleDomain::new();
+        for (source, level) in [
+            ("console-api", "error"),
+            ("network", "error"),
+            ("network", "warning"),
+            ("javascript", "log"),
+        ] {
+            console
+                .handle_method(
+                    "messageAdded",
+                    Some(json!({"message": {"source": source, "level": level, "text": "m"}})),
+                )
+                .await
+                .unwrap();
+        }
+
+        let response = console
+            .handle_method("getMessages", Some(json!({"level": "error"})))
+            .await
+            .unwrap();
+        assert_eq!(response["messages"].as_array().unwrap().len(), 2);
+        // Counts always cover the unfiltered set
+        assert_eq!(response["counts"]["error"], 2);
+        assert_eq!(response["counts"]["warning"], 1);
+        assert_eq!(response["counts"]["log"], 1);
+        assert_eq!(response["counts"]["info"], 0);
+
+        let response = console
+            .handle_method("getMessages", Some(json!({"level": "error", "source": "network"})))
+            .await
+            .unwrap();
+        assert_eq!(response["messages"].as_array().unwrap().len(), 1);
+
+        let response = console
+            .handle_method("getMessages", Some(json!({"level": "verbose"})))
+            .await
+            .unwrap();
+        assert!(response["messages"].as_array().unwrap().is_empty());
+        assert_eq!(response["counts"]["error"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_console_evicts_oldest_beyond_capacity() {
+        let console = ConsoleDomain::with_capacity(3);
+        for i in 0..5 {
+            console
+                .handle_method(
+                    "messageAdded",
+                    Some(json!({"message": {
+                        "source": "console",
+                        "level": "log",
+                        "text": format!("msg {}", i)
+                    }})),
+                )
+                .await
+                .unwrap();
+        }
+
+        let response = console.handle_method("getMessages", None).await.unwrap();
+        let texts: Vec<&str> = response["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(texts, vec!["msg 2", "msg 3", "msg 4"]);
+        assert_eq!(response["dropped"], 2);
+
+        console.handle_method("clearMessages", None).await.unwrap();
+        let response = console.handle_method("getMessages", None).await.unwrap();
+        assert!(response["messages"].as_array().unwrap().is_empty());
+        assert_eq!(response["dropped"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_console_advertised_methods_are_routed() {
+        let console = ConsoleDomain::new();
+        for method in console.methods() {
+            let params = json!({"message": {"source": "console", "level": "log", "text": "x"}});
+            let result = console.handle_method(method, Some(params)).await;
+            if let Err(err) = result {
+                assert_ne!(err.code, -32601, "{} is advertised but not routed", method);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_console_count_increments_per_label() {
+        let console = ConsoleDomain::new();
+        for _ in 0..3 {
+            console
+                .handle_method("count", Some(json!({"label": "x"})))
+                .await
+                .unwrap();
+        }
+        let result = console.handle_method("count", None).await.unwrap();
+        assert_eq!(result["count"], 1);
+
+        let messages = console.get_messages();
+        assert_eq!(messages[2].text, "x: 3");
+        assert_eq!(messages[3].text, "default: 1");
+    }
+
+    #[tokio::test]
+    async fn test_console_failed_assert_adds_error() {
+        let console = ConsoleDomain::new();
+        let result = console
+            .handle_method(
+                "assert",
+                Some(json!({"condition": false, "message": "x should be positive"})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["failed"], true);
+
+        let messages = console.get_messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].level, ConsoleMessageLevel::Error);
+        assert_eq!(messages[0].source, ConsoleMessageSource::ConsoleApi);
+        assert_eq!(messages[0].text, "Assertion failed: x should be positive");
+    }
+
+    #[tokio::test]
+    async fn test_console_passing_assert_adds_nothing() {
+        let console = ConsoleDomain::new();
+        assert!(!console.assert(true, "never shown"));
+        let result = console
+            .handle_method("assert", Some(json!({"condition": true, "message": "ok"})))
+            .await
+            .unwrap();
+        assert_eq!(result["failed"], false);
+        assert!(console.get_messages().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_console_group_depth() {
+        let console = ConsoleDomain::new();
+        console.add_message(log_message("outside"));
+        console
+            .handle_method("group", Some(json!({"label": "outer"})))
+            .await
+            .unwrap();
+        console.handle_method("group", None).await.unwrap();
+        console.add_message(log_message("nested"));
+        console.handle_method("groupEnd", None).await.unwrap();
+        console.add_message(log_message("back"));
+
+        let depths: Vec<u32> = console.get_messages().iter().map(|m| m.group_depth).collect();
+        // outside, outer header, console.group header, nested, back
+        assert_eq!(depths, vec![0, 0, 1, 2, 1]);
+
+        console.handle_method("groupEnd", None).await.unwrap();
+        let result = console.handle_method("groupEnd", None).await.unwrap();
+        assert_eq!(result["groupDepth"], 0);
+        console.add_message(log_message("top"));
+        assert_eq!(console.get_messages().last().unwrap().group_depth, 0);
+    }
+
     #[tokio::test]
     async fn test_console_unknown_method() {
         let console = ConsoleDomain::new();
@@ -423,9 +1084,9 @@ mod tests {
         let result = storage.handle_method("setCookie", Some(params)).await;
 
         assert!(result.is_ok());
-        assert_eq!(storage.cookies.read().len(), 1);
+        assert_eq!(storage.cookies.len(), 1);
 
-        let cookie = &storage.cookies.read()[0];
+        let cookie = &storage.cookies.all()[0];
         assert_eq!(cookie.name, "session");
         assert_eq!(cookie.value, "abc123");
         assert_eq!(cookie.domain, "example.com");
@@ -485,13 +1146,13 @@ mod tests {
             .await
             .unwrap();
 
-        assert_eq!(storage.cookies.read().len(), 2);
+        assert_eq!(storage.cookies.len(), 2);
 
         // Clear cookies
         let result = storage.handle_method("clearCookies", None).await;
 
         assert!(result.is_ok());
-        assert_eq!(storage.cookies.read().len(), 0);
+        assert_eq!(storage.cookies.len(), 0);
     }
 
     #[tokio::test]
@@ -522,7 +1183,7 @@ mod tests {
             .await
             .unwrap();
 
-        assert_eq!(storage.cookies.read().len(), 2);
+        assert_eq!(storage.cookies.len(), 2);
 
         // Delete one cookie
         let delete_params = json!({
@@ -535,8 +1196,8 @@ mod tests {
             .await;
 
         assert!(result.is_ok());
-        assert_eq!(storage.cookies.read().len(), 1);
-        assert_eq!(storage.cookies.read()[0].name, "keep");
+        assert_eq!(storage.cookies.len(), 1);
+        assert_eq!(storage.cookies.all()[0].name, "keep");
     }
 
     #[tokio::test]
@@ -565,4 +1226,226 @@ mod tests {
         let err = result.unwrap_err();
         assert_eq!(err.code, -32602); // Invalid params
     }
+
+    #[tokio::test]
+    async fn test_storage_set_cookie_same_site() {
+        let storage = StorageDomain::new();
+
+        let params = json!({
+            "name": "strict",
+            "value": "1",
+            "domain": "example.com",
+            "sameSite": "Strict"
+        });
+        storage.handle_method("setCookie", Some(params)).await.unwrap();
+
+        let params = json!({
+            "name": "cross-site",
+            "value": "2",
+            "domain": "example.com",
+            "sameSite": "None",
+            "secure": true
+        });
+        storage.handle_method("setCookie", Some(params)).await.unwrap();
+
+        let result = storage.handle_method("getCookies", None).await.unwrap();
+        assert_eq!(result["cookies"][0]["sameSite"], "Strict");
+        assert_eq!(result["cookies"][1]["sameSite"], "None");
+    }
+
+    #[tokio::test]
+    async fn test_storage_set_cookie_invalid_same_site() {
+        let storage = StorageDomain::new();
+
+        let params = json!({
+            "name": "bad",
+            "value": "1",
+            "domain": "example.com",
+            "sameSite": "Sometimes"
+        });
+        let err = storage.handle_method("setCookie", Some(params)).await.unwrap_err();
+        assert_eq!(err.code, -32602);
+
+        // SameSite=None without Secure is rejected like browsers do
+        let params = json!({
+            "name": "insecure",
+            "value": "1",
+            "domain": "example.com",
+            "sameSite": "None"
+        });
+        let err = storage.handle_method("setCookie", Some(params)).await.unwrap_err();
+        assert_eq!(err.code, -32602);
+        assert!(storage.cookies.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_storage_get_cookies_filtered_by_urls() {
+        let storage = StorageDomain::new();
+        for (name, domain, path) in [
+            ("site", ".example.com", "/"),
+            ("account", "example.com", "/account"),
+            ("other", "other.com", "/"),
+        ] {
+            let params = json!({"name": name, "value": "v", "domain": domain, "path": path});
+            storage.handle_method("setCookie", Some(params)).await.unwrap();
+        }
+
+        let names = |result: &Value| -> Vec<String> {
+            result["cookies"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|c| c["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let result = storage
+            .handle_method(
+                "getCookies",
+                Some(json!({"urls": ["https://sub.example.com/page"]})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(names(&result), vec!["site"]);
+
+        let result = storage
+            .handle_method(
+                "getCookies",
+                Some(json!({"urls": ["https://example.com/accounting"]})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(names(&result), vec!["site"]);
+
+        let result = storage
+            .handle_method(
+                "getCookies",
+                Some(json!({"urls": ["https://example.com/account/settings?tab=1"]})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(names(&result), vec!["site", "account"]);
+
+        let result = storage
+            .handle_method("getCookies", Some(json!({"urls": ["https://other.com"]})))
+            .await
+            .unwrap();
+        assert_eq!(names(&result), vec!["other"]);
+
+        // Without urls every cookie is returned
+        let result = storage.handle_method("getCookies", None).await.unwrap();
+        assert_eq!(names(&result).len(), 3);
+    }
+
+    // ============================================================================
+    // DOM Storage Tests
+    // ============================================================================
+
+    fn storage_id(origin: &str, is_local: bool) -> Value {
+        json!({"securityOrigin": origin, "isLocalStorage": is_local})
+    }
+
+    #[tokio::test]
+    async fn test_dom_storage_set_and_get_items() {
+        let storage = StorageDomain::new();
+        let id = storage_id("https://x.com", true);
+
+        storage
+            .handle_method(
+                "setDOMStorageItem",
+                Some(json!({"storageId": id, "key": "a", "value": "1"})),
+            )
+            .await
+            .unwrap();
+        storage
+            .handle_method(
+                "setDOMStorageItem",
+                Some(json!({"storageId": id, "key": "b", "value": "2"})),
+            )
+            .await
+            .unwrap();
+        storage
+            .handle_method(
+                "setDOMStorageItem",
+                Some(json!({"storageId": id, "key": "a", "value": "3"})),
+            )
+            .await
+            .unwrap();
+
+        let result = storage
+            .handle_method("getDOMStorageItems", Some(json!({"storageId": id})))
+            .await
+            .unwrap();
+        assert_eq!(result["entries"], json!([["a", "3"], ["b", "2"]]));
+
+        storage
+            .handle_method("removeDOMStorageItem", Some(json!({"storageId": id, "key": "a"})))
+            .await
+            .unwrap();
+        let result = storage
+            .handle_method("getDOMStorageItems", Some(json!({"storageId": id})))
+            .await
+            .unwrap();
+        assert_eq!(result["entries"], json!([["b", "2"]]));
+
+        storage
+            .handle_method("clear", Some(json!({"storageId": id})))
+            .await
+            .unwrap();
+        let result = storage
+            .handle_method("getDOMStorageItems", Some(json!({"storageId": id})))
+            .await
+            .unwrap();
+        assert_eq!(result["entries"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_dom_storage_local_and_session_are_independent() {
+        let storage = StorageDomain::new();
+        let local = storage_id("https://x.com", true);
+        let session = storage_id("https://x.com", false);
+
+        storage
+            .handle_method(
+                "setDOMStorageItem",
+                Some(json!({"storageId": local, "key": "k", "value": "local"})),
+            )
+            .await
+            .unwrap();
+        storage
+            .handle_method(
+                "setDOMStorageItem",
+                Some(json!({"storageId": session, "key": "k", "value": "session"})),
+            )
+            .await
+            .unwrap();
+
+        storage
+            .handle_method("clear", Some(json!({"storageId": session})))
+            .await
+            .unwrap();
+
+        let result = storage
+            .handle_method("getDOMStorageItems", Some(json!({"storageId": local})))
+            .await
+            .unwrap();
+        assert_eq!(result["entries"], json!([["k", "local"]]));
+
+        let other_origin = storage_id("https://y.com", true);
+        let result = storage
+            .handle_method("getDOMStorageItems", Some(json!({"storageId": other_origin})))
+            .await
+            .unwrap();
+        assert_eq!(result["entries"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_dom_storage_missing_storage_id() {
+        let storage = StorageDomain::new();
+
+        let result = storage
+            .handle_method("setDOMStorageItem", Some(json!({"key": "a", "value": "1"})))
+            .await;
+        assert_eq!(result.unwrap_err().code, -32602);
+    }
 }
@@ -34,6 +34,16 @@ pub struct Cookie {
     pub same_site: Option<CookieSameSite>,
 }
 
+impl Cookie {
+    /// Size as counted against RFC 6265 storage limits
+    ///
+    /// The sum of the lengths of the name, value and the `Domain` and `Path`
+    /// attribute values.
+    pub fn computed_size(&self) -> u32 {
+        (self.name.len() + self.value.len() + self.domain.len() + self.path.len()) as u32
+    }
+}
+
 /// Cookie SameSite type
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CookieSameSite {
@@ -43,7 +53,7 @@ pub enum CookieSameSite {
 }
 
 /// Storage type
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum StorageType {
     LocalStorage,
@@ -26,11 +26,16 @@
 
 #![warn(missing_docs)]
 
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicU16, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
 use tokio::sync::RwLock;
 
 // Re-export public types from devtools_component
-pub use devtools_component::{DevToolsConfig, DevToolsError, Result};
+pub use devtools_component::{DevToolsConfig, DevToolsError, DevToolsMetrics, Result, VersionInfo};
+pub use protocol_handler::ProtocolHandler;
 
 use devtools_component::DevToolsComponent;
 
@@ -41,7 +46,9 @@ use devtools_component::DevToolsComponent;
 pub struct DevTools {
     component: Arc<RwLock<Option<DevToolsComponent>>>,
     base_config: DevToolsConfig,
-    actual_port: Arc<RwLock<Option<u16>>>,
+    /// Port bound by the running server (0 when stopped)
+    actual_port: Arc<AtomicU16>,
+    handler: Option<Arc<ProtocolHandler>>,
 }
 
 impl DevTools {
@@ -67,7 +74,30 @@ impl DevTools {
         Ok(Self {
             component: Arc::new(RwLock::new(None)),
             base_config: config,
-            actual_port: Arc::new(RwLock::new(None)),
+            actual_port: Arc::new(AtomicU16::new(0)),
+            handler: None,
+        })
+    }
+
+    /// Create a DevTools instance that routes through a caller-supplied handler
+    ///
+    /// The default domains are not registered; only the domains, middleware
+    /// and policy already on `handler` are used. The same handler is reused
+    /// across restarts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use devtools_api::{DevTools, DevToolsConfig, ProtocolHandler};
+    /// use std::sync::Arc;
+    ///
+    /// let handler = Arc::new(ProtocolHandler::new());
+    /// let devtools = DevTools::with_handler(DevToolsConfig::default(), handler).unwrap();
+    /// ```
+    pub fn with_handler(config: DevToolsConfig, handler: Arc<ProtocolHandler>) -> Result<Self> {
+        Ok(Self {
+            handler: Some(handler),
+            ..Self::new(config)?
         })
     }
 
@@ -100,6 +130,47 @@ impl DevTools {
             return Err(DevToolsError::ServerAlreadyRunning);
         }
 
+        *component_lock = Some(self.launch(port).await?);
+
+        Ok(())
+    }
+
+    /// Stop the running server (if any) and start a fresh one on `port`
+    ///
+    /// Acts like [`DevTools::start`] when the server isn't running. Errors
+    /// from stopping the old server are ignored; only a failure to start the
+    /// new one is reported. Concurrent restarts are serialized, so only one
+    /// component is ever running.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use devtools_api::{DevTools, DevToolsConfig};
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let devtools = DevTools::new(DevToolsConfig::default())?;
+    /// devtools.start(9222).await?;
+    /// devtools.restart(9223).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn restart(&self, port: u16) -> Result<()> {
+        let mut component_lock = self.component.write().await;
+
+        if let Some(component) = component_lock.take() {
+            let _ = component.stop().await;
+            self.actual_port.store(0, Ordering::SeqCst);
+        }
+
+        *component_lock = Some(self.launch(port).await?);
+
+        Ok(())
+    }
+
+    /// Build and start a component on `port`, recording the bound port
+    ///
+    /// Callers must hold the `component` write lock.
+    async fn launch(&self, port: u16) -> Result<DevToolsComponent> {
         // Create a new config with the specified port
         let mut config_builder = DevToolsConfig::builder();
 
@@ -114,22 +185,25 @@ impl DevTools {
 
         config_builder = config_builder
             .port(port)
-            .max_message_size(self.base_config.max_message_size());
+            .max_message_size(self.base_config.max_message_size())
+            .browser_name(self.base_config.browser_name().to_string())
+            .browser_version(self.base_config.browser_version().to_string())
+            .user_agent(self.base_config.user_agent().to_string());
 
         let config = config_builder.build();
 
         // Create and start the component
-        let component = DevToolsComponent::new(config)?;
+        let component = match &self.handler {
+            Some(handler) => DevToolsComponent::with_handler(config, Arc::clone(handler))?,
+            None => DevToolsComponent::new(config)?,
+        };
         component.start().await?;
 
         // Store the actual port (might be different if port was 0)
         let actual_port = component.actual_port().unwrap_or(port);
-        *self.actual_port.write().await = Some(actual_port);
-
-        // Store the component
-        *component_lock = Some(component);
+        self.actual_port.store(actual_port, Ordering::SeqCst);
 
-        Ok(())
+        Ok(component)
     }
 
     /// Stop the DevTools server
@@ -156,13 +230,127 @@ impl DevTools {
         if let Some(component) = component_lock.as_ref() {
             component.stop().await?;
             *component_lock = None;
-            *self.actual_port.write().await = None;
+            self.actual_port.store(0, Ordering::SeqCst);
             Ok(())
         } else {
             Err(DevToolsError::ServerNotRunning)
         }
     }
 
+    /// Wait until the server accepts connections
+    ///
+    /// `start` returns once the component is running, but the listener may
+    /// not be accepting yet. This polls a TCP connect to the server's bound
+    /// address (loopback when bound to all interfaces) until it succeeds.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` once the server is reachable, `ServerNotRunning` if
+    /// it hasn't been started, or `Timeout` if `timeout` elapses first.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use devtools_api::{DevTools, DevToolsConfig};
+    /// # use std::time::Duration;
+    /// # #[tokio::main]
+    /// # async fn main() -> anyhow::Result<()> {
+    /// let devtools = DevTools::new(DevToolsConfig::default())?;
+    /// devtools.start(0).await?;
+    /// devtools.wait_until_ready(Duration::from_secs(5)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_until_ready(&self, timeout: Duration) -> Result<()> {
+        let component = self.component.read().await;
+        let mut addr = match component.as_ref() {
+            Some(component) => component.local_addr().await,
+            None => None,
+        }
+        .ok_or(DevToolsError::ServerNotRunning)?;
+        drop(component);
+
+        if addr.ip().is_unspecified() {
+            let loopback = match addr {
+                SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            };
+            addr.set_ip(loopback);
+        }
+
+        let poll = async {
+            while TcpStream::connect(addr).await.is_err() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, poll)
+            .await
+            .map_err(|_| DevToolsError::Timeout(timeout))
+    }
+
+    /// Get a snapshot of DevTools activity
+    ///
+    /// Reports active sessions, requests handled, events emitted and uptime.
+    /// Returns zeroed metrics when the server is not running.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use devtools_api::{DevTools, DevToolsConfig};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let devtools = DevTools::new(DevToolsConfig::default()).unwrap();
+    /// let metrics = devtools.metrics().await;
+    /// assert_eq!(metrics.total_requests, 0);
+    /// # }
+    /// ```
+    pub async fn metrics(&self) -> DevToolsMetrics {
+        match self.component.read().await.as_ref() {
+            Some(component) => component.metrics().await,
+            None => DevToolsMetrics::default(),
+        }
+    }
+
+    /// Get the port the server is bound to
+    ///
+    /// When started with port 0 this is the port the OS picked. Returns
+    /// `None` when the server is not running.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use devtools_api::{DevTools, DevToolsConfig};
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let devtools = DevTools::new(DevToolsConfig::default()).unwrap();
+    /// devtools.start(0).await.unwrap();
+    /// assert!(devtools.actual_port().is_some_and(|port| port != 0));
+    /// # }
+    /// ```
+    pub fn actual_port(&self) -> Option<u16> {
+        match self.actual_port.load(Ordering::SeqCst) {
+            0 => None,
+            port => Some(port),
+        }
+    }
+
+    /// Get the browser and protocol identification served at `/json/version`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use devtools_api::{DevTools, DevToolsConfig};
+    /// let config = DevToolsConfig::builder()
+    ///     .user_agent("MyEmbedder/1.0".to_string())
+    ///     .build();
+    /// let devtools = DevTools::new(config).unwrap();
+    /// assert_eq!(devtools.version_info().user_agent, "MyEmbedder/1.0");
+    /// ```
+    pub fn version_info(&self) -> VersionInfo {
+        self.base_config.version_info()
+    }
+
     /// Get the DevTools server URL
     ///
     /// Returns the HTTP endpoint URL for the DevTools JSON API.
@@ -181,10 +369,7 @@ impl DevTools {
     /// ```
     pub fn get_url(&self) -> String {
         // Use actual port if started, otherwise use base config port
-        let port = match self.actual_port.try_read() {
-            Ok(lock) => lock.unwrap_or(self.base_config.port()),
-            Err(_) => self.base_config.port(),
-        };
+        let port = self.actual_port().unwrap_or(self.base_config.port());
         format!("http://localhost:{}/json", port)
     }
 
@@ -209,10 +394,7 @@ impl DevTools {
     /// ```
     pub fn get_debugger_url(&self, target_id: &str) -> String {
         // Use actual port if started, otherwise use base config port
-        let port = match self.actual_port.try_read() {
-            Ok(lock) => lock.unwrap_or(self.base_config.port()),
-            Err(_) => self.base_config.port(),
-        };
+        let port = self.actual_port().unwrap_or(self.base_config.port());
         format!("ws://localhost:{}/devtools/page/{}", port, target_id)
     }
 }
@@ -301,6 +483,57 @@ mod tests {
         assert!(result.is_ok(), "Should be able to restart after stop");
     }
 
+    #[tokio::test]
+    async fn test_restart_when_not_running_starts() {
+        let devtools = DevTools::new(DevToolsConfig::default()).unwrap();
+
+        devtools.restart(0).await.unwrap();
+        assert!(devtools.actual_port().is_some());
+
+        devtools.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restart_replaces_running_component() {
+        let devtools = DevTools::new(DevToolsConfig::default()).unwrap();
+        devtools.start(0).await.unwrap();
+
+        let (a, b) = tokio::join!(devtools.restart(0), devtools.restart(0));
+        a.unwrap();
+        b.unwrap();
+
+        let port = devtools.actual_port().unwrap();
+        assert_eq!(
+            devtools.get_url(),
+            format!("http://localhost:{}/json", port)
+        );
+
+        // Exactly one component is running, so a single stop leaves none
+        devtools.stop().await.unwrap();
+        assert!(matches!(
+            devtools.stop().await,
+            Err(DevToolsError::ServerNotRunning)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_version_info_reports_custom_user_agent() {
+        let config = DevToolsConfig::builder()
+            .user_agent("Embedder/3.2 (test)".to_string())
+            .build();
+        let devtools = DevTools::new(config).unwrap();
+
+        let info = devtools.version_info();
+        assert_eq!(info.user_agent, "Embedder/3.2 (test)");
+        assert_eq!(info.browser_name, "CortenBrowser");
+        assert_eq!(info.protocol_version, "1.3");
+
+        // Survives a start, which rebuilds the component config
+        devtools.start(0).await.unwrap();
+        assert_eq!(devtools.version_info(), info);
+        devtools.stop().await.unwrap();
+    }
+
     #[test]
     fn test_get_url_with_default_port() {
         // Test getting the DevTools URL
@@ -326,6 +559,29 @@ mod tests {
         assert_eq!(url, "http://localhost:8080/json");
     }
 
+    #[tokio::test]
+    async fn test_urls_report_ephemeral_port() {
+        let config = DevToolsConfig::builder().port(0).build();
+        let devtools = DevTools::new(config).unwrap();
+        assert_eq!(devtools.actual_port(), None);
+
+        devtools.start(0).await.unwrap();
+        let port = devtools.actual_port().unwrap();
+        assert_ne!(port, 0);
+        assert_eq!(
+            devtools.get_url(),
+            format!("http://localhost:{}/json", port)
+        );
+        assert_eq!(
+            devtools.get_debugger_url("page-1"),
+            format!("ws://localhost:{}/devtools/page/page-1", port)
+        );
+
+        devtools.stop().await.unwrap();
+        assert_eq!(devtools.actual_port(), None);
+        assert_eq!(devtools.get_url(), "http://localhost:0/json");
+    }
+
     #[test]
     fn test_get_debugger_url() {
         // Test getting the WebSocket debugger URL
@@ -368,6 +624,99 @@ mod tests {
         assert!(result.is_err(), "Should not be able to start server twice");
     }
 
+    /// Route a message through the running component's domains
+    async fn route(devtools: &DevTools, message: &str) -> String {
+        let component = devtools.component.read().await;
+        component.as_ref().unwrap().handle_message(message).await
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_routed_requests() {
+        let devtools = DevTools::new(DevToolsConfig::default()).unwrap();
+        assert_eq!(devtools.metrics().await, DevToolsMetrics::default());
+
+        devtools.start(0).await.unwrap();
+
+        let response = route(&devtools, r#"{"id": 1, "method": "Browser.getVersion"}"#).await;
+        assert!(response.contains("\"result\""));
+        route(&devtools, r#"{"id": 2, "method": "Page.enable"}"#).await;
+
+        let metrics = devtools.metrics().await;
+        assert_eq!(metrics.total_requests, 2);
+        assert_eq!(metrics.active_sessions, 0);
+
+        devtools.stop().await.unwrap();
+        assert_eq!(devtools.metrics().await, DevToolsMetrics::default());
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_ready_accepts_connections() {
+        let devtools = DevTools::new(DevToolsConfig::default()).unwrap();
+        devtools.start(0).await.unwrap();
+
+        devtools
+            .wait_until_ready(Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let port = devtools.actual_port().unwrap();
+        assert!(TcpStream::connect(("127.0.0.1", port)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_ready_requires_running_server() {
+        let devtools = DevTools::new(DevToolsConfig::default()).unwrap();
+        let result = devtools.wait_until_ready(Duration::from_millis(50)).await;
+        assert!(matches!(result, Err(DevToolsError::ServerNotRunning)));
+    }
+
+    #[tokio::test]
+    async fn test_with_handler_routes_to_custom_domain() {
+        use async_trait::async_trait;
+        use cdp_types::CdpError;
+        use protocol_handler::DomainHandler;
+        use serde_json::{json, Value};
+
+        struct EchoDomain;
+
+        #[async_trait]
+        impl DomainHandler for EchoDomain {
+            fn name(&self) -> &str {
+                "Echo"
+            }
+
+            async fn handle_method(
+                &self,
+                method: &str,
+                params: Option<Value>,
+            ) -> std::result::Result<Value, CdpError> {
+                match method {
+                    "echo" => Ok(params.unwrap_or(Value::Null)),
+                    _ => Err(CdpError::method_not_found(format!("Echo.{}", method))),
+                }
+            }
+        }
+
+        let handler = Arc::new(ProtocolHandler::new());
+        handler.register_domain(Arc::new(EchoDomain));
+        let devtools = DevTools::with_handler(DevToolsConfig::default(), handler).unwrap();
+        devtools.start(0).await.unwrap();
+
+        let response = route(
+            &devtools,
+            r#"{"id": 1, "method": "Echo.echo", "params": {"v": 7}}"#,
+        )
+        .await;
+        let response: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["result"], json!({"v": 7}));
+
+        // Default domains are not registered
+        let response = route(&devtools, r#"{"id": 2, "method": "Browser.getVersion"}"#).await;
+        assert!(response.contains("\"error\""));
+
+        devtools.stop().await.unwrap();
+    }
+
     #[test]
     fn test_config_reexport() {
         // Verify that DevToolsConfig is properly re-exported
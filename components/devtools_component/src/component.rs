@@ -1,20 +1,25 @@
 //! Main DevTools component implementation
 
-use crate::{DevToolsConfig, DevToolsError, Result};
+use crate::{DevToolsConfig, DevToolsError, DevToolsMetrics, Result, VersionInfo};
 use cdp_server::{CdpWebSocketServer, ServerConfig};
 use protocol_handler::ProtocolHandler;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
 use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Instant;
+use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinHandle;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 // Import all domain handlers
 use browser_page_domains::{BrowserDomain, EmulationDomain, PageDomain, SecurityDomain};
-use console_storage::{ConsoleDomain, StorageDomain};
+use console_storage::{ConsoleDomain, CookieStore, StorageDomain};
 use dom_domain::{CssDomain, DomDomain};
 use network_domain::NetworkDomain;
-use profiler_domains::{HeapProfilerDomain, ProfilerDomain};
+use profiler_domains::{HeapProfilerDomain, MemoryProfiler, ProfilerDomain};
+use runtime_debugger::runtime::DEFAULT_EXECUTION_CONTEXT_ID;
 use runtime_debugger::{DebuggerDomain, RuntimeDomain};
 
 /// Main DevTools component that orchestrates all domains and the CDP server
@@ -50,19 +55,33 @@ pub struct DevToolsComponent {
     config: DevToolsConfig,
 
     /// Protocol handler that routes messages to domains
-    /// Note: Currently registered with all domains but not yet integrated with server message handling.
-    /// Future enhancement will connect this to the server's message processing.
-    #[allow(dead_code)]
     protocol_handler: Arc<ProtocolHandler>,
 
     /// Server task handle (when running)
     server_handle: Arc<RwLock<Option<JoinHandle<()>>>>,
 
+    /// Running server (kept for session inspection)
+    server: Arc<RwLock<Option<Arc<CdpWebSocketServer>>>>,
+
+    /// When the server was started (when running)
+    started_at: Arc<RwLock<Option<Instant>>>,
+
     /// Whether the server is currently running
     running: Arc<AtomicBool>,
 
     /// Actual port the server is bound to (may differ from config if using ephemeral port)
     actual_port: Arc<AtomicU16>,
+
+    /// Connected sessions that receive events, keyed by session ID
+    event_sessions: Arc<RwLock<HashMap<String, EventSession>>>,
+}
+
+/// Event delivery state for one connected session
+struct EventSession {
+    /// Where serialized events are sent
+    sink: mpsc::UnboundedSender<String>,
+    /// Domains this session has enabled; only their events are delivered
+    enabled_domains: HashSet<String>,
 }
 
 impl DevToolsComponent {
@@ -98,12 +117,38 @@ impl DevToolsComponent {
         // Register all domains
         Self::register_all_domains(&protocol_handler)?;
 
+        Self::with_handler(config, protocol_handler)
+    }
+
+    /// Create a DevToolsComponent that routes through a caller-supplied handler
+    ///
+    /// Unlike [`DevToolsComponent::new`], no domains are registered; the
+    /// handler is used exactly as given, including any middleware or policy
+    /// already installed on it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use devtools_component::{DevToolsComponent, DevToolsConfig};
+    /// use protocol_handler::ProtocolHandler;
+    /// use std::sync::Arc;
+    ///
+    /// let handler = Arc::new(ProtocolHandler::new());
+    /// let devtools = DevToolsComponent::with_handler(DevToolsConfig::default(), handler).unwrap();
+    /// ```
+    pub fn with_handler(
+        config: DevToolsConfig,
+        protocol_handler: Arc<ProtocolHandler>,
+    ) -> Result<Self> {
         Ok(Self {
             config,
             protocol_handler,
             server_handle: Arc::new(RwLock::new(None)),
+            server: Arc::new(RwLock::new(None)),
+            started_at: Arc::new(RwLock::new(None)),
             running: Arc::new(AtomicBool::new(false)),
             actual_port: Arc::new(AtomicU16::new(0)),
+            event_sessions: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
@@ -121,7 +166,9 @@ impl DevToolsComponent {
 
         // Browser/Page domains (4)
         handler.register_domain(Arc::new(BrowserDomain::new()));
-        handler.register_domain(Arc::new(PageDomain::new()));
+        // Page clones share state, so the navigation callback can be set later
+        let page = PageDomain::new();
+        handler.register_domain(Arc::new(page.clone()));
         handler.register_domain(Arc::new(SecurityDomain::new()));
         handler.register_domain(Arc::new(EmulationDomain::new()));
 
@@ -129,20 +176,38 @@ impl DevToolsComponent {
         handler.register_domain(Arc::new(DomDomain::new()));
         handler.register_domain(Arc::new(CssDomain::new()));
 
+        // Network and Storage share one cookie jar
+        let cookie_store = Arc::new(CookieStore::new());
+
         // Network domain (1)
-        handler.register_domain(Arc::new(NetworkDomain::new()));
+        let network = NetworkDomain::with_cookie_store(Arc::clone(&cookie_store));
+        handler.register_domain(Arc::new(network));
 
         // Runtime/Debugger domains (2)
-        handler.register_domain(Arc::new(RuntimeDomain::new()));
+        // Runtime.discardConsoleEntries clears the Console domain's buffer and
+        // Runtime.getHeapUsage reads the heap profiler's allocation stats
+        let console = Arc::new(ConsoleDomain::new());
+        let memory_profiler = Arc::new(MemoryProfiler::new());
+        let runtime = Arc::new(RuntimeDomain::new());
+        let discard_target = Arc::clone(&console);
+        runtime.set_console_discard_callback(Arc::new(move || discard_target.clear()));
+        runtime.set_memory_provider(memory_profiler.clone());
+        handler.register_domain(runtime.clone());
+        // Navigation destroys the page's execution context and its objects
+        let navigated_runtime = Arc::clone(&runtime);
+        page.set_navigation_callback(Arc::new(move || {
+            navigated_runtime.release_execution_context(DEFAULT_EXECUTION_CONTEXT_ID);
+        }));
         handler.register_domain(Arc::new(DebuggerDomain::new()));
 
         // Profiler domains (2)
         handler.register_domain(Arc::new(ProfilerDomain::new()));
-        handler.register_domain(Arc::new(HeapProfilerDomain::new()));
+        let heap_profiler = HeapProfilerDomain::with_memory_profiler(memory_profiler);
+        handler.register_domain(Arc::new(heap_profiler));
 
         // Console/Storage domains (2)
-        handler.register_domain(Arc::new(ConsoleDomain::new()));
-        handler.register_domain(Arc::new(StorageDomain::new()));
+        handler.register_domain(console);
+        handler.register_domain(Arc::new(StorageDomain::with_cookie_store(cookie_store)));
 
         info!("Successfully registered 13 CDP domain handlers");
 
@@ -196,36 +261,47 @@ impl DevToolsComponent {
         // Mark as running before spawning task
         self.running.store(true, Ordering::SeqCst);
 
+        // Metrics count activity since this start
+        self.protocol_handler.reset_counters();
+
         // Create server configuration
         let server_config = ServerConfig {
             port: actual_port,
             bind_address: "127.0.0.1".to_string(),
             allowed_origins: self.config.allowed_origins().to_vec(),
             max_message_size: self.config.max_message_size(),
+            ..Default::default()
         };
 
         // Create server
-        let server = CdpWebSocketServer::new(server_config)?;
+        let server = Arc::new(CdpWebSocketServer::new(server_config)?);
 
         // Spawn server in background task
         // Note: We drop the listener here - the server will create its own
         drop(listener);
 
+        let task_server = Arc::clone(&server);
         let handle = tokio::spawn(async move {
-            if let Err(e) = server.start().await {
+            if let Err(e) = task_server.start().await {
                 error!("Server error: {}", e);
             }
         });
 
         // Store task handle
         *self.server_handle.write().await = Some(handle);
+        *self.server.write().await = Some(server);
+        *self.started_at.write().await = Some(Instant::now());
 
         info!(
             "DevTools server started successfully on port {}",
             actual_port
         );
-        info!("WebSocket URL: ws://localhost:{}", actual_port);
-        info!("JSON endpoint: http://localhost:{}/json", actual_port);
+        info!(
+            "WebSocket URL: ws://localhost:{}{}",
+            actual_port,
+            self.config.path_prefix()
+        );
+        info!("JSON endpoint: {}", self.get_json_url());
 
         Ok(())
     }
@@ -271,6 +347,9 @@ impl DevToolsComponent {
             let _ = handle.await;
         }
 
+        *self.server.write().await = None;
+        *self.started_at.write().await = None;
+
         // Mark as not running
         self.running.store(false, Ordering::SeqCst);
         self.actual_port.store(0, Ordering::SeqCst);
@@ -309,6 +388,161 @@ impl DevToolsComponent {
         }
     }
 
+    /// Address the running server is bound to
+    ///
+    /// Returns `None` when the server is not running.
+    pub async fn local_addr(&self) -> Option<SocketAddr> {
+        if !self.is_running() {
+            return None;
+        }
+        self.server
+            .read()
+            .await
+            .as_ref()
+            .and_then(|server| server.config().socket_addr().ok())
+    }
+
+    /// Route a raw CDP message through the registered domains
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - JSON string containing the CDP request
+    ///
+    /// # Returns
+    ///
+    /// JSON string containing the CDP response
+    pub async fn handle_message(&self, message: &str) -> String {
+        self.protocol_handler.handle_message(message).await
+    }
+
+    /// Register a session that receives events through `sink`
+    ///
+    /// The session starts with no domains enabled, so it receives no events
+    /// until it calls `<Domain>.enable` through `handle_session_message`.
+    pub async fn connect_session(
+        &self,
+        session_id: impl Into<String>,
+        sink: mpsc::UnboundedSender<String>,
+    ) {
+        let session_id = session_id.into();
+        debug!("Connecting event session {}", session_id);
+        self.event_sessions.write().await.insert(
+            session_id,
+            EventSession {
+                sink,
+                enabled_domains: HashSet::new(),
+            },
+        );
+    }
+
+    /// Forget a session and its enabled domains
+    pub async fn disconnect_session(&self, session_id: &str) -> bool {
+        self.event_sessions
+            .write()
+            .await
+            .remove(session_id)
+            .is_some()
+    }
+
+    /// Route a CDP message sent by `session_id`
+    ///
+    /// A successful `<Domain>.enable` or `<Domain>.disable` updates which
+    /// domains' events the session receives.
+    pub async fn handle_session_message(&self, session_id: &str, message: &str) -> String {
+        let response = self.protocol_handler.handle_message(message).await;
+
+        let method = serde_json::from_str::<Value>(message)
+            .ok()
+            .and_then(|m| m.get("method").and_then(|v| v.as_str()).map(str::to_string));
+        let succeeded = serde_json::from_str::<Value>(&response)
+            .map(|r| r.get("error").is_none())
+            .unwrap_or(false);
+        if let (Some(method), true) = (method, succeeded) {
+            if let Some((domain, action)) = method.split_once('.') {
+                let mut sessions = self.event_sessions.write().await;
+                if let Some(session) = sessions.get_mut(session_id) {
+                    match action {
+                        "enable" => {
+                            session.enabled_domains.insert(domain.to_string());
+                        }
+                        "disable" => {
+                            session.enabled_domains.remove(domain);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        response
+    }
+
+    /// Domains `session_id` has enabled, sorted
+    pub async fn enabled_domains(&self, session_id: &str) -> Vec<String> {
+        let mut domains: Vec<String> = self
+            .event_sessions
+            .read()
+            .await
+            .get(session_id)
+            .map(|s| s.enabled_domains.iter().cloned().collect())
+            .unwrap_or_default();
+        domains.sort();
+        domains
+    }
+
+    /// Send an event to every session that enabled its domain
+    ///
+    /// As in CDP, a session only receives `<Domain>.*` events after calling
+    /// `<Domain>.enable`. Returns the number of sessions the event reached.
+    pub async fn emit_event(&self, method: &str, params: Value) -> usize {
+        let domain = method.split_once('.').map(|(d, _)| d).unwrap_or(method);
+        let event = json!({ "method": method, "params": params }).to_string();
+
+        let mut delivered = 0;
+        for (session_id, session) in self.event_sessions.read().await.iter() {
+            if !session.enabled_domains.contains(domain) {
+                continue;
+            }
+            if session.sink.send(event.clone()).is_err() {
+                warn!("Event sink for session {} is closed", session_id);
+                continue;
+            }
+            self.protocol_handler.record_event();
+            delivered += 1;
+        }
+        delivered
+    }
+
+    /// Get a snapshot of server activity
+    ///
+    /// Returns zeroed metrics when the server is not running.
+    pub async fn metrics(&self) -> DevToolsMetrics {
+        if !self.is_running() {
+            return DevToolsMetrics::default();
+        }
+
+        let active_sessions = self
+            .server
+            .read()
+            .await
+            .as_ref()
+            .map(|server| server.get_sessions().len())
+            .unwrap_or(0);
+        let uptime = self
+            .started_at
+            .read()
+            .await
+            .map(|started| started.elapsed())
+            .unwrap_or_default();
+
+        DevToolsMetrics {
+            active_sessions,
+            total_requests: self.protocol_handler.requests_handled(),
+            total_events: self.protocol_handler.events_emitted(),
+            uptime,
+        }
+    }
+
     /// Get the configuration used by this component
     ///
     /// # Returns
@@ -318,6 +552,11 @@ impl DevToolsComponent {
         &self.config
     }
 
+    /// Get the browser and protocol identification served at `/json/version`
+    pub fn version_info(&self) -> VersionInfo {
+        self.config.version_info()
+    }
+
     /// Get list of registered domain names
     ///
     /// # Returns
@@ -360,7 +599,8 @@ impl DevToolsComponent {
     ///
     /// # Returns
     ///
-    /// The WebSocket URL for debugging the specified target
+    /// The WebSocket URL for debugging the specified target, including the
+    /// configured path prefix
     ///
     /// # Example
     ///
@@ -374,7 +614,12 @@ impl DevToolsComponent {
     /// ```
     pub fn get_debugger_url(&self, target_id: &str) -> String {
         let port = self.actual_port().unwrap_or(self.config.port());
-        format!("ws://localhost:{}/devtools/page/{}", port, target_id)
+        format!(
+            "ws://localhost:{}{}/devtools/page/{}",
+            port,
+            self.config.path_prefix(),
+            target_id
+        )
     }
 
     /// Get the JSON endpoint URL
@@ -383,7 +628,7 @@ impl DevToolsComponent {
     ///
     /// # Returns
     ///
-    /// The HTTP URL for the JSON endpoint
+    /// The HTTP URL for the JSON endpoint, including the configured path prefix
     ///
     /// # Example
     ///
@@ -397,7 +642,7 @@ impl DevToolsComponent {
     /// ```
     pub fn get_json_url(&self) -> String {
         let port = self.actual_port().unwrap_or(self.config.port());
-        format!("http://localhost:{}/json", port)
+        format!("http://localhost:{}{}/json", port, self.config.path_prefix())
     }
 }
 
@@ -421,6 +666,107 @@ mod tests {
         assert_eq!(component.config().port(), 8888);
     }
 
+    #[tokio::test]
+    async fn test_with_handler_uses_supplied_domains() {
+        let handler = Arc::new(ProtocolHandler::new());
+        handler.register_domain(Arc::new(BrowserDomain::new()));
+        let component =
+            DevToolsComponent::with_handler(DevToolsConfig::default(), handler).unwrap();
+
+        let response = component
+            .handle_message(r#"{"id": 1, "method": "Browser.getVersion"}"#)
+            .await;
+        assert!(response.contains("\"result\""));
+
+        let response = component
+            .handle_message(r#"{"id": 2, "method": "Page.enable"}"#)
+            .await;
+        assert!(response.contains("\"error\""));
+    }
+
+    #[tokio::test]
+    async fn test_events_only_reach_sessions_that_enabled_the_domain() {
+        let component = DevToolsComponent::new(DevToolsConfig::default()).unwrap();
+        let (enabled_tx, mut enabled_rx) = mpsc::unbounded_channel();
+        let (other_tx, mut other_rx) = mpsc::unbounded_channel();
+        component.connect_session("enabled", enabled_tx).await;
+        component.connect_session("other", other_tx).await;
+
+        let response = component
+            .handle_session_message("enabled", r#"{"id": 1, "method": "Network.enable"}"#)
+            .await;
+        assert!(response.contains("\"result\""));
+        assert_eq!(component.enabled_domains("enabled").await, vec!["Network"]);
+        assert!(component.enabled_domains("other").await.is_empty());
+
+        let delivered = component
+            .emit_event("Network.responseReceived", json!({"requestId": "r1"}))
+            .await;
+        assert_eq!(delivered, 1);
+
+        let event: Value = serde_json::from_str(&enabled_rx.try_recv().unwrap()).unwrap();
+        assert_eq!(event["method"], "Network.responseReceived");
+        assert_eq!(event["params"]["requestId"], "r1");
+        assert!(other_rx.try_recv().is_err());
+
+        component
+            .handle_session_message("enabled", r#"{"id": 2, "method": "Network.disable"}"#)
+            .await;
+        let delivered = component
+            .emit_event("Network.responseReceived", json!({"requestId": "r2"}))
+            .await;
+        assert_eq!(delivered, 0);
+        assert!(enabled_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_network_and_storage_share_cookies() {
+        let component = DevToolsComponent::new(DevToolsConfig::default()).unwrap();
+
+        let response = component
+            .handle_message(
+                r#"{"id": 1, "method": "Storage.setCookie", "params": {"name": "session", "value": "abc", "domain": "example.com"}}"#,
+            )
+            .await;
+        assert!(response.contains("\"result\""));
+
+        let response = component
+            .handle_message(r#"{"id": 2, "method": "Network.getAllCookies"}"#)
+            .await;
+        assert!(response.contains("\"session\""));
+
+        component
+            .handle_message(r#"{"id": 3, "method": "Network.clearBrowserCookies"}"#)
+            .await;
+        let response: Value = serde_json::from_str(
+            &component
+                .handle_message(r#"{"id": 4, "method": "Storage.getCookies"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["result"]["cookies"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_runtime_heap_usage_comes_from_memory_profiler() {
+        let component = DevToolsComponent::new(DevToolsConfig::default()).unwrap();
+        component
+            .handle_message(
+                r#"{"id": 1, "method": "Runtime.evaluate", "params": {"expression": "[1, 2, 3]"}}"#,
+            )
+            .await;
+
+        // Without the profiler the runtime would estimate from its objects
+        let response: Value = serde_json::from_str(
+            &component
+                .handle_message(r#"{"id": 2, "method": "Runtime.getHeapUsage"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["result"]["usedSize"], 0);
+        assert_eq!(response["result"]["totalSize"], 0);
+    }
+
     #[test]
     fn test_registered_domains() {
         let component = DevToolsComponent::new(DevToolsConfig::default()).unwrap();
@@ -468,6 +814,32 @@ mod tests {
         assert_eq!(url, "http://localhost:9222/json");
     }
 
+    #[test]
+    fn test_urls_include_path_prefix() {
+        let config = DevToolsConfig::builder()
+            .port(9222)
+            .path_prefix("/devtools-proxy".to_string())
+            .build();
+        let component = DevToolsComponent::new(config).unwrap();
+
+        assert_eq!(
+            component.get_json_url(),
+            "http://localhost:9222/devtools-proxy/json"
+        );
+        assert_eq!(
+            component.get_debugger_url("test-page"),
+            "ws://localhost:9222/devtools-proxy/devtools/page/test-page"
+        );
+
+        // Generated URLs resolve back through the prefixed routes
+        let debugger_url = component.get_debugger_url("test-page");
+        let path = debugger_url.trim_start_matches("ws://localhost:9222");
+        assert_eq!(
+            component.config().resolve_route(path),
+            Some(crate::HttpRoute::DevToolsPage("test-page".to_string()))
+        );
+    }
+
     #[tokio::test]
     async fn test_start_stop_lifecycle() {
         let config = DevToolsConfig::builder().port(0).build();
@@ -521,6 +893,48 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_metrics_zeroed_when_not_running() {
+        let component = DevToolsComponent::new(DevToolsConfig::default()).unwrap();
+        component
+            .handle_message(r#"{"id": 1, "method": "Browser.getVersion"}"#)
+            .await;
+
+        assert_eq!(component.metrics().await, DevToolsMetrics::default());
+    }
+
+    #[tokio::test]
+    async fn test_local_addr_reports_bound_address() {
+        let config = DevToolsConfig::builder().port(0).build();
+        let component = DevToolsComponent::new(config).unwrap();
+        assert_eq!(component.local_addr().await, None);
+
+        component.start().await.unwrap();
+        let addr = component.local_addr().await.unwrap();
+        assert!(addr.ip().is_loopback());
+        assert_eq!(Some(addr.port()), component.actual_port());
+
+        component.stop().await.unwrap();
+        assert_eq!(component.local_addr().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_reset_on_restart() {
+        let config = DevToolsConfig::builder().port(0).build();
+        let component = DevToolsComponent::new(config).unwrap();
+
+        component.start().await.unwrap();
+        component
+            .handle_message(r#"{"id": 1, "method": "Browser.getVersion"}"#)
+            .await;
+        assert_eq!(component.metrics().await.total_requests, 1);
+        component.stop().await.unwrap();
+
+        component.start().await.unwrap();
+        assert_eq!(component.metrics().await.total_requests, 0);
+        component.stop().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_can_restart() {
         let config = DevToolsConfig::builder().port(0).build();
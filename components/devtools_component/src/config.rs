@@ -22,6 +22,56 @@ pub struct DevToolsConfig {
 
     /// Chrome DevTools Protocol version
     protocol_version: String,
+
+    /// Path prefix for HTTP routes and WebSocket URLs (e.g. "/devtools-proxy")
+    #[serde(default)]
+    path_prefix: String,
+
+    /// Browser name reported by `/json/version`
+    #[serde(default = "default_browser_name")]
+    browser_name: String,
+
+    /// Browser version reported by `/json/version`
+    #[serde(default = "default_browser_version")]
+    browser_version: String,
+
+    /// User-Agent string reported by `/json/version`
+    #[serde(default = "default_user_agent")]
+    user_agent: String,
+}
+
+/// Browser and protocol identification served at `/json/version`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionInfo {
+    /// Browser name (e.g. "CortenBrowser")
+    pub browser_name: String,
+    /// Browser version (e.g. "1.0")
+    pub browser_version: String,
+    /// User-Agent string
+    pub user_agent: String,
+    /// Chrome DevTools Protocol version
+    pub protocol_version: String,
+}
+
+impl VersionInfo {
+    /// Product string in `name/version` form, as used by the `Browser` field
+    pub fn product(&self) -> String {
+        format!("{}/{}", self.browser_name, self.browser_version)
+    }
+}
+
+/// HTTP endpoint resolved from a request path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpRoute {
+    /// `/json` or `/json/list` - list of debugging targets
+    TargetList,
+    /// `/json/version` - browser and protocol version
+    Version,
+    /// `/health` - liveness check
+    Health,
+    /// `/devtools/page/{id}` - WebSocket endpoint for a target
+    DevToolsPage(String),
 }
 
 impl DevToolsConfig {
@@ -65,6 +115,80 @@ impl DevToolsConfig {
     pub fn protocol_version(&self) -> &str {
         &self.protocol_version
     }
+
+    /// Get the browser name
+    pub fn browser_name(&self) -> &str {
+        &self.browser_name
+    }
+
+    /// Get the browser version
+    pub fn browser_version(&self) -> &str {
+        &self.browser_version
+    }
+
+    /// Get the User-Agent string
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Get the identification served at `/json/version`
+    pub fn version_info(&self) -> VersionInfo {
+        VersionInfo {
+            browser_name: self.browser_name.clone(),
+            browser_version: self.browser_version.clone(),
+            user_agent: self.user_agent.clone(),
+            protocol_version: self.protocol_version.clone(),
+        }
+    }
+
+    /// Get the path prefix (empty, or starting with `/` without a trailing `/`)
+    pub fn path_prefix(&self) -> &str {
+        &self.path_prefix
+    }
+
+    /// Resolve a request path to an HTTP route, honoring the path prefix
+    ///
+    /// Returns `None` for paths outside the prefix or unknown endpoints.
+    pub fn resolve_route(&self, path: &str) -> Option<HttpRoute> {
+        let path = path.split('?').next().unwrap_or(path);
+        let path = path.strip_prefix(self.path_prefix.as_str())?;
+
+        match path.trim_end_matches('/') {
+            "/json" | "/json/list" => Some(HttpRoute::TargetList),
+            "/json/version" => Some(HttpRoute::Version),
+            "/health" => Some(HttpRoute::Health),
+            other => other
+                .strip_prefix("/devtools/page/")
+                .filter(|id| !id.is_empty() && !id.contains('/'))
+                .map(|id| HttpRoute::DevToolsPage(id.to_string())),
+        }
+    }
+}
+
+fn default_browser_name() -> String {
+    "CortenBrowser".to_string()
+}
+
+fn default_browser_version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+fn default_user_agent() -> String {
+    format!(
+        "Mozilla/5.0 (X11; Linux x86_64) {}/{}",
+        default_browser_name(),
+        default_browser_version()
+    )
+}
+
+/// Normalize a path prefix to "" or "/segment[/segment...]"
+fn normalize_path_prefix(prefix: &str) -> String {
+    let trimmed = prefix.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
 }
 
 impl Default for DevToolsConfig {
@@ -76,6 +200,10 @@ impl Default for DevToolsConfig {
     /// - allowed_origins: ["http://localhost:*"]
     /// - max_message_size: 100 MB
     /// - protocol_version: "1.3"
+    /// - path_prefix: "" (no prefix)
+    /// - browser_name: "CortenBrowser"
+    /// - browser_version: the crate version
+    /// - user_agent: "Mozilla/5.0 (X11; Linux x86_64) CortenBrowser/<version>"
     fn default() -> Self {
         Self {
             port: 9222,
@@ -83,6 +211,10 @@ impl Default for DevToolsConfig {
             allowed_origins: vec!["http://localhost:*".to_string()],
             max_message_size: 100 * 1024 * 1024, // 100 MB
             protocol_version: "1.3".to_string(),
+            path_prefix: String::new(),
+            browser_name: default_browser_name(),
+            browser_version: default_browser_version(),
+            user_agent: default_user_agent(),
         }
     }
 }
@@ -97,6 +229,10 @@ pub struct DevToolsConfigBuilder {
     allowed_origins: Vec<String>,
     max_message_size: Option<usize>,
     protocol_version: Option<String>,
+    path_prefix: Option<String>,
+    browser_name: Option<String>,
+    browser_version: Option<String>,
+    user_agent: Option<String>,
 }
 
 impl DevToolsConfigBuilder {
@@ -150,6 +286,46 @@ impl DevToolsConfigBuilder {
         self
     }
 
+    /// Set the path prefix for HTTP routes and WebSocket URLs
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Subpath the server is reachable at (e.g., "/devtools-proxy")
+    pub fn path_prefix(mut self, prefix: String) -> Self {
+        self.path_prefix = Some(prefix);
+        self
+    }
+
+    /// Set the browser name reported by `/json/version`
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Browser name (e.g., "CortenBrowser")
+    pub fn browser_name(mut self, name: String) -> Self {
+        self.browser_name = Some(name);
+        self
+    }
+
+    /// Set the browser version reported by `/json/version`
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - Browser version (e.g., "1.0")
+    pub fn browser_version(mut self, version: String) -> Self {
+        self.browser_version = Some(version);
+        self
+    }
+
+    /// Set the User-Agent string reported by `/json/version`
+    ///
+    /// # Arguments
+    ///
+    /// * `user_agent` - Full User-Agent string
+    pub fn user_agent(mut self, user_agent: String) -> Self {
+        self.user_agent = Some(user_agent);
+        self
+    }
+
     /// Build the DevToolsConfig
     ///
     /// Uses default values for any options not explicitly set.
@@ -169,6 +345,13 @@ impl DevToolsConfigBuilder {
             allowed_origins,
             max_message_size: self.max_message_size.unwrap_or(default.max_message_size),
             protocol_version: self.protocol_version.unwrap_or(default.protocol_version),
+            path_prefix: self
+                .path_prefix
+                .map(|prefix| normalize_path_prefix(&prefix))
+                .unwrap_or(default.path_prefix),
+            browser_name: self.browser_name.unwrap_or(default.browser_name),
+            browser_version: self.browser_version.unwrap_or(default.browser_version),
+            user_agent: self.user_agent.unwrap_or(default.user_agent),
         }
     }
 }
@@ -237,4 +420,69 @@ mod tests {
         assert_eq!(config.max_message_size(), default.max_message_size());
         assert_eq!(config.protocol_version(), default.protocol_version());
     }
+
+    #[test]
+    fn test_version_info() {
+        let info = DevToolsConfig::default().version_info();
+        assert_eq!(info.browser_name, "CortenBrowser");
+        assert!(info.user_agent.contains("CortenBrowser/"));
+        assert_eq!(info.protocol_version, "1.3");
+
+        let config = DevToolsConfig::builder()
+            .browser_name("Corten".to_string())
+            .browser_version("2.1".to_string())
+            .user_agent("CustomAgent/9".to_string())
+            .build();
+        let info = config.version_info();
+        assert_eq!(info.product(), "Corten/2.1");
+        assert_eq!(info.user_agent, "CustomAgent/9");
+    }
+
+    #[test]
+    fn test_path_prefix_normalized() {
+        assert_eq!(DevToolsConfig::default().path_prefix(), "");
+
+        let config = DevToolsConfig::builder()
+            .path_prefix("devtools-proxy/".to_string())
+            .build();
+        assert_eq!(config.path_prefix(), "/devtools-proxy");
+
+        let config = DevToolsConfig::builder().path_prefix("/".to_string()).build();
+        assert_eq!(config.path_prefix(), "");
+    }
+
+    #[test]
+    fn test_resolve_route_with_prefix() {
+        let config = DevToolsConfig::builder()
+            .path_prefix("/devtools-proxy".to_string())
+            .build();
+
+        assert_eq!(
+            config.resolve_route("/devtools-proxy/json"),
+            Some(HttpRoute::TargetList)
+        );
+        assert_eq!(
+            config.resolve_route("/devtools-proxy/json/version"),
+            Some(HttpRoute::Version)
+        );
+        assert_eq!(
+            config.resolve_route("/devtools-proxy/health"),
+            Some(HttpRoute::Health)
+        );
+        assert_eq!(
+            config.resolve_route("/devtools-proxy/devtools/page/page-1"),
+            Some(HttpRoute::DevToolsPage("page-1".to_string()))
+        );
+        assert_eq!(config.resolve_route("/json"), None);
+        assert_eq!(config.resolve_route("/devtools-proxy/unknown"), None);
+    }
+
+    #[test]
+    fn test_resolve_route_without_prefix() {
+        let config = DevToolsConfig::default();
+
+        assert_eq!(config.resolve_route("/json/list"), Some(HttpRoute::TargetList));
+        assert_eq!(config.resolve_route("/json?for=tab"), Some(HttpRoute::TargetList));
+        assert_eq!(config.resolve_route("/devtools/page/"), None);
+    }
 }
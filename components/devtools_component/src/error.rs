@@ -25,6 +25,10 @@ pub enum DevToolsError {
     #[error("Invalid configuration: {0}")]
     InvalidConfiguration(String),
 
+    /// Timed out waiting for the server
+    #[error("Timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
     /// CDP server error
     #[error("CDP server error: {0}")]
     CdpServerError(#[from] cdp_server::CdpServerError),
@@ -55,5 +59,8 @@ mod tests {
 
         let err = DevToolsError::InvalidConfiguration("test".to_string());
         assert_eq!(err.to_string(), "Invalid configuration: test");
+
+        let err = DevToolsError::Timeout(std::time::Duration::from_millis(250));
+        assert_eq!(err.to_string(), "Timed out after 250ms");
     }
 }
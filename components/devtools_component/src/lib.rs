@@ -24,10 +24,12 @@
 mod component;
 mod config;
 mod error;
+mod metrics;
 
 pub use component::DevToolsComponent;
-pub use config::{DevToolsConfig, DevToolsConfigBuilder};
+pub use config::{DevToolsConfig, DevToolsConfigBuilder, HttpRoute, VersionInfo};
 pub use error::{DevToolsError, Result};
+pub use metrics::DevToolsMetrics;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,32 @@
+//! Runtime metrics for the DevTools component
+
+use std::time::Duration;
+
+/// Point-in-time snapshot of DevTools activity
+///
+/// All counters are zero when the server is not running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DevToolsMetrics {
+    /// Number of currently connected sessions
+    pub active_sessions: usize,
+    /// Total CDP requests handled since the server started
+    pub total_requests: u64,
+    /// Total CDP events emitted since the server started
+    pub total_events: u64,
+    /// Time since the server started
+    pub uptime: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_metrics_are_zeroed() {
+        let metrics = DevToolsMetrics::default();
+        assert_eq!(metrics.active_sessions, 0);
+        assert_eq!(metrics.total_requests, 0);
+        assert_eq!(metrics.total_events, 0);
+        assert_eq!(metrics.uptime, Duration::ZERO);
+    }
+}
@@ -7,9 +7,10 @@
 //! - Accessibility tree inspection
 //! - ARIA attributes inspection
 //! - Contrast ratio checking
+//! - ARIA validation (role context, owned elements, supported attributes)
 
 use async_trait::async_trait;
-use cdp_types::domains::dom::NodeId;
+use cdp_types::domains::dom::{Node, NodeId, NodeType};
 use cdp_types::CdpError;
 use protocol_handler::DomainHandler;
 use serde::{Deserialize, Serialize};
@@ -286,6 +287,226 @@ pub struct AriaAttribute {
     pub value: String,
 }
 
+/// Kind of ARIA authoring mistake
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AriaIssueCode {
+    /// Role requires a particular parent role that is missing
+    MissingRequiredContext,
+    /// Role requires owned elements of a particular role that are missing
+    MissingRequiredOwnedElements,
+    /// ARIA attribute is not supported by the node's role
+    UnsupportedAttribute,
+}
+
+/// ARIA validation issue for a node
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AriaIssue {
+    /// Node the issue was found on
+    pub node_id: NodeId,
+    /// Issue code
+    pub code: AriaIssueCode,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Text node whose contrast falls below WCAG AA
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContrastIssue {
+    /// Element containing the text
+    pub node_id: NodeId,
+    /// Measured contrast ratio
+    pub contrast_ratio: f64,
+    /// Minimum ratio required for the text size
+    pub threshold: f64,
+}
+
+/// WCAG AA minimum contrast for normal text
+const AA_NORMAL_THRESHOLD: f64 = 4.5;
+/// WCAG AA minimum contrast for large text
+const AA_LARGE_THRESHOLD: f64 = 3.0;
+/// WCAG AAA minimum contrast for normal text
+const AAA_NORMAL_THRESHOLD: f64 = 7.0;
+/// WCAG AAA minimum contrast for large text
+const AAA_LARGE_THRESHOLD: f64 = 4.5;
+
+/// Roles a role must be contained in
+fn required_context_roles(role: &str) -> &'static [&'static str] {
+    match role {
+        "listitem" => &["list", "directory"],
+        "option" => &["listbox", "group"],
+        "tab" => &["tablist"],
+        "menuitem" | "menuitemcheckbox" | "menuitemradio" => &["menu", "menubar", "group"],
+        "treeitem" => &["tree", "group"],
+        "row" => &["table", "grid", "treegrid", "rowgroup"],
+        "cell" | "columnheader" | "gridcell" | "rowheader" => &["row"],
+        _ => &[],
+    }
+}
+
+/// Roles a role must own at least one of
+fn required_owned_roles(role: &str) -> &'static [&'static str] {
+    match role {
+        "list" => &["listitem"],
+        "listbox" => &["option", "group"],
+        "tablist" => &["tab"],
+        "menu" | "menubar" => &["menuitem", "menuitemcheckbox", "menuitemradio", "group"],
+        "tree" => &["treeitem", "group"],
+        "table" | "grid" | "treegrid" => &["row", "rowgroup"],
+        _ => &[],
+    }
+}
+
+/// Roles supporting a role-specific ARIA attribute; `None` for global attributes
+fn supported_roles(attribute: &str) -> Option<&'static [&'static str]> {
+    match attribute {
+        "aria-checked" => Some(&[
+            "checkbox",
+            "menuitemcheckbox",
+            "menuitemradio",
+            "option",
+            "radio",
+            "switch",
+            "treeitem",
+        ]),
+        "aria-selected" => Some(&["gridcell", "option", "row", "tab", "treeitem"]),
+        "aria-pressed" => Some(&["button"]),
+        "aria-level" => Some(&["heading", "listitem", "row", "treeitem"]),
+        "aria-multiselectable" => Some(&["grid", "listbox", "tablist", "tree", "treegrid"]),
+        "aria-required" => Some(&[
+            "checkbox",
+            "combobox",
+            "gridcell",
+            "listbox",
+            "radiogroup",
+            "spinbutton",
+            "textbox",
+            "tree",
+        ]),
+        _ => None,
+    }
+}
+
+/// Get an attribute value from a node's flat attribute list
+fn node_attribute<'a>(node: &'a Node, name: &str) -> Option<&'a str> {
+    node.attributes.as_ref().and_then(|attrs| {
+        attrs
+            .chunks(2)
+            .find(|pair| pair[0] == name)
+            .and_then(|pair| pair.get(1))
+            .map(String::as_str)
+    })
+}
+
+/// Explicit `role`, falling back to the element's implicit role
+///
+/// Returns `None` for generic elements (div, span, etc.).
+fn node_role(node: &Node) -> Option<String> {
+    if let Some(role) = node_attribute(node, "role") {
+        return role.split_whitespace().next().map(str::to_string);
+    }
+
+    let implicit = match node.local_name.as_deref()? {
+        "ul" | "ol" | "menu" => "list",
+        "li" => "listitem",
+        "button" => "button",
+        "a" => "link",
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => "heading",
+        "table" => "table",
+        "tr" => "row",
+        "td" => "cell",
+        "th" => "columnheader",
+        "select" => "listbox",
+        "option" => "option",
+        _ => return None,
+    };
+    Some(implicit.to_string())
+}
+
+/// Parse a computed CSS color (`rgb()`, `rgba()`, hex, or a few keywords)
+fn parse_css_color(value: &str) -> Option<RGBA> {
+    let value = value.trim().to_ascii_lowercase();
+    match value.as_str() {
+        "black" => return Some(RGBA::new(0, 0, 0, 1.0)),
+        "white" => return Some(RGBA::new(255, 255, 255, 1.0)),
+        "transparent" => return Some(RGBA::new(0, 0, 0, 0.0)),
+        _ => {}
+    }
+
+    if let Some(hex) = value.strip_prefix('#') {
+        let expanded: String = match hex.len() {
+            3 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 => hex.to_string(),
+            _ => return None,
+        };
+        let channel = |i: usize| u8::from_str_radix(&expanded[i..i + 2], 16).ok();
+        return Some(RGBA::new(channel(0)?, channel(2)?, channel(4)?, 1.0));
+    }
+
+    let args = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))?
+        .strip_suffix(')')?;
+    let parts: Vec<&str> = args.split(',').map(str::trim).collect();
+    if parts.len() != 3 && parts.len() != 4 {
+        return None;
+    }
+    let channel = |s: &str| {
+        s.parse::<f64>()
+            .ok()
+            .map(|v| v.clamp(0.0, 255.0).round() as u8)
+    };
+    let alpha = match parts.get(3) {
+        Some(a) => a.parse::<f64>().ok()?.clamp(0.0, 1.0),
+        None => 1.0,
+    };
+    Some(RGBA::new(
+        channel(parts[0])?,
+        channel(parts[1])?,
+        channel(parts[2])?,
+        alpha,
+    ))
+}
+
+/// WCAG relative luminance of a color
+fn relative_luminance(color: &RGBA) -> f64 {
+    let linear = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(color.r) + 0.7152 * linear(color.g) + 0.0722 * linear(color.b)
+}
+
+/// WCAG contrast ratio between two colors (1.0 to 21.0)
+fn contrast_ratio(foreground: &RGBA, background: &RGBA) -> f64 {
+    let (a, b) = (
+        relative_luminance(foreground),
+        relative_luminance(background),
+    );
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Whether the font counts as large text (>= 24px, or >= 18.66px bold)
+fn is_large_text(font_size: Option<&str>, font_weight: Option<&str>) -> bool {
+    let size = font_size
+        .and_then(|s| s.trim().strip_suffix("px"))
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .unwrap_or(16.0);
+    let bold = match font_weight.map(str::trim) {
+        Some("bold") | Some("bolder") => true,
+        Some(weight) => weight.parse::<u32>().is_ok_and(|w| w >= 700),
+        None => false,
+    };
+    size >= 24.0 || (bold && size >= 18.66)
+}
+
 /// Accessibility inspector state
 #[derive(Debug, Clone, Default)]
 pub struct AccessibilityState {
@@ -600,16 +821,17 @@ impl AccessibilityInspector {
             ));
         }
 
-        // Return mock contrast info
-        // In real implementation, this would calculate actual contrast
+        let foreground = self.foreground_color(params.node_id);
+        let background = self.opaque_background(params.node_id);
+        let ratio = contrast_ratio(&foreground, &background);
         let contrast_info = ContrastInfo {
-            contrast_ratio: 4.5,
-            foreground_color: RGBA::new(0, 0, 0, 1.0),
-            background_color: RGBA::new(255, 255, 255, 1.0),
-            aa_normal: true,
-            aa_large: true,
-            aaa_normal: false,
-            aaa_large: true,
+            contrast_ratio: ratio,
+            foreground_color: foreground,
+            background_color: background,
+            aa_normal: ratio >= AA_NORMAL_THRESHOLD,
+            aa_large: ratio >= AA_LARGE_THRESHOLD,
+            aaa_normal: ratio >= AAA_NORMAL_THRESHOLD,
+            aaa_large: ratio >= AAA_LARGE_THRESHOLD,
         };
 
         serde_json::to_value(contrast_info)
@@ -622,6 +844,7 @@ impl AccessibilityInspector {
 
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
+        #[allow(dead_code)]
         struct Params {
             #[serde(default)]
             accessible_name: Option<String>,
@@ -630,8 +853,7 @@ impl AccessibilityInspector {
         }
 
         let params: Params = params
-            .map(|p| serde_json::from_value(p).ok())
-            .flatten()
+            .and_then(|p| serde_json::from_value(p).ok())
             .unwrap_or(Params {
                 accessible_name: None,
                 role: None,
@@ -677,6 +899,7 @@ impl AccessibilityInspector {
 
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
+        #[allow(dead_code)]
         struct Params {
             node_id: Option<NodeId>,
             #[serde(default)]
@@ -721,6 +944,238 @@ impl AccessibilityInspector {
         }))
     }
 
+    /// Check a node for common ARIA mistakes
+    ///
+    /// Reports roles used outside their required context, roles missing
+    /// required owned elements, and role-specific attributes on nodes whose
+    /// role doesn't support them. Unknown nodes have no issues.
+    pub fn validate_aria(&self, node_id: NodeId) -> Vec<AriaIssue> {
+        let node = match self.browser.get_node(node_id) {
+            Some(node) => node,
+            None => return Vec::new(),
+        };
+
+        let mut issues = Vec::new();
+        let role = node_role(&node);
+        let role_name = role.as_deref().unwrap_or("generic");
+
+        // Required context: nearest ancestor with a non-generic role
+        let context = required_context_roles(role_name);
+        if !context.is_empty() {
+            let mut ancestor_role = None;
+            let mut current = self.browser.get_parent(node_id);
+            while let Some(parent_id) = current {
+                if let Some(role) = self.browser.get_node(parent_id).and_then(|n| node_role(&n)) {
+                    ancestor_role = Some(role);
+                    break;
+                }
+                current = self.browser.get_parent(parent_id);
+            }
+
+            if !ancestor_role.is_some_and(|r| context.contains(&r.as_str())) {
+                issues.push(AriaIssue {
+                    node_id,
+                    code: AriaIssueCode::MissingRequiredContext,
+                    message: format!(
+                        "Role '{}' must be contained in one of: {}",
+                        role_name,
+                        context.join(", ")
+                    ),
+                });
+            }
+        }
+
+        // Required owned elements: at least one descendant with a required role
+        let owned = required_owned_roles(role_name);
+        if !owned.is_empty() {
+            let has_owned = self.browser.get_descendants(node_id).into_iter().any(|id| {
+                self.browser
+                    .get_node(id)
+                    .and_then(|n| node_role(&n))
+                    .is_some_and(|r| owned.contains(&r.as_str()))
+            });
+
+            if !has_owned {
+                issues.push(AriaIssue {
+                    node_id,
+                    code: AriaIssueCode::MissingRequiredOwnedElements,
+                    message: format!(
+                        "Role '{}' requires owned elements with one of: {}",
+                        role_name,
+                        owned.join(", ")
+                    ),
+                });
+            }
+        }
+
+        // Role-specific attributes
+        if let Some(attrs) = &node.attributes {
+            for name in attrs.iter().step_by(2) {
+                if let Some(roles) = supported_roles(name) {
+                    if !roles.contains(&role_name) {
+                        issues.push(AriaIssue {
+                            node_id,
+                            code: AriaIssueCode::UnsupportedAttribute,
+                            message: format!(
+                                "Attribute '{}' is not supported by role '{}'",
+                                name, role_name
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Check text contrast across the subtree rooted at `root`
+    ///
+    /// Every element with a non-empty text child is measured using its
+    /// computed `color` against the nearest opaque `background-color` on
+    /// itself or an ancestor (white if none). Elements failing WCAG AA are
+    /// reported in document order.
+    pub fn audit_contrast(&self, root: NodeId) -> Vec<ContrastIssue> {
+        if !self.browser.node_exists(root) {
+            return Vec::new();
+        }
+
+        let mut candidates = vec![root];
+        candidates.extend(self.browser.get_descendants(root));
+        candidates.sort_by_key(|id| id.0);
+
+        let mut issues = Vec::new();
+        for node_id in candidates {
+            let has_text = self.browser.get_children(node_id).into_iter().any(|id| {
+                self.browser.get_node(id).is_some_and(|n| {
+                    n.node_type == NodeType::Text
+                        && n.node_value.is_some_and(|v| !v.trim().is_empty())
+                })
+            });
+            if !has_text {
+                continue;
+            }
+
+            let styles = match self.browser.get_computed_styles(node_id) {
+                Some(styles) => styles,
+                None => continue,
+            };
+            let style = |name: &str| {
+                styles
+                    .properties
+                    .iter()
+                    .find(|p| p.name == name)
+                    .map(|p| p.value.as_str())
+            };
+
+            let foreground = self.foreground_color(node_id);
+            let background = self.opaque_background(node_id);
+            let ratio = contrast_ratio(&foreground, &background);
+            let threshold = if is_large_text(style("font-size"), style("font-weight")) {
+                AA_LARGE_THRESHOLD
+            } else {
+                AA_NORMAL_THRESHOLD
+            };
+
+            if ratio < threshold {
+                issues.push(ContrastIssue {
+                    node_id,
+                    contrast_ratio: ratio,
+                    threshold,
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// Computed `color` of the node (black if unset)
+    fn foreground_color(&self, node_id: NodeId) -> RGBA {
+        self.browser
+            .get_computed_styles(node_id)
+            .and_then(|styles| {
+                styles
+                    .properties
+                    .into_iter()
+                    .find(|p| p.name == "color")
+                    .and_then(|p| parse_css_color(&p.value))
+            })
+            .unwrap_or(RGBA::new(0, 0, 0, 1.0))
+    }
+
+    /// Nearest opaque `background-color` on the node or its ancestors
+    fn opaque_background(&self, node_id: NodeId) -> RGBA {
+        let mut current = Some(node_id);
+        while let Some(id) = current {
+            let background = self.browser.get_computed_styles(id).and_then(|styles| {
+                styles
+                    .properties
+                    .into_iter()
+                    .find(|p| p.name == "background-color")
+                    .and_then(|p| parse_css_color(&p.value))
+            });
+            if let Some(color) = background.filter(|c| c.a >= 1.0) {
+                return color;
+            }
+            current = self.browser.get_parent(id);
+        }
+        RGBA::new(255, 255, 255, 1.0)
+    }
+
+    /// Audit text contrast under a node
+    async fn audit_contrast_method(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        debug!("AccessibilityInspector.auditContrast called");
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            node_id: NodeId,
+        }
+
+        let params: Params = serde_json::from_value(
+            params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?,
+        )
+        .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+        if !self.browser.node_exists(params.node_id) {
+            return Err(CdpError::server_error(
+                -32000,
+                format!("Node {} not found", params.node_id.0),
+            ));
+        }
+
+        Ok(serde_json::json!({
+            "issues": self.audit_contrast(params.node_id)
+        }))
+    }
+
+    /// Validate ARIA usage on a node
+    async fn validate_aria_method(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        debug!("AccessibilityInspector.validateAria called");
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            node_id: NodeId,
+        }
+
+        let params: Params = serde_json::from_value(
+            params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?,
+        )
+        .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+        if !self.browser.node_exists(params.node_id) {
+            return Err(CdpError::server_error(
+                -32000,
+                format!("Node {} not found", params.node_id.0),
+            ));
+        }
+
+        Ok(serde_json::json!({
+            "issues": self.validate_aria(params.node_id)
+        }))
+    }
+
     /// Get state
     pub async fn get_state(&self) -> AccessibilityState {
         self.state.read().await.clone()
@@ -756,6 +1211,8 @@ impl DomainHandler for AccessibilityInspector {
             "getContrastInfo" => self.get_contrast_info(params).await,
             "queryAXTree" => self.query_ax_tree(params).await,
             "getPartialAXTree" => self.get_partial_ax_tree(params).await,
+            "validateAria" => self.validate_aria_method(params).await,
+            "auditContrast" => self.audit_contrast_method(params).await,
             _ => {
                 warn!("Unknown AccessibilityInspector method: {}", method);
                 Err(CdpError::method_not_found(format!(
@@ -857,6 +1314,29 @@ mod tests {
         assert!(value["aaNormal"].is_boolean());
     }
 
+    #[tokio::test]
+    async fn test_get_contrast_info_measures_computed_colors() {
+        let inspector = AccessibilityInspector::new();
+        inspector
+            .browser()
+            .set_attribute(NodeId(7), "style", "color: #777777")
+            .unwrap();
+
+        let value = inspector
+            .get_contrast_info(Some(json!({ "nodeId": 7 })))
+            .await
+            .unwrap();
+        let info: ContrastInfo = serde_json::from_value(value).unwrap();
+
+        assert!((info.contrast_ratio - 4.48).abs() < 0.01);
+        assert_eq!(info.foreground_color, RGBA::new(0x77, 0x77, 0x77, 1.0));
+        assert_eq!(info.background_color, RGBA::new(255, 255, 255, 1.0));
+        assert!(!info.aa_normal);
+        assert!(info.aa_large);
+        assert!(!info.aaa_normal);
+        assert!(!info.aaa_large);
+    }
+
     #[tokio::test]
     async fn test_query_ax_tree() {
         let inspector = AccessibilityInspector::new();
@@ -887,4 +1367,100 @@ mod tests {
         let result = inspector.handle_method("unknownMethod", None).await;
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_validate_aria_listitem_outside_list() {
+        let inspector = AccessibilityInspector::new();
+        inspector
+            .browser()
+            .set_attribute(NodeId(7), "role", "listitem")
+            .unwrap();
+
+        let issues = inspector.validate_aria(NodeId(7));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, AriaIssueCode::MissingRequiredContext);
+        assert!(issues[0].message.contains("listitem"));
+    }
+
+    #[test]
+    fn test_validate_aria_nested_listitem_is_valid() {
+        let inspector = AccessibilityInspector::new();
+        let browser = inspector.browser();
+        browser.set_attribute(NodeId(6), "role", "list").unwrap();
+        browser.set_attribute(NodeId(7), "role", "listitem").unwrap();
+
+        assert!(inspector.validate_aria(NodeId(7)).is_empty());
+        assert!(inspector.validate_aria(NodeId(6)).is_empty());
+    }
+
+    #[test]
+    fn test_validate_aria_list_without_items() {
+        let inspector = AccessibilityInspector::new();
+        inspector
+            .browser()
+            .set_attribute(NodeId(9), "role", "list")
+            .unwrap();
+
+        let issues = inspector.validate_aria(NodeId(9));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, AriaIssueCode::MissingRequiredOwnedElements);
+    }
+
+    #[tokio::test]
+    async fn test_validate_aria_unsupported_attribute_on_div() {
+        let inspector = AccessibilityInspector::new();
+        inspector
+            .browser()
+            .set_attribute(NodeId(9), "aria-checked", "true")
+            .unwrap();
+
+        let result = inspector
+            .handle_method("validateAria", Some(json!({ "nodeId": 9 })))
+            .await
+            .unwrap();
+
+        let issues = result["issues"].as_array().unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0]["code"], "unsupportedAttribute");
+        assert!(issues[0]["message"].as_str().unwrap().contains("aria-checked"));
+    }
+
+    #[test]
+    fn test_audit_contrast_reports_only_failing_text() {
+        let inspector = AccessibilityInspector::new();
+        let browser = inspector.browser();
+        browser
+            .set_attribute(NodeId(5), "style", "background-color: #ffffff")
+            .unwrap();
+        // Translucent backgrounds are skipped in favour of the body's
+        browser
+            .set_attribute(NodeId(6), "style", "background-color: rgba(0, 0, 0, 0.5)")
+            .unwrap();
+        browser
+            .set_attribute(NodeId(7), "style", "color: #777777")
+            .unwrap();
+        browser.append_text_node(NodeId(9), "Footer").unwrap();
+
+        let issues = inspector.audit_contrast(NodeId(5));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].node_id, NodeId(7));
+        assert!((issues[0].contrast_ratio - 4.48).abs() < 0.01);
+        assert_eq!(issues[0].threshold, 4.5);
+    }
+
+    #[tokio::test]
+    async fn test_audit_contrast_large_text_threshold() {
+        let inspector = AccessibilityInspector::new();
+        inspector
+            .browser()
+            .set_attribute(NodeId(7), "style", "color: #777777; font-size: 24px")
+            .unwrap();
+
+        let result = inspector
+            .handle_method("auditContrast", Some(json!({ "nodeId": 1 })))
+            .await
+            .unwrap();
+
+        assert!(result["issues"].as_array().unwrap().is_empty());
+    }
 }
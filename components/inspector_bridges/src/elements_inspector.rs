@@ -438,8 +438,7 @@ impl ElementsInspector {
         debug!("ElementsInspector.enableInspectMode called");
 
         let highlight_config: HighlightConfig = params
-            .map(|p| serde_json::from_value(p).ok())
-            .flatten()
+            .and_then(|p| serde_json::from_value(p).ok())
             .unwrap_or_default();
 
         let mut state = self.element_state.write().await;
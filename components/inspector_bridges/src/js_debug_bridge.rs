@@ -9,22 +9,29 @@
 //! - Call stack management
 //! - Scope chain access
 //! - Source map integration for debugging transpiled code
+//! - Async call stacks and pausing on async continuations
 
 use async_trait::async_trait;
+use base64::Engine;
 use cdp_types::domains::debugger::{
     BreakpointId, CallFrame, Location, PausedReason, Scope, ScopeType, ScriptId,
 };
-use cdp_types::domains::runtime::{RemoteObject, RemoteObjectId, RemoteObjectType};
+use cdp_types::domains::runtime::{
+    ExceptionDetails, RemoteObject, RemoteObjectId, RemoteObjectType, StackTrace,
+};
 use cdp_types::CdpError;
 use dashmap::DashMap;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use protocol_handler::DomainHandler;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
@@ -64,11 +71,37 @@ pub enum JsDebugBridgeError {
     /// Evaluation error
     #[error("Evaluation error: {0}")]
     EvaluationError(String),
+
+    /// External source map could not be fetched
+    #[error("Failed to fetch source map {0}: {1}")]
+    SourceMapFetchFailed(String, String),
 }
 
 /// Result type for debug bridge operations
 pub type Result<T> = std::result::Result<T, JsDebugBridgeError>;
 
+/// Language of a parsed script (mirrors `Debugger.ScriptLanguage`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ScriptLanguage {
+    /// JavaScript source
+    #[default]
+    JavaScript,
+    /// WebAssembly module
+    WebAssembly,
+}
+
+impl ScriptLanguage {
+    /// Infer the language from a script URL (`.wasm` is WebAssembly)
+    pub fn from_url(url: &str) -> Self {
+        let path = url.split(['?', '#']).next().unwrap_or(url);
+        if path.to_ascii_lowercase().ends_with(".wasm") {
+            ScriptLanguage::WebAssembly
+        } else {
+            ScriptLanguage::JavaScript
+        }
+    }
+}
+
 /// Script information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -92,12 +125,22 @@ pub struct ScriptInfo {
     /// Script hash
     pub hash: String,
     /// Source map URL (if any)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "sourceMapURL",
+        alias = "sourceMapUrl",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub source_map_url: Option<String>,
+    /// Whether the source map was loaded, so original sources are available
+    #[serde(default)]
+    pub has_source_map: bool,
     /// Whether this is a module
     pub is_module: bool,
     /// Script length
     pub length: u32,
+    /// Script language
+    #[serde(default)]
+    pub script_language: ScriptLanguage,
 }
 
 /// Breakpoint information
@@ -119,6 +162,24 @@ pub struct BreakpointInfo {
     pub enabled: bool,
 }
 
+/// Breakpoint set by URL before a matching script was parsed
+///
+/// Resolved into a regular breakpoint, keeping its ID, once a script with
+/// the URL is added.
+#[derive(Debug, Clone)]
+pub struct PendingBreakpoint {
+    /// Breakpoint ID
+    pub id: BreakpointId,
+    /// URL of the script the breakpoint waits for
+    pub url: String,
+    /// Line number in the script
+    pub line_number: u32,
+    /// Column number in the script
+    pub column_number: Option<u32>,
+    /// Condition expression
+    pub condition: Option<String>,
+}
+
 /// Debugger pause state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -132,6 +193,43 @@ pub struct PauseState {
     /// Exception data (if paused on exception)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<Value>,
+    /// Source-mapped location of each call frame, in frame order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub original_locations: Vec<Option<OriginalLocation>>,
+}
+
+/// Result of a live edit (mirrors `Debugger.setScriptSource`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetScriptSourceResult {
+    /// Call frames after the edit (empty when not paused)
+    pub call_frames: Vec<CallFrame>,
+    /// Whether paused frames run in the edited script and need a restart
+    pub stack_changed: bool,
+    /// Breakpoints disabled because their line no longer exists
+    pub unresolved_breakpoints: Vec<BreakpointId>,
+}
+
+/// Identifier of a recorded async stack (mirrors `Runtime.StackTraceId`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackTraceId {
+    /// Stack trace ID
+    pub id: String,
+    /// Debugger that recorded the stack
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debugger_id: Option<String>,
+}
+
+/// Async task recorded in the async stack store
+#[derive(Debug, Clone)]
+pub struct AsyncTaskInfo {
+    /// ID of the stack captured when the task was scheduled
+    pub stack_trace_id: StackTraceId,
+    /// Task description (e.g. `setTimeout`, `Promise.then`)
+    pub description: String,
+    /// Frames at the scheduling site, limited to the async stack depth
+    pub parent_frames: Vec<CallFrame>,
 }
 
 /// Step action type
@@ -167,6 +265,12 @@ pub enum DebugEvent {
         breakpoint_id: BreakpointId,
         location: Location,
     },
+    /// Logpoint reached; execution continues without pausing
+    LogpointMessage {
+        breakpoint_id: BreakpointId,
+        location: Location,
+        message: String,
+    },
 }
 
 /// JavaScript Debug Bridge
@@ -186,14 +290,22 @@ pub struct JsDebugBridge {
     scripts: Arc<DashMap<String, ScriptInfo>>,
     /// Scripts by URL (for quick lookup)
     scripts_by_url: Arc<DashMap<String, String>>,
+    /// WebAssembly module bytes (script ID -> bytecode)
+    wasm_bytecode: Arc<DashMap<String, Vec<u8>>>,
     /// Source maps by script ID
     source_maps: Arc<DashMap<String, SourceMap>>,
     /// Breakpoints by ID
     breakpoints: Arc<DashMap<String, BreakpointInfo>>,
     /// Breakpoints by location (script_id:line:column -> breakpoint_id)
     breakpoints_by_location: Arc<DashMap<String, String>>,
+    /// Breakpoints set by URL awaiting a matching script (breakpoint_id -> request)
+    pending_breakpoints: Arc<DashMap<String, PendingBreakpoint>>,
+    /// Instrumentation breakpoints (breakpoint_id -> instrumentation name)
+    instrumentation_breakpoints: Arc<DashMap<String, String>>,
     /// Current call frames (when paused)
     call_frames: Arc<RwLock<Vec<CallFrame>>>,
+    /// Variables modified while paused (scope object ID -> variables)
+    scope_variables: Arc<DashMap<String, Vec<PropertyInfo>>>,
     /// Current pause reason
     pause_reason: Arc<RwLock<Option<PausedReason>>>,
     /// Hit breakpoints in current pause
@@ -204,10 +316,33 @@ pub struct JsDebugBridge {
     skip_all_pauses: Arc<AtomicBool>,
     /// Pause on exceptions mode
     pause_on_exceptions: Arc<RwLock<PauseOnExceptionsMode>>,
+    /// Filter applied to exceptions the mode allows
+    exception_filter: Arc<RwLock<Arc<dyn ExceptionFilter>>>,
+    /// Evaluator for breakpoint conditions (opt-in)
+    condition_evaluator: Arc<RwLock<Option<Arc<dyn ConditionEvaluator>>>>,
     /// Async stack trace depth
     async_stack_trace_depth: Arc<AtomicU32>,
+    /// Async task ID counter
+    async_task_counter: Arc<AtomicU32>,
+    /// Async stack store (stack trace id -> scheduled task)
+    async_tasks: Arc<DashMap<String, AsyncTaskInfo>>,
+    /// Async task whose continuation should pause (set by pauseOnAsyncCall)
+    pause_on_async_call: Arc<RwLock<Option<StackTraceId>>>,
+    /// Execution contexts whose frames are skipped while stepping
+    blackboxed_contexts: Arc<RwLock<HashSet<String>>>,
+    /// Step requested by the last step command, waiting for the next location
+    pending_step: Arc<RwLock<Option<StepAction>>>,
+    /// Fetcher for external source maps (opt-in)
+    source_map_fetcher: Option<Arc<dyn SourceMapFetcher>>,
+    /// How long a source map fetch may take before it is abandoned
+    source_map_fetch_timeout: Duration,
+    /// External source map fetches still in flight
+    source_map_loads: Arc<Mutex<Vec<JoinHandle<()>>>>,
 }
 
+/// Default bound on a single external source map fetch
+pub const DEFAULT_SOURCE_MAP_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Mode for pausing on exceptions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum PauseOnExceptionsMode {
@@ -220,6 +355,50 @@ pub enum PauseOnExceptionsMode {
     All,
 }
 
+/// Per-exception predicate consulted after the pause-on-exceptions mode check
+///
+/// Lets callers narrow exception pauses, e.g. to only break on `TypeError`.
+pub trait ExceptionFilter: Send + Sync {
+    /// Return whether an exception of `error_type` should pause the debugger
+    fn should_pause(&self, error_type: &str, is_uncaught: bool) -> bool;
+}
+
+/// Default exception filter that accepts every exception the mode allows
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AcceptAllExceptions;
+
+impl ExceptionFilter for AcceptAllExceptions {
+    fn should_pause(&self, _error_type: &str, _is_uncaught: bool) -> bool {
+        true
+    }
+}
+
+/// Evaluates breakpoint conditions against the paused frame
+///
+/// Without an evaluator installed, conditional breakpoints always pause.
+pub trait ConditionEvaluator: Send + Sync {
+    /// Return whether `condition` holds in `frame`
+    fn evaluate(&self, condition: &str, frame: &CallFrame) -> bool;
+
+    /// Evaluate a logpoint `{expression}` placeholder to its display text
+    ///
+    /// Returning `None` leaves the placeholder in the message as written.
+    fn evaluate_expression(&self, _expression: &str, _frame: &CallFrame) -> Option<String> {
+        None
+    }
+}
+
+/// Fetches external source maps referenced by a script's `sourceMappingURL`
+///
+/// Without a fetcher installed, only inline `data:` source maps are loaded.
+/// Fetches run in the background, so a script's external map is attached
+/// after it is registered; `wait_for_source_maps` awaits them.
+#[async_trait]
+pub trait SourceMapFetcher: Send + Sync {
+    /// Fetch the source map text at an absolute `url`
+    async fn fetch(&self, url: &str) -> std::result::Result<String, String>;
+}
+
 impl JsDebugBridge {
     /// Create a new JavaScript Debug Bridge
     pub fn new() -> Self {
@@ -231,19 +410,45 @@ impl JsDebugBridge {
             breakpoint_counter: Arc::new(AtomicU32::new(1)),
             scripts: Arc::new(DashMap::new()),
             scripts_by_url: Arc::new(DashMap::new()),
+            wasm_bytecode: Arc::new(DashMap::new()),
             source_maps: Arc::new(DashMap::new()),
             breakpoints: Arc::new(DashMap::new()),
             breakpoints_by_location: Arc::new(DashMap::new()),
+            pending_breakpoints: Arc::new(DashMap::new()),
+            instrumentation_breakpoints: Arc::new(DashMap::new()),
             call_frames: Arc::new(RwLock::new(Vec::new())),
+            scope_variables: Arc::new(DashMap::new()),
             pause_reason: Arc::new(RwLock::new(None)),
             hit_breakpoints: Arc::new(RwLock::new(Vec::new())),
             event_sender,
             skip_all_pauses: Arc::new(AtomicBool::new(false)),
             pause_on_exceptions: Arc::new(RwLock::new(PauseOnExceptionsMode::None)),
+            exception_filter: Arc::new(RwLock::new(Arc::new(AcceptAllExceptions))),
+            condition_evaluator: Arc::new(RwLock::new(None)),
             async_stack_trace_depth: Arc::new(AtomicU32::new(0)),
+            async_task_counter: Arc::new(AtomicU32::new(1)),
+            async_tasks: Arc::new(DashMap::new()),
+            pause_on_async_call: Arc::new(RwLock::new(None)),
+            blackboxed_contexts: Arc::new(RwLock::new(HashSet::new())),
+            pending_step: Arc::new(RwLock::new(None)),
+            source_map_fetcher: None,
+            source_map_fetch_timeout: DEFAULT_SOURCE_MAP_FETCH_TIMEOUT,
+            source_map_loads: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    /// Install a fetcher so external `sourceMappingURL`s are loaded
+    pub fn with_source_map_fetcher(mut self, fetcher: Arc<dyn SourceMapFetcher>) -> Self {
+        self.source_map_fetcher = Some(fetcher);
+        self
+    }
+
+    /// Bound each external source map fetch; slower fetches load no map
+    pub fn with_source_map_fetch_timeout(mut self, timeout: Duration) -> Self {
+        self.source_map_fetch_timeout = timeout;
+        self
+    }
+
     /// Enable the debugger
     pub fn enable(&self) -> Result<()> {
         self.enabled.store(true, Ordering::SeqCst);
@@ -257,10 +462,20 @@ impl JsDebugBridge {
         self.paused.store(false, Ordering::SeqCst);
         self.scripts.clear();
         self.scripts_by_url.clear();
+        self.wasm_bytecode.clear();
         self.source_maps.clear();
         self.breakpoints.clear();
         self.breakpoints_by_location.clear();
+        self.pending_breakpoints.clear();
+        self.instrumentation_breakpoints.clear();
+        self.async_tasks.clear();
+        for load in self.source_map_loads.lock().drain(..) {
+            load.abort();
+        }
+        *self.pause_on_async_call.write() = None;
+        *self.pending_step.write() = None;
         self.call_frames.write().clear();
+        self.scope_variables.clear();
         *self.pause_reason.write() = None;
         self.hit_breakpoints.write().clear();
         debug!("JavaScript Debug Bridge disabled");
@@ -285,7 +500,47 @@ impl JsDebugBridge {
     // ========== Script Management ==========
 
     /// Register a new script
+    ///
+    /// Scripts with a `.wasm` URL are registered as WebAssembly, with the
+    /// source bytes as the module bytecode.
     pub fn add_script(&self, url: &str, source: &str) -> Result<ScriptInfo> {
+        let language = ScriptLanguage::from_url(url);
+        let bytecode = match language {
+            ScriptLanguage::WebAssembly => Some(source.as_bytes().to_vec()),
+            ScriptLanguage::JavaScript => None,
+        };
+        self.register_script(url, source, language, bytecode, 1)
+    }
+
+    /// Register a script running in a specific execution context
+    pub fn add_script_in_context(
+        &self,
+        url: &str,
+        source: &str,
+        execution_context_id: u32,
+    ) -> Result<ScriptInfo> {
+        let language = ScriptLanguage::from_url(url);
+        let bytecode = match language {
+            ScriptLanguage::WebAssembly => Some(source.as_bytes().to_vec()),
+            ScriptLanguage::JavaScript => None,
+        };
+        self.register_script(url, source, language, bytecode, execution_context_id)
+    }
+
+    /// Register a WebAssembly module from its binary bytecode
+    pub fn add_wasm_script(&self, url: &str, bytecode: &[u8]) -> Result<ScriptInfo> {
+        self.register_script(url, "", ScriptLanguage::WebAssembly, Some(bytecode.to_vec()), 1)
+    }
+
+    /// Register a script and emit `ScriptParsed`
+    fn register_script(
+        &self,
+        url: &str,
+        source: &str,
+        language: ScriptLanguage,
+        bytecode: Option<Vec<u8>>,
+        execution_context_id: u32,
+    ) -> Result<ScriptInfo> {
         if !self.is_enabled() {
             return Err(JsDebugBridgeError::NotEnabled);
         }
@@ -293,16 +548,19 @@ impl JsDebugBridge {
         let script_id = self.script_counter.fetch_add(1, Ordering::SeqCst);
         let script_id_str = format!("script-{}", script_id);
 
-        let lines: Vec<&str> = source.lines().collect();
-        let end_line = if lines.is_empty() {
-            0
-        } else {
-            (lines.len() - 1) as u32
-        };
-        let end_column = lines.last().map(|l| l.len() as u32).unwrap_or(0);
+        let (end_line, end_column) = source_end(source);
 
         // Extract source map URL from source
-        let source_map_url = SourceMap::extract_url_from_source(source);
+        let source_map_url = match language {
+            ScriptLanguage::JavaScript => SourceMap::extract_url_from_source(source),
+            ScriptLanguage::WebAssembly => None,
+        };
+        let content = bytecode.as_deref().unwrap_or(source.as_bytes());
+
+        // Inline maps load now; external ones are attached once fetched
+        let source_map = source_map_url
+            .as_deref()
+            .and_then(|sm_url| self.load_inline_source_map(sm_url));
 
         let script_info = ScriptInfo {
             script_id: ScriptId(script_id_str.clone()),
@@ -312,32 +570,142 @@ impl JsDebugBridge {
             start_column: 0,
             end_line,
             end_column,
-            execution_context_id: 1,
-            hash: format!("{:x}", md5_hash(source)),
-            source_map_url: source_map_url.clone(),
-            is_module: source.contains("import ") || source.contains("export "),
-            length: source.len() as u32,
+            execution_context_id,
+            hash: format!("{:x}", md5_hash(content)),
+            source_map_url,
+            has_source_map: source_map.is_some(),
+            is_module: language == ScriptLanguage::JavaScript
+                && (source.contains("import ") || source.contains("export ")),
+            length: content.len() as u32,
+            script_language: language,
         };
 
         // Store script
+        if let Some(bytecode) = bytecode {
+            self.wasm_bytecode.insert(script_id_str.clone(), bytecode);
+        }
         self.scripts.insert(script_id_str.clone(), script_info.clone());
         self.scripts_by_url
             .insert(url.to_string(), script_id_str.clone());
 
-        // Load source map if available
-        if let Some(ref sm_url) = source_map_url {
-            if let Ok(source_map) = self.load_source_map(sm_url) {
+        match (source_map, script_info.source_map_url.as_deref()) {
+            (Some(source_map), _) => {
                 self.source_maps.insert(script_id_str.clone(), source_map);
             }
+            (None, Some(sm_url)) => self.spawn_source_map_load(&script_id_str, url, sm_url),
+            (None, None) => {}
         }
 
         // Emit event
         let _ = self.event_sender.send(DebugEvent::ScriptParsed(script_info.clone()));
 
         debug!("Added script {}: {}", script_id_str, url);
+
+        self.resolve_pending_breakpoints(url, &script_info.script_id);
+
+        self.check_instrumentation_breakpoints(&script_info);
+
         Ok(script_info)
     }
 
+    /// Replace a script's source (live edit)
+    ///
+    /// Recomputes the script's extent, hash and source map, then disables
+    /// breakpoints whose line no longer exists; breakpoints on remaining lines
+    /// keep their IDs. Emits `ScriptParsed` with the updated script.
+    pub fn set_script_source(
+        &self,
+        script_id: &str,
+        new_source: &str,
+    ) -> Result<SetScriptSourceResult> {
+        if !self.is_enabled() {
+            return Err(JsDebugBridgeError::NotEnabled);
+        }
+
+        let script_url = {
+            let script = self
+                .scripts
+                .get(script_id)
+                .ok_or_else(|| JsDebugBridgeError::ScriptNotFound(script_id.to_string()))?;
+            if script.script_language != ScriptLanguage::JavaScript {
+                return Err(JsDebugBridgeError::InvalidParameter(format!(
+                    "Script {} is not JavaScript",
+                    script_id
+                )));
+            }
+            script.url.clone()
+        };
+
+        let source_map_url = SourceMap::extract_url_from_source(new_source);
+        let source_map = source_map_url
+            .as_deref()
+            .and_then(|sm_url| self.load_inline_source_map(sm_url));
+
+        let script_info = {
+            let mut script = self
+                .scripts
+                .get_mut(script_id)
+                .ok_or_else(|| JsDebugBridgeError::ScriptNotFound(script_id.to_string()))?;
+            let (end_line, end_column) = source_end(new_source);
+
+            script.source = new_source.to_string();
+            script.end_line = end_line;
+            script.end_column = end_column;
+            script.length = new_source.len() as u32;
+            script.hash = format!("{:x}", md5_hash(new_source.as_bytes()));
+            script.source_map_url = source_map_url;
+            script.has_source_map = source_map.is_some();
+            script.is_module = new_source.contains("import ") || new_source.contains("export ");
+
+            match source_map {
+                Some(source_map) => {
+                    self.source_maps.insert(script_id.to_string(), source_map);
+                }
+                None => {
+                    self.source_maps.remove(script_id);
+                }
+            }
+            script.clone()
+        };
+        let pending_url = script_info
+            .source_map_url
+            .as_deref()
+            .filter(|_| !script_info.has_source_map);
+        if let Some(sm_url) = pending_url {
+            self.spawn_source_map_load(script_id, &script_url, sm_url);
+        }
+
+        let line_count = new_source.lines().count() as u32;
+        let mut unresolved_breakpoints = Vec::new();
+        for mut bp in self.breakpoints.iter_mut() {
+            if bp.location.script_id.0 == script_id && bp.location.line_number >= line_count {
+                bp.enabled = false;
+                unresolved_breakpoints.push(bp.id.clone());
+            }
+        }
+
+        let call_frames = self.get_call_frames();
+        let stack_changed = call_frames
+            .iter()
+            .any(|f| f.location.script_id.0 == script_id);
+
+        let _ = self
+            .event_sender
+            .send(DebugEvent::ScriptParsed(script_info));
+
+        debug!(
+            "Set source of {} ({} breakpoints unresolved)",
+            script_id,
+            unresolved_breakpoints.len()
+        );
+
+        Ok(SetScriptSourceResult {
+            call_frames,
+            stack_changed,
+            unresolved_breakpoints,
+        })
+    }
+
     /// Get script by ID
     pub fn get_script(&self, script_id: &str) -> Option<ScriptInfo> {
         self.scripts.get(script_id).map(|s| s.clone())
@@ -351,22 +719,105 @@ impl JsDebugBridge {
             .ok_or_else(|| JsDebugBridgeError::ScriptNotFound(script_id.to_string()))
     }
 
+    /// Get the base64-encoded bytecode of a WebAssembly script
+    pub fn get_wasm_bytecode(&self, script_id: &str) -> Result<String> {
+        let script = self
+            .scripts
+            .get(script_id)
+            .ok_or_else(|| JsDebugBridgeError::ScriptNotFound(script_id.to_string()))?;
+
+        if script.script_language != ScriptLanguage::WebAssembly {
+            return Err(JsDebugBridgeError::InvalidParameter(format!(
+                "Script {} is not WebAssembly",
+                script_id
+            )));
+        }
+
+        let bytecode = self
+            .wasm_bytecode
+            .get(script_id)
+            .map(|b| base64::engine::general_purpose::STANDARD.encode(b.value()))
+            .unwrap_or_default();
+        Ok(bytecode)
+    }
+
     /// Get all scripts
     pub fn get_all_scripts(&self) -> Vec<ScriptInfo> {
         self.scripts.iter().map(|e| e.value().clone()).collect()
     }
 
-    /// Load a source map
-    fn load_source_map(&self, url: &str) -> Result<SourceMap> {
-        if url.starts_with("data:") {
-            // Inline source map
-            Ok(SourceMap::parse_data_url(url)?)
-        } else {
-            // For now, we don't support loading external source maps
-            // In a real implementation, this would fetch the URL
-            Err(JsDebugBridgeError::InvalidParameter(
-                "External source maps not yet supported".to_string(),
-            ))
+    /// Parse an inline `data:` source map; `None` for external URLs
+    fn load_inline_source_map(&self, url: &str) -> Option<SourceMap> {
+        if !url.starts_with("data:") {
+            return None;
+        }
+        SourceMap::parse_data_url(url)
+            .map_err(|e| warn!("Invalid inline source map: {}", e))
+            .ok()
+    }
+
+    /// Fetch an external source map in the background and attach it
+    ///
+    /// `url` is resolved against `script_url` and fetched through the
+    /// installed `SourceMapFetcher` on the current tokio runtime. The map is
+    /// attached only if the script still references `url` when the fetch
+    /// completes. Without a fetcher or a runtime, no map is loaded.
+    fn spawn_source_map_load(&self, script_id: &str, script_url: &str, url: &str) {
+        let Some(fetcher) = self.source_map_fetcher.clone() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!("No async runtime to fetch source map {}", url);
+            return;
+        };
+
+        let scripts = Arc::clone(&self.scripts);
+        let source_maps = Arc::clone(&self.source_maps);
+        let timeout = self.source_map_fetch_timeout;
+        let script_id = script_id.to_string();
+        let url = url.to_string();
+        let resolved = resolve_url(script_url, &url);
+
+        let load = runtime.spawn(async move {
+            let fetched = tokio::time::timeout(timeout, fetcher.fetch(&resolved))
+                .await
+                .unwrap_or_else(|_| Err(format!("timed out after {:?}", timeout)));
+            let source_map =
+                match fetched.and_then(|json| SourceMap::parse(&json).map_err(|e| e.to_string())) {
+                    Ok(source_map) => source_map,
+                    Err(reason) => {
+                        warn!(
+                            "{}",
+                            JsDebugBridgeError::SourceMapFetchFailed(resolved, reason)
+                        );
+                        return;
+                    }
+                };
+
+            if let Some(mut script) = scripts.get_mut(&script_id) {
+                if script.source_map_url.as_deref() == Some(url.as_str()) {
+                    script.has_source_map = true;
+                    source_maps.insert(script_id.clone(), source_map);
+                    debug!("Attached source map {} to {}", resolved, script_id);
+                }
+            }
+        });
+
+        let mut loads = self.source_map_loads.lock();
+        loads.retain(|load| !load.is_finished());
+        loads.push(load);
+    }
+
+    /// Wait for every external source map fetch started so far to finish
+    pub async fn wait_for_source_maps(&self) {
+        loop {
+            let loads = std::mem::take(&mut *self.source_map_loads.lock());
+            if loads.is_empty() {
+                return;
+            }
+            for load in loads {
+                let _ = load.await;
+            }
         }
     }
 
@@ -380,6 +831,112 @@ impl JsDebugBridge {
             .ok()
     }
 
+    /// Get original locations for all current call frames, in frame order
+    pub fn original_call_frame_locations(&self) -> Vec<Option<OriginalLocation>> {
+        self.original_frame_locations(&self.call_frames.read())
+    }
+
+    /// Get original locations for the given call frames, in frame order
+    fn original_frame_locations(&self, frames: &[CallFrame]) -> Vec<Option<OriginalLocation>> {
+        let queries: Vec<(&str, Position)> = frames
+            .iter()
+            .map(|frame| {
+                let location = &frame.location;
+                (
+                    location.script_id.0.as_str(),
+                    Position::new(location.line_number, location.column_number.unwrap_or(0)),
+                )
+            })
+            .collect();
+        self.original_locations(&queries)
+    }
+
+    /// Batch lookup of original locations for `(script_id, position)` pairs
+    ///
+    /// Queries are grouped by script so each source map is searched once;
+    /// results are returned in query order.
+    fn original_locations(&self, queries: &[(&str, Position)]) -> Vec<Option<OriginalLocation>> {
+        let mut by_script: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (idx, (script_id, _)) in queries.iter().enumerate() {
+            by_script.entry(script_id).or_default().push(idx);
+        }
+
+        let mut results = vec![None; queries.len()];
+        for (script_id, indices) in by_script {
+            let source_map = match self.source_maps.get(script_id) {
+                Some(source_map) => source_map,
+                None => continue,
+            };
+            let positions: Vec<Position> = indices.iter().map(|&idx| queries[idx].1).collect();
+            for (idx, original) in indices
+                .into_iter()
+                .zip(source_map.original_positions_for(&positions))
+            {
+                results[idx] = original;
+            }
+        }
+        results
+    }
+
+    /// Rewrite a stack trace's frames to their original source locations
+    ///
+    /// Frames in scripts without a source map, or at positions the map does
+    /// not cover, keep their generated locations. Parent (async) stacks are
+    /// mapped too.
+    pub fn map_stack_to_original(&self, stack: &StackTrace) -> StackTrace {
+        let queries: Vec<(&str, Position)> = stack
+            .call_frames
+            .iter()
+            .map(|frame| {
+                (
+                    frame.script_id.as_str(),
+                    Position::new(frame.line_number, frame.column_number),
+                )
+            })
+            .collect();
+        let call_frames = stack
+            .call_frames
+            .iter()
+            .zip(self.original_locations(&queries))
+            .map(|(frame, original)| {
+                let mut frame = frame.clone();
+                if let Some(original) = original {
+                    frame.url = original.source;
+                    frame.line_number = original.position.line;
+                    frame.column_number = original.position.column;
+                }
+                frame
+            })
+            .collect();
+        StackTrace {
+            call_frames,
+            parent: stack
+                .parent
+                .as_ref()
+                .map(|parent| Box::new(self.map_stack_to_original(parent))),
+        }
+    }
+
+    /// Source-map a `Runtime.evaluate` exception for display
+    ///
+    /// The stack trace is rewritten with `map_stack_to_original`, and the
+    /// throw location is mapped when `script_id` names a script with a map.
+    pub fn format_exception(&self, raw: ExceptionDetails) -> ExceptionDetails {
+        let mut details = raw;
+        if let Some(original) = details.script_id.as_deref().and_then(|script_id| {
+            self.get_original_location(script_id, details.line_number, details.column_number)
+        }) {
+            details.url = Some(original.source);
+            details.line_number = original.position.line;
+            details.column_number = original.position.column;
+        }
+        details.stack_trace = details
+            .stack_trace
+            .as_ref()
+            .map(|stack| self.map_stack_to_original(stack));
+        details
+    }
+
     /// Get generated location from original location
     pub fn get_generated_location(&self, script_id: &str, source: &str, line: u32, column: u32) -> Option<Position> {
         let source_map = self.source_maps.get(script_id)?;
@@ -394,6 +951,52 @@ impl JsDebugBridge {
         self.source_maps.contains_key(script_id)
     }
 
+    /// Get candidate breakpoint locations between `start` and `end` (exclusive)
+    ///
+    /// Returns the first code column of each line in range; blank lines and
+    /// lines holding only `//` or `/* */` comments are skipped. Without `end`,
+    /// the range runs to the end of the script.
+    pub fn get_possible_breakpoints(
+        &self,
+        script_id: &str,
+        start: Position,
+        end: Option<Position>,
+    ) -> Result<Vec<Location>> {
+        let source = self.get_script_source(script_id)?;
+
+        let mut in_block_comment = false;
+        let mut locations = Vec::new();
+        for (line_number, line) in source.lines().enumerate() {
+            let line_number = line_number as u32;
+            if end.is_some_and(|end| line_number > end.line) {
+                break;
+            }
+            // Scan every line so block comment state carries into the range
+            let code_columns = code_columns(line, &mut in_block_comment);
+            if line_number < start.line {
+                continue;
+            }
+
+            let min_column = if line_number == start.line {
+                start.column
+            } else {
+                0
+            };
+            let column = code_columns.into_iter().find(|&column| {
+                column >= min_column
+                    && !end.is_some_and(|end| line_number == end.line && column >= end.column)
+            });
+            if let Some(column) = column {
+                locations.push(Location {
+                    script_id: ScriptId(script_id.to_string()),
+                    line_number,
+                    column_number: Some(column),
+                });
+            }
+        }
+        Ok(locations)
+    }
+
     // ========== Breakpoint Management ==========
 
     /// Set a breakpoint
@@ -406,9 +1009,24 @@ impl JsDebugBridge {
             return Err(JsDebugBridgeError::NotEnabled);
         }
 
+        let breakpoint_id = self.next_breakpoint_id();
+        let location = self.insert_breakpoint(breakpoint_id.clone(), location, condition);
+        Ok((breakpoint_id, location))
+    }
+
+    /// Allocate a fresh breakpoint ID
+    fn next_breakpoint_id(&self) -> BreakpointId {
         let bp_id = self.breakpoint_counter.fetch_add(1, Ordering::SeqCst);
-        let breakpoint_id = BreakpointId(format!("bp-{}", bp_id));
+        BreakpointId(format!("bp-{}", bp_id))
+    }
 
+    /// Store a breakpoint under `breakpoint_id` and emit `BreakpointResolved`
+    fn insert_breakpoint(
+        &self,
+        breakpoint_id: BreakpointId,
+        location: Location,
+        condition: Option<String>,
+    ) -> Location {
         // Try to get original location if source map available
         let original_location = self.get_original_location(
             &location.script_id.0,
@@ -446,14 +1064,34 @@ impl JsDebugBridge {
 
         // Emit event
         let _ = self.event_sender.send(DebugEvent::BreakpointResolved {
-            breakpoint_id: breakpoint_id.clone(),
+            breakpoint_id,
             location: location.clone(),
         });
 
-        Ok((breakpoint_id, location))
+        location
+    }
+
+    /// Set a logpoint that emits `message` instead of pausing
+    ///
+    /// `{expression}` placeholders in the message are filled in by the
+    /// `ConditionEvaluator` when the logpoint is hit.
+    pub fn set_logpoint(
+        &self,
+        location: Location,
+        message: impl Into<String>,
+    ) -> Result<(BreakpointId, Location)> {
+        let (bp_id, actual_location) = self.set_breakpoint(location, None)?;
+        if let Some(mut bp) = self.breakpoints.get_mut(&bp_id.0) {
+            bp.log_message = Some(message.into());
+        }
+        Ok((bp_id, actual_location))
     }
 
     /// Set breakpoint by URL
+    ///
+    /// If no script with the URL has been parsed yet, the breakpoint is kept
+    /// pending and no locations are returned; it resolves (emitting
+    /// `BreakpointResolved`) when a matching script is added.
     pub fn set_breakpoint_by_url(
         &self,
         url: &str,
@@ -466,11 +1104,27 @@ impl JsDebugBridge {
         }
 
         // Find script by URL
-        let script_id = self
-            .scripts_by_url
-            .get(url)
-            .map(|s| s.clone())
-            .ok_or_else(|| JsDebugBridgeError::ScriptNotFound(url.to_string()))?;
+        let script_id = match self.scripts_by_url.get(url).map(|s| s.clone()) {
+            Some(script_id) => script_id,
+            None => {
+                let breakpoint_id = self.next_breakpoint_id();
+                self.pending_breakpoints.insert(
+                    breakpoint_id.0.clone(),
+                    PendingBreakpoint {
+                        id: breakpoint_id.clone(),
+                        url: url.to_string(),
+                        line_number,
+                        column_number,
+                        condition,
+                    },
+                );
+                debug!(
+                    "Breakpoint {} pending until {} is parsed",
+                    breakpoint_id.0, url
+                );
+                return Ok((breakpoint_id, Vec::new()));
+            }
+        };
 
         let location = Location {
             script_id: ScriptId(script_id),
@@ -483,12 +1137,54 @@ impl JsDebugBridge {
         Ok((bp_id, vec![actual_location]))
     }
 
+    /// Get breakpoints set by URL that are still waiting for their script
+    pub fn get_pending_breakpoints(&self) -> Vec<PendingBreakpoint> {
+        self.pending_breakpoints
+            .iter()
+            .map(|e| e.value().clone())
+            .collect()
+    }
+
+    /// Turn pending breakpoints for `url` into breakpoints in a newly added script
+    fn resolve_pending_breakpoints(&self, url: &str, script_id: &ScriptId) {
+        let resolved: Vec<PendingBreakpoint> = self
+            .pending_breakpoints
+            .iter()
+            .filter(|p| p.url == url)
+            .map(|p| p.value().clone())
+            .collect();
+
+        for pending in resolved {
+            self.pending_breakpoints.remove(&pending.id.0);
+            let location = Location {
+                script_id: script_id.clone(),
+                line_number: pending.line_number,
+                column_number: pending.column_number,
+            };
+            self.insert_breakpoint(pending.id, location, pending.condition);
+        }
+    }
+
     /// Remove a breakpoint
     pub fn remove_breakpoint(&self, breakpoint_id: &BreakpointId) -> Result<()> {
         if !self.is_enabled() {
             return Err(JsDebugBridgeError::NotEnabled);
         }
 
+        if self
+            .instrumentation_breakpoints
+            .remove(&breakpoint_id.0)
+            .is_some()
+        {
+            debug!("Removed instrumentation breakpoint {}", breakpoint_id.0);
+            return Ok(());
+        }
+
+        if self.pending_breakpoints.remove(&breakpoint_id.0).is_some() {
+            debug!("Removed pending breakpoint {}", breakpoint_id.0);
+            return Ok(());
+        }
+
         let bp_info = self
             .breakpoints
             .remove(&breakpoint_id.0)
@@ -507,6 +1203,53 @@ impl JsDebugBridge {
         Ok(())
     }
 
+    /// Remove every breakpoint in scripts loaded from `url`
+    ///
+    /// Covers all scripts sharing the URL, not just the most recent one, and
+    /// breakpoints still pending for it. Returns the number of breakpoints
+    /// removed.
+    pub fn remove_breakpoints_by_url(&self, url: &str) -> usize {
+        if !self.is_enabled() {
+            return 0;
+        }
+
+        let pending_before = self.pending_breakpoints.len();
+        self.pending_breakpoints.retain(|_, p| p.url != url);
+        let pending_removed = pending_before - self.pending_breakpoints.len();
+
+        let script_ids: HashSet<String> = self
+            .scripts
+            .iter()
+            .filter(|s| s.url == url)
+            .map(|s| s.script_id.0.clone())
+            .collect();
+
+        let removed: Vec<BreakpointInfo> = self
+            .breakpoints
+            .iter()
+            .filter(|bp| script_ids.contains(&bp.location.script_id.0))
+            .map(|bp| bp.value().clone())
+            .collect();
+        for bp in &removed {
+            self.breakpoints.remove(&bp.id.0);
+            let loc_key = format!(
+                "{}:{}:{}",
+                bp.location.script_id.0,
+                bp.location.line_number,
+                bp.location.column_number.unwrap_or(0)
+            );
+            self.breakpoints_by_location
+                .remove_if(&loc_key, |_, id| *id == bp.id.0);
+        }
+
+        debug!(
+            "Removed {} breakpoints for {}",
+            removed.len() + pending_removed,
+            url
+        );
+        removed.len() + pending_removed
+    }
+
     /// Get all breakpoints
     pub fn get_breakpoints(&self) -> Vec<BreakpointInfo> {
         self.breakpoints.iter().map(|e| e.value().clone()).collect()
@@ -523,6 +1266,125 @@ impl JsDebugBridge {
         self.breakpoints_by_location.contains_key(&loc_key)
     }
 
+    /// Set an instrumentation breakpoint
+    ///
+    /// Supported instrumentations are `beforeScriptExecution` and
+    /// `beforeScriptWithSourceMapExecution`. Remove with `remove_breakpoint`.
+    pub fn set_instrumentation_breakpoint(&self, instrumentation: &str) -> Result<BreakpointId> {
+        if !self.is_enabled() {
+            return Err(JsDebugBridgeError::NotEnabled);
+        }
+
+        if !matches!(
+            instrumentation,
+            "beforeScriptExecution" | "beforeScriptWithSourceMapExecution"
+        ) {
+            return Err(JsDebugBridgeError::InvalidParameter(format!(
+                "Unknown instrumentation: {}",
+                instrumentation
+            )));
+        }
+
+        let bp_id = self.breakpoint_counter.fetch_add(1, Ordering::SeqCst);
+        let breakpoint_id = BreakpointId(format!("instrumentation-bp-{}", bp_id));
+
+        self.instrumentation_breakpoints
+            .insert(breakpoint_id.0.clone(), instrumentation.to_string());
+
+        debug!(
+            "Set instrumentation breakpoint {} for {}",
+            breakpoint_id.0, instrumentation
+        );
+        Ok(breakpoint_id)
+    }
+
+    /// Get active instrumentation breakpoints as (breakpoint_id, instrumentation) pairs
+    pub fn get_instrumentation_breakpoints(&self) -> Vec<(BreakpointId, String)> {
+        self.instrumentation_breakpoints
+            .iter()
+            .map(|e| (BreakpointId(e.key().clone()), e.value().clone()))
+            .collect()
+    }
+
+    /// Pause before a newly added script runs if an instrumentation breakpoint matches
+    fn check_instrumentation_breakpoints(&self, script: &ScriptInfo) {
+        if self.is_paused() || self.skip_all_pauses.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let matches: Vec<(BreakpointId, String)> = self
+            .instrumentation_breakpoints
+            .iter()
+            .filter(|e| match e.value().as_str() {
+                "beforeScriptExecution" => true,
+                "beforeScriptWithSourceMapExecution" => script.source_map_url.is_some(),
+                _ => false,
+            })
+            .map(|e| (BreakpointId(e.key().clone()), e.value().clone()))
+            .collect();
+
+        if matches.is_empty() {
+            return;
+        }
+
+        let instrumentation = if matches
+            .iter()
+            .any(|(_, name)| name == "beforeScriptWithSourceMapExecution")
+        {
+            "beforeScriptWithSourceMapExecution"
+        } else {
+            "beforeScriptExecution"
+        };
+        let hit: Vec<BreakpointId> = matches.into_iter().map(|(id, _)| id).collect();
+
+        let frame = CallFrame {
+            call_frame_id: format!("frame-{}", Uuid::new_v4()),
+            function_name: String::new(),
+            location: Location {
+                script_id: script.script_id.clone(),
+                line_number: script.start_line,
+                column_number: Some(script.start_column),
+            },
+            url: script.url.clone(),
+            scope_chain: vec![Scope {
+                scope_type: ScopeType::Global,
+                object: create_mock_remote_object("Global"),
+                name: Some("Global".to_string()),
+                start_location: None,
+                end_location: None,
+            }],
+            this: create_mock_remote_object("global"),
+            return_value: None,
+        };
+
+        self.paused.store(true, Ordering::SeqCst);
+        *self.pause_reason.write() = Some(PausedReason::Instrumentation);
+        *self.call_frames.write() = vec![frame];
+        *self.hit_breakpoints.write() = hit;
+
+        let mut data = json!({
+            "instrumentation": instrumentation,
+            "scriptId": script.script_id.0,
+            "url": script.url,
+        });
+        if let Some(ref sm_url) = script.source_map_url {
+            data["sourceMapURL"] = json!(sm_url);
+        }
+
+        let pause_state = self.pause_state(
+            PausedReason::Instrumentation,
+            self.call_frames.read().clone(),
+            self.hit_breakpoints.read().clone(),
+            Some(data),
+        );
+        let _ = self.event_sender.send(DebugEvent::Paused(pause_state));
+
+        debug!(
+            "Paused on instrumentation {} before {}",
+            instrumentation, script.script_id.0
+        );
+    }
+
     // ========== Execution Control ==========
 
     /// Pause execution
@@ -542,20 +1404,242 @@ impl JsDebugBridge {
         self.create_mock_call_frames();
 
         // Emit event
-        let pause_state = PauseState {
-            reason: PausedReason::Other,
-            call_frames: self.call_frames.read().clone(),
-            hit_breakpoints: self.hit_breakpoints.read().clone(),
-            data: None,
-        };
+        let pause_state = self.pause_state(
+            PausedReason::Other,
+            self.call_frames.read().clone(),
+            self.hit_breakpoints.read().clone(),
+            None,
+        );
         let _ = self.event_sender.send(DebugEvent::Paused(pause_state));
 
         debug!("Debugger paused");
         Ok(())
     }
 
-    /// Resume execution
-    pub fn resume(&self) -> Result<()> {
+    /// Report that execution reached a location, pausing if a breakpoint is set there
+    ///
+    /// Returns `true` if execution paused. The breakpoint's condition and hit
+    /// count are handled as in `should_pause_at`, with the condition checked
+    /// in the frame that would pause, and logpoints emit their
+    /// `LogpointMessage` without pausing. Hit counts are tracked even while
+    /// pauses are skipped, but no `Paused` event is emitted.
+    pub fn hit_breakpoint_at(&self, script_id: &str, line: u32, column: u32) -> Result<bool> {
+        if !self.is_enabled() {
+            return Err(JsDebugBridgeError::NotEnabled);
+        }
+
+        let location = Location {
+            script_id: ScriptId(script_id.to_string()),
+            line_number: line,
+            column_number: Some(column),
+        };
+        let frames = self.mock_call_frames_at(script_id, location);
+        let bp_id = match self.breakpoint_to_pause_at(script_id, line, column, &frames[0]) {
+            Some(bp_id) => bp_id,
+            None => return Ok(false),
+        };
+
+        *self.pending_step.write() = None;
+        self.pause_with_frames(frames, vec![bp_id.clone()]);
+
+        debug!("Paused at breakpoint {}", bp_id.0);
+        Ok(true)
+    }
+
+    /// Decide whether the breakpoint at a location should pause in `frame`
+    ///
+    /// A breakpoint's condition is checked with the installed
+    /// `ConditionEvaluator`; its hit count only increases when the condition
+    /// passes. Returns `false` without a matching enabled breakpoint, in a
+    /// blackboxed execution context, or while paused or skipping pauses.
+    /// Logpoints never pause: they emit a `LogpointMessage` event with their
+    /// interpolated message instead.
+    pub fn should_pause_at(
+        &self,
+        script_id: &str,
+        line: u32,
+        column: u32,
+        frame: &CallFrame,
+    ) -> bool {
+        self.breakpoint_to_pause_at(script_id, line, column, frame)
+            .is_some()
+    }
+
+    /// The breakpoint to pause at for a location, if any; see `should_pause_at`
+    fn breakpoint_to_pause_at(
+        &self,
+        script_id: &str,
+        line: u32,
+        column: u32,
+        frame: &CallFrame,
+    ) -> Option<BreakpointId> {
+        let loc_key = format!("{}:{}:{}", script_id, line, column);
+        let bp_id = self.breakpoints_by_location.get(&loc_key)?.clone();
+
+        // Clone what we need so the evaluator runs without holding the entry
+        let (condition, log_message, location) = match self.breakpoints.get(&bp_id) {
+            Some(bp) if bp.enabled => (
+                bp.condition.clone(),
+                bp.log_message.clone(),
+                bp.location.clone(),
+            ),
+            _ => return None,
+        };
+        let evaluator = self.condition_evaluator.read().clone();
+
+        if let Some(message) = log_message {
+            if let Some(mut bp) = self.breakpoints.get_mut(&bp_id) {
+                bp.hit_count += 1;
+            }
+            let message = interpolate_log_message(&message, evaluator.as_deref(), frame);
+            debug!("Logpoint {}: {}", bp_id, message);
+            let _ = self.event_sender.send(DebugEvent::LogpointMessage {
+                breakpoint_id: BreakpointId(bp_id),
+                location,
+                message,
+            });
+            return None;
+        }
+
+        if let Some(condition) = condition {
+            if let Some(evaluator) = evaluator {
+                if !evaluator.evaluate(&condition, frame) {
+                    debug!("Condition '{}' of breakpoint {} is false", condition, bp_id);
+                    return None;
+                }
+            }
+        }
+
+        if let Some(mut bp) = self.breakpoints.get_mut(&bp_id) {
+            bp.hit_count += 1;
+        }
+        if self.is_paused() || self.is_skipping_pauses() || self.is_script_blackboxed(script_id) {
+            debug!("Skipping pause at breakpoint {}", bp_id);
+            return None;
+        }
+        Some(BreakpointId(bp_id))
+    }
+
+    /// Report that execution reached a new statement while a step is pending
+    ///
+    /// Pauses there and returns `true`, unless the script belongs to a
+    /// blackboxed execution context: then the location is stepped through
+    /// and the step stays pending for the next reported location.
+    pub fn step_reached_at(&self, script_id: &str, line: u32, column: u32) -> Result<bool> {
+        if !self.is_enabled() {
+            return Err(JsDebugBridgeError::NotEnabled);
+        }
+
+        let action = match *self.pending_step.read() {
+            Some(action) => action,
+            None => return Ok(false),
+        };
+
+        if self.is_paused() || self.is_skipping_pauses() {
+            return Ok(false);
+        }
+
+        if self.is_script_blackboxed(script_id) {
+            debug!("Stepping through blackboxed script {}", script_id);
+            return Ok(false);
+        }
+
+        *self.pending_step.write() = None;
+        let location = Location {
+            script_id: ScriptId(script_id.to_string()),
+            line_number: line,
+            column_number: Some(column),
+        };
+        self.pause_at_location(script_id, location, Vec::new());
+
+        debug!("Paused after {:?} at {}:{}:{}", action, script_id, line, column);
+        Ok(true)
+    }
+
+    /// Enter a pause with the top call frame at `location` and emit `Paused`
+    fn pause_at_location(
+        &self,
+        script_id: &str,
+        location: Location,
+        hit_breakpoints: Vec<BreakpointId>,
+    ) {
+        let frames = self.mock_call_frames_at(script_id, location);
+        self.pause_with_frames(frames, hit_breakpoints);
+    }
+
+    /// Mock call stack whose top frame is at `location` in `script_id`
+    fn mock_call_frames_at(&self, script_id: &str, location: Location) -> Vec<CallFrame> {
+        let mut frames = self.mock_call_frames();
+        if let Some(top) = frames.first_mut() {
+            top.url = self
+                .scripts
+                .get(script_id)
+                .map(|s| s.url.clone())
+                .unwrap_or_default();
+            top.location = location;
+        }
+        frames
+    }
+
+    /// Pause with the given call stack and emit `Paused`
+    fn pause_with_frames(&self, frames: Vec<CallFrame>, hit_breakpoints: Vec<BreakpointId>) {
+        *self.call_frames.write() = frames;
+        self.paused.store(true, Ordering::SeqCst);
+        *self.pause_reason.write() = Some(PausedReason::Other);
+        *self.hit_breakpoints.write() = hit_breakpoints;
+
+        let pause_state = self.pause_state(
+            PausedReason::Other,
+            self.call_frames.read().clone(),
+            self.hit_breakpoints.read().clone(),
+            None,
+        );
+        let _ = self.event_sender.send(DebugEvent::Paused(pause_state));
+    }
+
+    /// Build a `Paused` payload, source-mapping the call frames in one batch
+    fn pause_state(
+        &self,
+        reason: PausedReason,
+        call_frames: Vec<CallFrame>,
+        hit_breakpoints: Vec<BreakpointId>,
+        data: Option<Value>,
+    ) -> PauseState {
+        PauseState {
+            original_locations: self.original_frame_locations(&call_frames),
+            reason,
+            call_frames,
+            hit_breakpoints,
+            data,
+        }
+    }
+
+    // ========== Blackboxing ==========
+
+    /// Skip frames from the given execution contexts while pausing or stepping
+    ///
+    /// IDs are matched against `ScriptInfo.execution_context_id`. Breakpoints
+    /// and exceptions in those contexts don't pause, and steps pass through
+    /// them. Replaces the previous list; an empty list restores normal pausing.
+    pub fn set_blackbox_execution_contexts(&self, unique_ids: Vec<String>) {
+        debug!("Blackboxing {} execution contexts", unique_ids.len());
+        *self.blackboxed_contexts.write() = unique_ids.into_iter().collect();
+    }
+
+    /// Whether a script runs in a blackboxed execution context
+    pub fn is_script_blackboxed(&self, script_id: &str) -> bool {
+        let contexts = self.blackboxed_contexts.read();
+        if contexts.is_empty() {
+            return false;
+        }
+        self.scripts
+            .get(script_id)
+            .map(|s| contexts.contains(&s.execution_context_id.to_string()))
+            .unwrap_or(false)
+    }
+
+    /// Resume execution
+    pub fn resume(&self) -> Result<()> {
         if !self.is_enabled() {
             return Err(JsDebugBridgeError::NotEnabled);
         }
@@ -565,7 +1649,9 @@ impl JsDebugBridge {
         }
 
         self.paused.store(false, Ordering::SeqCst);
+        *self.pending_step.write() = None;
         self.call_frames.write().clear();
+        self.scope_variables.clear();
         *self.pause_reason.write() = None;
         self.hit_breakpoints.write().clear();
 
@@ -602,8 +1688,8 @@ impl JsDebugBridge {
 
         debug!("Performing step action: {:?}", action);
 
-        // In a real implementation, this would instruct the JS engine to step
-        // For now, we just resume
+        // The engine resumes and reports the next statement via `step_reached_at`
+        *self.pending_step.write() = Some(action);
         self.paused.store(false, Ordering::SeqCst);
         self.call_frames.write().clear();
         *self.pause_reason.write() = None;
@@ -624,18 +1710,221 @@ impl JsDebugBridge {
         *self.pause_on_exceptions.read()
     }
 
+    /// Install a filter consulted for exceptions the mode allows
+    pub fn set_exception_filter(&self, filter: Arc<dyn ExceptionFilter>) {
+        *self.exception_filter.write() = filter;
+    }
+
+    /// Restore the default filter that accepts every exception
+    pub fn clear_exception_filter(&self) {
+        *self.exception_filter.write() = Arc::new(AcceptAllExceptions);
+    }
+
+    /// Install the evaluator used for conditional breakpoints
+    pub fn set_condition_evaluator(&self, evaluator: Arc<dyn ConditionEvaluator>) {
+        *self.condition_evaluator.write() = Some(evaluator);
+    }
+
+    /// Remove the condition evaluator; conditional breakpoints always pause
+    pub fn clear_condition_evaluator(&self) {
+        *self.condition_evaluator.write() = None;
+    }
+
+    /// Report a thrown exception, pausing if the mode and filter allow it
+    ///
+    /// The exception type passed to the filter is taken from the exception's
+    /// `className` (falling back to `name`). Skip-all-pauses, or a throwing
+    /// frame in a blackboxed execution context, suppresses the pause
+    /// regardless of mode. Returns whether the debugger paused.
+    pub fn report_exception(
+        &self,
+        is_uncaught: bool,
+        exception: Value,
+        call_frames: Vec<CallFrame>,
+    ) -> bool {
+        if !self.is_enabled() || self.is_paused() || self.is_skipping_pauses() {
+            return false;
+        }
+
+        let mode_allows = match self.get_pause_on_exceptions() {
+            PauseOnExceptionsMode::None => false,
+            PauseOnExceptionsMode::Uncaught => is_uncaught,
+            PauseOnExceptionsMode::All => true,
+        };
+        if !mode_allows {
+            return false;
+        }
+
+        let error_type = exception
+            .get("className")
+            .or_else(|| exception.get("name"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let filter = self.exception_filter.read().clone();
+        if !filter.should_pause(&error_type, is_uncaught) {
+            debug!("Exception filter skipped {} exception", error_type);
+            return false;
+        }
+
+        if let Some(top) = call_frames.first() {
+            if self.is_script_blackboxed(&top.location.script_id.0) {
+                debug!("Skipping exception thrown in blackboxed script");
+                return false;
+            }
+        }
+
+        self.paused.store(true, Ordering::SeqCst);
+        *self.pause_reason.write() = Some(PausedReason::Exception);
+        *self.call_frames.write() = call_frames;
+        self.hit_breakpoints.write().clear();
+
+        let pause_state = self.pause_state(
+            PausedReason::Exception,
+            self.call_frames.read().clone(),
+            Vec::new(),
+            Some(exception),
+        );
+        let _ = self.event_sender.send(DebugEvent::Paused(pause_state));
+
+        debug!("Paused on {} exception (uncaught: {})", error_type, is_uncaught);
+        true
+    }
+
     /// Set skip all pauses
+    ///
+    /// While set, breakpoints, instrumentation breakpoints, exceptions and
+    /// `pause()` never enter a pause and no `Paused` event is emitted.
     pub fn set_skip_all_pauses(&self, skip: bool) {
         self.skip_all_pauses.store(skip, Ordering::SeqCst);
         debug!("Set skip all pauses: {}", skip);
     }
 
+    /// Check if all pauses are being skipped
+    pub fn is_skipping_pauses(&self) -> bool {
+        self.skip_all_pauses.load(Ordering::SeqCst)
+    }
+
     /// Set async stack trace depth
     pub fn set_async_stack_trace_depth(&self, depth: u32) {
         self.async_stack_trace_depth.store(depth, Ordering::SeqCst);
         debug!("Set async stack trace depth: {}", depth);
     }
 
+    // ========== Async Stacks ==========
+
+    /// Record that an async task was scheduled from the current location
+    ///
+    /// The current call frames (up to the async stack depth) are kept as the
+    /// task's parent stack. Returns the ID to pass to `record_async_task_started`.
+    pub fn record_async_task_scheduled(&self, description: &str) -> StackTraceId {
+        let task_num = self.async_task_counter.fetch_add(1, Ordering::SeqCst);
+        let stack_trace_id = StackTraceId {
+            id: format!("async-task-{}", task_num),
+            debugger_id: None,
+        };
+
+        let depth = self.async_stack_trace_depth.load(Ordering::SeqCst) as usize;
+        let parent_frames = self
+            .call_frames
+            .read()
+            .iter()
+            .take(depth)
+            .cloned()
+            .collect();
+
+        self.async_tasks.insert(
+            stack_trace_id.id.clone(),
+            AsyncTaskInfo {
+                stack_trace_id: stack_trace_id.clone(),
+                description: description.to_string(),
+                parent_frames,
+            },
+        );
+
+        debug!("Recorded async task {} ({})", stack_trace_id.id, description);
+        stack_trace_id
+    }
+
+    /// Get a recorded async task
+    pub fn get_async_task(&self, stack_trace_id: &StackTraceId) -> Option<AsyncTaskInfo> {
+        self.async_tasks.get(&stack_trace_id.id).map(|t| t.clone())
+    }
+
+    /// Pause when the continuation of the given async task starts running
+    ///
+    /// Used when stepping into an async call: the pause happens at the
+    /// scheduled continuation rather than the scheduling call. One-shot.
+    pub fn set_pause_on_async_call(&self, parent_stack_trace_id: StackTraceId) -> Result<()> {
+        if !self.is_enabled() {
+            return Err(JsDebugBridgeError::NotEnabled);
+        }
+
+        if !self.async_tasks.contains_key(&parent_stack_trace_id.id) {
+            return Err(JsDebugBridgeError::InvalidParameter(format!(
+                "Unknown async task: {}",
+                parent_stack_trace_id.id
+            )));
+        }
+
+        debug!("Pause on async call {}", parent_stack_trace_id.id);
+        *self.pause_on_async_call.write() = Some(parent_stack_trace_id);
+        Ok(())
+    }
+
+    /// Record that an async task's continuation started running
+    ///
+    /// The task's record is dropped, since a continuation runs once. Returns
+    /// `true` if this pauses execution because of a pending
+    /// `set_pause_on_async_call` for the task.
+    pub fn record_async_task_started(
+        &self,
+        stack_trace_id: &StackTraceId,
+        continuation_frames: Vec<CallFrame>,
+    ) -> Result<bool> {
+        if !self.is_enabled() {
+            return Err(JsDebugBridgeError::NotEnabled);
+        }
+
+        let task = self
+            .async_tasks
+            .remove(&stack_trace_id.id)
+            .map(|(_, task)| task);
+
+        {
+            let mut pending = self.pause_on_async_call.write();
+            if pending.as_ref().map(|p| &p.id) != Some(&stack_trace_id.id) {
+                return Ok(false);
+            }
+            *pending = None;
+        }
+
+        if self.is_paused() || self.is_skipping_pauses() {
+            return Ok(false);
+        }
+
+        let description = task.map(|t| t.description).unwrap_or_default();
+
+        self.paused.store(true, Ordering::SeqCst);
+        *self.pause_reason.write() = Some(PausedReason::Other);
+        *self.call_frames.write() = continuation_frames;
+        self.hit_breakpoints.write().clear();
+
+        let pause_state = self.pause_state(
+            PausedReason::Other,
+            self.call_frames.read().clone(),
+            Vec::new(),
+            Some(json!({
+                "asyncTaskId": stack_trace_id.id,
+                "description": description,
+            })),
+        );
+        let _ = self.event_sender.send(DebugEvent::Paused(pause_state));
+
+        debug!("Paused on async continuation {}", stack_trace_id.id);
+        Ok(true)
+    }
+
     // ========== Call Stack Management ==========
 
     /// Get current call frames
@@ -654,8 +1943,12 @@ impl JsDebugBridge {
 
     /// Create mock call frames for testing
     fn create_mock_call_frames(&self) {
-        let mut frames = self.call_frames.write();
-        frames.clear();
+        *self.call_frames.write() = self.mock_call_frames();
+    }
+
+    /// Build the mock call stack used while paused
+    fn mock_call_frames(&self) -> Vec<CallFrame> {
+        let mut frames = Vec::with_capacity(2);
 
         // Get first script (if any) for realistic mock data
         let (script_id, url) = self
@@ -722,6 +2015,7 @@ impl JsDebugBridge {
             this: create_mock_remote_object("global"),
             return_value: None,
         });
+        frames
     }
 
     // ========== Evaluation ==========
@@ -778,33 +2072,73 @@ impl JsDebugBridge {
 
         debug!("Getting scope variables for: {}", scope_object_id);
 
-        // Mock scope variables
-        Ok(vec![
-            PropertyInfo {
-                name: "x".to_string(),
-                value: create_mock_remote_object_value("number", Some(json!(42))),
-                writable: true,
-                configurable: true,
-                enumerable: true,
-            },
-            PropertyInfo {
-                name: "name".to_string(),
-                value: create_mock_remote_object_value("string", Some(json!("test"))),
-                writable: true,
-                configurable: true,
-                enumerable: true,
-            },
-            PropertyInfo {
-                name: "arr".to_string(),
-                value: create_mock_remote_object_value("object", None),
-                writable: true,
-                configurable: true,
-                enumerable: true,
-            },
-        ])
+        Ok(self
+            .scope_variables
+            .get(scope_object_id)
+            .map(|vars| vars.clone())
+            .unwrap_or_else(mock_scope_variables))
+    }
+
+    /// Set a variable in one of a call frame's scopes
+    ///
+    /// `scope_number` indexes the frame's scope chain (0 is the innermost
+    /// scope). The new value is visible through `get_scope_variables` until
+    /// execution resumes.
+    pub fn set_variable_value(
+        &self,
+        scope_number: usize,
+        variable_name: &str,
+        new_value: Value,
+        call_frame_id: &str,
+    ) -> Result<()> {
+        if !self.is_enabled() {
+            return Err(JsDebugBridgeError::NotEnabled);
+        }
+
+        if !self.is_paused() {
+            return Err(JsDebugBridgeError::NotPaused);
+        }
+
+        let frame = self
+            .get_call_frame(call_frame_id)
+            .ok_or_else(|| JsDebugBridgeError::CallFrameNotFound(call_frame_id.to_string()))?;
+        let scope_object_id = frame
+            .scope_chain
+            .get(scope_number)
+            .and_then(|scope| scope.object.object_id.clone())
+            .ok_or_else(|| {
+                JsDebugBridgeError::InvalidParameter(format!(
+                    "Scope {} not found in frame {}",
+                    scope_number, call_frame_id
+                ))
+            })?;
+
+        let mut variables = self
+            .scope_variables
+            .entry(scope_object_id.0)
+            .or_insert_with(mock_scope_variables);
+        let variable = variables
+            .iter_mut()
+            .find(|v| v.name == variable_name)
+            .ok_or_else(|| {
+                JsDebugBridgeError::InvalidParameter(format!(
+                    "Variable not found: {}",
+                    variable_name
+                ))
+            })?;
+        variable.value = remote_object_from_value(new_value);
+
+        debug!(
+            "Set variable {} in scope {} of frame {}",
+            variable_name, scope_number, call_frame_id
+        );
+        Ok(())
     }
 
     /// Restart frame (for Edit and Continue)
+    ///
+    /// Pops the frames above `call_frame_id` so it becomes the top frame, and
+    /// emits `Resumed` then `Paused` with the new stack.
     pub fn restart_frame(&self, call_frame_id: &str) -> Result<Vec<CallFrame>> {
         if !self.is_enabled() {
             return Err(JsDebugBridgeError::NotEnabled);
@@ -814,19 +2148,39 @@ impl JsDebugBridge {
             return Err(JsDebugBridgeError::NotPaused);
         }
 
-        // Verify call frame exists
-        let frames = self.call_frames.read();
-        if !frames.iter().any(|f| f.call_frame_id == call_frame_id) {
-            return Err(JsDebugBridgeError::CallFrameNotFound(
-                call_frame_id.to_string(),
-            ));
-        }
+        // Drop the frames above the restarted one
+        let frames = {
+            let mut frames = self.call_frames.write();
+            let index = frames
+                .iter()
+                .position(|f| f.call_frame_id == call_frame_id)
+                .ok_or_else(|| JsDebugBridgeError::CallFrameNotFound(call_frame_id.to_string()))?;
+            frames.drain(..index);
+            frames.clone()
+        };
 
         debug!("Restarting frame: {}", call_frame_id);
 
-        // In a real implementation, this would restart execution from the frame
-        // For now, just return current frames
-        Ok(frames.clone())
+        *self.pending_step.write() = None;
+        *self.pause_reason.write() = Some(PausedReason::Other);
+        self.hit_breakpoints.write().clear();
+
+        // Resumed + Paused so clients rebuild their stack view
+        let _ = self.event_sender.send(DebugEvent::Resumed);
+        let _ = self.event_sender.send(DebugEvent::Paused(self.pause_state(
+            PausedReason::Other,
+            frames.clone(),
+            Vec::new(),
+            None,
+        )));
+
+        Ok(frames)
+    }
+}
+
+impl runtime_debugger::ExceptionFormatter for JsDebugBridge {
+    fn format_exception(&self, raw: ExceptionDetails) -> ExceptionDetails {
+        JsDebugBridge::format_exception(self, raw)
     }
 }
 
@@ -891,6 +2245,46 @@ fn create_mock_remote_object_value(type_name: &str, value: Option<Value>) -> Rem
     }
 }
 
+/// Variables reported for a scope that hasn't been modified
+fn mock_scope_variables() -> Vec<PropertyInfo> {
+    vec![
+        PropertyInfo {
+            name: "x".to_string(),
+            value: create_mock_remote_object_value("number", Some(json!(42))),
+            writable: true,
+            configurable: true,
+            enumerable: true,
+        },
+        PropertyInfo {
+            name: "name".to_string(),
+            value: create_mock_remote_object_value("string", Some(json!("test"))),
+            writable: true,
+            configurable: true,
+            enumerable: true,
+        },
+        PropertyInfo {
+            name: "arr".to_string(),
+            value: create_mock_remote_object_value("object", None),
+            writable: true,
+            configurable: true,
+            enumerable: true,
+        },
+    ]
+}
+
+/// Wrap a JSON value (a `CallArgument.value`) as a RemoteObject
+fn remote_object_from_value(value: Value) -> RemoteObject {
+    match value {
+        Value::Null => mock_evaluate("null"),
+        Value::Number(_) => create_mock_remote_object_value("number", Some(value)),
+        Value::String(_) => create_mock_remote_object_value("string", Some(value)),
+        Value::Bool(_) => create_mock_remote_object_value("boolean", Some(value)),
+        Value::Array(_) | Value::Object(_) => {
+            create_mock_remote_object_value("object", Some(value))
+        }
+    }
+}
+
 /// Mock expression evaluation
 fn mock_evaluate(expression: &str) -> RemoteObject {
     match expression.trim() {
@@ -977,10 +2371,148 @@ fn mock_evaluate(expression: &str) -> RemoteObject {
     }
 }
 
+/// Fill `{expression}` placeholders in a logpoint message
+///
+/// Placeholders the evaluator can't resolve (or all of them, without an
+/// evaluator) are kept verbatim.
+fn interpolate_log_message(
+    message: &str,
+    evaluator: Option<&dyn ConditionEvaluator>,
+    frame: &CallFrame,
+) -> String {
+    let mut output = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(offset) => start + offset,
+            None => break,
+        };
+        output.push_str(&rest[..start]);
+        let expression = &rest[start + 1..end];
+        match evaluator.and_then(|e| e.evaluate_expression(expression.trim(), frame)) {
+            Some(value) => output.push_str(&value),
+            None => output.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Columns where a run of code starts on a source line, skipping whitespace,
+/// comments and string contents
+///
+/// `in_block_comment` carries an unterminated `/*` over to the next line.
+fn code_columns(line: &str, in_block_comment: &mut bool) -> Vec<u32> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut columns = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut in_code = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if *in_block_comment {
+            if c == '*' && next == Some('/') {
+                *in_block_comment = false;
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+        if let Some(q) = quote {
+            if c == '\\' {
+                i += 1;
+            } else if c == q {
+                quote = None;
+            }
+            i += 1;
+            continue;
+        }
+        match (c, next) {
+            ('/', Some('/')) => break,
+            ('/', Some('*')) => {
+                *in_block_comment = true;
+                in_code = false;
+                i += 2;
+                continue;
+            }
+            _ if c.is_whitespace() => in_code = false,
+            _ => {
+                if !in_code {
+                    columns.push(i as u32);
+                    in_code = true;
+                }
+                if matches!(c, '"' | '\'' | '`') {
+                    quote = Some(c);
+                }
+            }
+        }
+        i += 1;
+    }
+    columns
+}
+
+/// Last line and its length, as reported in `scriptParsed`
+fn source_end(source: &str) -> (u32, u32) {
+    let lines: Vec<&str> = source.lines().collect();
+    let end_line = if lines.is_empty() {
+        0
+    } else {
+        (lines.len() - 1) as u32
+    };
+    let end_column = lines.last().map(|l| l.len() as u32).unwrap_or(0);
+    (end_line, end_column)
+}
+
+/// Resolve a (possibly relative) URL against the URL of the referencing script
+///
+/// Absolute references and bases without a scheme are returned unchanged.
+fn resolve_url(base: &str, reference: &str) -> String {
+    if reference.contains("://") || reference.starts_with("data:") {
+        return reference.to_string();
+    }
+    let base = base.split(['?', '#']).next().unwrap_or(base);
+    let Some(scheme_end) = base.find("://") else {
+        return reference.to_string();
+    };
+    if reference.starts_with("//") {
+        return format!("{}:{}", &base[..scheme_end], reference);
+    }
+
+    let authority_start = scheme_end + 3;
+    let path_start = base[authority_start..]
+        .find('/')
+        .map(|i| authority_start + i)
+        .unwrap_or(base.len());
+    let path = if reference.starts_with('/') {
+        reference.to_string()
+    } else {
+        let base_path = &base[path_start..];
+        let dir = base_path
+            .rfind('/')
+            .map(|i| &base_path[..=i])
+            .unwrap_or("/");
+        format!("{}{}", dir, reference)
+    };
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/').skip(1) {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            other => segments.push(other),
+        }
+    }
+    format!("{}/{}", &base[..path_start], segments.join("/"))
+}
+
 /// Simple hash function (not cryptographic)
-fn md5_hash(input: &str) -> u64 {
+fn md5_hash(input: &[u8]) -> u64 {
     let mut hash: u64 = 0;
-    for (i, byte) in input.bytes().enumerate() {
+    for (i, &byte) in input.iter().enumerate() {
         hash = hash.wrapping_add((byte as u64).wrapping_mul((i as u64).wrapping_add(1)));
         hash = hash.rotate_left(7);
     }
@@ -1034,6 +2566,19 @@ impl DomainHandler for JsDebugBridge {
                     .map_err(|e| CdpError::internal_error(e.to_string()))?;
                 Ok(json!({}))
             }
+            "setBlackboxExecutionContexts" => {
+                let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+                let unique_ids: Vec<String> = serde_json::from_value(
+                    params
+                        .get("uniqueIds")
+                        .cloned()
+                        .ok_or_else(|| CdpError::invalid_params("Missing uniqueIds"))?,
+                )
+                .map_err(|e| CdpError::invalid_params(e.to_string()))?;
+
+                self.set_blackbox_execution_contexts(unique_ids);
+                Ok(json!({}))
+            }
             "setBreakpoint" => {
                 let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
                 let location: Location = serde_json::from_value(
@@ -1084,6 +2629,22 @@ impl DomainHandler for JsDebugBridge {
                     "locations": locations
                 }))
             }
+            "setInstrumentationBreakpoint" => {
+                let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+                let instrumentation = params
+                    .get("instrumentation")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| CdpError::invalid_params("Missing instrumentation"))?;
+
+                let bp_id = self
+                    .set_instrumentation_breakpoint(instrumentation)
+                    .map_err(|e| match e {
+                        JsDebugBridgeError::InvalidParameter(msg) => CdpError::invalid_params(msg),
+                        other => CdpError::internal_error(other.to_string()),
+                    })?;
+
+                Ok(json!({ "breakpointId": bp_id.0 }))
+            }
             "removeBreakpoint" => {
                 let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
                 let bp_id_str = params
@@ -1096,6 +2657,61 @@ impl DomainHandler for JsDebugBridge {
 
                 Ok(json!({}))
             }
+            "getPossibleBreakpoints" => {
+                let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+                let start: Location = serde_json::from_value(
+                    params
+                        .get("start")
+                        .cloned()
+                        .ok_or_else(|| CdpError::invalid_params("Missing start"))?,
+                )
+                .map_err(|e| CdpError::invalid_params(e.to_string()))?;
+                let end: Option<Location> = params
+                    .get("end")
+                    .cloned()
+                    .map(serde_json::from_value)
+                    .transpose()
+                    .map_err(|e| CdpError::invalid_params(e.to_string()))?;
+
+                let to_position =
+                    |l: &Location| Position::new(l.line_number, l.column_number.unwrap_or(0));
+                let locations = self
+                    .get_possible_breakpoints(
+                        &start.script_id.0,
+                        to_position(&start),
+                        end.as_ref().map(to_position),
+                    )
+                    .map_err(|e| CdpError::internal_error(e.to_string()))?;
+
+                Ok(json!({ "locations": locations }))
+            }
+            "setScriptSource" => {
+                let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+                let script_id = params
+                    .get("scriptId")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| CdpError::invalid_params("Missing scriptId"))?;
+                let script_source = params
+                    .get("scriptSource")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| CdpError::invalid_params("Missing scriptSource"))?;
+
+                let result =
+                    self.set_script_source(script_id, script_source)
+                        .map_err(|e| match e {
+                            JsDebugBridgeError::InvalidParameter(msg) => {
+                                CdpError::invalid_params(msg)
+                            }
+                            other => CdpError::internal_error(other.to_string()),
+                        })?;
+
+                Ok(json!({
+                    "callFrames": result.call_frames,
+                    "stackChanged": result.stack_changed,
+                    "unresolvedBreakpoints": result.unresolved_breakpoints,
+                    "status": "Ok"
+                }))
+            }
             "getScriptSource" => {
                 let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
                 let script_id = params
@@ -1109,11 +2725,25 @@ impl DomainHandler for JsDebugBridge {
 
                 Ok(json!({ "scriptSource": source }))
             }
-            "evaluateOnCallFrame" => {
+            "getWasmBytecode" => {
                 let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
-                let call_frame_id = params
-                    .get("callFrameId")
-                    .and_then(|v| v.as_str())
+                let script_id = params
+                    .get("scriptId")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| CdpError::invalid_params("Missing scriptId"))?;
+
+                let bytecode = self.get_wasm_bytecode(script_id).map_err(|e| match e {
+                    JsDebugBridgeError::InvalidParameter(msg) => CdpError::invalid_params(msg),
+                    other => CdpError::internal_error(other.to_string()),
+                })?;
+
+                Ok(json!({ "bytecode": bytecode }))
+            }
+            "evaluateOnCallFrame" => {
+                let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+                let call_frame_id = params
+                    .get("callFrameId")
+                    .and_then(|v| v.as_str())
                     .ok_or_else(|| CdpError::invalid_params("Missing callFrameId"))?;
                 let expression = params
                     .get("expression")
@@ -1132,6 +2762,48 @@ impl DomainHandler for JsDebugBridge {
                     "result": result
                 }))
             }
+            "restartFrame" => {
+                let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+                let call_frame_id = params
+                    .get("callFrameId")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| CdpError::invalid_params("Missing callFrameId"))?;
+
+                let call_frames = self
+                    .restart_frame(call_frame_id)
+                    .map_err(|e| CdpError::internal_error(e.to_string()))?;
+
+                Ok(json!({ "callFrames": call_frames }))
+            }
+            "setVariableValue" => {
+                let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+                let scope_number = params
+                    .get("scopeNumber")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| CdpError::invalid_params("Missing scopeNumber"))?
+                    as usize;
+                let variable_name = params
+                    .get("variableName")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| CdpError::invalid_params("Missing variableName"))?;
+                let new_value = params
+                    .get("newValue")
+                    .and_then(|v| v.get("value"))
+                    .cloned()
+                    .ok_or_else(|| CdpError::invalid_params("Missing newValue"))?;
+                let call_frame_id = params
+                    .get("callFrameId")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| CdpError::invalid_params("Missing callFrameId"))?;
+
+                self.set_variable_value(scope_number, variable_name, new_value, call_frame_id)
+                    .map_err(|e| match e {
+                        JsDebugBridgeError::InvalidParameter(msg) => CdpError::invalid_params(msg),
+                        other => CdpError::internal_error(other.to_string()),
+                    })?;
+
+                Ok(json!({}))
+            }
             "setPauseOnExceptions" => {
                 let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
                 let state = params
@@ -1175,6 +2847,22 @@ impl DomainHandler for JsDebugBridge {
                 self.set_async_stack_trace_depth(depth);
                 Ok(json!({}))
             }
+            "pauseOnAsyncCall" => {
+                let params = params.ok_or_else(|| CdpError::invalid_params("Missing params"))?;
+                let parent: StackTraceId = serde_json::from_value(
+                    params
+                        .get("parentStackTraceId")
+                        .cloned()
+                        .ok_or_else(|| CdpError::invalid_params("Missing parentStackTraceId"))?,
+                )
+                .map_err(|e| CdpError::invalid_params(e.to_string()))?;
+
+                self.set_pause_on_async_call(parent).map_err(|e| match e {
+                    JsDebugBridgeError::InvalidParameter(msg) => CdpError::invalid_params(msg),
+                    other => CdpError::internal_error(other.to_string()),
+                })?;
+                Ok(json!({}))
+            }
             _ => {
                 warn!("Unknown JsDebugBridge method: {}", method);
                 Err(CdpError::method_not_found(format!(
@@ -1291,6 +2979,64 @@ mod tests {
         assert!(!locations.is_empty());
     }
 
+    #[test]
+    fn test_breakpoint_by_url_resolves_when_script_added() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let mut events = bridge.subscribe();
+
+        let (bp_id, locations) = bridge
+            .set_breakpoint_by_url("https://example.com/app.js", 1, Some(2), None)
+            .unwrap();
+        assert!(locations.is_empty());
+        assert!(bridge.get_breakpoint(&bp_id.0).is_none());
+        let pending = bridge.get_pending_breakpoints();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, bp_id);
+
+        let script = bridge
+            .add_script("https://example.com/app.js", "let a = 1;\nlet b = 2;")
+            .unwrap();
+
+        assert!(bridge.get_pending_breakpoints().is_empty());
+        let bp = bridge.get_breakpoint(&bp_id.0).unwrap();
+        assert_eq!(bp.location.script_id, script.script_id);
+        assert_eq!(bp.location.line_number, 1);
+        assert_eq!(bp.location.column_number, Some(2));
+        assert!(bridge.has_breakpoint_at(&script.script_id.0, 1, 2));
+
+        let mut resolved = None;
+        while let Ok(event) = events.try_recv() {
+            if let DebugEvent::BreakpointResolved {
+                breakpoint_id,
+                location,
+            } = event
+            {
+                resolved = Some((breakpoint_id, location));
+            }
+        }
+        let (resolved_id, resolved_location) = resolved.expect("BreakpointResolved emitted");
+        assert_eq!(resolved_id, bp_id);
+        assert_eq!(resolved_location.script_id, script.script_id);
+    }
+
+    #[test]
+    fn test_remove_pending_breakpoint() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let (bp_id, _) = bridge
+            .set_breakpoint_by_url("https://example.com/late.js", 0, None, None)
+            .unwrap();
+        bridge.remove_breakpoint(&bp_id).unwrap();
+        assert!(bridge.get_pending_breakpoints().is_empty());
+
+        bridge
+            .add_script("https://example.com/late.js", "let a = 1;")
+            .unwrap();
+        assert!(bridge.get_breakpoints().is_empty());
+    }
+
     #[test]
     fn test_remove_breakpoint() {
         let bridge = JsDebugBridge::new();
@@ -1365,6 +3111,82 @@ mod tests {
         assert!(!bridge.is_paused());
     }
 
+    #[test]
+    fn test_step_skips_blackboxed_execution_context() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let extension = bridge
+            .add_script_in_context("chrome-extension://abc/inject.js", "hook();", 7)
+            .unwrap();
+        let page = bridge.add_script("https://example.com/app.js", "main();").unwrap();
+        bridge.set_blackbox_execution_contexts(vec!["7".to_string()]);
+        assert!(bridge.is_script_blackboxed(&extension.script_id.0));
+        assert!(!bridge.is_script_blackboxed(&page.script_id.0));
+
+        bridge.pause().unwrap();
+        bridge.step_into().unwrap();
+
+        // The injected frame is stepped through; the step lands in the page
+        assert!(!bridge.step_reached_at(&extension.script_id.0, 0, 0).unwrap());
+        assert!(!bridge.is_paused());
+        assert!(bridge.step_reached_at(&page.script_id.0, 0, 0).unwrap());
+        assert!(bridge.is_paused());
+        assert_eq!(bridge.get_call_frames()[0].location.script_id, page.script_id);
+    }
+
+    #[test]
+    fn test_clearing_blackboxed_contexts_restores_pausing() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let extension = bridge
+            .add_script_in_context("chrome-extension://abc/inject.js", "hook();", 7)
+            .unwrap();
+        bridge
+            .set_breakpoint(
+                Location {
+                    script_id: extension.script_id.clone(),
+                    line_number: 0,
+                    column_number: Some(0),
+                },
+                None,
+            )
+            .unwrap();
+        bridge.set_pause_on_exceptions(PauseOnExceptionsMode::All);
+        bridge.set_blackbox_execution_contexts(vec!["7".to_string()]);
+
+        // Breakpoints and exceptions in the blackboxed context don't pause
+        assert!(!bridge
+            .hit_breakpoint_at(&extension.script_id.0, 0, 0)
+            .unwrap());
+        let thrower = bridge.mock_call_frames_at(
+            &extension.script_id.0,
+            Location {
+                script_id: extension.script_id.clone(),
+                line_number: 0,
+                column_number: Some(0),
+            },
+        );
+        assert!(!bridge.report_exception(true, json!({ "className": "Error" }), thrower.clone()));
+        assert!(!bridge.is_paused());
+
+        bridge.set_blackbox_execution_contexts(Vec::new());
+        assert!(bridge
+            .hit_breakpoint_at(&extension.script_id.0, 0, 0)
+            .unwrap());
+        bridge.resume().unwrap();
+        assert!(bridge.report_exception(true, json!({ "className": "Error" }), thrower));
+        bridge.resume().unwrap();
+
+        bridge.pause().unwrap();
+        bridge.step_over().unwrap();
+        assert!(bridge.step_reached_at(&extension.script_id.0, 0, 0).unwrap());
+        assert!(bridge.is_paused());
+
+        // No step pending after the pause
+        bridge.resume().unwrap();
+        assert!(!bridge.step_reached_at(&extension.script_id.0, 0, 0).unwrap());
+    }
+
     #[test]
     fn test_step_not_paused() {
         let bridge = JsDebugBridge::new();
@@ -1447,6 +3269,59 @@ mod tests {
         assert!(!bridge.is_paused());
     }
 
+    #[tokio::test]
+    async fn test_skip_all_pauses_suppresses_breakpoint_pause() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge.add_script("file:///app.js", "let a = 1;\nlet b = 2;").unwrap();
+        let location = Location {
+            script_id: script.script_id.clone(),
+            line_number: 1,
+            column_number: Some(0),
+        };
+        let (bp_id, _) = bridge.set_breakpoint(location, None).unwrap();
+
+        bridge
+            .handle_method("setSkipAllPauses", Some(json!({ "skip": true })))
+            .await
+            .unwrap();
+        assert!(bridge.is_skipping_pauses());
+
+        let mut events = bridge.subscribe();
+        assert!(!bridge.hit_breakpoint_at(&script.script_id.0, 1, 0).unwrap());
+        assert!(!bridge.is_paused());
+        assert!(events.try_recv().is_err());
+        assert_eq!(bridge.get_breakpoint(&bp_id.0).unwrap().hit_count, 1);
+
+        // Turning skipping off restores pausing
+        bridge
+            .handle_method("setSkipAllPauses", Some(json!({ "skip": false })))
+            .await
+            .unwrap();
+        assert!(!bridge.is_skipping_pauses());
+
+        assert!(bridge.hit_breakpoint_at(&script.script_id.0, 1, 0).unwrap());
+        assert!(bridge.is_paused());
+        match events.try_recv() {
+            Ok(DebugEvent::Paused(state)) => {
+                assert_eq!(state.hit_breakpoints, vec![bp_id]);
+                assert_eq!(state.call_frames[0].location.line_number, 1);
+                assert_eq!(state.call_frames[0].url, "file:///app.js");
+            }
+            other => panic!("Expected Paused event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hit_location_without_breakpoint_does_not_pause() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge.add_script("file:///app.js", "let a = 1;").unwrap();
+
+        assert!(!bridge.hit_breakpoint_at(&script.script_id.0, 0, 0).unwrap());
+        assert!(!bridge.is_paused());
+    }
+
     #[test]
     fn test_get_scope_variables() {
         let bridge = JsDebugBridge::new();
@@ -1474,6 +3349,232 @@ mod tests {
         );
     }
 
+    struct MapFetcher {
+        maps: HashMap<String, String>,
+        requested: parking_lot::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl SourceMapFetcher for MapFetcher {
+        async fn fetch(&self, url: &str) -> std::result::Result<String, String> {
+            self.requested.lock().push(url.to_string());
+            self.maps
+                .get(url)
+                .cloned()
+                .ok_or_else(|| format!("404 for {}", url))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_external_source_map_fetched_relative_to_script() {
+        let map = r#"{"version": 3, "sources": ["app.ts"], "names": [], "mappings": "AAAA"}"#;
+        let fetcher = Arc::new(MapFetcher {
+            maps: HashMap::from([(
+                "https://example.com/maps/app.js.map".to_string(),
+                map.to_string(),
+            )]),
+            requested: parking_lot::Mutex::new(Vec::new()),
+        });
+        let bridge = JsDebugBridge::new().with_source_map_fetcher(fetcher.clone());
+        bridge.enable().unwrap();
+
+        let source = "const x = 1;\n//# sourceMappingURL=../maps/app.js.map";
+        let script = bridge
+            .add_script("https://example.com/js/app.js?v=2", source)
+            .unwrap();
+        bridge.wait_for_source_maps().await;
+
+        assert!(
+            bridge
+                .get_script(&script.script_id.0)
+                .unwrap()
+                .has_source_map
+        );
+        assert!(bridge.has_source_map(&script.script_id.0));
+        assert_eq!(
+            *fetcher.requested.lock(),
+            vec!["https://example.com/maps/app.js.map".to_string()]
+        );
+
+        let missing = bridge
+            .add_script(
+                "https://example.com/js/other.js",
+                "//# sourceMappingURL=other.js.map",
+            )
+            .unwrap();
+        bridge.wait_for_source_maps().await;
+        assert!(!bridge.has_source_map(&missing.script_id.0));
+    }
+
+    /// Never finishes a fetch
+    struct StalledFetcher;
+
+    #[async_trait]
+    impl SourceMapFetcher for StalledFetcher {
+        async fn fetch(&self, _url: &str) -> std::result::Result<String, String> {
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_external_source_map_fetch_times_out() {
+        let bridge = JsDebugBridge::new()
+            .with_source_map_fetcher(Arc::new(StalledFetcher))
+            .with_source_map_fetch_timeout(Duration::from_millis(20));
+        bridge.enable().unwrap();
+
+        let script = bridge
+            .add_script(
+                "https://example.com/app.js",
+                "//# sourceMappingURL=app.js.map",
+            )
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(1), bridge.wait_for_source_maps())
+            .await
+            .unwrap();
+        assert!(!bridge.has_source_map(&script.script_id.0));
+    }
+
+    #[tokio::test]
+    async fn test_add_script_does_not_wait_for_fetch() {
+        let bridge = JsDebugBridge::new().with_source_map_fetcher(Arc::new(StalledFetcher));
+        bridge.enable().unwrap();
+
+        let started = std::time::Instant::now();
+        let script = bridge
+            .add_script(
+                "https://example.com/app.js",
+                "//# sourceMappingURL=app.js.map",
+            )
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(!script.has_source_map);
+
+        // Disabling abandons the fetch
+        bridge.disable().unwrap();
+        tokio::time::timeout(Duration::from_secs(1), bridge.wait_for_source_maps())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stale_source_map_fetch_is_not_attached() {
+        let map = r#"{"version": 3, "sources": ["app.ts"], "names": [], "mappings": "AAAA"}"#;
+        let fetcher = Arc::new(MapFetcher {
+            maps: HashMap::from([(
+                "https://example.com/app.js.map".to_string(),
+                map.to_string(),
+            )]),
+            requested: parking_lot::Mutex::new(Vec::new()),
+        });
+        let bridge = JsDebugBridge::new().with_source_map_fetcher(fetcher);
+        bridge.enable().unwrap();
+
+        let script = bridge
+            .add_script(
+                "https://example.com/app.js",
+                "//# sourceMappingURL=app.js.map",
+            )
+            .unwrap();
+        // Replaced before the fetch completes
+        bridge
+            .set_script_source(&script.script_id.0, "const x = 2;")
+            .unwrap();
+        bridge.wait_for_source_maps().await;
+
+        assert!(!bridge.has_source_map(&script.script_id.0));
+        assert!(
+            !bridge
+                .get_script(&script.script_id.0)
+                .unwrap()
+                .has_source_map
+        );
+    }
+
+    /// Reads the bridge's scripts while fetching
+    struct ReentrantFetcher {
+        bridge: std::sync::OnceLock<Arc<JsDebugBridge>>,
+    }
+
+    #[async_trait]
+    impl SourceMapFetcher for ReentrantFetcher {
+        async fn fetch(&self, _url: &str) -> std::result::Result<String, String> {
+            let bridge = self.bridge.get().ok_or("bridge not set")?;
+            assert_eq!(bridge.get_all_scripts().len(), 1);
+            Ok(
+                r#"{"version": 3, "sources": ["app.ts"], "names": [], "mappings": "AAAA"}"#
+                    .to_string(),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_script_source_fetches_without_holding_script() {
+        let fetcher = Arc::new(ReentrantFetcher {
+            bridge: std::sync::OnceLock::new(),
+        });
+        let bridge = Arc::new(JsDebugBridge::new().with_source_map_fetcher(fetcher.clone()));
+        bridge.enable().unwrap();
+        let script = bridge
+            .add_script("https://example.com/app.js", "const x = 1;")
+            .unwrap();
+        let _ = fetcher.bridge.set(Arc::clone(&bridge));
+
+        bridge
+            .set_script_source(
+                &script.script_id.0,
+                "const x = 2;\n//# sourceMappingURL=app.js.map",
+            )
+            .unwrap();
+        bridge.wait_for_source_maps().await;
+        assert!(bridge.has_source_map(&script.script_id.0));
+    }
+
+    #[test]
+    fn test_external_source_map_ignored_without_fetcher() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let source = "const x = 1;\n//# sourceMappingURL=app.js.map";
+        let script = bridge
+            .add_script("https://example.com/app.js", source)
+            .unwrap();
+        assert!(!script.has_source_map);
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        let base = "https://example.com/js/app.js";
+        assert_eq!(
+            resolve_url(base, "app.js.map"),
+            "https://example.com/js/app.js.map"
+        );
+        assert_eq!(
+            resolve_url(base, "./app.js.map"),
+            "https://example.com/js/app.js.map"
+        );
+        assert_eq!(
+            resolve_url(base, "/maps/app.js.map"),
+            "https://example.com/maps/app.js.map"
+        );
+        assert_eq!(
+            resolve_url(base, "//cdn.example.com/a.map"),
+            "https://cdn.example.com/a.map"
+        );
+        assert_eq!(
+            resolve_url(base, "http://other.com/a.map"),
+            "http://other.com/a.map"
+        );
+        assert_eq!(
+            resolve_url("file:///test.js", "test.js.map"),
+            "file:///test.js.map"
+        );
+        assert_eq!(
+            resolve_url("https://example.com", "a.map"),
+            "https://example.com/a.map"
+        );
+    }
+
     #[test]
     fn test_has_breakpoint_at() {
         let bridge = JsDebugBridge::new();
@@ -1610,6 +3711,76 @@ mod tests {
         assert_eq!(value["scriptSource"], source);
     }
 
+    const POSSIBLE_BREAKPOINTS_SOURCE: &str = "function f() {\n\
+        \n\
+        \x20 // comment\n\
+        \x20 const a = 1; // trailing\n\
+        \x20 /* block\n\
+        \x20    still comment */\n\
+        \x20 /* inline */ return a;\n\
+        }\n";
+
+    #[tokio::test]
+    async fn test_domain_handler_get_possible_breakpoints() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge
+            .add_script("file:///test.js", POSSIBLE_BREAKPOINTS_SOURCE)
+            .unwrap();
+
+        let params = json!({
+            "start": { "scriptId": script.script_id.0, "lineNumber": 0 }
+        });
+        let value = bridge
+            .handle_method("getPossibleBreakpoints", Some(params))
+            .await
+            .unwrap();
+
+        let lines: Vec<(u64, u64)> = value["locations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|l| {
+                (
+                    l["lineNumber"].as_u64().unwrap(),
+                    l["columnNumber"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(lines, vec![(0, 0), (3, 2), (6, 15), (7, 0)]);
+    }
+
+    #[test]
+    fn test_get_possible_breakpoints_range() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge
+            .add_script("file:///test.js", POSSIBLE_BREAKPOINTS_SOURCE)
+            .unwrap();
+
+        let locations = bridge
+            .get_possible_breakpoints(
+                &script.script_id.0,
+                Position::new(1, 0),
+                Some(Position::new(6, 0)),
+            )
+            .unwrap();
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].line_number, 3);
+
+        // Starting mid-line picks the next run of code on that line
+        let locations = bridge
+            .get_possible_breakpoints(&script.script_id.0, Position::new(3, 5), None)
+            .unwrap();
+        assert_eq!(locations[0].line_number, 3);
+        assert_eq!(locations[0].column_number, Some(8));
+
+        assert!(matches!(
+            bridge.get_possible_breakpoints("missing", Position::new(0, 0), None),
+            Err(JsDebugBridgeError::ScriptNotFound(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_domain_handler_set_pause_on_exceptions() {
         let bridge = JsDebugBridge::new();
@@ -1624,26 +3795,1029 @@ mod tests {
     }
 
     #[test]
-    fn test_restart_frame() {
+    fn test_instrumentation_breakpoint_pauses_on_new_script() {
         let bridge = JsDebugBridge::new();
         bridge.enable().unwrap();
-        bridge.add_script("file:///test.js", "const x = 1;").unwrap();
-        bridge.pause().unwrap();
+        let mut events = bridge.subscribe();
 
-        let frames = bridge.get_call_frames();
-        let frame_id = &frames[0].call_frame_id;
+        let bp_id = bridge
+            .set_instrumentation_breakpoint("beforeScriptExecution")
+            .unwrap();
+        let script_info = bridge.add_script("file:///app.js", "const x = 1;").unwrap();
 
-        let result = bridge.restart_frame(frame_id);
-        assert!(result.is_ok());
+        assert!(bridge.is_paused());
+
+        // ScriptParsed comes first, then the instrumentation pause
+        assert!(matches!(events.try_recv(), Ok(DebugEvent::ScriptParsed(_))));
+        match events.try_recv() {
+            Ok(DebugEvent::Paused(state)) => {
+                assert_eq!(state.reason, PausedReason::Instrumentation);
+                assert_eq!(state.hit_breakpoints, vec![bp_id]);
+                assert_eq!(state.call_frames[0].location.script_id, script_info.script_id);
+                let data = state.data.unwrap();
+                assert_eq!(data["instrumentation"], "beforeScriptExecution");
+                assert_eq!(data["scriptId"], script_info.script_id.0);
+            }
+            other => panic!("Expected Paused event, got {:?}", other),
+        }
     }
 
     #[test]
-    fn test_restart_frame_not_found() {
+    fn test_remove_instrumentation_breakpoint_stops_pausing() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let bp_id = bridge
+            .set_instrumentation_breakpoint("beforeScriptExecution")
+            .unwrap();
+        bridge.add_script("file:///a.js", "const a = 1;").unwrap();
+        assert!(bridge.is_paused());
+        bridge.resume().unwrap();
+
+        bridge.remove_breakpoint(&bp_id).unwrap();
+        assert!(bridge.get_instrumentation_breakpoints().is_empty());
+
+        bridge.add_script("file:///b.js", "const b = 2;").unwrap();
+        assert!(!bridge.is_paused());
+    }
+
+    #[test]
+    fn test_source_map_instrumentation_ignores_plain_scripts() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        bridge
+            .set_instrumentation_breakpoint("beforeScriptWithSourceMapExecution")
+            .unwrap();
+        bridge.add_script("file:///plain.js", "const x = 1;").unwrap();
+        assert!(!bridge.is_paused());
+
+        assert!(bridge.set_instrumentation_breakpoint("unknown").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_domain_handler_set_instrumentation_breakpoint() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let params = json!({ "instrumentation": "beforeScriptExecution" });
+        let result = bridge
+            .handle_method("setInstrumentationBreakpoint", Some(params))
+            .await
+            .unwrap();
+        assert!(result["breakpointId"].is_string());
+
+        let params = json!({ "instrumentation": "bogus" });
+        let err = bridge
+            .handle_method("setInstrumentationBreakpoint", Some(params))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, -32602);
+    }
+
+    #[test]
+    fn test_set_script_source_updates_script() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge.add_script("file:///app.js", "a();").unwrap();
+        let mut events = bridge.subscribe();
+
+        let result = bridge
+            .set_script_source(&script.script_id.0, "a();\nlonger();")
+            .unwrap();
+        assert!(!result.stack_changed);
+        assert!(result.unresolved_breakpoints.is_empty());
+
+        let updated = bridge.get_script(&script.script_id.0).unwrap();
+        assert_eq!(updated.source, "a();\nlonger();");
+        assert_eq!(updated.end_line, 1);
+        assert_eq!(updated.end_column, 9);
+        assert_eq!(updated.length, 14);
+        assert_ne!(updated.hash, script.hash);
+
+        match events.try_recv() {
+            Ok(DebugEvent::ScriptParsed(info)) => {
+                assert_eq!(info.script_id, script.script_id);
+                assert_eq!(info.end_line, 1);
+            }
+            other => panic!("Expected ScriptParsed event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_set_script_source_disables_out_of_range_breakpoints() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge
+            .add_script("file:///app.js", "a();\nb();\nc();")
+            .unwrap();
+        let at = |line: u32| Location {
+            script_id: script.script_id.clone(),
+            line_number: line,
+            column_number: Some(0),
+        };
+        let (kept, _) = bridge.set_breakpoint(at(1), None).unwrap();
+        let (dropped, _) = bridge.set_breakpoint(at(2), None).unwrap();
+
+        let result = bridge
+            .set_script_source(&script.script_id.0, "a();\nb();")
+            .unwrap();
+        assert_eq!(result.unresolved_breakpoints, vec![dropped.clone()]);
+
+        assert!(bridge.get_breakpoint(&kept.0).unwrap().enabled);
+        assert!(!bridge.get_breakpoint(&dropped.0).unwrap().enabled);
+        assert!(bridge.hit_breakpoint_at(&script.script_id.0, 1, 0).unwrap());
+        bridge.resume().unwrap();
+        assert!(!bridge.hit_breakpoint_at(&script.script_id.0, 2, 0).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_domain_handler_set_script_source_reports_stack_change() {
         let bridge = JsDebugBridge::new();
         bridge.enable().unwrap();
+        let script = bridge.add_script("file:///app.js", "a();").unwrap();
         bridge.pause().unwrap();
 
-        let result = bridge.restart_frame("nonexistent");
-        assert!(result.is_err());
+        let params = json!({
+            "scriptId": script.script_id.0,
+            "scriptSource": "b();"
+        });
+        let value = bridge
+            .handle_method("setScriptSource", Some(params))
+            .await
+            .unwrap();
+        assert_eq!(value["stackChanged"], true);
+        assert_eq!(value["callFrames"].as_array().unwrap().len(), 2);
+
+        let params = json!({ "scriptId": "script-missing", "scriptSource": "" });
+        assert!(bridge
+            .handle_method("setScriptSource", Some(params))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_remove_breakpoints_by_url() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let first = bridge.add_script("file:///app.js", "a();\nb();").unwrap();
+        let reloaded = bridge
+            .add_script("file:///app.js", "a();\nb();\nc();")
+            .unwrap();
+        let other = bridge.add_script("file:///other.js", "d();").unwrap();
+
+        let at = |script: &ScriptInfo, line: u32| Location {
+            script_id: script.script_id.clone(),
+            line_number: line,
+            column_number: Some(0),
+        };
+        bridge.set_breakpoint(at(&first, 0), None).unwrap();
+        bridge.set_breakpoint(at(&reloaded, 1), None).unwrap();
+        bridge.set_breakpoint(at(&reloaded, 2), None).unwrap();
+        let (kept, _) = bridge.set_breakpoint(at(&other, 0), None).unwrap();
+
+        assert_eq!(bridge.remove_breakpoints_by_url("file:///app.js"), 3);
+        assert_eq!(bridge.get_breakpoints().len(), 1);
+        assert!(bridge.get_breakpoint(&kept.0).is_some());
+        assert!(!bridge.has_breakpoint_at(&reloaded.script_id.0, 1, 0));
+        assert!(bridge.has_breakpoint_at(&other.script_id.0, 0, 0));
+
+        assert_eq!(bridge.remove_breakpoints_by_url("file:///app.js"), 0);
+    }
+
+    #[test]
+    fn test_restart_frame() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.add_script("file:///test.js", "const x = 1;").unwrap();
+        bridge.pause().unwrap();
+
+        let frames = bridge.get_call_frames();
+        let frame_id = &frames[0].call_frame_id;
+
+        let result = bridge.restart_frame(frame_id);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_restart_frame_not_found() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.pause().unwrap();
+
+        let result = bridge.restart_frame("nonexistent");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_restart_frame_pops_frames_and_emits_events() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.pause().unwrap();
+        let mut events = bridge.subscribe();
+
+        let frames = bridge.get_call_frames();
+        assert_eq!(frames.len(), 2);
+        let outer_id = frames[1].call_frame_id.clone();
+
+        let restarted = bridge.restart_frame(&outer_id).unwrap();
+        assert_eq!(restarted.len(), 1);
+        assert_eq!(restarted[0].call_frame_id, outer_id);
+        assert_eq!(bridge.get_call_frames().len(), 1);
+        assert!(bridge.is_paused());
+
+        assert!(matches!(events.try_recv().unwrap(), DebugEvent::Resumed));
+        match events.try_recv().unwrap() {
+            DebugEvent::Paused(state) => {
+                assert_eq!(state.call_frames.len(), 1);
+                assert_eq!(state.call_frames[0].call_frame_id, outer_id);
+            }
+            other => panic!("expected Paused, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_restart_frame_and_set_variable_require_pause() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.pause().unwrap();
+        let frame_id = bridge.get_call_frames()[0].call_frame_id.clone();
+        bridge.resume().unwrap();
+
+        assert!(matches!(
+            bridge.restart_frame(&frame_id),
+            Err(JsDebugBridgeError::NotPaused)
+        ));
+        assert!(matches!(
+            bridge.set_variable_value(0, "x", json!(1), &frame_id),
+            Err(JsDebugBridgeError::NotPaused)
+        ));
+    }
+
+    #[test]
+    fn test_set_variable_value() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.pause().unwrap();
+
+        let frame = bridge.get_call_frames()[0].clone();
+        let local_id = frame.scope_chain[0].object.object_id.clone().unwrap().0;
+        let closure_id = frame.scope_chain[1].object.object_id.clone().unwrap().0;
+
+        bridge
+            .set_variable_value(0, "x", json!(7), &frame.call_frame_id)
+            .unwrap();
+
+        let x = |scope_id: &str| {
+            bridge
+                .get_scope_variables(scope_id)
+                .unwrap()
+                .into_iter()
+                .find(|v| v.name == "x")
+                .unwrap()
+                .value
+        };
+        assert_eq!(x(&local_id).value, Some(json!(7)));
+        assert_eq!(x(&closure_id).value, Some(json!(42)));
+
+        assert!(matches!(
+            bridge.set_variable_value(0, "missing", json!(1), &frame.call_frame_id),
+            Err(JsDebugBridgeError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            bridge.set_variable_value(9, "x", json!(1), &frame.call_frame_id),
+            Err(JsDebugBridgeError::InvalidParameter(_))
+        ));
+
+        // Modifications don't outlive the pause
+        bridge.resume().unwrap();
+        assert_eq!(x(&local_id).value, Some(json!(42)));
+    }
+
+    #[tokio::test]
+    async fn test_domain_handler_set_variable_value() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.pause().unwrap();
+        let frame = bridge.get_call_frames()[0].clone();
+
+        let params = json!({
+            "scopeNumber": 0,
+            "variableName": "name",
+            "newValue": { "value": "changed" },
+            "callFrameId": frame.call_frame_id
+        });
+        bridge
+            .handle_method("setVariableValue", Some(params))
+            .await
+            .unwrap();
+
+        let scope_id = frame.scope_chain[0].object.object_id.clone().unwrap().0;
+        let vars = bridge.get_scope_variables(&scope_id).unwrap();
+        let name = vars.iter().find(|v| v.name == "name").unwrap();
+        assert_eq!(name.value.value, Some(json!("changed")));
+
+        let params = json!({ "callFrameId": frame.call_frame_id });
+        let value = bridge
+            .handle_method("restartFrame", Some(params))
+            .await
+            .unwrap();
+        assert_eq!(value["callFrames"].as_array().unwrap().len(), 2);
+    }
+
+    // ========== Async Stack Tests ==========
+
+    fn continuation_frame(bridge: &JsDebugBridge, function_name: &str) -> CallFrame {
+        bridge.pause().unwrap();
+        let mut frame = bridge.get_call_frames()[0].clone();
+        bridge.resume().unwrap();
+        frame.function_name = function_name.to_string();
+        frame
+    }
+
+    #[test]
+    fn test_pause_on_async_call_pauses_at_continuation() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.set_async_stack_trace_depth(8);
+        bridge.add_script("file:///app.js", "setTimeout(cb, 0);").unwrap();
+
+        // Scheduled while paused at the call site
+        bridge.pause().unwrap();
+        let task = bridge.record_async_task_scheduled("setTimeout");
+        assert!(!bridge.get_async_task(&task).unwrap().parent_frames.is_empty());
+        bridge.set_pause_on_async_call(task.clone()).unwrap();
+        bridge.resume().unwrap();
+
+        let frame = continuation_frame(&bridge, "cb");
+        let mut events = bridge.subscribe();
+        assert!(bridge
+            .record_async_task_started(&task, vec![frame])
+            .unwrap());
+        assert!(bridge.is_paused());
+        // The record is released once the continuation starts
+        assert!(bridge.get_async_task(&task).is_none());
+
+        match events.try_recv() {
+            Ok(DebugEvent::Paused(state)) => {
+                assert_eq!(state.call_frames.len(), 1);
+                assert_eq!(state.call_frames[0].function_name, "cb");
+                let data = state.data.unwrap();
+                assert_eq!(data["asyncTaskId"], task.id);
+                assert_eq!(data["description"], "setTimeout");
+            }
+            other => panic!("Expected Paused event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unrelated_async_task_does_not_pause() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let watched = bridge.record_async_task_scheduled("Promise.then");
+        let other = bridge.record_async_task_scheduled("setTimeout");
+        bridge.set_pause_on_async_call(watched.clone()).unwrap();
+
+        let frame = continuation_frame(&bridge, "other");
+        let mut events = bridge.subscribe();
+        assert!(!bridge
+            .record_async_task_started(&other, vec![frame.clone()])
+            .unwrap());
+        assert!(!bridge.is_paused());
+        assert!(events.try_recv().is_err());
+        assert!(bridge.get_async_task(&other).is_none());
+        assert!(bridge.get_async_task(&watched).is_some());
+
+        // The watched task still pauses, once
+        assert!(bridge
+            .record_async_task_started(&watched, vec![frame.clone()])
+            .unwrap());
+        bridge.resume().unwrap();
+        assert!(!bridge
+            .record_async_task_started(&watched, vec![frame])
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_pause_on_async_call_route_rejects_unknown_task() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let params = json!({ "parentStackTraceId": { "id": "async-task-999" } });
+        let err = bridge
+            .handle_method("pauseOnAsyncCall", Some(params))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, -32602);
+
+        let task = bridge.record_async_task_scheduled("queueMicrotask");
+        let params = json!({ "parentStackTraceId": { "id": task.id } });
+        assert!(bridge
+            .handle_method("pauseOnAsyncCall", Some(params))
+            .await
+            .is_ok());
+    }
+
+    // ========== Script Language Tests ==========
+
+    #[tokio::test]
+    async fn test_wasm_script_language_and_bytecode() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let mut events = bridge.subscribe();
+
+        let module = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+        let script = bridge
+            .add_wasm_script("https://example.com/module.wasm", &module)
+            .unwrap();
+        assert_eq!(script.script_language, ScriptLanguage::WebAssembly);
+
+        match events.try_recv() {
+            Ok(DebugEvent::ScriptParsed(parsed)) => {
+                assert_eq!(parsed.script_language, ScriptLanguage::WebAssembly);
+                let value = serde_json::to_value(&parsed).unwrap();
+                assert_eq!(value["scriptLanguage"], "WebAssembly");
+            }
+            other => panic!("Expected ScriptParsed event, got {:?}", other),
+        }
+
+        let bytecode = bridge.get_wasm_bytecode(&script.script_id.0).unwrap();
+        assert_eq!(bytecode, "AGFzbQEAAAA=");
+
+        let result = bridge
+            .handle_method("getWasmBytecode", Some(json!({ "scriptId": script.script_id.0 })))
+            .await
+            .unwrap();
+        assert_eq!(result["bytecode"], "AGFzbQEAAAA=");
+
+        // Language is also inferred from the URL
+        let inferred = bridge.add_script("https://example.com/lib.wasm?v=2", "wasm").unwrap();
+        assert_eq!(inferred.script_language, ScriptLanguage::WebAssembly);
+        assert_eq!(bridge.get_wasm_bytecode(&inferred.script_id.0).unwrap(), "d2FzbQ==");
+    }
+
+    #[test]
+    fn test_js_script_has_no_wasm_bytecode() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let script = bridge.add_script("https://example.com/app.js", "let x = 1;").unwrap();
+        assert_eq!(script.script_language, ScriptLanguage::JavaScript);
+        assert!(matches!(
+            bridge.get_wasm_bytecode(&script.script_id.0),
+            Err(JsDebugBridgeError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            bridge.get_wasm_bytecode("script-missing"),
+            Err(JsDebugBridgeError::ScriptNotFound(_))
+        ));
+    }
+
+    // ========== Exception Filter Tests ==========
+
+    struct TypeErrorOnly;
+
+    impl ExceptionFilter for TypeErrorOnly {
+        fn should_pause(&self, error_type: &str, _is_uncaught: bool) -> bool {
+            error_type == "TypeError"
+        }
+    }
+
+    #[test]
+    fn test_exception_filter_only_pauses_on_type_error() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.set_pause_on_exceptions(PauseOnExceptionsMode::All);
+        bridge.set_exception_filter(Arc::new(TypeErrorOnly));
+
+        let range_error = json!({"type": "object", "className": "RangeError"});
+        assert!(!bridge.report_exception(false, range_error, Vec::new()));
+        assert!(!bridge.is_paused());
+
+        let mut events = bridge.subscribe();
+        let type_error = json!({"type": "object", "className": "TypeError"});
+        assert!(bridge.report_exception(false, type_error, Vec::new()));
+        assert!(bridge.is_paused());
+
+        match events.try_recv() {
+            Ok(DebugEvent::Paused(state)) => {
+                assert_eq!(state.reason, PausedReason::Exception);
+                assert_eq!(state.data.unwrap()["className"], "TypeError");
+            }
+            other => panic!("Expected Paused event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_default_exception_filter_accepts_mode_allowed() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        // Mode None never pauses, regardless of filter
+        let error = json!({"className": "RangeError"});
+        assert!(!bridge.report_exception(true, error.clone(), Vec::new()));
+
+        bridge.set_pause_on_exceptions(PauseOnExceptionsMode::All);
+        bridge.set_exception_filter(Arc::new(TypeErrorOnly));
+        bridge.clear_exception_filter();
+        assert!(bridge.report_exception(false, error, Vec::new()));
+    }
+
+    #[test]
+    fn test_uncaught_mode_ignores_caught_exceptions() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.set_pause_on_exceptions(PauseOnExceptionsMode::Uncaught);
+        bridge.pause().unwrap();
+        let frames = bridge.get_call_frames();
+        bridge.resume().unwrap();
+        let mut events = bridge.subscribe();
+
+        let error = json!({"className": "Error"});
+        assert!(!bridge.report_exception(false, error.clone(), frames.clone()));
+        assert!(!bridge.is_paused());
+        assert!(bridge.get_call_frames().is_empty());
+        assert!(events.try_recv().is_err());
+
+        assert!(bridge.report_exception(true, error, frames.clone()));
+        assert!(bridge.is_paused());
+        assert_eq!(bridge.get_call_frames().len(), frames.len());
+        assert!(matches!(
+            events.try_recv(),
+            Ok(DebugEvent::Paused(PauseState {
+                reason: PausedReason::Exception,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_skip_all_pauses_suppresses_exception_pause() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        bridge.set_pause_on_exceptions(PauseOnExceptionsMode::All);
+        bridge.set_skip_all_pauses(true);
+        let mut events = bridge.subscribe();
+
+        assert!(!bridge.report_exception(true, json!({"className": "Error"}), Vec::new()));
+        assert!(!bridge.is_paused());
+        assert!(events.try_recv().is_err());
+
+        bridge.set_skip_all_pauses(false);
+        assert!(bridge.report_exception(true, json!({"className": "Error"}), Vec::new()));
+    }
+
+    fn exception_with_frames(
+        script_id: &str,
+        frames: Vec<cdp_types::domains::runtime::CallFrame>,
+    ) -> ExceptionDetails {
+        ExceptionDetails {
+            exception_id: 1,
+            text: "Uncaught Error: boom".to_string(),
+            line_number: frames[0].line_number,
+            column_number: frames[0].column_number,
+            script_id: Some(script_id.to_string()),
+            url: Some(frames[0].url.clone()),
+            stack_trace: Some(StackTrace {
+                call_frames: frames,
+                parent: None,
+            }),
+            exception: None,
+        }
+    }
+
+    fn runtime_frame(
+        name: &str,
+        script: &ScriptInfo,
+        line: u32,
+        column: u32,
+    ) -> cdp_types::domains::runtime::CallFrame {
+        cdp_types::domains::runtime::CallFrame {
+            function_name: name.to_string(),
+            script_id: script.script_id.0.clone(),
+            url: script.url.clone(),
+            line_number: line,
+            column_number: column,
+        }
+    }
+
+    /// Evaluator that throws the given exception
+    struct ThrowingEvaluator(ExceptionDetails);
+
+    impl runtime_debugger::ScriptEvaluator for ThrowingEvaluator {
+        fn evaluate(&self, _expression: &str) -> runtime_debugger::Result<Value> {
+            Err(runtime_debugger::RuntimeDebuggerError::Exception(Box::new(
+                self.0.clone(),
+            )))
+        }
+    }
+
+    /// Evaluate through a Runtime domain that uses `bridge` as its formatter
+    async fn evaluate_throwing(
+        bridge: Arc<JsDebugBridge>,
+        raw: ExceptionDetails,
+    ) -> ExceptionDetails {
+        let runtime = runtime_debugger::RuntimeDomain::new();
+        runtime.enable();
+        runtime.set_evaluator(Arc::new(ThrowingEvaluator(raw)));
+        runtime.set_exception_formatter(bridge);
+
+        let result = runtime
+            .handle_method("evaluate", Some(json!({ "expression": "run()" })))
+            .await
+            .unwrap();
+        let response: cdp_types::domains::runtime::EvaluateResponse =
+            serde_json::from_value(result).unwrap();
+        response.exception_details.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_runtime_evaluate_maps_exception_to_original() {
+        let bridge = Arc::new(JsDebugBridge::new());
+        bridge.enable().unwrap();
+
+        let map =
+            r#"{"version": 3, "sources": ["src/app.ts"], "names": [], "mappings": "AAAA;AAEA"}"#;
+        let source = format!(
+            "let a = 1;\nthrow new Error('boom');\n//# sourceMappingURL=data:application/json;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(map)
+        );
+        let mapped = bridge.add_script("file:///app.js", &source).unwrap();
+        let plain = bridge.add_script("file:///plain.js", "run();").unwrap();
+
+        let raw = exception_with_frames(
+            &mapped.script_id.0,
+            vec![
+                runtime_frame("thrower", &mapped, 1, 0),
+                runtime_frame("caller", &plain, 0, 0),
+            ],
+        );
+        let formatted = evaluate_throwing(bridge, raw).await;
+
+        assert_eq!(formatted.url.as_deref(), Some("src/app.ts"));
+        assert_eq!(formatted.line_number, 2);
+        let frames = &formatted.stack_trace.unwrap().call_frames;
+        assert_eq!(frames[0].function_name, "thrower");
+        assert_eq!(frames[0].url, "src/app.ts");
+        assert_eq!(frames[0].line_number, 2);
+        assert_eq!(frames[0].column_number, 0);
+        // Frames in unmapped scripts are left alone
+        assert_eq!(frames[1].url, "file:///plain.js");
+        assert_eq!(frames[1].line_number, 0);
+    }
+
+    #[tokio::test]
+    async fn test_runtime_evaluate_without_source_maps_is_unchanged() {
+        let bridge = Arc::new(JsDebugBridge::new());
+        bridge.enable().unwrap();
+        let script = bridge
+            .add_script("file:///plain.js", "let a = 1;\nthrow new Error('boom');")
+            .unwrap();
+
+        let mut raw = exception_with_frames(
+            &script.script_id.0,
+            vec![runtime_frame("thrower", &script, 1, 6)],
+        );
+        raw.stack_trace.as_mut().unwrap().parent = Some(Box::new(StackTrace {
+            call_frames: vec![runtime_frame("scheduler", &script, 0, 4)],
+            parent: None,
+        }));
+
+        assert_eq!(evaluate_throwing(bridge, raw.clone()).await, raw);
+    }
+
+    #[test]
+    fn test_original_call_frame_locations() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let map = r#"{"version": 3, "sources": ["app.ts"], "names": [], "mappings": "AAAA;AACA"}"#;
+        let source = format!(
+            "let a = 1;\nlet b = 2;\n//# sourceMappingURL=data:application/json;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(map)
+        );
+        let mapped = bridge.add_script("file:///app.js", &source).unwrap();
+        let plain = bridge.add_script("file:///plain.js", "let c = 3;").unwrap();
+        assert!(bridge.has_source_map(&mapped.script_id.0));
+
+        bridge.pause().unwrap();
+        let template = bridge.get_call_frames()[0].clone();
+        bridge.resume().unwrap();
+
+        let frame_at = |script_id: &ScriptId, line: u32, column: u32| {
+            let mut frame = template.clone();
+            frame.location = Location {
+                script_id: script_id.clone(),
+                line_number: line,
+                column_number: Some(column),
+            };
+            frame
+        };
+        let mut events = bridge.subscribe();
+        bridge.set_pause_on_exceptions(PauseOnExceptionsMode::All);
+        assert!(bridge.report_exception(
+            true,
+            json!({ "className": "Error" }),
+            vec![
+                frame_at(&mapped.script_id, 1, 4),
+                frame_at(&plain.script_id, 0, 0),
+                frame_at(&mapped.script_id, 0, 2),
+            ],
+        ));
+
+        let locations = bridge.original_call_frame_locations();
+        assert_eq!(locations.len(), 3);
+        assert_eq!(locations[0].as_ref().unwrap().position, Position::new(1, 0));
+        assert!(locations[1].is_none());
+        assert_eq!(locations[2].as_ref().unwrap().source, "app.ts");
+        assert_eq!(locations[2].as_ref().unwrap().position, Position::new(0, 0));
+
+        // The Paused event carries the same translation
+        match events.try_recv() {
+            Ok(DebugEvent::Paused(state)) => assert_eq!(state.original_locations, locations),
+            other => panic!("Expected Paused event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_breakpoint_pause_reports_original_locations() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+
+        let map = r#"{"version": 3, "sources": ["app.ts"], "names": [], "mappings": "AAAA;AAEA"}"#;
+        let source = format!(
+            "let a = 1;\nlet b = 2;\n//# sourceMappingURL=data:application/json;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(map)
+        );
+        let script = bridge.add_script("file:///app.js", &source).unwrap();
+        bridge
+            .set_breakpoint(
+                Location {
+                    script_id: script.script_id.clone(),
+                    line_number: 1,
+                    column_number: Some(0),
+                },
+                None,
+            )
+            .unwrap();
+
+        let mut events = bridge.subscribe();
+        assert!(bridge.hit_breakpoint_at(&script.script_id.0, 1, 0).unwrap());
+
+        let state = loop {
+            match events.try_recv() {
+                Ok(DebugEvent::Paused(state)) => break state,
+                Ok(_) => continue,
+                Err(e) => panic!("Expected Paused event: {:?}", e),
+            }
+        };
+        assert_eq!(state.original_locations.len(), state.call_frames.len());
+        let top = state.original_locations[0].as_ref().unwrap();
+        assert_eq!(top.source, "app.ts");
+        assert_eq!(top.position, Position::new(2, 0));
+    }
+
+    #[test]
+    fn test_script_parsed_reports_inline_source_map_loaded() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let mut events = bridge.subscribe();
+
+        let map = r#"{"version": 3, "sources": ["app.ts"], "names": [], "mappings": "AAAA"}"#;
+        let source = format!(
+            "let a = 1;\n//# sourceMappingURL=data:application/json;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(map)
+        );
+        let script = bridge.add_script("file:///app.js", &source).unwrap();
+        assert!(script.has_source_map);
+
+        match events.try_recv() {
+            Ok(DebugEvent::ScriptParsed(parsed)) => {
+                assert!(parsed.has_source_map);
+                let value = serde_json::to_value(&parsed).unwrap();
+                assert_eq!(value["hasSourceMap"], true);
+                assert!(value["sourceMapURL"].as_str().unwrap().starts_with("data:"));
+            }
+            other => panic!("Expected ScriptParsed event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_script_parsed_reports_external_source_map_not_loaded() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let mut events = bridge.subscribe();
+
+        let source = "let a = 1;\n//# sourceMappingURL=app.js.map";
+        let script = bridge.add_script("file:///app.js", source).unwrap();
+        assert!(!script.has_source_map);
+        assert!(!bridge.has_source_map(&script.script_id.0));
+
+        match events.try_recv() {
+            Ok(DebugEvent::ScriptParsed(parsed)) => {
+                let value = serde_json::to_value(&parsed).unwrap();
+                assert_eq!(value["hasSourceMap"], false);
+                assert_eq!(value["sourceMapURL"], "app.js.map");
+            }
+            other => panic!("Expected ScriptParsed event, got {:?}", other),
+        }
+    }
+
+    // ========== Condition Evaluator Tests ==========
+
+    /// Rejects `x > 5` and counts how often it was consulted
+    #[derive(Default)]
+    struct RejectXGreaterThanFive {
+        calls: AtomicU32,
+    }
+
+    impl ConditionEvaluator for RejectXGreaterThanFive {
+        fn evaluate(&self, condition: &str, _frame: &CallFrame) -> bool {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            condition != "x > 5"
+        }
+    }
+
+    fn bridge_with_conditional_breakpoint(
+        condition: &str,
+    ) -> (JsDebugBridge, ScriptId, BreakpointId) {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge.add_script("file:///app.js", "let x = 10;").unwrap();
+        let location = Location {
+            script_id: script.script_id.clone(),
+            line_number: 0,
+            column_number: Some(0),
+        };
+        let (bp_id, _) = bridge
+            .set_breakpoint(location, Some(condition.to_string()))
+            .unwrap();
+        (bridge, script.script_id, bp_id)
+    }
+
+    fn mock_frame(bridge: &JsDebugBridge) -> CallFrame {
+        bridge.pause().unwrap();
+        let frame = bridge.get_call_frames()[0].clone();
+        bridge.resume().unwrap();
+        frame
+    }
+
+    #[test]
+    fn test_condition_evaluator_rejects_pause() {
+        let (bridge, script_id, bp_id) = bridge_with_conditional_breakpoint("x > 5");
+        let evaluator = Arc::new(RejectXGreaterThanFive::default());
+        bridge.set_condition_evaluator(evaluator.clone());
+        let frame = mock_frame(&bridge);
+
+        assert!(!bridge.should_pause_at(&script_id.0, 0, 0, &frame));
+        assert_eq!(evaluator.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(bridge.get_breakpoint(&bp_id.0).unwrap().hit_count, 0);
+        assert!(!bridge.is_paused());
+    }
+
+    #[test]
+    fn test_condition_evaluator_counts_passing_hits() {
+        let (bridge, script_id, bp_id) = bridge_with_conditional_breakpoint("x < 20");
+        bridge.set_condition_evaluator(Arc::new(RejectXGreaterThanFive::default()));
+        let frame = mock_frame(&bridge);
+
+        assert!(bridge.should_pause_at(&script_id.0, 0, 0, &frame));
+        assert!(bridge.should_pause_at(&script_id.0, 0, 0, &frame));
+        assert_eq!(bridge.get_breakpoint(&bp_id.0).unwrap().hit_count, 2);
+
+        // No breakpoint at this location
+        assert!(!bridge.should_pause_at(&script_id.0, 3, 0, &frame));
+    }
+
+    #[test]
+    fn test_conditional_breakpoint_pauses_without_evaluator() {
+        let (bridge, script_id, bp_id) = bridge_with_conditional_breakpoint("x > 5");
+        let frame = mock_frame(&bridge);
+
+        assert!(bridge.should_pause_at(&script_id.0, 0, 0, &frame));
+        assert_eq!(bridge.get_breakpoint(&bp_id.0).unwrap().hit_count, 1);
+
+        bridge.set_condition_evaluator(Arc::new(RejectXGreaterThanFive::default()));
+        assert!(!bridge.should_pause_at(&script_id.0, 0, 0, &frame));
+        bridge.clear_condition_evaluator();
+        assert!(bridge.should_pause_at(&script_id.0, 0, 0, &frame));
+    }
+
+    #[test]
+    fn test_hit_breakpoint_at_checks_condition() {
+        let (bridge, script_id, bp_id) = bridge_with_conditional_breakpoint("x > 5");
+        let evaluator = Arc::new(RejectXGreaterThanFive::default());
+        bridge.set_condition_evaluator(evaluator.clone());
+
+        assert!(!bridge.hit_breakpoint_at(&script_id.0, 0, 0).unwrap());
+        assert_eq!(evaluator.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(bridge.get_breakpoint(&bp_id.0).unwrap().hit_count, 0);
+        assert!(!bridge.is_paused());
+    }
+
+    #[test]
+    fn test_hit_breakpoint_at_shares_hit_count_with_should_pause_at() {
+        let (bridge, script_id, bp_id) = bridge_with_conditional_breakpoint("x < 20");
+        bridge.set_condition_evaluator(Arc::new(RejectXGreaterThanFive::default()));
+        let frame = mock_frame(&bridge);
+
+        assert!(bridge.should_pause_at(&script_id.0, 0, 0, &frame));
+        assert!(bridge.hit_breakpoint_at(&script_id.0, 0, 0).unwrap());
+        assert!(bridge.is_paused());
+        assert_eq!(bridge.get_breakpoint(&bp_id.0).unwrap().hit_count, 2);
+        assert_eq!(bridge.get_call_frames()[0].location.script_id, script_id);
+    }
+
+    // ========== Logpoint Tests ==========
+
+    /// Resolves `x` to `10` and nothing else
+    struct XIsTen;
+
+    impl ConditionEvaluator for XIsTen {
+        fn evaluate(&self, _condition: &str, _frame: &CallFrame) -> bool {
+            true
+        }
+
+        fn evaluate_expression(&self, expression: &str, _frame: &CallFrame) -> Option<String> {
+            (expression == "x").then(|| "10".to_string())
+        }
+    }
+
+    #[test]
+    fn test_logpoint_emits_message_without_pausing() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge.add_script("file:///app.js", "let x = 10;").unwrap();
+        let frame = mock_frame(&bridge);
+        bridge.set_condition_evaluator(Arc::new(XIsTen));
+
+        let location = Location {
+            script_id: script.script_id.clone(),
+            line_number: 0,
+            column_number: Some(0),
+        };
+        let (bp_id, _) = bridge.set_logpoint(location, "x is {x}, y is {y}").unwrap();
+        let bp = bridge.get_breakpoint(&bp_id.0).unwrap();
+        assert_eq!(bp.log_message.as_deref(), Some("x is {x}, y is {y}"));
+        assert!(bp.condition.is_none());
+
+        let mut events = bridge.subscribe();
+        assert!(!bridge.should_pause_at(&script.script_id.0, 0, 0, &frame));
+        assert!(!bridge.is_paused());
+        assert_eq!(bridge.get_breakpoint(&bp_id.0).unwrap().hit_count, 1);
+
+        match events.try_recv() {
+            Ok(DebugEvent::LogpointMessage {
+                breakpoint_id,
+                message,
+                ..
+            }) => {
+                assert_eq!(breakpoint_id, bp_id);
+                assert_eq!(message, "x is 10, y is {y}");
+            }
+            other => panic!("Expected LogpointMessage event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hit_breakpoint_at_logpoint_does_not_pause() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let script = bridge.add_script("file:///app.js", "let x = 10;").unwrap();
+        bridge.set_condition_evaluator(Arc::new(XIsTen));
+        let location = Location {
+            script_id: script.script_id.clone(),
+            line_number: 0,
+            column_number: Some(0),
+        };
+        let (bp_id, _) = bridge.set_logpoint(location, "x is {x}").unwrap();
+
+        let mut events = bridge.subscribe();
+        assert!(!bridge.hit_breakpoint_at(&script.script_id.0, 0, 0).unwrap());
+        assert!(!bridge.is_paused());
+        assert_eq!(bridge.get_breakpoint(&bp_id.0).unwrap().hit_count, 1);
+
+        match events.try_recv() {
+            Ok(DebugEvent::LogpointMessage { message, .. }) => assert_eq!(message, "x is 10"),
+            other => panic!("Expected LogpointMessage event, got {:?}", other),
+        }
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_interpolate_log_message_without_evaluator() {
+        let bridge = JsDebugBridge::new();
+        bridge.enable().unwrap();
+        let frame = mock_frame(&bridge);
+
+        assert_eq!(
+            interpolate_log_message("value: {x} {unclosed", None, &frame),
+            "value: {x} {unclosed"
+        );
+        assert_eq!(
+            interpolate_log_message("{ x }!", Some(&XIsTen), &frame),
+            "10!"
+        );
     }
 }
@@ -6,7 +6,9 @@
 //! Features:
 //! - Flexbox overlay
 //! - Grid overlay
-//! - Box model visualization
+//! - Box model visualization (writing-mode and direction aware)
+//! - Media query resolution for responsive breakpoints
+//! - Highlight overlay geometry dump (`getHighlightObjectForTest`)
 
 use async_trait::async_trait;
 use cdp_types::domains::dom::NodeId;
@@ -20,7 +22,7 @@ use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
 use crate::mock_browser::MockBrowser;
-use crate::types::{BoxModel, RGBA};
+use crate::types::{BoxModel, HighlightConfig, Quad, RGBA};
 
 /// Flexbox container info
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -201,6 +203,191 @@ impl Default for GridOverlayConfig {
     }
 }
 
+/// CSS `writing-mode`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WritingMode {
+    /// `horizontal-tb`
+    #[default]
+    HorizontalTb,
+    /// `vertical-rl`
+    VerticalRl,
+    /// `vertical-lr`
+    VerticalLr,
+}
+
+impl WritingMode {
+    /// Parse a computed `writing-mode` value, defaulting to `horizontal-tb`
+    pub fn from_css(value: &str) -> Self {
+        match value.trim() {
+            "vertical-rl" | "tb-rl" | "sideways-rl" => Self::VerticalRl,
+            "vertical-lr" | "sideways-lr" => Self::VerticalLr,
+            _ => Self::HorizontalTb,
+        }
+    }
+}
+
+/// CSS `direction`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDirection {
+    /// Left to right
+    #[default]
+    Ltr,
+    /// Right to left
+    Rtl,
+}
+
+impl TextDirection {
+    /// Parse a computed `direction` value, defaulting to `ltr`
+    pub fn from_css(value: &str) -> Self {
+        match value.trim() {
+            "rtl" => Self::Rtl,
+            _ => Self::Ltr,
+        }
+    }
+}
+
+/// Logical box side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LogicalSide {
+    /// Start of the block axis
+    BlockStart,
+    /// End of the block axis
+    BlockEnd,
+    /// Start of the inline axis
+    InlineStart,
+    /// End of the inline axis
+    InlineEnd,
+}
+
+/// Logical side label for each physical side of a box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhysicalSideLabels {
+    /// Label for the top side
+    pub top: LogicalSide,
+    /// Label for the right side
+    pub right: LogicalSide,
+    /// Label for the bottom side
+    pub bottom: LogicalSide,
+    /// Label for the left side
+    pub left: LogicalSide,
+}
+
+impl PhysicalSideLabels {
+    /// Map physical sides to logical sides for a writing mode and direction
+    pub fn resolve(writing_mode: WritingMode, direction: TextDirection) -> Self {
+        use LogicalSide::*;
+
+        // (inline start, inline end) along the inline axis
+        let (inline_start, inline_end) = match direction {
+            TextDirection::Ltr => (InlineStart, InlineEnd),
+            TextDirection::Rtl => (InlineEnd, InlineStart),
+        };
+
+        match writing_mode {
+            WritingMode::HorizontalTb => Self {
+                top: BlockStart,
+                bottom: BlockEnd,
+                left: inline_start,
+                right: inline_end,
+            },
+            WritingMode::VerticalRl => Self {
+                right: BlockStart,
+                left: BlockEnd,
+                top: inline_start,
+                bottom: inline_end,
+            },
+            WritingMode::VerticalLr => Self {
+                left: BlockStart,
+                right: BlockEnd,
+                top: inline_start,
+                bottom: inline_end,
+            },
+        }
+    }
+}
+
+/// Box model with logical side labels for the overlay
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxModelMetrics {
+    /// Physical box model
+    pub model: BoxModel,
+    /// Computed writing mode
+    pub writing_mode: WritingMode,
+    /// Computed direction
+    pub direction: TextDirection,
+    /// Logical label for each physical side
+    pub sides: PhysicalSideLabels,
+}
+
+/// Result of evaluating a stylesheet media query against a viewport
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaQueryResult {
+    /// Media query text as written in the stylesheet
+    pub media_text: String,
+    /// Whether the query matches the viewport
+    pub matches: bool,
+}
+
+/// Evaluate a media query list against a viewport
+///
+/// Supports media types (`all`, `screen`, `print`) and the `min-width`,
+/// `max-width` and `orientation` features. Comma-separated queries match if
+/// any query matches; unsupported features never match.
+fn evaluate_media_query(media_text: &str, width: f64, height: f64) -> bool {
+    media_text
+        .split(',')
+        .any(|query| evaluate_single_media_query(query, width, height))
+}
+
+/// Evaluate one query of a media query list
+fn evaluate_single_media_query(query: &str, width: f64, height: f64) -> bool {
+    let query = query.trim().to_ascii_lowercase();
+    let query = query.strip_prefix("only ").unwrap_or(&query);
+
+    query.split(" and ").all(|term| {
+        let term = term.trim();
+        match term {
+            "" | "all" | "screen" => return true,
+            "print" => return false,
+            _ => {}
+        }
+
+        let feature = match term.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            Some(feature) => feature,
+            None => return false,
+        };
+        let (name, value) = match feature.split_once(':') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => return false,
+        };
+
+        match name {
+            "min-width" => parse_px(value).is_some_and(|px| width >= px),
+            "max-width" => parse_px(value).is_some_and(|px| width <= px),
+            "orientation" => match value {
+                "landscape" => width > height,
+                "portrait" => height >= width,
+                _ => false,
+            },
+            _ => false,
+        }
+    })
+}
+
+/// Parse a CSS pixel length (`600px`, or unitless `0`)
+fn parse_px(value: &str) -> Option<f64> {
+    match value.strip_suffix("px") {
+        Some(number) => number.trim().parse().ok(),
+        None if value == "0" => Some(0.0),
+        None => None,
+    }
+}
+
 /// Layout overlay state
 #[derive(Debug, Clone, Default)]
 pub struct LayoutOverlayState {
@@ -372,6 +559,75 @@ impl LayoutInspector {
         }))
     }
 
+    /// Get the highlight overlay geometry for a node
+    async fn get_highlight_object_for_test(
+        &self,
+        params: Option<Value>,
+    ) -> Result<Value, CdpError> {
+        debug!("LayoutInspector.getHighlightObjectForTest called");
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            node_id: NodeId,
+            #[serde(default)]
+            highlight_config: Option<HighlightConfig>,
+        }
+
+        let params: Params = serde_json::from_value(
+            params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?,
+        )
+        .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+        let config = params.highlight_config.unwrap_or_default();
+        let highlight = self.highlight_object(params.node_id, &config)?;
+
+        Ok(serde_json::json!({ "highlight": highlight }))
+    }
+
+    /// Get box model metrics with logical side labels
+    async fn get_box_model_metrics(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        debug!("LayoutInspector.getBoxModelMetrics called");
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            node_id: NodeId,
+        }
+
+        let params: Params = serde_json::from_value(
+            params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?,
+        )
+        .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+        let metrics = self.box_model_metrics(params.node_id).ok_or_else(|| {
+            CdpError::server_error(-32000, format!("Node {} not found", params.node_id.0))
+        })?;
+
+        serde_json::to_value(metrics)
+            .map_err(|e| CdpError::internal_error(format!("Serialization error: {}", e)))
+    }
+
+    /// Resolve media queries for a viewport
+    async fn handle_resolve_media_queries(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        debug!("LayoutInspector.resolveMediaQueries called");
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params {
+            viewport_width: f64,
+            viewport_height: f64,
+        }
+
+        let params: Params = serde_json::from_value(
+            params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?,
+        )
+        .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+        let results = self.resolve_media_queries(params.viewport_width, params.viewport_height);
+        Ok(serde_json::json!({ "mediaQueries": results }))
+    }
+
     /// Enable flex overlay for a container
     async fn show_flex_overlay(&self, params: Option<Value>) -> Result<Value, CdpError> {
         debug!("LayoutInspector.showFlexOverlay called");
@@ -550,6 +806,114 @@ impl LayoutInspector {
         self.browser.get_box_model(node_id)
     }
 
+    /// Get box model metrics for a node, honoring `writing-mode` and `direction`
+    pub fn box_model_metrics(&self, node_id: NodeId) -> Option<BoxModelMetrics> {
+        let model = self.browser.get_box_model(node_id)?;
+        let styles = self.browser.get_computed_styles(node_id)?;
+
+        let computed = |name: &str| {
+            styles
+                .properties
+                .iter()
+                .find(|p| p.name == name)
+                .map(|p| p.value.as_str())
+                .unwrap_or("")
+        };
+        let writing_mode = WritingMode::from_css(computed("writing-mode"));
+        let direction = TextDirection::from_css(computed("direction"));
+
+        Some(BoxModelMetrics {
+            model,
+            writing_mode,
+            direction,
+            sides: PhysicalSideLabels::resolve(writing_mode, direction),
+        })
+    }
+
+    /// Build the overlay geometry DevTools would draw to highlight a node
+    ///
+    /// `paths` holds the content, padding, border and margin quads as
+    /// `M`/`L`/`Z` path commands, filled with the config's colors.
+    /// `elementInfo` is only included when `show_info` is set.
+    pub fn highlight_object(
+        &self,
+        node_id: NodeId,
+        config: &HighlightConfig,
+    ) -> Result<Value, CdpError> {
+        let not_found = || CdpError::server_error(-32000, format!("Node {} not found", node_id.0));
+        let node = self.browser.get_node(node_id).ok_or_else(not_found)?;
+        let model = self.browser.get_box_model(node_id).ok_or_else(not_found)?;
+
+        let paths: Vec<Value> = [
+            ("content", &model.content, &config.content_color),
+            ("padding", &model.padding, &config.padding_color),
+            ("border", &model.border, &config.border_color),
+            ("margin", &model.margin, &config.margin_color),
+        ]
+        .into_iter()
+        .map(|(name, quad, color)| {
+            let mut path = serde_json::json!({
+                "name": name,
+                "path": quad_path(quad),
+            });
+            if let Some(color) = color {
+                path["fillColor"] = Value::String(css_rgba(color));
+            }
+            path
+        })
+        .collect();
+
+        let mut highlight = serde_json::json!({
+            "paths": paths,
+            "showRulers": config.show_rulers,
+            "showExtensionLines": config.show_extension_lines,
+        });
+
+        if config.show_info {
+            let attributes = node.attributes.unwrap_or_default();
+            let attribute = |name: &str| {
+                attributes
+                    .chunks(2)
+                    .find(|pair| pair[0] == name)
+                    .and_then(|pair| pair.get(1))
+                    .cloned()
+                    .unwrap_or_default()
+            };
+            let class_name: String = attribute("class")
+                .split_whitespace()
+                .map(|class| format!(".{}", class))
+                .collect();
+
+            highlight["elementInfo"] = serde_json::json!({
+                "tagName": node.local_name.unwrap_or_else(|| node.node_name.to_lowercase()),
+                "idValue": attribute("id"),
+                "className": class_name,
+                "nodeWidth": model.width,
+                "nodeHeight": model.height,
+            });
+        }
+
+        Ok(highlight)
+    }
+
+    /// Evaluate the stylesheet's media queries against a viewport size
+    ///
+    /// Used by the overlay to show which responsive breakpoints are active.
+    pub fn resolve_media_queries(
+        &self,
+        viewport_width: f64,
+        viewport_height: f64,
+    ) -> Vec<MediaQueryResult> {
+        self.browser
+            .get_media_queries()
+            .into_iter()
+            .map(|media_text| MediaQueryResult {
+                matches: evaluate_media_query(&media_text, viewport_width, viewport_height),
+                media_text,
+            })
+            .collect()
+    }
+
     /// Get browser (for testing)
     pub fn browser(&self) -> &MockBrowser {
         &self.browser
@@ -577,6 +941,9 @@ impl DomainHandler for LayoutInspector {
             "getFlexContainerInfo" => self.get_flex_container_info(params).await,
             "getGridContainerInfo" => self.get_grid_container_info(params).await,
             "getBoxModel" => self.get_box_model(params).await,
+            "getBoxModelMetrics" => self.get_box_model_metrics(params).await,
+            "getHighlightObjectForTest" => self.get_highlight_object_for_test(params).await,
+            "resolveMediaQueries" => self.handle_resolve_media_queries(params).await,
             "showFlexOverlay" => self.show_flex_overlay(params).await,
             "hideFlexOverlay" => self.hide_flex_overlay(params).await,
             "showGridOverlay" => self.show_grid_overlay(params).await,
@@ -594,6 +961,23 @@ impl DomainHandler for LayoutInspector {
     }
 }
 
+/// Quad as overlay path commands: `["M", x1, y1, "L", x2, y2, ..., "Z"]`
+fn quad_path(quad: &Quad) -> Vec<Value> {
+    let mut path = Vec::with_capacity(13);
+    for (i, point) in quad.chunks(2).enumerate() {
+        path.push(Value::from(if i == 0 { "M" } else { "L" }));
+        path.push(Value::from(point[0]));
+        path.push(Value::from(point[1]));
+    }
+    path.push(Value::from("Z"));
+    path
+}
+
+/// CSS `rgba()` notation for an overlay color
+fn css_rgba(color: &RGBA) -> String {
+    format!("rgba({}, {}, {}, {})", color.r, color.g, color.b, color.a)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -672,6 +1056,110 @@ mod tests {
         assert!(value["model"]["width"].is_number());
     }
 
+    #[test]
+    fn test_box_model_metrics_horizontal_ltr() {
+        let inspector = LayoutInspector::new();
+        let metrics = inspector.box_model_metrics(NodeId(6)).unwrap();
+
+        assert_eq!(metrics.writing_mode, WritingMode::HorizontalTb);
+        assert_eq!(metrics.direction, TextDirection::Ltr);
+        assert_eq!(metrics.sides.top, LogicalSide::BlockStart);
+        assert_eq!(metrics.sides.bottom, LogicalSide::BlockEnd);
+        assert_eq!(metrics.sides.left, LogicalSide::InlineStart);
+        assert_eq!(metrics.sides.right, LogicalSide::InlineEnd);
+    }
+
+    #[test]
+    fn test_box_model_metrics_vertical_writing_mode() {
+        let inspector = LayoutInspector::new();
+        inspector
+            .browser()
+            .set_attribute(NodeId(6), "style", "writing-mode: vertical-rl")
+            .unwrap();
+
+        let metrics = inspector.box_model_metrics(NodeId(6)).unwrap();
+        assert_eq!(metrics.writing_mode, WritingMode::VerticalRl);
+        assert_eq!(metrics.sides.top, LogicalSide::InlineStart);
+        assert_eq!(metrics.sides.bottom, LogicalSide::InlineEnd);
+        assert_eq!(metrics.sides.right, LogicalSide::BlockStart);
+        assert_eq!(metrics.sides.left, LogicalSide::BlockEnd);
+
+        let lr = PhysicalSideLabels::resolve(WritingMode::VerticalLr, TextDirection::Ltr);
+        assert_eq!(lr.left, LogicalSide::BlockStart);
+        assert_eq!(lr.top, LogicalSide::InlineStart);
+    }
+
+    #[tokio::test]
+    async fn test_box_model_metrics_rtl_swaps_inline_sides() {
+        let inspector = LayoutInspector::new();
+        inspector
+            .browser()
+            .set_attribute(NodeId(6), "style", "direction: rtl")
+            .unwrap();
+
+        let value = inspector
+            .handle_method("getBoxModelMetrics", Some(json!({ "nodeId": 6 })))
+            .await
+            .unwrap();
+        assert_eq!(value["direction"], "rtl");
+        assert_eq!(value["writingMode"], "horizontal-tb");
+        assert_eq!(value["sides"]["left"], "inlineEnd");
+        assert_eq!(value["sides"]["right"], "inlineStart");
+        assert_eq!(value["sides"]["top"], "blockStart");
+        assert!(value["model"]["width"].as_f64().unwrap() > 0.0);
+    }
+
+    fn matches(results: &[MediaQueryResult], media_text: &str) -> bool {
+        results
+            .iter()
+            .find(|r| r.media_text == media_text)
+            .map(|r| r.matches)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_media_queries_min_width() {
+        let inspector = LayoutInspector::new();
+
+        let wide = inspector.resolve_media_queries(800.0, 600.0);
+        assert!(matches(&wide, "(min-width: 600px)"));
+        assert!(!matches(&wide, "(max-width: 599px)"));
+        assert!(matches(&wide, "screen and (min-width: 600px) and (max-width: 1023px)"));
+        assert!(!matches(&wide, "print"));
+
+        let narrow = inspector.resolve_media_queries(500.0, 900.0);
+        assert!(!matches(&narrow, "(min-width: 600px)"));
+        assert!(matches(&narrow, "(max-width: 599px)"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_media_queries_orientation() {
+        let inspector = LayoutInspector::new();
+
+        let landscape = inspector.resolve_media_queries(1280.0, 720.0);
+        assert!(matches(&landscape, "(orientation: landscape)"));
+        assert!(!matches(&landscape, "(orientation: portrait)"));
+
+        let result = inspector
+            .handle_method(
+                "resolveMediaQueries",
+                Some(json!({ "viewportWidth": 375, "viewportHeight": 812 })),
+            )
+            .await
+            .unwrap();
+        let portrait: Vec<MediaQueryResult> =
+            serde_json::from_value(result["mediaQueries"].clone()).unwrap();
+        assert!(matches(&portrait, "(orientation: portrait)"));
+        assert!(!matches(&portrait, "(orientation: landscape)"));
+    }
+
+    #[test]
+    fn test_evaluate_media_query_lists() {
+        assert!(evaluate_media_query("print, (min-width: 100px)", 200.0, 100.0));
+        assert!(!evaluate_media_query("(min-resolution: 2dppx)", 200.0, 100.0));
+        assert!(evaluate_media_query("only screen and (max-width: 200px)", 200.0, 100.0));
+    }
+
     #[tokio::test]
     async fn test_show_flex_overlay() {
         let inspector = LayoutInspector::new();
@@ -804,4 +1292,63 @@ mod tests {
         assert!(bm.width > 0.0);
         assert!(bm.height > 0.0);
     }
+
+    #[test]
+    fn test_highlight_object_paths_follow_box_model() {
+        let inspector = LayoutInspector::new();
+        let config = HighlightConfig {
+            show_rulers: true,
+            ..HighlightConfig::default()
+        };
+        let highlight = inspector.highlight_object(NodeId(6), &config).unwrap();
+        let model = inspector.get_element_box_model(NodeId(6)).unwrap();
+
+        let paths = highlight["paths"].as_array().unwrap();
+        let names: Vec<&str> = paths.iter().map(|p| p["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["content", "padding", "border", "margin"]);
+
+        let quads = [model.content, model.padding, model.border, model.margin];
+        for (path, quad) in paths.iter().zip(quads) {
+            assert_eq!(path["path"], json!(quad_path(&quad)));
+        }
+        assert_eq!(
+            paths[0]["path"],
+            json!(["M", 70.0, 70.0, "L", 230.0, 70.0, "L", 230.0, 180.0, "L", 70.0, 180.0, "Z"])
+        );
+        assert_eq!(paths[0]["fillColor"], "rgba(111, 168, 220, 0.66)");
+        assert_eq!(paths[3]["fillColor"], "rgba(246, 178, 107, 0.66)");
+        assert_eq!(highlight["showRulers"], true);
+    }
+
+    #[tokio::test]
+    async fn test_highlight_object_element_info() {
+        let inspector = LayoutInspector::new();
+
+        let result = inspector
+            .handle_method("getHighlightObjectForTest", Some(json!({ "nodeId": 6 })))
+            .await
+            .unwrap();
+        let info = &result["highlight"]["elementInfo"];
+        assert_eq!(info["tagName"], "div");
+        assert_eq!(info["idValue"], "container");
+        assert_eq!(info["className"], ".wrapper");
+        assert_eq!(info["nodeWidth"], 160.0);
+        assert_eq!(info["nodeHeight"], 110.0);
+
+        let params = json!({ "nodeId": 6, "highlightConfig": { "showInfo": false } });
+        let result = inspector
+            .handle_method("getHighlightObjectForTest", Some(params))
+            .await
+            .unwrap();
+        assert!(result["highlight"].get("elementInfo").is_none());
+        assert!(result["highlight"]["paths"][0].get("fillColor").is_none());
+
+        let result = inspector
+            .handle_method(
+                "getHighlightObjectForTest",
+                Some(json!({ "nodeId": 99999 })),
+            )
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -99,15 +99,17 @@ pub use accessibility_inspector::AccessibilityInspector;
 pub use dom_inspector_bridge::DomInspectorBridge;
 pub use elements_inspector::ElementsInspector;
 pub use js_debug_bridge::{
-    BreakpointInfo, DebugEvent, JsDebugBridge, JsDebugBridgeError, PauseOnExceptionsMode,
-    PauseState, PropertyInfo, ScriptInfo, StepAction,
+    AcceptAllExceptions, AsyncTaskInfo, BreakpointInfo, ConditionEvaluator, DebugEvent,
+    ExceptionFilter, JsDebugBridge, JsDebugBridgeError, PauseOnExceptionsMode, PauseState,
+    PendingBreakpoint, PropertyInfo, ScriptInfo, ScriptLanguage, SetScriptSourceResult,
+    SourceMapFetcher, StackTraceId, StepAction,
 };
 pub use layout_inspector::LayoutInspector;
 pub use mock_browser::MockBrowser;
 pub use render_inspector_bridge::RenderInspectorBridge;
 pub use source_map::{
-    GeneratedLocation, Mapping, OriginalLocation, Position, RawSourceMap, SourceMap,
-    SourceMapError,
+    GeneratedLocation, Mapping, OriginalLocation, Position, RawSourceMap, RawSourceMapSection,
+    SourceMap, SourceMapError, SourceMapStats,
 };
 pub use storage_bridge::StorageBridge;
 
@@ -117,15 +119,19 @@ pub use types::{
 };
 
 // Re-export types from new modules
-pub use accessibility_inspector::{AXNode, AXValue, AriaAttributesInfo, ContrastInfo};
+pub use accessibility_inspector::{
+    AXNode, AXValue, AriaAttributesInfo, AriaIssue, AriaIssueCode, ContrastInfo,
+};
 pub use elements_inspector::{ElementInfo, ElementState, StyleModification};
 pub use layout_inspector::{
-    FlexContainerInfo, FlexItemInfo, FlexOverlayConfig, GridContainerInfo, GridItemInfo,
-    GridOverlayConfig, LayoutOverlayState,
+    BoxModelMetrics, FlexContainerInfo, FlexItemInfo, FlexOverlayConfig, GridContainerInfo,
+    GridItemInfo, GridOverlayConfig, LayoutOverlayState, LogicalSide, MediaQueryResult,
+    PhysicalSideLabels, TextDirection, WritingMode,
 };
 pub use storage_bridge::{
-    Cookie, CookieSameSite, DatabaseInfo, IndexInfo, ObjectStoreInfo, StorageAreaType,
-    StorageBridgeState, StorageItem,
+    Cookie, CookieSameSite, CursorDirection, CursorHandle, DataEntry, DatabaseInfo, IndexInfo,
+    IndexedDbExport, ObjectStoreInfo, OriginStorageSnapshot, QuotaChangedEvent, StorageAreaType,
+    StorageBridgeState, StorageItem, StorageOrigin,
 };
 
 #[cfg(test)]
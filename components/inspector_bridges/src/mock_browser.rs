@@ -235,6 +235,14 @@ impl MockBrowser {
             .unwrap_or_default()
     }
 
+    /// Get the parent of a node
+    pub fn get_parent(&self, node_id: NodeId) -> Option<NodeId> {
+        self.children
+            .iter()
+            .find(|entry| entry.value().contains(&node_id))
+            .map(|entry| *entry.key())
+    }
+
     /// Get all descendant node IDs
     pub fn get_descendants(&self, node_id: NodeId) -> Vec<NodeId> {
         let mut descendants = Vec::new();
@@ -350,6 +358,7 @@ impl MockBrowser {
                 previous_sibling_id: None,
                 attribute_name: Some(name.to_string()),
                 old_value,
+                new_value: Some(value.to_string()),
             });
 
             Ok(())
@@ -402,6 +411,7 @@ impl MockBrowser {
                 previous_sibling_id: None,
                 attribute_name: Some(name.to_string()),
                 old_value,
+                new_value: None,
             });
 
             Ok(())
@@ -410,6 +420,53 @@ impl MockBrowser {
         }
     }
 
+    /// Append a text node to an element, returning the new node's ID
+    pub fn append_text_node(&self, parent_id: NodeId, text: &str) -> Result<NodeId, String> {
+        match self.nodes.get(&parent_id) {
+            Some(parent) if parent.node_type == NodeType::Element => {}
+            Some(_) => return Err("Cannot append children to non-element node".to_string()),
+            None => return Err(format!("Node {} not found", parent_id.0)),
+        }
+
+        let node_id = NodeId(self.next_node_id.fetch_add(1, Ordering::SeqCst));
+        self.nodes.insert(
+            node_id,
+            Node {
+                node_id,
+                node_type: NodeType::Text,
+                node_name: "#text".to_string(),
+                local_name: None,
+                node_value: Some(text.to_string()),
+                child_node_count: None,
+                children: None,
+                attributes: None,
+            },
+        );
+
+        let (previous_sibling_id, child_count) = {
+            let mut children = self.children.entry(parent_id).or_default();
+            let previous_sibling_id = children.last().copied();
+            children.push(node_id);
+            (previous_sibling_id, children.len() as u32)
+        };
+        if let Some(mut parent) = self.nodes.get_mut(&parent_id) {
+            parent.child_node_count = Some(child_count);
+        }
+
+        let _ = self.mutation_sender.send(MutationRecord {
+            mutation_type: MutationType::ChildListAdded,
+            target_node_id: parent_id,
+            added_node_ids: Some(vec![node_id]),
+            removed_node_ids: None,
+            previous_sibling_id,
+            attribute_name: None,
+            old_value: None,
+            new_value: None,
+        });
+
+        Ok(node_id)
+    }
+
     /// Search for nodes containing text
     pub fn perform_search(
         &self,
@@ -490,64 +547,138 @@ impl MockBrowser {
 
     /// Get computed styles for a node (mock)
     pub fn get_computed_styles(&self, node_id: NodeId) -> Option<ComputedStyles> {
-        if self.nodes.contains_key(&node_id) {
-            Some(ComputedStyles {
-                properties: vec![
-                    CSSProperty {
-                        name: "display".to_string(),
-                        value: "block".to_string(),
-                        important: Some(false),
-                        implicit: Some(false),
-                        text: Some("display: block".to_string()),
-                        parsed_ok: Some(true),
-                        disabled: Some(false),
-                        range: None,
-                    },
-                    CSSProperty {
-                        name: "color".to_string(),
-                        value: "rgb(0, 0, 0)".to_string(),
-                        important: Some(false),
-                        implicit: Some(false),
-                        text: Some("color: rgb(0, 0, 0)".to_string()),
-                        parsed_ok: Some(true),
-                        disabled: Some(false),
-                        range: None,
-                    },
-                    CSSProperty {
-                        name: "font-size".to_string(),
-                        value: "16px".to_string(),
-                        important: Some(false),
-                        implicit: Some(false),
-                        text: Some("font-size: 16px".to_string()),
-                        parsed_ok: Some(true),
-                        disabled: Some(false),
-                        range: None,
-                    },
-                    CSSProperty {
-                        name: "margin".to_string(),
-                        value: "0px".to_string(),
-                        important: Some(false),
-                        implicit: Some(false),
-                        text: Some("margin: 0px".to_string()),
-                        parsed_ok: Some(true),
-                        disabled: Some(false),
-                        range: None,
-                    },
-                    CSSProperty {
-                        name: "padding".to_string(),
-                        value: "0px".to_string(),
-                        important: Some(false),
-                        implicit: Some(false),
-                        text: Some("padding: 0px".to_string()),
-                        parsed_ok: Some(true),
-                        disabled: Some(false),
-                        range: None,
-                    },
-                ],
-            })
-        } else {
-            None
+        let node = self.nodes.get(&node_id)?;
+
+        let mut properties = vec![
+            CSSProperty {
+                name: "display".to_string(),
+                value: "block".to_string(),
+                important: Some(false),
+                implicit: Some(false),
+                text: Some("display: block".to_string()),
+                parsed_ok: Some(true),
+                disabled: Some(false),
+                range: None,
+            },
+            CSSProperty {
+                name: "color".to_string(),
+                value: "rgb(0, 0, 0)".to_string(),
+                important: Some(false),
+                implicit: Some(false),
+                text: Some("color: rgb(0, 0, 0)".to_string()),
+                parsed_ok: Some(true),
+                disabled: Some(false),
+                range: None,
+            },
+            CSSProperty {
+                name: "font-size".to_string(),
+                value: "16px".to_string(),
+                important: Some(false),
+                implicit: Some(false),
+                text: Some("font-size: 16px".to_string()),
+                parsed_ok: Some(true),
+                disabled: Some(false),
+                range: None,
+            },
+            CSSProperty {
+                name: "margin".to_string(),
+                value: "0px".to_string(),
+                important: Some(false),
+                implicit: Some(false),
+                text: Some("margin: 0px".to_string()),
+                parsed_ok: Some(true),
+                disabled: Some(false),
+                range: None,
+            },
+            CSSProperty {
+                name: "padding".to_string(),
+                value: "0px".to_string(),
+                important: Some(false),
+                implicit: Some(false),
+                text: Some("padding: 0px".to_string()),
+                parsed_ok: Some(true),
+                disabled: Some(false),
+                range: None,
+            },
+        ];
+
+        // Inline `style` declarations override the defaults
+        if let Some(style) = self.get_attribute_value(&node, "style") {
+            for declaration in style.split(';') {
+                let Some((name, value)) = declaration.split_once(':') else {
+                    continue;
+                };
+                let (name, value) = (name.trim(), value.trim());
+                if name.is_empty() {
+                    continue;
+                }
+
+                let property = CSSProperty {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                    important: Some(false),
+                    implicit: Some(false),
+                    text: Some(format!("{}: {}", name, value)),
+                    parsed_ok: Some(true),
+                    disabled: Some(false),
+                    range: None,
+                };
+                match properties.iter_mut().find(|p| p.name == name) {
+                    Some(existing) => *existing = property,
+                    None => properties.push(property),
+                }
+            }
+        }
+
+        Some(ComputedStyles { properties })
+    }
+
+    /// Get pseudo-class rules from the mock stylesheet (e.g. `:hover`)
+    ///
+    /// Returns the declarations that apply to the node while the given
+    /// pseudo-class is active. Unknown pseudo-classes have no rules.
+    pub fn get_pseudo_class_styles(&self, node_id: NodeId, pseudo: &str) -> Vec<CSSProperty> {
+        if !self.nodes.contains_key(&node_id) {
+            return Vec::new();
         }
+
+        let declarations: &[(&str, &str)] = match pseudo.trim_start_matches(':') {
+            "hover" => &[("color", "rgb(0, 0, 238)"), ("cursor", "pointer")],
+            "focus" | "focus-visible" => &[("outline", "auto 1px")],
+            "active" => &[("color", "rgb(255, 0, 0)")],
+            "visited" => &[("color", "rgb(85, 26, 139)")],
+            _ => &[],
+        };
+
+        declarations
+            .iter()
+            .map(|(name, value)| CSSProperty {
+                name: name.to_string(),
+                value: value.to_string(),
+                important: Some(false),
+                implicit: Some(false),
+                text: Some(format!("{}: {}", name, value)),
+                parsed_ok: Some(true),
+                disabled: Some(false),
+                range: None,
+            })
+            .collect()
+    }
+
+    /// Get the media query conditions used by the mock stylesheet
+    pub fn get_media_queries(&self) -> Vec<String> {
+        [
+            "(max-width: 599px)",
+            "(min-width: 600px)",
+            "screen and (min-width: 600px) and (max-width: 1023px)",
+            "(min-width: 1024px)",
+            "(orientation: portrait)",
+            "(orientation: landscape)",
+            "print",
+        ]
+        .iter()
+        .map(|q| q.to_string())
+        .collect()
     }
 
     /// Get box model for a node (mock)
@@ -677,6 +808,13 @@ mod tests {
         assert!(browser.get_document().is_some());
     }
 
+    #[test]
+    fn test_get_parent() {
+        let browser = MockBrowser::new();
+        assert_eq!(browser.get_parent(NodeId(7)), Some(NodeId(6)));
+        assert_eq!(browser.get_parent(NodeId(1)), None);
+    }
+
     #[test]
     fn test_get_document() {
         let browser = MockBrowser::new();
@@ -732,6 +870,52 @@ mod tests {
         assert!(attrs.contains(&"data-test".to_string()));
     }
 
+    #[test]
+    fn test_set_attribute_mutation_records_old_value() {
+        let browser = MockBrowser::new();
+        let mut receiver = browser.subscribe_mutations();
+
+        browser
+            .set_attribute(NodeId(6), "class", "updated")
+            .unwrap();
+
+        let record = receiver.try_recv().unwrap();
+        assert_eq!(record.mutation_type, MutationType::AttributeModified);
+        assert_eq!(record.target_node_id, NodeId(6));
+        assert_eq!(record.attribute_name.as_deref(), Some("class"));
+        assert_eq!(record.old_value.as_deref(), Some("wrapper"));
+        assert_eq!(record.new_value.as_deref(), Some("updated"));
+    }
+
+    #[test]
+    fn test_add_attribute_mutation_has_no_old_value() {
+        let browser = MockBrowser::new();
+        let mut receiver = browser.subscribe_mutations();
+
+        browser
+            .set_attribute(NodeId(6), "data-new", "fresh")
+            .unwrap();
+
+        let record = receiver.try_recv().unwrap();
+        assert_eq!(record.target_node_id, NodeId(6));
+        assert_eq!(record.attribute_name.as_deref(), Some("data-new"));
+        assert_eq!(record.old_value, None);
+        assert_eq!(record.new_value.as_deref(), Some("fresh"));
+    }
+
+    #[test]
+    fn test_remove_attribute_mutation_has_no_new_value() {
+        let browser = MockBrowser::new();
+        let mut receiver = browser.subscribe_mutations();
+
+        browser.remove_attribute(NodeId(6), "class").unwrap();
+
+        let record = receiver.try_recv().unwrap();
+        assert_eq!(record.target_node_id, NodeId(6));
+        assert_eq!(record.old_value.as_deref(), Some("wrapper"));
+        assert_eq!(record.new_value, None);
+    }
+
     #[test]
     fn test_search() {
         let browser = MockBrowser::new();
@@ -740,6 +924,14 @@ mod tests {
         assert!(count > 0);
     }
 
+    #[test]
+    fn test_get_media_queries() {
+        let browser = MockBrowser::new();
+        let queries = browser.get_media_queries();
+        assert!(queries.contains(&"(min-width: 600px)".to_string()));
+        assert!(queries.contains(&"(orientation: landscape)".to_string()));
+    }
+
     #[test]
     fn test_get_box_model() {
         let browser = MockBrowser::new();
@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use cdp_types::domains::css::ComputedStyles;
 use cdp_types::domains::dom::NodeId;
 use cdp_types::CdpError;
+use dashmap::DashMap;
 use protocol_handler::DomainHandler;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -37,6 +38,19 @@ pub struct GetBoxModelParams {
 pub struct GetComputedStyleParams {
     /// Node ID
     pub node_id: NodeId,
+    /// Pseudo-class to preview (e.g. `:hover`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pseudo: Option<String>,
+}
+
+/// Parameters for forcing element pseudo-class state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForceElementStateParams {
+    /// Node ID
+    pub node_id: NodeId,
+    /// Pseudo-classes to force (e.g. `["hover", "focus"]`)
+    pub forced_pseudo_classes: Vec<String>,
 }
 
 /// Parameters for getting matched styles
@@ -128,6 +142,11 @@ pub struct ShorthandEntry {
     pub value: String,
 }
 
+/// Normalize a pseudo-class name to its bare form (`:hover` -> `hover`)
+fn normalize_pseudo_class(pseudo: &str) -> String {
+    pseudo.trim().trim_start_matches(':').to_string()
+}
+
 /// Render Inspector Bridge
 ///
 /// Provides a bridge for render tree inspection including box model,
@@ -135,6 +154,8 @@ pub struct ShorthandEntry {
 pub struct RenderInspectorBridge {
     /// Mock browser for testing
     browser: Arc<MockBrowser>,
+    /// Forced pseudo-class states per node
+    forced_states: Arc<DashMap<NodeId, Vec<String>>>,
 }
 
 impl RenderInspectorBridge {
@@ -147,14 +168,15 @@ impl RenderInspectorBridge {
     /// let bridge = RenderInspectorBridge::new();
     /// ```
     pub fn new() -> Self {
-        Self {
-            browser: Arc::new(MockBrowser::new()),
-        }
+        Self::with_browser(Arc::new(MockBrowser::new()))
     }
 
     /// Create with custom browser (for testing)
     pub fn with_browser(browser: Arc<MockBrowser>) -> Self {
-        Self { browser }
+        Self {
+            browser,
+            forced_states: Arc::new(DashMap::new()),
+        }
     }
 
     /// Get box model for a node
@@ -191,8 +213,7 @@ impl RenderInspectorBridge {
         .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
         let styles = self
-            .browser
-            .get_computed_styles(params.node_id)
+            .compute_styles(params.node_id, params.pseudo.as_deref())
             .ok_or_else(|| {
                 CdpError::server_error(-32000, format!("Node {} not found", params.node_id.0))
             })?;
@@ -204,6 +225,76 @@ impl RenderInspectorBridge {
         Ok(response)
     }
 
+    /// Force pseudo-class state for a node
+    async fn force_element_state_handler(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        debug!("RenderInspector.forceElementState called");
+
+        let params: ForceElementStateParams = serde_json::from_value(
+            params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?,
+        )
+        .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
+
+        self.force_element_state(params.node_id, params.forced_pseudo_classes)
+            .map_err(|e| CdpError::server_error(-32000, e))?;
+
+        Ok(serde_json::json!({}))
+    }
+
+    /// Compute styles with forced states and an optional pseudo-class applied
+    fn compute_styles(&self, node_id: NodeId, pseudo: Option<&str>) -> Option<ComputedStyles> {
+        let mut styles = self.browser.get_computed_styles(node_id)?;
+
+        let mut states = self
+            .forced_states
+            .get(&node_id)
+            .map(|s| s.value().clone())
+            .unwrap_or_default();
+        if let Some(pseudo) = pseudo {
+            states.push(normalize_pseudo_class(pseudo));
+        }
+
+        for state in &states {
+            for override_prop in self.browser.get_pseudo_class_styles(node_id, state) {
+                match styles
+                    .properties
+                    .iter_mut()
+                    .find(|p| p.name == override_prop.name)
+                {
+                    Some(existing) => *existing = override_prop,
+                    None => styles.properties.push(override_prop),
+                }
+            }
+        }
+
+        Some(styles)
+    }
+
+    /// Persist forced pseudo-class states for a node
+    ///
+    /// An empty list clears any forced state.
+    pub fn force_element_state(&self, node_id: NodeId, states: Vec<String>) -> Result<(), String> {
+        if !self.browser.node_exists(node_id) {
+            return Err(format!("Node {} not found", node_id.0));
+        }
+
+        if states.is_empty() {
+            self.forced_states.remove(&node_id);
+        } else {
+            let states = states.iter().map(|s| normalize_pseudo_class(s)).collect();
+            self.forced_states.insert(node_id, states);
+        }
+
+        Ok(())
+    }
+
+    /// Get forced pseudo-class states for a node
+    pub fn get_forced_element_state(&self, node_id: NodeId) -> Vec<String> {
+        self.forced_states
+            .get(&node_id)
+            .map(|s| s.value().clone())
+            .unwrap_or_default()
+    }
+
     /// Get matched styles for a node
     async fn get_matched_styles_for_node(&self, params: Option<Value>) -> Result<Value, CdpError> {
         debug!("RenderInspector.getMatchedStylesForNode called");
@@ -374,9 +465,9 @@ impl RenderInspectorBridge {
         self.browser.get_box_model(node_id)
     }
 
-    /// Get computed styles directly (for external use)
+    /// Get computed styles directly (for external use), with forced states applied
     pub fn get_element_computed_styles(&self, node_id: NodeId) -> Option<ComputedStyles> {
-        self.browser.get_computed_styles(node_id)
+        self.compute_styles(node_id, None)
     }
 
     /// Get layer tree directly (for external use)
@@ -403,6 +494,7 @@ impl DomainHandler for RenderInspectorBridge {
         match method {
             "getBoxModel" => self.get_box_model(params).await,
             "getComputedStyleForNode" => self.get_computed_style_for_node(params).await,
+            "forceElementState" => self.force_element_state_handler(params).await,
             "getMatchedStylesForNode" => self.get_matched_styles_for_node(params).await,
             "getInlineStylesForNode" => self.get_inline_styles_for_node(params).await,
             "getLayerTree" => self.get_layer_tree(params).await,
@@ -475,6 +567,86 @@ mod tests {
         assert!(has_display);
     }
 
+    fn computed_value(value: &Value, name: &str) -> Option<String> {
+        value["computedStyle"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == name)
+            .and_then(|p| p["value"].as_str().map(String::from))
+    }
+
+    #[tokio::test]
+    async fn test_get_computed_style_with_pseudo() {
+        let bridge = RenderInspectorBridge::new();
+
+        let base = bridge
+            .get_computed_style_for_node(Some(json!({ "nodeId": 6 })))
+            .await
+            .unwrap();
+        assert_eq!(
+            computed_value(&base, "color").as_deref(),
+            Some("rgb(0, 0, 0)")
+        );
+
+        let hover = bridge
+            .get_computed_style_for_node(Some(json!({ "nodeId": 6, "pseudo": ":hover" })))
+            .await
+            .unwrap();
+        assert_eq!(
+            computed_value(&hover, "color").as_deref(),
+            Some("rgb(0, 0, 238)")
+        );
+        assert_eq!(computed_value(&hover, "cursor").as_deref(), Some("pointer"));
+
+        // Explicit pseudo does not persist
+        let again = bridge
+            .get_computed_style_for_node(Some(json!({ "nodeId": 6 })))
+            .await
+            .unwrap();
+        assert_eq!(
+            computed_value(&again, "color").as_deref(),
+            Some("rgb(0, 0, 0)")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_force_element_state_changes_computed_style() {
+        let bridge = RenderInspectorBridge::new();
+        let params = json!({ "nodeId": 6, "forcedPseudoClasses": ["hover"] });
+        bridge
+            .handle_method("forceElementState", Some(params))
+            .await
+            .unwrap();
+        assert_eq!(bridge.get_forced_element_state(NodeId(6)), vec!["hover"]);
+
+        let value = bridge
+            .get_computed_style_for_node(Some(json!({ "nodeId": 6 })))
+            .await
+            .unwrap();
+        assert_eq!(
+            computed_value(&value, "color").as_deref(),
+            Some("rgb(0, 0, 238)")
+        );
+
+        // Clearing forced states restores the default
+        bridge.force_element_state(NodeId(6), Vec::new()).unwrap();
+        let styles = bridge.get_element_computed_styles(NodeId(6)).unwrap();
+        let color = styles
+            .properties
+            .iter()
+            .find(|p| p.name == "color")
+            .unwrap();
+        assert_eq!(color.value, "rgb(0, 0, 0)");
+    }
+
+    #[tokio::test]
+    async fn test_force_element_state_invalid_node() {
+        let bridge = RenderInspectorBridge::new();
+        let result = bridge.force_element_state(NodeId(9999), vec![":focus".to_string()]);
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_get_computed_style_invalid_node() {
         let bridge = RenderInspectorBridge::new();
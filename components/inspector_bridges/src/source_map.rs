@@ -6,11 +6,12 @@
 //! - Source map parsing (JSON format, VLQ decoding)
 //! - Original position lookup
 //! - Generated position lookup
-//! - Source content resolution
+//! - Source content resolution (with `sourceRoot` URL fallback)
 //! - Inline source map support (data URLs)
+//! - Indexed source maps (`sections`)
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 
 /// Source map parsing and lookup errors
@@ -20,9 +21,17 @@ pub enum SourceMapError {
     #[error("Invalid source map JSON: {0}")]
     InvalidJson(String),
 
-    /// Invalid VLQ encoding
-    #[error("Invalid VLQ encoding: {0}")]
-    InvalidVlq(String),
+    /// Invalid VLQ encoding in `mappings`
+    ///
+    /// `segment` counts the non-empty segments of `mappings` from zero and
+    /// `char_offset` is the position of the bad digit within that segment (its
+    /// length for a truncated continuation).
+    #[error("Invalid VLQ at segment {segment}, offset {char_offset}")]
+    InvalidVlq { segment: usize, char_offset: usize },
+
+    /// A segment decoded but doesn't describe a valid mapping
+    #[error("Invalid mapping at segment {segment}: {reason}")]
+    InvalidMapping { segment: usize, reason: String },
 
     /// Invalid base64 encoding
     #[error("Invalid base64 encoding: {0}")]
@@ -91,6 +100,22 @@ pub struct GeneratedLocation {
     pub position: Position,
 }
 
+/// Mapping statistics for diagnosing broken source maps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceMapStats {
+    /// Total number of mappings
+    pub mapping_count: usize,
+    /// Mappings that reference a symbol name
+    pub named_mapping_count: usize,
+    /// Distinct sources referenced by at least one mapping
+    pub source_count: usize,
+    /// Generated lines with at least one mapping
+    pub lines_covered: usize,
+    /// Generated lines with no mappings at all
+    pub unmapped_lines: usize,
+}
+
 /// Raw source map JSON structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -104,6 +129,7 @@ pub struct RawSourceMap {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_root: Option<String>,
     /// List of original source files
+    #[serde(default)]
     pub sources: Vec<String>,
     /// Optional source content
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -112,7 +138,30 @@ pub struct RawSourceMap {
     #[serde(default)]
     pub names: Vec<String>,
     /// VLQ-encoded mappings
+    #[serde(default)]
     pub mappings: String,
+    /// Sections of an indexed source map (replaces `sources`/`mappings`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sections: Option<Vec<RawSourceMapSection>>,
+    /// Indexes into `sources` of third-party code debuggers should skip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_list: Option<Vec<usize>>,
+    /// Pre-standard spelling of `ignoreList`; bundlers often emit both
+    #[serde(
+        default,
+        rename = "x_google_ignoreList",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub x_google_ignore_list: Option<Vec<usize>>,
+}
+
+/// One section of an indexed source map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawSourceMapSection {
+    /// Position in the generated file where this section starts
+    pub offset: Position,
+    /// Source map covering this section
+    pub map: RawSourceMap,
 }
 
 /// Parsed source map with efficient lookup
@@ -130,8 +179,12 @@ pub struct SourceMap {
     pub sources_content: HashMap<usize, String>,
     /// List of symbol names
     pub names: Vec<String>,
+    /// Indexes of sources marked as third-party by `ignoreList`
+    pub ignore_list: HashSet<usize>,
     /// All parsed mappings
     mappings: Vec<Mapping>,
+    /// Number of generated lines described by the mappings string
+    generated_line_count: usize,
     /// Index for generated position lookup (line -> column -> mapping index)
     generated_index: HashMap<u32, Vec<(u32, usize)>>,
     /// Index for original position lookup (source_idx -> line -> column -> mapping index)
@@ -154,8 +207,7 @@ impl SourceMap {
         if !data_url.starts_with(prefix) {
             // Try alternate format without explicit mime type
             let alt_prefix = "data:;base64,";
-            if data_url.starts_with(alt_prefix) {
-                let base64_data = &data_url[alt_prefix.len()..];
+            if let Some(base64_data) = data_url.strip_prefix(alt_prefix) {
                 let decoded = decode_base64(base64_data)?;
                 let json = String::from_utf8(decoded)
                     .map_err(|e| SourceMapError::InvalidDataUrl(e.to_string()))?;
@@ -198,8 +250,17 @@ impl SourceMap {
     }
 
     /// Create source map from raw parsed JSON
-    fn from_raw(raw: RawSourceMap) -> Result<Self> {
+    fn from_raw(mut raw: RawSourceMap) -> Result<Self> {
+        if let Some(sections) = raw.sections.take() {
+            return Self::from_sections(raw, sections);
+        }
+
         let mappings = parse_vlq_mappings(&raw.mappings)?;
+        let generated_line_count = if raw.mappings.is_empty() {
+            0
+        } else {
+            raw.mappings.split(';').count()
+        };
 
         // Build sources_content map
         let mut sources_content = HashMap::new();
@@ -211,6 +272,96 @@ impl SourceMap {
             }
         }
 
+        Ok(Self {
+            version: raw.version,
+            file: raw.file,
+            source_root: raw.source_root,
+            sources: raw.sources,
+            sources_content,
+            names: raw.names,
+            ignore_list: raw
+                .ignore_list
+                .into_iter()
+                .chain(raw.x_google_ignore_list)
+                .flatten()
+                .collect(),
+            mappings,
+            generated_line_count,
+            generated_index: HashMap::new(),
+            original_index: HashMap::new(),
+        }
+        .with_indexes())
+    }
+
+    /// Merge the sections of an indexed source map into one lookup structure
+    ///
+    /// Each section's mappings are shifted by its offset: every line moves
+    /// down by `offset.line`, and columns on the section's first line move
+    /// right by `offset.column`. Sources and names are appended, with each
+    /// section's `sourceRoot` folded into its source paths.
+    fn from_sections(raw: RawSourceMap, sections: Vec<RawSourceMapSection>) -> Result<Self> {
+        let mut merged = Self {
+            version: raw.version,
+            file: raw.file,
+            source_root: None,
+            sources: Vec::new(),
+            sources_content: HashMap::new(),
+            names: Vec::new(),
+            ignore_list: HashSet::new(),
+            mappings: Vec::new(),
+            generated_line_count: 0,
+            generated_index: HashMap::new(),
+            original_index: HashMap::new(),
+        };
+
+        for section in sections {
+            let offset = section.offset;
+            let section_map = Self::from_raw(section.map)?;
+            let source_base = merged.sources.len();
+            let name_base = merged.names.len();
+
+            for mut mapping in section_map.mappings {
+                if mapping.generated.line == 0 {
+                    mapping.generated.column += offset.column;
+                }
+                mapping.generated.line += offset.line;
+                mapping.source_index = mapping.source_index.map(|idx| idx + source_base);
+                mapping.name_index = mapping.name_index.map(|idx| idx + name_base);
+                merged.mappings.push(mapping);
+            }
+
+            let root = section_map.source_root.unwrap_or_default();
+            merged.sources.extend(
+                section_map
+                    .sources
+                    .into_iter()
+                    .map(|source| join_source_root(&root, &source)),
+            );
+            merged.sources_content.extend(
+                section_map
+                    .sources_content
+                    .into_iter()
+                    .map(|(idx, content)| (idx + source_base, content)),
+            );
+            merged.names.extend(section_map.names);
+            merged.ignore_list.extend(
+                section_map
+                    .ignore_list
+                    .into_iter()
+                    .map(|idx| idx + source_base),
+            );
+            merged.generated_line_count = merged
+                .generated_line_count
+                .max(offset.line as usize + section_map.generated_line_count);
+        }
+
+        Ok(merged.with_indexes())
+    }
+
+    /// Build the generated and original position indexes from `mappings`
+    fn with_indexes(mut self) -> Self {
+        let mappings = &self.mappings;
+
         // Build generated index
         let mut generated_index: HashMap<u32, Vec<(u32, usize)>> = HashMap::new();
         for (idx, mapping) in mappings.iter().enumerate() {
@@ -245,30 +396,68 @@ impl SourceMap {
             }
         }
 
-        Ok(Self {
-            version: raw.version,
-            file: raw.file,
-            source_root: raw.source_root,
-            sources: raw.sources,
-            sources_content,
-            names: raw.names,
-            mappings,
-            generated_index,
-            original_index,
-        })
+        self.generated_index = generated_index;
+        self.original_index = original_index;
+        self
     }
 
     /// Look up original position from generated position
+    ///
+    /// Optional data degrades gracefully: a name index with no matching entry
+    /// in `names` yields `name: None` rather than an error.
     pub fn original_position_for(&self, generated: Position) -> Result<OriginalLocation> {
         let columns = self
             .generated_index
             .get(&generated.line)
             .ok_or(SourceMapError::MappingNotFound)?;
 
-        // Find the mapping with column <= generated.column (binary search)
-        let mapping_idx = find_closest_mapping(columns, generated.column)
+        // Positions between tokens map through the closest preceding mapping;
+        // there's nothing to fall back on before the line's first mapping
+        let mapping_idx = find_preceding_mapping(columns, generated.column)
             .ok_or(SourceMapError::MappingNotFound)?;
 
+        self.original_location_of(mapping_idx)
+    }
+
+    /// Look up original positions for many generated positions at once
+    ///
+    /// Queries are sorted internally so each generated line's mappings are
+    /// walked once; results are returned in the order of `positions`.
+    pub fn original_positions_for(&self, positions: &[Position]) -> Vec<Option<OriginalLocation>> {
+        let mut order: Vec<usize> = (0..positions.len()).collect();
+        order.sort_by_key(|&i| (positions[i].line, positions[i].column));
+
+        let mut results = vec![None; positions.len()];
+        let mut current_line = None;
+        let mut columns: &[(u32, usize)] = &[];
+        let mut cursor = 0;
+
+        for i in order {
+            let query = positions[i];
+            if current_line != Some(query.line) {
+                current_line = Some(query.line);
+                columns = self
+                    .generated_index
+                    .get(&query.line)
+                    .map(Vec::as_slice)
+                    .unwrap_or(&[]);
+                cursor = 0;
+            }
+
+            // Advance past every mapping at or before the query column
+            while cursor < columns.len() && columns[cursor].0 <= query.column {
+                cursor += 1;
+            }
+            if cursor > 0 {
+                results[i] = self.original_location_of(columns[cursor - 1].1).ok();
+            }
+        }
+
+        results
+    }
+
+    /// Resolve the original location of the mapping at `mapping_idx`
+    fn original_location_of(&self, mapping_idx: usize) -> Result<OriginalLocation> {
         let mapping = &self.mappings[mapping_idx];
         let source_idx = mapping
             .source_index
@@ -281,10 +470,9 @@ impl SourceMap {
             .ok_or_else(|| SourceMapError::SourceNotFound(format!("index {}", source_idx)))?
             .clone();
 
-        let full_source = if let Some(ref root) = self.source_root {
-            format!("{}{}", root, source)
-        } else {
-            source
+        let full_source = match self.source_root {
+            Some(ref root) => join_source_root(root, &source),
+            None => source,
         };
 
         let name = mapping
@@ -333,16 +521,73 @@ impl SourceMap {
         self.sources_content.get(&source_idx).map(|s| s.as_str())
     }
 
+    /// Get source content, falling back to the resolved source URL
+    ///
+    /// Bundlers often omit `sourcesContent`; in that case the returned URL
+    /// (see [`SourceMap::resolved_source_url`]) is where the content lives.
+    pub fn source_content_for(&self, source: &str) -> Option<String> {
+        match self.source_content(source) {
+            Some(content) => Some(content.to_string()),
+            None => self.resolved_source_url(source),
+        }
+    }
+
+    /// Resolve a source to its URL by joining it onto `sourceRoot`
+    ///
+    /// Entries that are already absolute are returned unchanged.
+    pub fn resolved_source_url(&self, source: &str) -> Option<String> {
+        let source_idx = self.find_source_index(source).ok()?;
+        let path = &self.sources[source_idx];
+        Some(match self.source_root {
+            Some(ref root) => join_source_root(root, path),
+            None => path.clone(),
+        })
+    }
+
     /// Get all source files
     pub fn source_files(&self) -> &[String] {
         &self.sources
     }
 
+    /// Check whether `source` is listed in `ignoreList` (third-party code)
+    ///
+    /// Unknown sources are not ignored.
+    pub fn is_ignored_source(&self, source: &str) -> bool {
+        self.find_source_index(source)
+            .map(|idx| self.ignore_list.contains(&idx))
+            .unwrap_or(false)
+    }
+
     /// Get number of mappings
     pub fn mapping_count(&self) -> usize {
         self.mappings.len()
     }
 
+    /// Summarize the mappings for diagnostics
+    ///
+    /// A non-zero `unmapped_lines` usually explains breakpoints that don't
+    /// resolve on those generated lines.
+    pub fn stats(&self) -> SourceMapStats {
+        let sources: HashSet<usize> = self
+            .mappings
+            .iter()
+            .filter_map(|m| m.source_index)
+            .collect();
+        let lines_covered = self.generated_index.len();
+
+        SourceMapStats {
+            mapping_count: self.mappings.len(),
+            named_mapping_count: self
+                .mappings
+                .iter()
+                .filter(|m| m.name_index.is_some())
+                .count(),
+            source_count: sources.len(),
+            lines_covered,
+            unmapped_lines: self.generated_line_count.saturating_sub(lines_covered),
+        }
+    }
+
     /// Find source index by name (with or without source root)
     fn find_source_index(&self, source: &str) -> Result<usize> {
         // Try exact match first
@@ -352,7 +597,8 @@ impl SourceMap {
 
         // Try with source root stripped
         if let Some(ref root) = self.source_root {
-            if let Some(stripped) = source.strip_prefix(root) {
+            if let Some(stripped) = source.strip_prefix(root.as_str()) {
+                let stripped = stripped.trim_start_matches('/');
                 if let Some(idx) = self.sources.iter().position(|s| s == stripped) {
                     return Ok(idx);
                 }
@@ -373,12 +619,29 @@ impl SourceMap {
     }
 }
 
+/// Join a source path onto `sourceRoot`, tolerating a missing trailing slash
+///
+/// Absolute sources (URLs with a scheme, or rooted paths) ignore the root.
+fn join_source_root(root: &str, source: &str) -> String {
+    if root.is_empty() || source.contains("://") || source.starts_with('/') {
+        return source.to_string();
+    }
+    if root.ends_with('/') {
+        format!("{}{}", root, source)
+    } else {
+        format!("{}/{}", root, source)
+    }
+}
+
 /// Find the mapping index with column closest to but not exceeding the target
+///
+/// Falls back to the first mapping when the target precedes all of them.
 fn find_closest_mapping(columns: &[(u32, usize)], target_column: u32) -> Option<usize> {
-    if columns.is_empty() {
-        return None;
-    }
+    find_preceding_mapping(columns, target_column).or_else(|| columns.first().map(|c| c.1))
+}
 
+/// Find the mapping index with the greatest column <= the target, if any
+fn find_preceding_mapping(columns: &[(u32, usize)], target_column: u32) -> Option<usize> {
     // Binary search for the largest column <= target_column
     let mut left = 0;
     let mut right = columns.len();
@@ -396,8 +659,7 @@ fn find_closest_mapping(columns: &[(u32, usize)], target_column: u32) -> Option<
     if left > 0 {
         Some(columns[left - 1].1)
     } else {
-        // If target is less than all columns, use the first one
-        Some(columns[0].1)
+        None
     }
 }
 
@@ -448,6 +710,8 @@ fn parse_vlq_mappings(mappings: &str) -> Result<Vec<Mapping>> {
     let mut prev_orig_col: i64 = 0;
     let mut prev_name: i64 = 0;
 
+    let mut segment_idx = 0;
+
     for (gen_line, line_mappings) in mappings.split(';').enumerate() {
         let mut prev_gen_col: i64 = 0; // Reset column at start of each line
 
@@ -455,11 +719,27 @@ fn parse_vlq_mappings(mappings: &str) -> Result<Vec<Mapping>> {
             if segment.is_empty() {
                 continue;
             }
+            let current_segment = segment_idx;
+            segment_idx += 1;
+            let invalid = |reason: String| SourceMapError::InvalidMapping {
+                segment: current_segment,
+                reason,
+            };
 
-            let values = decode_vlq(segment)?;
+            let values = decode_vlq(segment).map_err(|char_offset| SourceMapError::InvalidVlq {
+                segment: current_segment,
+                char_offset,
+            })?;
             if values.is_empty() {
                 continue;
             }
+            if !matches!(values.len(), 1 | 4 | 5) {
+                return Err(invalid(format!(
+                    "'{}' has {} fields (expected 1, 4 or 5)",
+                    segment,
+                    values.len()
+                )));
+            }
 
             // First value: generated column (delta from previous)
             prev_gen_col += values[0];
@@ -489,6 +769,20 @@ fn parse_vlq_mappings(mappings: &str) -> Result<Vec<Mapping>> {
                     prev_name += values[4];
                     mapping.name_index = Some(prev_name as usize);
                 }
+
+                if prev_source < 0 || prev_orig_line < 0 || prev_orig_col < 0 || prev_name < 0 {
+                    return Err(invalid(format!(
+                        "'{}' decodes to a negative index",
+                        segment
+                    )));
+                }
+            }
+
+            if prev_gen_col < 0 {
+                return Err(invalid(format!(
+                    "'{}' decodes to a negative column",
+                    segment
+                )));
             }
 
             result.push(mapping);
@@ -499,7 +793,10 @@ fn parse_vlq_mappings(mappings: &str) -> Result<Vec<Mapping>> {
 }
 
 /// Decode a VLQ-encoded segment into a list of integers
-fn decode_vlq(segment: &str) -> Result<Vec<i64>> {
+///
+/// On failure returns the character offset within the segment at which
+/// decoding broke down.
+fn decode_vlq(segment: &str) -> std::result::Result<Vec<i64>, usize> {
     const VLQ_CHARS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
     const VLQ_CONTINUATION_BIT: u8 = 32; // 6th bit
     const VLQ_VALUE_MASK: u8 = 31; // Lower 5 bits
@@ -508,11 +805,10 @@ fn decode_vlq(segment: &str) -> Result<Vec<i64>> {
     let mut value: i64 = 0;
     let mut shift = 0;
 
-    for c in segment.chars() {
-        let digit = VLQ_CHARS
-            .find(c)
-            .ok_or_else(|| SourceMapError::InvalidVlq(format!("Invalid VLQ character: {}", c)))?
-            as u8;
+    let mut length = 0;
+    for (offset, c) in segment.chars().enumerate() {
+        length = offset + 1;
+        let digit = VLQ_CHARS.find(c).ok_or(offset)? as u8;
 
         value += ((digit & VLQ_VALUE_MASK) as i64) << shift;
         shift += 5;
@@ -533,9 +829,8 @@ fn decode_vlq(segment: &str) -> Result<Vec<i64>> {
     }
 
     if shift > 0 {
-        return Err(SourceMapError::InvalidVlq(
-            "Incomplete VLQ sequence".to_string(),
-        ));
+        // Incomplete sequence: the continuation runs off the end
+        return Err(length);
     }
 
     Ok(result)
@@ -703,6 +998,109 @@ mod tests {
         assert_eq!(orig2.position.line, 2);
     }
 
+    #[test]
+    fn test_indexed_source_map_sections() {
+        // Section two starts mid-way through generated line 1
+        let source_map_json = r#"{
+            "version": 3,
+            "file": "bundle.js",
+            "sections": [
+                {
+                    "offset": {"line": 0, "column": 0},
+                    "map": {
+                        "version": 3,
+                        "sources": ["first.js"],
+                        "names": ["alpha"],
+                        "mappings": "AAAAA;AACA"
+                    }
+                },
+                {
+                    "offset": {"line": 1, "column": 20},
+                    "map": {
+                        "version": 3,
+                        "sourceRoot": "src/",
+                        "sources": ["second.js"],
+                        "names": ["beta"],
+                        "mappings": "AAAAA;AACA"
+                    }
+                }
+            ]
+        }"#;
+
+        let sm = SourceMap::parse(source_map_json).unwrap();
+        assert_eq!(sm.source_files(), &["first.js", "src/second.js"]);
+        assert_eq!(sm.mapping_count(), 4);
+
+        // Before the boundary on line 1 we're still in the first section
+        let first = sm.original_position_for(Position::new(1, 5)).unwrap();
+        assert_eq!(first.source, "first.js");
+        assert_eq!(first.position, Position::new(1, 0));
+
+        let second = sm.original_position_for(Position::new(1, 25)).unwrap();
+        assert_eq!(second.source, "src/second.js");
+        assert_eq!(second.position, Position::new(0, 0));
+        assert_eq!(second.name.as_deref(), Some("beta"));
+
+        let next_line = sm.original_position_for(Position::new(2, 0)).unwrap();
+        assert_eq!(next_line.source, "src/second.js");
+        assert_eq!(next_line.position, Position::new(1, 0));
+
+        let generated = sm
+            .generated_position_for("src/second.js", Position::new(0, 0))
+            .unwrap();
+        assert_eq!(generated.position, Position::new(1, 20));
+        assert_eq!(sm.stats().unmapped_lines, 0);
+    }
+
+    #[test]
+    fn test_indexed_source_map_section_root_without_trailing_slash() {
+        let source_map_json = r#"{
+            "version": 3,
+            "sections": [
+                {
+                    "offset": {"line": 0, "column": 0},
+                    "map": {
+                        "version": 3,
+                        "sourceRoot": "src",
+                        "sources": ["app.js", "https://cdn.example.com/lib.js"],
+                        "names": [],
+                        "mappings": "AAAA"
+                    }
+                }
+            ]
+        }"#;
+
+        let sm = SourceMap::parse(source_map_json).unwrap();
+        assert_eq!(
+            sm.source_files(),
+            &["src/app.js", "https://cdn.example.com/lib.js"]
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        // Line 1 is intentionally left unmapped
+        let source_map_json = r#"{
+            "version": 3,
+            "sources": ["a.js", "b.js", "unused.js"],
+            "names": ["foo"],
+            "mappings": "AAAAA,IAAI;;ACAA"
+        }"#;
+
+        let sm = SourceMap::parse(source_map_json).unwrap();
+        let stats = sm.stats();
+        assert_eq!(
+            stats,
+            SourceMapStats {
+                mapping_count: 3,
+                named_mapping_count: 1,
+                source_count: 2,
+                lines_covered: 2,
+                unmapped_lines: 1,
+            }
+        );
+    }
+
     #[test]
     fn test_extract_url_single_line_comment() {
         let source = r#"
@@ -839,6 +1237,67 @@ mod tests {
         assert_eq!(sm.source_files(), &["a.js", "b.js", "c.js"]);
     }
 
+    #[test]
+    fn test_ignore_list_marks_sources() {
+        let source_map_json = r#"{
+            "version": 3,
+            "sources": ["app.js", "node_modules/react.js"],
+            "names": [],
+            "mappings": "",
+            "ignoreList": [1]
+        }"#;
+
+        let sm = SourceMap::parse(source_map_json).unwrap();
+        assert!(sm.is_ignored_source("node_modules/react.js"));
+        assert!(!sm.is_ignored_source("app.js"));
+        assert!(!sm.is_ignored_source("missing.js"));
+    }
+
+    #[test]
+    fn test_legacy_ignore_list_field() {
+        let source_map_json = r#"{
+            "version": 3,
+            "sources": ["app.js", "vendor.js"],
+            "names": [],
+            "mappings": "",
+            "x_google_ignoreList": [1]
+        }"#;
+
+        let sm = SourceMap::parse(source_map_json).unwrap();
+        assert!(sm.is_ignored_source("vendor.js"));
+    }
+
+    #[test]
+    fn test_both_ignore_list_fields_are_merged() {
+        let source_map_json = r#"{
+            "version": 3,
+            "sources": ["app.js", "vendor.js", "polyfill.js"],
+            "names": [],
+            "mappings": "",
+            "ignoreList": [1],
+            "x_google_ignoreList": [1, 2]
+        }"#;
+
+        let sm = SourceMap::parse(source_map_json).unwrap();
+        assert!(!sm.is_ignored_source("app.js"));
+        assert!(sm.is_ignored_source("vendor.js"));
+        assert!(sm.is_ignored_source("polyfill.js"));
+    }
+
+    #[test]
+    fn test_no_ignore_list_ignores_nothing() {
+        let source_map_json = r#"{
+            "version": 3,
+            "sources": ["a.js", "b.js"],
+            "names": [],
+            "mappings": ""
+        }"#;
+
+        let sm = SourceMap::parse(source_map_json).unwrap();
+        assert!(!sm.is_ignored_source("a.js"));
+        assert!(!sm.is_ignored_source("b.js"));
+    }
+
     #[test]
     fn test_negative_vlq_values() {
         // Test that negative values are handled correctly
@@ -852,4 +1311,198 @@ mod tests {
         let result = decode_vlq("H").unwrap();
         assert_eq!(result, vec![-3]);
     }
+
+    #[test]
+    fn test_name_index_without_names_is_partial() {
+        // Segment references name 0, but the map has no names array
+        let json = r#"{
+            "version": 3,
+            "sources": ["src/app.ts"],
+            "mappings": "AAAAA"
+        }"#;
+
+        let map = SourceMap::parse(json).unwrap();
+        let location = map.original_position_for(Position::new(0, 0)).unwrap();
+        assert_eq!(location.source, "src/app.ts");
+        assert_eq!(location.position, Position::new(0, 0));
+        assert_eq!(location.name, None);
+        assert!(map.source_content("src/app.ts").is_none());
+    }
+
+    #[test]
+    fn test_corrupt_vlq_errors() {
+        // Truncated: trailing continuation digit
+        let json = r#"{"version": 3, "sources": ["a.js"], "mappings": "AAAg"}"#;
+        assert!(matches!(
+            SourceMap::parse(json),
+            Err(SourceMapError::InvalidVlq {
+                segment: 0,
+                char_offset: 4
+            })
+        ));
+
+        // Two fields is not a valid segment length
+        let json = r#"{"version": 3, "sources": ["a.js"], "mappings": "AA"}"#;
+        assert!(matches!(
+            SourceMap::parse(json),
+            Err(SourceMapError::InvalidMapping { segment: 0, .. })
+        ));
+
+        // Source index delta below zero
+        let json = r#"{"version": 3, "sources": ["a.js"], "mappings": "ADAA"}"#;
+        assert!(matches!(
+            SourceMap::parse(json),
+            Err(SourceMapError::InvalidMapping { segment: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_vlq_reports_segment_and_offset() {
+        // Segments count across lines; the bad digit is the third of segment 2
+        let json = r#"{"version": 3, "sources": ["a.js"], "mappings": "AAAA;AACA,AA!A"}"#;
+        let err = SourceMap::parse(json).unwrap_err();
+        assert!(matches!(
+            err,
+            SourceMapError::InvalidVlq {
+                segment: 2,
+                char_offset: 2
+            }
+        ));
+        assert_eq!(err.to_string(), "Invalid VLQ at segment 2, offset 2");
+
+        // A continuation digit at the very end of `mappings`
+        let json = r#"{"version": 3, "sources": ["a.js"], "mappings": "AAAA,CAAC,g"}"#;
+        let err = SourceMap::parse(json).unwrap_err();
+        assert!(matches!(
+            err,
+            SourceMapError::InvalidVlq {
+                segment: 2,
+                char_offset: 1
+            }
+        ));
+    }
+
+    fn map_with_root(root: &str, sources: &str) -> SourceMap {
+        let json = format!(
+            r#"{{"version": 3, "sourceRoot": "{}", "sources": {}, "names": [], "mappings": "AAAA"}}"#,
+            root, sources
+        );
+        SourceMap::parse(&json).unwrap()
+    }
+
+    #[test]
+    fn test_source_content_for_prefers_sources_content() {
+        let sm = SourceMap::parse(
+            r#"{
+            "version": 3,
+            "sourceRoot": "https://example.com/src/",
+            "sources": ["a.ts", "b.ts"],
+            "sourcesContent": ["const a = 1;", null],
+            "names": [],
+            "mappings": "AAAA"
+        }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sm.source_content_for("a.ts"),
+            Some("const a = 1;".to_string())
+        );
+        assert_eq!(
+            sm.source_content_for("b.ts"),
+            Some("https://example.com/src/b.ts".to_string())
+        );
+        assert!(sm.source_content_for("missing.ts").is_none());
+    }
+
+    #[test]
+    fn test_resolved_source_url_with_and_without_trailing_slash() {
+        let with_slash = map_with_root("https://example.com/src/", r#"["app.ts"]"#);
+        let without_slash = map_with_root("https://example.com/src", r#"["app.ts"]"#);
+
+        for sm in [&with_slash, &without_slash] {
+            assert_eq!(
+                sm.resolved_source_url("app.ts"),
+                Some("https://example.com/src/app.ts".to_string())
+            );
+            let original = sm.original_position_for(Position::new(0, 0)).unwrap();
+            assert_eq!(original.source, "https://example.com/src/app.ts");
+            assert!(sm
+                .generated_position_for(&original.source, Position::new(0, 0))
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_resolved_source_url_keeps_absolute_sources() {
+        let sm = map_with_root(
+            "https://example.com/src/",
+            r#"["webpack://app/main.ts", "/abs/lib.ts"]"#,
+        );
+
+        assert_eq!(
+            sm.resolved_source_url("webpack://app/main.ts"),
+            Some("webpack://app/main.ts".to_string())
+        );
+        assert_eq!(
+            sm.resolved_source_url("/abs/lib.ts"),
+            Some("/abs/lib.ts".to_string())
+        );
+    }
+
+    #[test]
+    fn test_original_position_between_mappings_uses_preceding() {
+        // Generated columns 0 and 8 map to original columns 0 and 4
+        let sm = SourceMap::parse(
+            r#"{"version": 3, "sources": ["a.ts"], "names": [], "mappings": "AAAA,QAAI"}"#,
+        )
+        .unwrap();
+
+        let between = sm.original_position_for(Position::new(0, 5)).unwrap();
+        assert_eq!(between.position, Position::new(0, 0));
+
+        let past_last = sm.original_position_for(Position::new(0, 20)).unwrap();
+        assert_eq!(past_last.position, Position::new(0, 4));
+    }
+
+    #[test]
+    fn test_original_position_before_first_mapping_is_not_found() {
+        // The only mapping on the line starts at generated column 4
+        let sm = SourceMap::parse(
+            r#"{"version": 3, "sources": ["a.ts"], "names": [], "mappings": "IAAA"}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            sm.original_position_for(Position::new(0, 2)),
+            Err(SourceMapError::MappingNotFound)
+        ));
+        assert!(sm.original_position_for(Position::new(0, 4)).is_ok());
+    }
+
+    #[test]
+    fn test_original_positions_for_preserves_input_order() {
+        // Line 0: columns 0 and 8; line 1: column 0
+        let sm = SourceMap::parse(
+            r#"{"version": 3, "sources": ["a.ts"], "names": [], "mappings": "AAAA,QAAI;AACJ"}"#,
+        )
+        .unwrap();
+
+        let positions = [
+            Position::new(1, 3),
+            Position::new(0, 9),
+            Position::new(5, 0),
+            Position::new(0, 2),
+        ];
+        let results = sm.original_positions_for(&positions);
+
+        assert_eq!(results.len(), positions.len());
+        for (position, result) in positions.iter().zip(&results) {
+            assert_eq!(result, &sm.original_position_for(*position).ok());
+        }
+        assert_eq!(results[0].as_ref().unwrap().position, Position::new(1, 0));
+        assert_eq!(results[1].as_ref().unwrap().position, Position::new(0, 4));
+        assert!(results[2].is_none());
+        assert_eq!(results[3].as_ref().unwrap().position, Position::new(0, 0));
+    }
 }
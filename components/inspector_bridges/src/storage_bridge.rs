@@ -7,17 +7,27 @@
 //! - Local/Session storage access
 //! - IndexedDB inspection
 //! - Cookie management
+//! - Quota threshold notifications
+//! - Per-origin export/import of application state
 
 use async_trait::async_trait;
 use cdp_types::CdpError;
+use console_storage::CookieStore;
 use protocol_handler::DomainHandler;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, warn};
 
+/// Default storage quota per origin (10MB)
+pub const DEFAULT_STORAGE_QUOTA: u64 = 10 * 1024 * 1024;
+
+/// Default usage fraction at which a quota warning is emitted
+pub const DEFAULT_QUOTA_WARNING_THRESHOLD: f64 = 0.8;
+
 /// Storage item
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -89,6 +99,57 @@ pub enum CookieSameSite {
     None,
 }
 
+impl From<&console_storage::Cookie> for Cookie {
+    fn from(cookie: &console_storage::Cookie) -> Self {
+        let secure = cookie.secure.unwrap_or(false);
+        Self {
+            name: cookie.name.clone(),
+            value: cookie.value.clone(),
+            domain: cookie.domain.clone(),
+            path: cookie.path.clone(),
+            expires: cookie.expires,
+            size: cookie.size,
+            http_only: cookie.http_only.unwrap_or(false),
+            secure,
+            session: cookie.session.unwrap_or(cookie.expires.is_none()),
+            same_site: cookie.same_site.as_ref().map(|s| match s {
+                console_storage::CookieSameSite::Strict => CookieSameSite::Strict,
+                console_storage::CookieSameSite::Lax => CookieSameSite::Lax,
+                console_storage::CookieSameSite::None => CookieSameSite::None,
+            }),
+            priority: Some(CookiePriority::Medium),
+            same_party: false,
+            source_scheme: if secure {
+                Some(CookieSourceScheme::Secure)
+            } else {
+                Some(CookieSourceScheme::NonSecure)
+            },
+            source_port: None,
+        }
+    }
+}
+
+impl From<&Cookie> for console_storage::Cookie {
+    fn from(cookie: &Cookie) -> Self {
+        Self {
+            name: cookie.name.clone(),
+            value: cookie.value.clone(),
+            domain: cookie.domain.clone(),
+            path: cookie.path.clone(),
+            expires: cookie.expires,
+            size: cookie.size,
+            http_only: Some(cookie.http_only),
+            secure: Some(cookie.secure),
+            session: Some(cookie.session),
+            same_site: cookie.same_site.as_ref().map(|s| match s {
+                CookieSameSite::Strict => console_storage::CookieSameSite::Strict,
+                CookieSameSite::Lax => console_storage::CookieSameSite::Lax,
+                CookieSameSite::None => console_storage::CookieSameSite::None,
+            }),
+        }
+    }
+}
+
 /// Cookie priority
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CookiePriority {
@@ -211,8 +272,104 @@ pub struct StorageOrigin {
     pub usage: u64,
 }
 
+/// Exported IndexedDB database: schema plus object store records
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedDbExport {
+    /// Database schema
+    pub database: DatabaseInfo,
+    /// Records per object store name
+    #[serde(default)]
+    pub data: HashMap<String, Vec<DataEntry>>,
+}
+
+/// All storage for a single origin, as produced by `export_origin_storage`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginStorageSnapshot {
+    /// Security origin the snapshot was taken from
+    pub origin: String,
+    /// Cookies whose domain matches the origin host
+    #[serde(default)]
+    pub cookies: Vec<Cookie>,
+    /// localStorage items
+    #[serde(default)]
+    pub local_storage: HashMap<String, String>,
+    /// sessionStorage items
+    #[serde(default)]
+    pub session_storage: HashMap<String, String>,
+    /// IndexedDB databases
+    #[serde(default, rename = "indexedDB")]
+    pub indexed_db: Vec<IndexedDbExport>,
+}
+
+/// Emitted when an origin's storage usage crosses a quota threshold
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaChangedEvent {
+    /// Security origin
+    pub origin: String,
+    /// Current usage (bytes)
+    pub usage: u64,
+    /// Quota (bytes)
+    pub quota: u64,
+    /// Threshold that was crossed, as a fraction of quota
+    pub threshold: f64,
+}
+
+/// IndexedDB cursor direction
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CursorDirection {
+    /// Ascending key order, all records
+    Next,
+    /// Ascending key order, first record for each distinct key
+    NextUnique,
+    /// Descending key order, all records
+    Prev,
+    /// Descending key order, first record for each distinct key
+    PrevUnique,
+}
+
+impl CursorDirection {
+    fn is_forward(self) -> bool {
+        matches!(self, CursorDirection::Next | CursorDirection::NextUnique)
+    }
+
+    fn is_unique(self) -> bool {
+        matches!(
+            self,
+            CursorDirection::NextUnique | CursorDirection::PrevUnique
+        )
+    }
+}
+
+/// Handle to an open IndexedDB cursor
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CursorHandle(pub u64);
+
+/// Open IndexedDB cursor
+///
+/// Only the last visited `(key, primaryKey)` pair is kept; each step scans the
+/// object store for the next record instead of materializing the whole store.
+#[derive(Debug, Clone)]
+pub struct IndexedDbCursor {
+    /// Security origin
+    pub origin: String,
+    /// Database name
+    pub database_name: String,
+    /// Object store name
+    pub object_store_name: String,
+    /// Key path of the index being iterated, or `None` for the primary key
+    pub index_key_path: Option<KeyPath>,
+    /// Iteration direction
+    pub direction: CursorDirection,
+    /// Last visited `(key, primaryKey)`, `None` before the first step
+    pub position: Option<(Value, Value)>,
+}
+
 /// Storage bridge state
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct StorageBridgeState {
     /// Local storage per origin
     pub local_storage: HashMap<String, HashMap<String, String>>,
@@ -222,12 +379,43 @@ pub struct StorageBridgeState {
     pub cookies: Vec<Cookie>,
     /// IndexedDB databases per origin
     pub indexed_db: HashMap<String, Vec<DatabaseInfo>>,
+    /// IndexedDB records per origin, keyed by database name then object store name
+    pub object_store_data: HashMap<String, HashMap<String, HashMap<String, Vec<DataEntry>>>>,
     /// Cache storage per origin
     pub cache_storage: HashMap<String, Vec<CacheInfo>>,
+    /// Quota overrides per origin (bytes)
+    pub quotas: HashMap<String, u64>,
+    /// Usage fractions that trigger a quota warning, ascending
+    pub quota_thresholds: Vec<f64>,
+    /// Number of thresholds each origin currently exceeds
+    pub quota_levels: HashMap<String, usize>,
+    /// Open IndexedDB cursors
+    pub cursors: HashMap<CursorHandle, IndexedDbCursor>,
+    /// Next cursor handle to hand out
+    pub next_cursor_id: u64,
     /// Whether tracking is enabled
     pub enabled: bool,
 }
 
+impl Default for StorageBridgeState {
+    fn default() -> Self {
+        Self {
+            local_storage: HashMap::new(),
+            session_storage: HashMap::new(),
+            cookies: Vec::new(),
+            indexed_db: HashMap::new(),
+            object_store_data: HashMap::new(),
+            cache_storage: HashMap::new(),
+            quotas: HashMap::new(),
+            quota_thresholds: vec![DEFAULT_QUOTA_WARNING_THRESHOLD],
+            quota_levels: HashMap::new(),
+            cursors: HashMap::new(),
+            next_cursor_id: 1,
+            enabled: false,
+        }
+    }
+}
+
 impl StorageBridgeState {
     /// Create with mock data
     pub fn with_mock_data() -> Self {
@@ -298,6 +486,94 @@ impl StorageBridgeState {
 
         state
     }
+
+    /// Local and session storage usage for an origin (bytes)
+    pub fn usage_for(&self, origin: &str) -> (u64, u64) {
+        let usage = |storage: &HashMap<String, HashMap<String, String>>| {
+            storage
+                .get(origin)
+                .map(|s| s.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>())
+                .unwrap_or(0) as u64
+        };
+        (usage(&self.local_storage), usage(&self.session_storage))
+    }
+
+    /// Cookies visible to an origin (domain matches the origin host)
+    pub fn cookies_for_origin(&self, origin: &str) -> impl Iterator<Item = &Cookie> {
+        let host = origin_host(origin).to_string();
+        self.cookies
+            .iter()
+            .filter(move |c| cookie_domain_matches(&c.domain, &host))
+    }
+
+    /// Quota for an origin (bytes)
+    pub fn quota_for(&self, origin: &str) -> u64 {
+        self.quotas
+            .get(origin)
+            .copied()
+            .unwrap_or(DEFAULT_STORAGE_QUOTA)
+    }
+}
+
+/// Host part of a security origin (`https://example.com:8443` -> `example.com`)
+fn origin_host(origin: &str) -> &str {
+    let without_scheme = origin.split_once("://").map(|(_, rest)| rest).unwrap_or(origin);
+    let authority = without_scheme.split('/').next().unwrap_or("");
+    authority.rsplit_once(':').map(|(host, _)| host).unwrap_or(authority)
+}
+
+/// Whether a cookie domain applies to a host (exact or parent-domain match)
+fn cookie_domain_matches(domain: &str, host: &str) -> bool {
+    let domain = domain.trim_start_matches('.');
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+/// Order IndexedDB keys: numbers < strings < arrays (element-wise)
+fn compare_keys(a: &Value, b: &Value) -> Ordering {
+    fn rank(key: &Value) -> u8 {
+        match key {
+            Value::Number(_) => 0,
+            Value::String(_) => 1,
+            Value::Array(_) => 2,
+            _ => 3,
+        }
+    }
+
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .unwrap_or(f64::NAN)
+            .total_cmp(&y.as_f64().unwrap_or(f64::NAN)),
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Array(x), Value::Array(y)) => x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| compare_keys(x, y))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| x.len().cmp(&y.len())),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// Evaluate a key path (`a.b.c`, or an array of such paths) against a record value
+fn extract_key(value: &Value, key_path: &KeyPath) -> Option<Value> {
+    let lookup = |path: &str| {
+        path.split('.')
+            .filter(|segment| !segment.is_empty())
+            .try_fold(value, |v, segment| v.get(segment))
+            .cloned()
+    };
+    match key_path.key_path_type {
+        KeyPathType::String => lookup(key_path.string.as_deref()?),
+        KeyPathType::Array => key_path
+            .array
+            .as_ref()?
+            .iter()
+            .map(|path| lookup(path))
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array),
+        KeyPathType::Null => None,
+    }
 }
 
 /// Storage Bridge
@@ -307,20 +583,367 @@ impl StorageBridgeState {
 pub struct StorageBridge {
     /// Storage state
     state: Arc<RwLock<StorageBridgeState>>,
+    /// Quota threshold event broadcaster
+    quota_sender: broadcast::Sender<QuotaChangedEvent>,
+    /// Shared cookie jar; when set, cookies live here instead of `state.cookies`
+    cookie_store: Option<Arc<CookieStore>>,
 }
 
 impl StorageBridge {
     /// Create a new Storage Bridge
     pub fn new() -> Self {
-        Self {
-            state: Arc::new(RwLock::new(StorageBridgeState::with_mock_data())),
-        }
+        Self::with_state(StorageBridgeState::with_mock_data())
     }
 
     /// Create with empty state (for testing)
     pub fn empty() -> Self {
+        Self::with_state(StorageBridgeState::default())
+    }
+
+    /// Create with empty state, keeping cookies in a shared cookie store
+    pub fn with_cookie_store(cookie_store: Arc<CookieStore>) -> Self {
+        let mut bridge = Self::empty();
+        bridge.cookie_store = Some(cookie_store);
+        bridge
+    }
+
+    fn with_state(state: StorageBridgeState) -> Self {
+        let (quota_sender, _) = broadcast::channel(100);
         Self {
-            state: Arc::new(RwLock::new(StorageBridgeState::default())),
+            state: Arc::new(RwLock::new(state)),
+            quota_sender,
+            cookie_store: None,
+        }
+    }
+
+    /// All cookies, from the shared store if one is attached
+    fn all_cookies(&self, state: &StorageBridgeState) -> Vec<Cookie> {
+        match &self.cookie_store {
+            Some(store) => store.all().iter().map(Cookie::from).collect(),
+            None => state.cookies.clone(),
+        }
+    }
+
+    /// Add or replace a cookie (same name, domain and path)
+    fn put_cookie(&self, state: &mut StorageBridgeState, cookie: Cookie) {
+        match &self.cookie_store {
+            Some(store) => store.set(console_storage::Cookie::from(&cookie)),
+            None => {
+                state.cookies.retain(|c| {
+                    !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path)
+                });
+                state.cookies.push(cookie);
+            }
+        }
+    }
+
+    /// Keep only the cookies matching a predicate
+    fn retain_cookies(&self, state: &mut StorageBridgeState, mut keep: impl FnMut(&Cookie) -> bool) {
+        match &self.cookie_store {
+            Some(store) => store.retain(|c| keep(&Cookie::from(c))),
+            None => state.cookies.retain(keep),
+        }
+    }
+
+    /// Subscribe to quota threshold events
+    pub fn subscribe_quota_changes(&self) -> broadcast::Receiver<QuotaChangedEvent> {
+        self.quota_sender.subscribe()
+    }
+
+    /// Set the storage quota for an origin
+    pub async fn set_quota(&self, origin: &str, bytes: u64) {
+        let mut state = self.state.write().await;
+        state.quotas.insert(origin.to_string(), bytes);
+        self.check_quota_thresholds(&mut state, origin);
+    }
+
+    /// Set the usage fractions that trigger quota events (e.g. `[0.8, 0.95]`)
+    pub async fn set_quota_thresholds(&self, mut thresholds: Vec<f64>) {
+        thresholds.sort_by(|a, b| a.total_cmp(b));
+        let mut state = self.state.write().await;
+        state.quota_thresholds = thresholds;
+        state.quota_levels.clear();
+    }
+
+    /// Add records to an IndexedDB object store
+    pub async fn add_object_store_entries(
+        &self,
+        origin: &str,
+        database_name: &str,
+        object_store_name: &str,
+        entries: Vec<DataEntry>,
+    ) {
+        let mut state = self.state.write().await;
+        state
+            .object_store_data
+            .entry(origin.to_string())
+            .or_default()
+            .entry(database_name.to_string())
+            .or_default()
+            .entry(object_store_name.to_string())
+            .or_default()
+            .extend(entries);
+    }
+
+    /// Open a cursor over an IndexedDB object store
+    ///
+    /// With `index_name` set, records are ordered by the index key taken from
+    /// the index's key path and records lacking that key are skipped; otherwise
+    /// they are ordered by primary key.
+    pub async fn open_cursor(
+        &self,
+        origin: &str,
+        database_name: &str,
+        object_store_name: &str,
+        index_name: Option<&str>,
+        direction: CursorDirection,
+    ) -> Result<CursorHandle, CdpError> {
+        let mut state = self.state.write().await;
+
+        let index_key_path = match index_name {
+            Some(index_name) => Some(
+                state
+                    .indexed_db
+                    .get(origin)
+                    .and_then(|dbs| dbs.iter().find(|db| db.name == database_name))
+                    .and_then(|db| {
+                        db.object_stores
+                            .iter()
+                            .find(|s| s.name == object_store_name)
+                    })
+                    .and_then(|store| store.indexes.iter().find(|i| i.name == index_name))
+                    .map(|index| index.key_path.clone())
+                    .ok_or_else(|| CdpError::server_error(-32000, "Index not found"))?,
+            ),
+            None => None,
+        };
+
+        let handle = CursorHandle(state.next_cursor_id);
+        state.next_cursor_id += 1;
+        state.cursors.insert(
+            handle,
+            IndexedDbCursor {
+                origin: origin.to_string(),
+                database_name: database_name.to_string(),
+                object_store_name: object_store_name.to_string(),
+                index_key_path,
+                direction,
+                position: None,
+            },
+        );
+        Ok(handle)
+    }
+
+    /// Move a cursor to the next record and return it
+    ///
+    /// Returns `None` once the cursor is exhausted or if the handle is unknown.
+    pub async fn continue_cursor(&self, handle: CursorHandle) -> Option<DataEntry> {
+        let mut state = self.state.write().await;
+        Self::step_cursor(&mut state, handle)
+    }
+
+    /// Skip up to `count` records, returning how many were actually skipped
+    pub async fn advance(&self, handle: CursorHandle, count: usize) -> usize {
+        let mut state = self.state.write().await;
+        (0..count)
+            .take_while(|_| Self::step_cursor(&mut state, handle).is_some())
+            .count()
+    }
+
+    /// Close a cursor, returning whether it was open
+    pub async fn close_cursor(&self, handle: CursorHandle) -> bool {
+        self.state.write().await.cursors.remove(&handle).is_some()
+    }
+
+    fn step_cursor(state: &mut StorageBridgeState, handle: CursorHandle) -> Option<DataEntry> {
+        let cursor = state.cursors.get(&handle)?;
+        let records = state
+            .object_store_data
+            .get(&cursor.origin)
+            .and_then(|dbs| dbs.get(&cursor.database_name))
+            .and_then(|stores| stores.get(&cursor.object_store_name))?;
+
+        let direction = cursor.direction;
+        let after_position = |key: &Value, primary_key: &Value| {
+            let Some((pos_key, pos_primary_key)) = &cursor.position else {
+                return true;
+            };
+            let order = if direction.is_unique() {
+                compare_keys(key, pos_key)
+            } else {
+                compare_keys(key, pos_key).then_with(|| compare_keys(primary_key, pos_primary_key))
+            };
+            if direction.is_forward() {
+                order.is_gt()
+            } else {
+                order.is_lt()
+            }
+        };
+
+        let mut best: Option<(Value, &DataEntry)> = None;
+        for record in records {
+            let key = match &cursor.index_key_path {
+                Some(key_path) => match extract_key(&record.value, key_path) {
+                    Some(key) => key,
+                    None => continue,
+                },
+                None => record.primary_key.clone(),
+            };
+            if !after_position(&key, &record.primary_key) {
+                continue;
+            }
+            let better = match &best {
+                None => true,
+                Some((best_key, best_record)) => {
+                    let by_key = compare_keys(&key, best_key);
+                    let by_primary_key =
+                        compare_keys(&record.primary_key, &best_record.primary_key);
+                    match direction {
+                        CursorDirection::Next | CursorDirection::NextUnique => {
+                            by_key.then(by_primary_key).is_lt()
+                        }
+                        CursorDirection::Prev => by_key.then(by_primary_key).is_gt(),
+                        // Walk keys backwards but still yield the first record per key
+                        CursorDirection::PrevUnique => {
+                            by_key.then(by_primary_key.reverse()).is_gt()
+                        }
+                    }
+                }
+            };
+            if better {
+                best = Some((key, record));
+            }
+        }
+
+        let (key, record) = best?;
+        let entry = DataEntry {
+            key: key.clone(),
+            primary_key: record.primary_key.clone(),
+            value: record.value.clone(),
+        };
+        if let Some(cursor) = state.cursors.get_mut(&handle) {
+            cursor.position = Some((key, entry.primary_key.clone()));
+        }
+        Some(entry)
+    }
+
+    /// Export all storage for an origin as a single JSON document
+    ///
+    /// Includes matching cookies, local/session storage, and IndexedDB schema
+    /// and records. Restore with `import_origin_storage`.
+    pub async fn export_origin_storage(&self, origin: &str) -> Value {
+        let state = self.state.read().await;
+
+        let databases = state.indexed_db.get(origin).cloned().unwrap_or_default();
+        let records = state.object_store_data.get(origin);
+        let indexed_db = databases
+            .into_iter()
+            .map(|database| {
+                let data = records
+                    .and_then(|dbs| dbs.get(&database.name))
+                    .cloned()
+                    .unwrap_or_default();
+                IndexedDbExport { database, data }
+            })
+            .collect();
+
+        let host = origin_host(origin).to_string();
+        let cookies = self
+            .all_cookies(&state)
+            .into_iter()
+            .filter(|c| cookie_domain_matches(&c.domain, &host))
+            .collect();
+
+        let snapshot = OriginStorageSnapshot {
+            origin: origin.to_string(),
+            cookies,
+            local_storage: state.local_storage.get(origin).cloned().unwrap_or_default(),
+            session_storage: state
+                .session_storage
+                .get(origin)
+                .cloned()
+                .unwrap_or_default(),
+            indexed_db,
+        };
+
+        serde_json::to_value(snapshot).unwrap_or_default()
+    }
+
+    /// Restore storage for an origin from an `export_origin_storage` document
+    ///
+    /// Existing cookies, storage items and databases for the origin are replaced.
+    pub async fn import_origin_storage(&self, origin: &str, data: Value) -> Result<(), CdpError> {
+        let snapshot: OriginStorageSnapshot = serde_json::from_value(data)
+            .map_err(|e| CdpError::invalid_params(format!("Invalid storage snapshot: {}", e)))?;
+
+        let mut state = self.state.write().await;
+        let host = origin_host(origin).to_string();
+
+        self.retain_cookies(&mut state, |c| !cookie_domain_matches(&c.domain, &host));
+        for cookie in snapshot.cookies {
+            self.put_cookie(&mut state, cookie);
+        }
+
+        state
+            .local_storage
+            .insert(origin.to_string(), snapshot.local_storage);
+        state
+            .session_storage
+            .insert(origin.to_string(), snapshot.session_storage);
+
+        let mut databases = Vec::new();
+        let mut records = HashMap::new();
+        for export in snapshot.indexed_db {
+            records.insert(export.database.name.clone(), export.data);
+            databases.push(export.database);
+        }
+        state.indexed_db.insert(origin.to_string(), databases);
+        state.object_store_data.insert(origin.to_string(), records);
+
+        self.check_quota_thresholds(&mut state, origin);
+        debug!("Imported storage for origin {}", origin);
+
+        Ok(())
+    }
+
+    /// Recompute usage for an origin and emit an event on upward threshold crossings
+    ///
+    /// Falling back below a threshold re-arms it, so a later crossing emits again.
+    fn check_quota_thresholds(&self, state: &mut StorageBridgeState, origin: &str) {
+        let (local, session) = state.usage_for(origin);
+        let usage = local + session;
+        let quota = state.quota_for(origin);
+        let fraction = if quota == 0 {
+            f64::INFINITY
+        } else {
+            usage as f64 / quota as f64
+        };
+
+        let level = state
+            .quota_thresholds
+            .iter()
+            .take_while(|t| fraction >= **t)
+            .count();
+        let previous = state
+            .quota_levels
+            .insert(origin.to_string(), level)
+            .unwrap_or(0);
+
+        if level > previous {
+            let threshold = state.quota_thresholds[level - 1];
+            debug!(
+                "Storage usage for {} crossed {:.0}% of quota ({} / {})",
+                origin,
+                threshold * 100.0,
+                usage,
+                quota
+            );
+            let _ = self.quota_sender.send(QuotaChangedEvent {
+                origin: origin.to_string(),
+                usage,
+                quota,
+                threshold,
+            });
         }
     }
 
@@ -412,10 +1035,12 @@ impl StorageBridge {
         };
 
         storage
-            .entry(params.security_origin)
+            .entry(params.security_origin.clone())
             .or_default()
             .insert(params.key, params.value);
 
+        self.check_quota_thresholds(&mut state, &params.security_origin);
+
         Ok(serde_json::json!({}))
     }
 
@@ -447,6 +1072,8 @@ impl StorageBridge {
             origin_storage.remove(&params.key);
         }
 
+        self.check_quota_thresholds(&mut state, &params.security_origin);
+
         Ok(serde_json::json!({}))
     }
 
@@ -477,6 +1104,8 @@ impl StorageBridge {
             origin_storage.clear();
         }
 
+        self.check_quota_thresholds(&mut state, &params.security_origin);
+
         Ok(serde_json::json!({}))
     }
 
@@ -494,21 +1123,15 @@ impl StorageBridge {
         }
 
         let params: Params = params
-            .map(|p| serde_json::from_value(p).ok())
-            .flatten()
+            .and_then(|p| serde_json::from_value(p).ok())
             .unwrap_or(Params { urls: None });
 
         let state = self.state.read().await;
 
-        let cookies: Vec<&Cookie> = if let Some(urls) = params.urls {
-            state
-                .cookies
-                .iter()
-                .filter(|c| urls.iter().any(|u| u.contains(&c.domain)))
-                .collect()
-        } else {
-            state.cookies.iter().collect()
-        };
+        let mut cookies = self.all_cookies(&state);
+        if let Some(urls) = params.urls {
+            cookies.retain(|c| urls.iter().any(|u| u.contains(&c.domain)));
+        }
 
         Ok(serde_json::json!({
             "cookies": cookies
@@ -572,12 +1195,8 @@ impl StorageBridge {
 
         let mut state = self.state.write().await;
 
-        // Remove existing cookie with same name and domain
-        state
-            .cookies
-            .retain(|c| !(c.name == cookie.name && c.domain == cookie.domain));
-
-        state.cookies.push(cookie);
+        // Replaces an existing cookie with same name and domain
+        self.put_cookie(&mut state, cookie);
 
         Ok(serde_json::json!({
             "success": true
@@ -601,9 +1220,9 @@ impl StorageBridge {
         .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
         let mut state = self.state.write().await;
-        state
-            .cookies
-            .retain(|c| !(c.name == params.name && c.domain == params.domain));
+        self.retain_cookies(&mut state, |c| {
+            !(c.name == params.name && c.domain == params.domain)
+        });
 
         Ok(serde_json::json!({}))
     }
@@ -613,7 +1232,7 @@ impl StorageBridge {
         debug!("StorageBridge.clearCookies called");
 
         let mut state = self.state.write().await;
-        state.cookies.clear();
+        self.retain_cookies(&mut state, |_| false);
 
         Ok(serde_json::json!({}))
     }
@@ -698,6 +1317,9 @@ impl StorageBridge {
         if let Some(dbs) = state.indexed_db.get_mut(&params.security_origin) {
             dbs.retain(|db| db.name != params.database_name);
         }
+        if let Some(dbs) = state.object_store_data.get_mut(&params.security_origin) {
+            dbs.remove(&params.database_name);
+        }
 
         Ok(serde_json::json!({}))
     }
@@ -714,12 +1336,21 @@ impl StorageBridge {
             object_store_name: String,
         }
 
-        let _params: Params = serde_json::from_value(
+        let params: Params = serde_json::from_value(
             params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?,
         )
         .map_err(|e| CdpError::invalid_params(format!("Invalid parameters: {}", e)))?;
 
-        // In a real implementation, this would clear the object store data
+        let mut state = self.state.write().await;
+        if let Some(records) = state
+            .object_store_data
+            .get_mut(&params.security_origin)
+            .and_then(|dbs| dbs.get_mut(&params.database_name))
+            .and_then(|stores| stores.get_mut(&params.object_store_name))
+        {
+            records.clear();
+        }
+
         Ok(serde_json::json!({}))
     }
 
@@ -742,21 +1373,9 @@ impl StorageBridge {
 
         let state = self.state.read().await;
 
-        // Calculate mock usage
-        let local_usage = state
-            .local_storage
-            .get(&params.security_origin)
-            .map(|s| s.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>())
-            .unwrap_or(0);
-
-        let session_usage = state
-            .session_storage
-            .get(&params.security_origin)
-            .map(|s| s.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>())
-            .unwrap_or(0);
-
-        let usage = (local_usage + session_usage) as u64;
-        let quota = 10 * 1024 * 1024; // 10MB mock quota
+        let (local_usage, session_usage) = state.usage_for(&params.security_origin);
+        let usage = local_usage + session_usage;
+        let quota = state.quota_for(&params.security_origin);
 
         Ok(serde_json::json!({
             "usage": usage,
@@ -1011,6 +1630,38 @@ mod tests {
         assert!(get_result["cookies"].as_array().unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_shared_cookie_store() {
+        use console_storage::StorageDomain;
+
+        let store = Arc::new(CookieStore::new());
+        let storage = StorageDomain::with_cookie_store(Arc::clone(&store));
+        let bridge = StorageBridge::with_cookie_store(Arc::clone(&store));
+
+        storage
+            .handle_method(
+                "setCookie",
+                Some(json!({"name": "sid", "value": "abc", "domain": "example.com"})),
+            )
+            .await
+            .unwrap();
+
+        let result = bridge.get_cookies(None).await.unwrap();
+        let cookies = result["cookies"].as_array().unwrap();
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0]["name"], "sid");
+        assert_eq!(cookies[0]["value"], "abc");
+
+        bridge
+            .set_cookie(Some(json!({"name": "theme", "value": "dark", "domain": "example.com"})))
+            .await
+            .unwrap();
+        assert_eq!(store.len(), 2);
+
+        bridge.clear_cookies(None).await.unwrap();
+        assert!(store.is_empty());
+    }
+
     // ==================== IndexedDB Tests ====================
 
     #[tokio::test]
@@ -1073,6 +1724,328 @@ mod tests {
         assert!(value["usageBreakdown"].is_array());
     }
 
+    #[tokio::test]
+    async fn test_get_usage_and_quota_reflects_set_quota() {
+        let bridge = StorageBridge::empty();
+        bridge.set_quota("https://example.com", 4096).await;
+
+        let params = json!({ "securityOrigin": "https://example.com" });
+        let value = bridge.get_usage_and_quota(Some(params)).await.unwrap();
+        assert_eq!(value["quota"], 4096);
+    }
+
+    // ==================== Quota Event Tests ====================
+
+    async fn set_item(bridge: &StorageBridge, key: &str, value: &str) {
+        let params = json!({
+            "securityOrigin": "https://example.com",
+            "storageArea": "localStorage",
+            "key": key,
+            "value": value
+        });
+        bridge.set_storage_item(Some(params)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_quota_event_emitted_once_on_crossing() {
+        let bridge = StorageBridge::empty();
+        bridge.set_quota("https://example.com", 100).await;
+        let mut events = bridge.subscribe_quota_changes();
+
+        // 1 + 49 = 50 bytes: below 80%
+        set_item(&bridge, "a", &"x".repeat(49)).await;
+        assert!(events.try_recv().is_err());
+
+        // +1 + 34 = 85 bytes: crosses 80%
+        set_item(&bridge, "b", &"x".repeat(34)).await;
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.origin, "https://example.com");
+        assert_eq!(event.usage, 85);
+        assert_eq!(event.quota, 100);
+        assert_eq!(event.threshold, DEFAULT_QUOTA_WARNING_THRESHOLD);
+
+        // 90 bytes: still above 80%, below any higher threshold
+        set_item(&bridge, "c", "xxxx").await;
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_quota_event_rearms_after_dropping_below() {
+        let bridge = StorageBridge::empty();
+        bridge.set_quota("https://example.com", 100).await;
+        let mut events = bridge.subscribe_quota_changes();
+
+        set_item(&bridge, "big", &"x".repeat(90)).await;
+        assert!(events.try_recv().is_ok());
+
+        let remove = json!({
+            "securityOrigin": "https://example.com",
+            "storageArea": "localStorage",
+            "key": "big"
+        });
+        bridge.remove_storage_item(Some(remove)).await.unwrap();
+        assert!(events.try_recv().is_err());
+
+        set_item(&bridge, "big", &"x".repeat(90)).await;
+        assert!(events.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_quota_event_for_each_higher_threshold() {
+        let bridge = StorageBridge::empty();
+        bridge.set_quota_thresholds(vec![0.95, 0.8]).await;
+        bridge.set_quota("https://example.com", 100).await;
+        let mut events = bridge.subscribe_quota_changes();
+
+        set_item(&bridge, "a", &"x".repeat(84)).await;
+        assert_eq!(events.try_recv().unwrap().threshold, 0.8);
+
+        set_item(&bridge, "b", &"x".repeat(10)).await;
+        assert_eq!(events.try_recv().unwrap().threshold, 0.95);
+    }
+
+    // ==================== Export/Import Tests ====================
+
+    #[tokio::test]
+    async fn test_export_clear_import_round_trip() {
+        let bridge = StorageBridge::new();
+        let origin = "https://example.com";
+        bridge
+            .add_object_store_entries(
+                origin,
+                "myApp",
+                "users",
+                vec![DataEntry {
+                    key: json!(1),
+                    primary_key: json!(1),
+                    value: json!({ "id": 1, "email": "a@example.com" }),
+                }],
+            )
+            .await;
+
+        let original = bridge.export_origin_storage(origin).await;
+        assert_eq!(original["cookies"].as_array().unwrap().len(), 1);
+        assert_eq!(original["localStorage"]["theme"], "dark");
+        assert_eq!(original["sessionStorage"]["session_token"], "abc123");
+        assert_eq!(original["indexedDB"][0]["database"]["name"], "myApp");
+        assert_eq!(
+            original["indexedDB"][0]["data"]["users"][0]["value"]["email"],
+            "a@example.com"
+        );
+
+        // Clear everything for the origin
+        for area in ["localStorage", "sessionStorage"] {
+            let params = json!({ "securityOrigin": origin, "storageArea": area });
+            bridge.clear_storage(Some(params)).await.unwrap();
+        }
+        bridge.clear_cookies(None).await.unwrap();
+        let params = json!({ "securityOrigin": origin, "databaseName": "myApp" });
+        bridge.delete_indexed_db_database(Some(params)).await.unwrap();
+
+        let cleared = bridge.export_origin_storage(origin).await;
+        assert_ne!(cleared, original);
+        assert!(cleared["cookies"].as_array().unwrap().is_empty());
+
+        bridge
+            .import_origin_storage(origin, original.clone())
+            .await
+            .unwrap();
+        assert_eq!(bridge.export_origin_storage(origin).await, original);
+    }
+
+    #[tokio::test]
+    async fn test_import_leaves_other_origins_untouched() {
+        let bridge = StorageBridge::new();
+        let snapshot = json!({ "origin": "https://other.com", "localStorage": { "k": "v" } });
+        bridge
+            .import_origin_storage("https://other.com", snapshot)
+            .await
+            .unwrap();
+
+        let state = bridge.get_state().await;
+        assert_eq!(state.cookies.len(), 1);
+        assert_eq!(state.local_storage["https://example.com"].len(), 3);
+        assert_eq!(state.local_storage["https://other.com"]["k"], "v");
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_invalid_snapshot() {
+        let bridge = StorageBridge::empty();
+        let result = bridge
+            .import_origin_storage("https://example.com", json!({ "cookies": "nope" }))
+            .await;
+        assert!(result.is_err());
+    }
+
+    // ==================== IndexedDB Cursor Tests ====================
+
+    /// `myApp.users` with a non-unique `by_team` index and records added out of order
+    async fn cursor_bridge() -> StorageBridge {
+        let bridge = StorageBridge::new();
+        bridge
+            .state
+            .write()
+            .await
+            .indexed_db
+            .get_mut("https://example.com")
+            .unwrap()[0]
+            .object_stores[0]
+            .indexes
+            .push(IndexInfo {
+                name: "by_team".to_string(),
+                key_path: KeyPath {
+                    key_path_type: KeyPathType::String,
+                    string: Some("team".to_string()),
+                    array: None,
+                },
+                unique: false,
+                multi_entry: false,
+            });
+        let user = |id: u64, team: &str| DataEntry {
+            key: json!(id),
+            primary_key: json!(id),
+            value: json!({ "id": id, "team": team }),
+        };
+        bridge
+            .add_object_store_entries(
+                "https://example.com",
+                "myApp",
+                "users",
+                vec![
+                    user(3, "blue"),
+                    user(1, "red"),
+                    user(4, "blue"),
+                    user(2, "red"),
+                ],
+            )
+            .await;
+        bridge
+    }
+
+    async fn drain(bridge: &StorageBridge, handle: CursorHandle) -> Vec<Value> {
+        let mut keys = Vec::new();
+        while let Some(entry) = bridge.continue_cursor(handle).await {
+            keys.push(json!([entry.key, entry.primary_key]));
+        }
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_cursor_next_yields_key_order() {
+        let bridge = cursor_bridge().await;
+        let handle = bridge
+            .open_cursor(
+                "https://example.com",
+                "myApp",
+                "users",
+                None,
+                CursorDirection::Next,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            drain(&bridge, handle).await,
+            vec![json!([1, 1]), json!([2, 2]), json!([3, 3]), json!([4, 4])]
+        );
+        assert!(bridge.continue_cursor(handle).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cursor_prev_reverses_order() {
+        let bridge = cursor_bridge().await;
+        let handle = bridge
+            .open_cursor(
+                "https://example.com",
+                "myApp",
+                "users",
+                Some("by_team"),
+                CursorDirection::Prev,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            drain(&bridge, handle).await,
+            vec![
+                json!(["red", 2]),
+                json!(["red", 1]),
+                json!(["blue", 4]),
+                json!(["blue", 3])
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cursor_nextunique_skips_duplicate_index_keys() {
+        let bridge = cursor_bridge().await;
+        let handle = bridge
+            .open_cursor(
+                "https://example.com",
+                "myApp",
+                "users",
+                Some("by_team"),
+                CursorDirection::NextUnique,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            drain(&bridge, handle).await,
+            vec![json!(["blue", 3]), json!(["red", 1])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cursor_advance_and_close() {
+        let bridge = cursor_bridge().await;
+        let handle = bridge
+            .open_cursor(
+                "https://example.com",
+                "myApp",
+                "users",
+                None,
+                CursorDirection::Next,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(bridge.advance(handle, 2).await, 2);
+        assert_eq!(
+            bridge.continue_cursor(handle).await.unwrap().primary_key,
+            json!(3)
+        );
+        assert_eq!(bridge.advance(handle, 5).await, 1);
+
+        assert!(bridge.close_cursor(handle).await);
+        assert!(!bridge.close_cursor(handle).await);
+        assert!(bridge.continue_cursor(handle).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cursor_unknown_index() {
+        let bridge = cursor_bridge().await;
+        let result = bridge
+            .open_cursor(
+                "https://example.com",
+                "myApp",
+                "users",
+                Some("nope"),
+                CursorDirection::Next,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_origin_host_and_cookie_domain_matching() {
+        assert_eq!(origin_host("https://example.com"), "example.com");
+        assert_eq!(origin_host("http://localhost:8080/path"), "localhost");
+        assert!(cookie_domain_matches(".example.com", "www.example.com"));
+        assert!(cookie_domain_matches("example.com", "example.com"));
+        assert!(!cookie_domain_matches("example.com", "badexample.com"));
+    }
+
     #[tokio::test]
     async fn test_unknown_method() {
         let bridge = StorageBridge::new();
@@ -144,9 +144,12 @@ pub struct MutationRecord {
     /// Attribute name (for attribute mutations)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub attribute_name: Option<String>,
-    /// Attribute old value
+    /// Attribute old value (None when the attribute was newly added)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub old_value: Option<String>,
+    /// Attribute new value (None when the attribute was removed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<String>,
 }
 
 /// Layer information for render tree
@@ -249,11 +252,14 @@ mod tests {
             previous_sibling_id: None,
             attribute_name: Some("class".to_string()),
             old_value: Some("old-class".to_string()),
+            new_value: Some("new-class".to_string()),
         };
 
         let json = serde_json::to_string(&record).expect("Failed to serialize");
         assert!(json.contains("attributeModified"));
         assert!(json.contains("class"));
+        assert!(json.contains("\"oldValue\":\"old-class\""));
+        assert!(json.contains("\"newValue\":\"new-class\""));
     }
 
     #[test]
@@ -8,15 +8,21 @@
 //! - **Network Inspector Bridge**: Full request/response inspection with body capture
 //! - **Cache Inspection**: Cache.requestCacheNames, requestEntries, deleteCache, deleteEntry
 //! - **WebSocket Frame Inspection**: Track WebSocket connections and frame traffic
+//! - **HAR Export**: Serialize tracked requests as a HAR 1.2 log
+//! - **State Persistence**: Save a recorded session and reload it into a fresh domain
 
 use async_trait::async_trait;
+use base64::Engine;
 use cdp_types::CdpError;
+use console_storage::CookieStore;
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use protocol_handler::DomainHandler;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::collections::{HashMap, VecDeque};
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
@@ -27,9 +33,21 @@ pub const DEFAULT_MAX_RESPONSE_BODY_SIZE: usize = 10 * 1024 * 1024;
 /// Default maximum request body size (5MB)
 pub const DEFAULT_MAX_REQUEST_BODY_SIZE: usize = 5 * 1024 * 1024;
 
+/// Default maximum number of frames retained per WebSocket connection
+pub const DEFAULT_MAX_WEBSOCKET_FRAMES: usize = 1000;
+
 /// HTTP Headers map type
 pub type HttpHeaders = std::collections::HashMap<String, String>;
 
+/// Event callback type for network events
+pub type EventCallback = Arc<dyn Fn(&str, Value) + Send + Sync>;
+
+/// HAR format version produced by `NetworkDomain::export_har`
+pub const HAR_VERSION: &str = "1.2";
+
+/// Format version written by `NetworkDomain::serialize_state`
+pub const STATE_VERSION: u32 = 1;
+
 /// Information about a tracked network request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RequestInfo {
@@ -55,8 +73,11 @@ pub struct RequestInfo {
     pub response_body: Option<String>,
     /// Whether the response body is base64 encoded
     pub is_base64: bool,
-    /// Response body size in bytes
+    /// Response body size in bytes (decoded size for compressed responses)
     pub response_size: Option<usize>,
+    /// Encoded body size in bytes for compressed responses
+    #[serde(default)]
+    pub compressed_size: Option<usize>,
     /// Timestamp when request started (ms since epoch)
     pub timestamp: f64,
     /// Time taken for response (ms)
@@ -65,6 +86,21 @@ pub struct RequestInfo {
     pub resource_type: Option<String>,
     /// Whether the response was from cache
     pub from_cache: bool,
+    /// Frame that issued the request
+    #[serde(default)]
+    pub frame_id: Option<String>,
+    /// Extra request info (associated cookies)
+    #[serde(default)]
+    pub extra_info: Option<RequestExtraInfo>,
+    /// What initiated the request
+    #[serde(default)]
+    pub initiator: Option<Initiator>,
+    /// Detailed timing breakdown (DNS, connect, TTFB)
+    #[serde(default)]
+    pub timing: Option<TimingBreakdown>,
+    /// Loading priority (inferred from the resource type unless overridden)
+    #[serde(default)]
+    pub priority: ResourcePriority,
 }
 
 impl RequestInfo {
@@ -87,10 +123,16 @@ impl RequestInfo {
             response_body: None,
             is_base64: false,
             response_size: None,
+            compressed_size: None,
             timestamp,
             response_time: None,
             resource_type: None,
             from_cache: false,
+            frame_id: None,
+            extra_info: None,
+            initiator: None,
+            timing: None,
+            priority: ResourcePriority::default(),
         }
     }
 }
@@ -267,9 +309,12 @@ pub struct WebSocketConnection {
     pub response_headers: HttpHeaders,
     /// Connection timestamp (ms since epoch)
     pub timestamp: f64,
-    /// Frames sent/received on this connection
+    /// Frames sent/received on this connection (oldest first)
     #[serde(default)]
-    pub frames: Vec<WebSocketFrame>,
+    pub frames: VecDeque<WebSocketFrame>,
+    /// Number of frames evicted because the frame limit was reached
+    #[serde(default)]
+    pub dropped_frame_count: u64,
 }
 
 impl WebSocketConnection {
@@ -287,13 +332,23 @@ impl WebSocketConnection {
             request_headers: HttpHeaders::new(),
             response_headers: HttpHeaders::new(),
             timestamp,
-            frames: Vec::new(),
+            frames: VecDeque::new(),
+            dropped_frame_count: 0,
         }
     }
 
     /// Add a frame to this connection
     pub fn add_frame(&mut self, frame: WebSocketFrame) {
-        self.frames.push(frame);
+        self.frames.push_back(frame);
+    }
+
+    /// Add a frame, evicting the oldest frames beyond `max_frames`
+    pub fn add_frame_capped(&mut self, frame: WebSocketFrame, max_frames: usize) {
+        self.frames.push_back(frame);
+        while self.frames.len() > max_frames {
+            self.frames.pop_front();
+            self.dropped_frame_count += 1;
+        }
     }
 }
 
@@ -311,6 +366,561 @@ pub struct InterceptionPattern {
     pub interception_stage: Option<String>,
 }
 
+impl InterceptionPattern {
+    /// Whether a request matches this pattern
+    ///
+    /// A missing `url_pattern` matches every URL. When `resource_type` is set,
+    /// the request's resource type must match it (ignoring case).
+    pub fn matches(&self, url: &str, resource_type: Option<&str>) -> bool {
+        if let Some(expected) = &self.resource_type {
+            match resource_type {
+                Some(actual) if actual.eq_ignore_ascii_case(expected) => {}
+                _ => return false,
+            }
+        }
+
+        self.url_pattern
+            .as_deref()
+            .map(|pattern| wildcard_match(pattern, url))
+            .unwrap_or(true)
+    }
+}
+
+/// Token of a compiled URL wildcard pattern
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WildcardToken {
+    /// `*`: any sequence of characters
+    AnySequence,
+    /// `?`: any single character
+    AnyChar,
+    /// Literal character (including `\`-escaped wildcards)
+    Literal(char),
+}
+
+/// Compile a URL pattern into wildcard tokens
+fn compile_wildcard(pattern: &str) -> Vec<WildcardToken> {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '*' => WildcardToken::AnySequence,
+            '?' => WildcardToken::AnyChar,
+            '\\' => WildcardToken::Literal(chars.next().unwrap_or('\\')),
+            c => WildcardToken::Literal(c),
+        });
+    }
+    tokens
+}
+
+/// Match `text` against a URL pattern with `*` and `?` wildcards
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let tokens = compile_wildcard(pattern);
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut t, mut p) = (0, 0);
+    // Position of the last `*` and the text index it is currently matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match tokens.get(p) {
+            Some(WildcardToken::AnySequence) => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(WildcardToken::AnyChar) => {
+                p += 1;
+                t += 1;
+            }
+            Some(WildcardToken::Literal(c)) if *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, t));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    tokens[p..].iter().all(|t| *t == WildcardToken::AnySequence)
+}
+
+// =============================================================================
+// Request Extra Info Types
+// =============================================================================
+
+/// Reason a cookie was not sent with a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CookieBlockedReason {
+    /// Cookie is `Secure` but the request is not
+    SecureOnly,
+    /// Cookie path does not match the request path
+    NotOnPath,
+    /// Cookie domain does not match the request host
+    DomainMismatch,
+    /// `SameSite=Strict` cookie on a cross-site request
+    SameSiteStrict,
+    /// `SameSite=Lax` cookie on a cross-site subresource request
+    SameSiteLax,
+    /// Cookie without `SameSite` treated as `Lax`
+    SameSiteUnspecifiedTreatedAsLax,
+    /// `SameSite=None` cookie without `Secure`
+    SameSiteNoneInsecure,
+    /// Blocked by user preferences
+    UserPreferences,
+    /// Unknown error
+    UnknownError,
+}
+
+/// Cookie as seen by a network request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkCookie {
+    /// Cookie name
+    pub name: String,
+    /// Cookie value
+    pub value: String,
+    /// Cookie domain
+    pub domain: String,
+    /// Cookie path
+    pub path: String,
+    /// Whether the cookie is `Secure`
+    #[serde(default)]
+    pub secure: bool,
+    /// Whether the cookie is `HttpOnly`
+    #[serde(default)]
+    pub http_only: bool,
+}
+
+/// Cookie associated with a request, and why it was blocked (if it was)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssociatedCookie {
+    /// The cookie
+    pub cookie: NetworkCookie,
+    /// Reasons the cookie was blocked; empty if it was sent
+    #[serde(default)]
+    pub blocked_reasons: Vec<CookieBlockedReason>,
+}
+
+impl AssociatedCookie {
+    /// Whether the cookie was actually sent
+    pub fn is_sent(&self) -> bool {
+        self.blocked_reasons.is_empty()
+    }
+}
+
+/// Extra request info (mirrors `Network.requestWillBeSentExtraInfo`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestExtraInfo {
+    /// Cookies considered for the request, sent or blocked
+    pub associated_cookies: Vec<AssociatedCookie>,
+}
+
+// =============================================================================
+// Request Timing Types
+// =============================================================================
+
+/// Per-request timing breakdown (mirrors `Network.ResourceTiming`)
+///
+/// All values are millisecond offsets from the request start. Phases that
+/// are unknown (or did not happen, e.g. a reused connection) are `None` and
+/// serialize as `null`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimingBreakdown {
+    /// DNS lookup start
+    pub dns_start: Option<f64>,
+    /// DNS lookup end
+    pub dns_end: Option<f64>,
+    /// Connection start
+    pub connect_start: Option<f64>,
+    /// Connection end
+    pub connect_end: Option<f64>,
+    /// TLS handshake start
+    pub ssl_start: Option<f64>,
+    /// TLS handshake end
+    pub ssl_end: Option<f64>,
+    /// Request send start
+    pub send_start: Option<f64>,
+    /// Request send end
+    pub send_end: Option<f64>,
+    /// Response headers received (time to first byte)
+    pub receive_headers_end: Option<f64>,
+}
+
+/// Loading priority of a request (mirrors `Network.ResourcePriority`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResourcePriority {
+    /// Lowest priority (prefetches, pings)
+    VeryLow,
+    /// Low priority (images, media)
+    Low,
+    /// Medium priority (everything without a more specific default)
+    #[default]
+    Medium,
+    /// High priority (scripts, fonts, XHR/fetch)
+    High,
+    /// Highest priority (documents, stylesheets)
+    VeryHigh,
+}
+
+impl ResourcePriority {
+    /// Default priority for a resource type (case-insensitive)
+    pub fn for_resource_type(resource_type: &str) -> Self {
+        match resource_type.to_ascii_lowercase().as_str() {
+            "document" | "stylesheet" => Self::VeryHigh,
+            "script" | "font" | "xhr" | "fetch" => Self::High,
+            "image" | "media" => Self::Low,
+            "prefetch" | "ping" => Self::VeryLow,
+            _ => Self::Medium,
+        }
+    }
+}
+
+// =============================================================================
+// Request Filter Types
+// =============================================================================
+
+/// Criteria for narrowing the tracked request list
+///
+/// Every field is optional; unset fields match all requests. Status bounds
+/// are inclusive and exclude requests that have no response yet.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestFilter {
+    /// Resource type to match (case-insensitive)
+    pub resource_type: Option<String>,
+    /// Substring that the URL must contain
+    pub url_contains: Option<String>,
+    /// Minimum response status code
+    pub status_min: Option<u16>,
+    /// Maximum response status code
+    pub status_max: Option<u16>,
+    /// Whether the response must (or must not) come from cache
+    pub from_cache: Option<bool>,
+}
+
+impl RequestFilter {
+    /// Check whether a request satisfies every set criterion
+    pub fn matches(&self, request: &RequestInfo) -> bool {
+        if let Some(expected) = &self.resource_type {
+            match &request.resource_type {
+                Some(actual) if actual.eq_ignore_ascii_case(expected) => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(needle) = &self.url_contains {
+            if !request.url.contains(needle.as_str()) {
+                return false;
+            }
+        }
+
+        if self.status_min.is_some() || self.status_max.is_some() {
+            let status = match request.status_code {
+                Some(status) => status,
+                None => return false,
+            };
+            if self.status_min.is_some_and(|min| status < min)
+                || self.status_max.is_some_and(|max| status > max)
+            {
+                return false;
+            }
+        }
+
+        if let Some(from_cache) = self.from_cache {
+            if request.from_cache != from_cache {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+// =============================================================================
+// Initiator Types
+// =============================================================================
+
+/// Grouping key used by `requests_by_initiator` for non-script initiators
+pub const DOCUMENT_INITIATOR_KEY: &str = "(document)";
+
+/// Kind of initiator for a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InitiatorType {
+    /// Initiated by the HTML parser
+    Parser,
+    /// Initiated by a script
+    Script,
+    /// Initiated by a preload
+    Preload,
+}
+
+/// Initiator of a request (mirrors `Network.Initiator`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Initiator {
+    /// Initiator type
+    #[serde(rename = "type")]
+    pub initiator_type: InitiatorType,
+    /// Initiating script URL (script initiators)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Initiating line number, 0-based (script initiators)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_number: Option<u32>,
+}
+
+impl Initiator {
+    /// Parser initiator
+    pub fn parser() -> Self {
+        Self {
+            initiator_type: InitiatorType::Parser,
+            url: None,
+            line_number: None,
+        }
+    }
+
+    /// Script initiator at the given location
+    pub fn script(url: impl Into<String>, line_number: u32) -> Self {
+        Self {
+            initiator_type: InitiatorType::Script,
+            url: Some(url.into()),
+            line_number: Some(line_number),
+        }
+    }
+
+    /// Key used to group requests by initiator
+    ///
+    /// Script initiators group by script URL; everything else groups under
+    /// `DOCUMENT_INITIATOR_KEY`.
+    pub fn group_key(&self) -> &str {
+        match (&self.initiator_type, &self.url) {
+            (InitiatorType::Script, Some(url)) => url,
+            _ => DOCUMENT_INITIATOR_KEY,
+        }
+    }
+}
+
+// =============================================================================
+// Security Isolation Types
+// =============================================================================
+
+/// Value of a `Cross-Origin-Embedder-Policy` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CrossOriginEmbedderPolicyValue {
+    /// No policy (header absent or unrecognized)
+    #[default]
+    None,
+    /// `credentialless`
+    Credentialless,
+    /// `require-corp`
+    RequireCorp,
+}
+
+impl CrossOriginEmbedderPolicyValue {
+    /// Parse a header value (without parameters)
+    pub fn from_header(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "require-corp" => Self::RequireCorp,
+            "credentialless" => Self::Credentialless,
+            _ => Self::None,
+        }
+    }
+}
+
+/// Value of a `Cross-Origin-Opener-Policy` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CrossOriginOpenerPolicyValue {
+    /// `same-origin`
+    SameOrigin,
+    /// `same-origin-allow-popups`
+    SameOriginAllowPopups,
+    /// `restrict-properties`
+    RestrictProperties,
+    /// `unsafe-none` (also used when the header is absent)
+    #[default]
+    UnsafeNone,
+}
+
+impl CrossOriginOpenerPolicyValue {
+    /// Parse a header value (without parameters)
+    pub fn from_header(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "same-origin" => Self::SameOrigin,
+            "same-origin-allow-popups" => Self::SameOriginAllowPopups,
+            "restrict-properties" => Self::RestrictProperties,
+            _ => Self::UnsafeNone,
+        }
+    }
+}
+
+/// Value of a `Cross-Origin-Resource-Policy` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrossOriginResourcePolicyValue {
+    /// `same-site`
+    SameSite,
+    /// `same-origin`
+    SameOrigin,
+    /// `cross-origin`
+    CrossOrigin,
+}
+
+impl CrossOriginResourcePolicyValue {
+    /// Parse a header value, returning None for unrecognized values
+    pub fn from_header(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "same-site" => Some(Self::SameSite),
+            "same-origin" => Some(Self::SameOrigin),
+            "cross-origin" => Some(Self::CrossOrigin),
+            _ => None,
+        }
+    }
+}
+
+/// COEP enforcement and report-only state
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrossOriginEmbedderPolicyStatus {
+    /// Enforced policy
+    pub value: CrossOriginEmbedderPolicyValue,
+    /// Report-only policy
+    pub report_only_value: CrossOriginEmbedderPolicyValue,
+    /// Reporting endpoint for the enforced policy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporting_endpoint: Option<String>,
+    /// Reporting endpoint for the report-only policy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report_only_reporting_endpoint: Option<String>,
+}
+
+/// COOP enforcement and report-only state
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrossOriginOpenerPolicyStatus {
+    /// Enforced policy
+    pub value: CrossOriginOpenerPolicyValue,
+    /// Report-only policy
+    pub report_only_value: CrossOriginOpenerPolicyValue,
+    /// Reporting endpoint for the enforced policy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporting_endpoint: Option<String>,
+    /// Reporting endpoint for the report-only policy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report_only_reporting_endpoint: Option<String>,
+}
+
+/// Security isolation status of a frame (Network.getSecurityIsolationStatus)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityIsolationStatus {
+    /// Cross-Origin-Opener-Policy state
+    pub coop: CrossOriginOpenerPolicyStatus,
+    /// Cross-Origin-Embedder-Policy state
+    pub coep: CrossOriginEmbedderPolicyStatus,
+    /// Cross-Origin-Resource-Policy of the document response, if set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corp: Option<CrossOriginResourcePolicyValue>,
+}
+
+impl SecurityIsolationStatus {
+    /// Build the status from a document's response headers
+    pub fn from_headers(headers: &HttpHeaders) -> Self {
+        let mut status = Self::default();
+
+        if let Some(raw) = header_value(headers, "Cross-Origin-Embedder-Policy") {
+            let (value, endpoint) = parse_policy_header(raw);
+            status.coep.value = CrossOriginEmbedderPolicyValue::from_header(value);
+            status.coep.reporting_endpoint = endpoint;
+        }
+        if let Some(raw) = header_value(headers, "Cross-Origin-Embedder-Policy-Report-Only") {
+            let (value, endpoint) = parse_policy_header(raw);
+            status.coep.report_only_value = CrossOriginEmbedderPolicyValue::from_header(value);
+            status.coep.report_only_reporting_endpoint = endpoint;
+        }
+        if let Some(raw) = header_value(headers, "Cross-Origin-Opener-Policy") {
+            let (value, endpoint) = parse_policy_header(raw);
+            status.coop.value = CrossOriginOpenerPolicyValue::from_header(value);
+            status.coop.reporting_endpoint = endpoint;
+        }
+        if let Some(raw) = header_value(headers, "Cross-Origin-Opener-Policy-Report-Only") {
+            let (value, endpoint) = parse_policy_header(raw);
+            status.coop.report_only_value = CrossOriginOpenerPolicyValue::from_header(value);
+            status.coop.report_only_reporting_endpoint = endpoint;
+        }
+        if let Some(raw) = header_value(headers, "Cross-Origin-Resource-Policy") {
+            status.corp = CrossOriginResourcePolicyValue::from_header(raw);
+        }
+
+        status
+    }
+}
+
+/// Look up a header by name, ignoring ASCII case
+fn header_value<'a>(headers: &'a HttpHeaders, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+/// MIME type from a Content-Type header, without parameters and lowercased
+///
+/// `text/html; charset=utf-8` becomes `text/html`.
+fn mime_type(headers: &HttpHeaders) -> String {
+    header_value(headers, "content-type")
+        .and_then(|ct| ct.split(';').next())
+        .map(|ct| ct.trim().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+/// Split a structured policy header into its value and optional `report-to` endpoint
+fn parse_policy_header(raw: &str) -> (&str, Option<String>) {
+    let mut parts = raw.split(';');
+    let value = parts.next().unwrap_or("").trim();
+    let endpoint = parts.find_map(|param| {
+        let (key, val) = param.split_once('=')?;
+        if key.trim().eq_ignore_ascii_case("report-to") {
+            Some(val.trim().trim_matches('"').to_string())
+        } else {
+            None
+        }
+    });
+    (value, endpoint)
+}
+
+// =============================================================================
+// State Persistence Types
+// =============================================================================
+
+/// Snapshot written by `NetworkDomain::serialize_state`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedState {
+    version: u32,
+    #[serde(default)]
+    requests: Vec<RequestInfo>,
+    #[serde(default)]
+    websockets: Vec<WebSocketConnection>,
+    #[serde(default)]
+    caches: Vec<SavedCache>,
+}
+
+/// A cache and its entries within a saved state
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedCache {
+    info: CacheInfo,
+    #[serde(default)]
+    entries: Vec<CacheEntry>,
+}
+
 /// Network domain implementation for Chrome DevTools Protocol
 ///
 /// Provides network monitoring, request/response inspection, interception capabilities,
@@ -322,7 +932,6 @@ pub struct InterceptionPattern {
 /// - **Response Body Capture**: Capture response bodies with size limits
 /// - **Cache Inspection**: Inspect browser cache contents
 /// - **WebSocket Frame Inspection**: Track WebSocket connections and frames
-#[derive(Debug)]
 pub struct NetworkDomain {
     /// Map of tracked requests (RequestId → RequestInfo)
     request_map: Arc<DashMap<String, RequestInfo>>,
@@ -340,6 +949,30 @@ pub struct NetworkDomain {
     cache_info: Arc<DashMap<String, CacheInfo>>,
     /// WebSocket connections (RequestId → WebSocketConnection)
     websocket_connections: Arc<DashMap<String, WebSocketConnection>>,
+    /// Maximum frames retained per WebSocket connection
+    max_websocket_frames: Arc<AtomicUsize>,
+    /// Browser cookie jar (may be shared with the Storage domain)
+    cookie_store: Arc<CookieStore>,
+    /// Event callback for sending events to client
+    event_callback: Arc<RwLock<Option<EventCallback>>>,
+}
+
+impl std::fmt::Debug for NetworkDomain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetworkDomain")
+            .field("request_map", &self.request_map)
+            .field("interception_enabled", &self.interception_enabled)
+            .field("interception_patterns", &self.interception_patterns)
+            .field("max_response_body_size", &self.max_response_body_size)
+            .field("max_request_body_size", &self.max_request_body_size)
+            .field("cache_storage", &self.cache_storage)
+            .field("cache_info", &self.cache_info)
+            .field("websocket_connections", &self.websocket_connections)
+            .field("max_websocket_frames", &self.max_websocket_frames)
+            .field("cookie_store", &self.cookie_store)
+            .field("event_callback", &"<callback>")
+            .finish()
+    }
 }
 
 impl NetworkDomain {
@@ -363,6 +996,9 @@ impl NetworkDomain {
             cache_storage: Arc::new(DashMap::new()),
             cache_info: Arc::new(DashMap::new()),
             websocket_connections: Arc::new(DashMap::new()),
+            max_websocket_frames: Arc::new(AtomicUsize::new(DEFAULT_MAX_WEBSOCKET_FRAMES)),
+            cookie_store: Arc::new(CookieStore::new()),
+            event_callback: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -381,6 +1017,66 @@ impl NetworkDomain {
             cache_storage: Arc::new(DashMap::new()),
             cache_info: Arc::new(DashMap::new()),
             websocket_connections: Arc::new(DashMap::new()),
+            max_websocket_frames: Arc::new(AtomicUsize::new(DEFAULT_MAX_WEBSOCKET_FRAMES)),
+            cookie_store: Arc::new(CookieStore::new()),
+            event_callback: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Create a NetworkDomain that keeps at most `limit` frames per WebSocket
+    ///
+    /// Older frames are evicted first and counted in `dropped_frame_count`.
+    pub fn with_websocket_frame_limit(limit: usize) -> Self {
+        let domain = Self::new();
+        domain.max_websocket_frames.store(limit, Ordering::Relaxed);
+        domain
+    }
+
+    /// Create a NetworkDomain backed by a shared cookie store
+    ///
+    /// Cookies set through other domains sharing the store are visible to
+    /// `getAllCookies`, and `clearBrowserCookies` clears them for everyone.
+    pub fn with_cookie_store(cookie_store: Arc<CookieStore>) -> Self {
+        let mut domain = Self::new();
+        domain.cookie_store = cookie_store;
+        domain
+    }
+
+    /// Get the cookie store backing this domain
+    pub fn cookie_store(&self) -> Arc<CookieStore> {
+        Arc::clone(&self.cookie_store)
+    }
+
+    /// Set event callback for sending events to client
+    ///
+    /// `track_request_full` emits `Network.requestWillBeSent` and
+    /// `store_response_full` emits `Network.responseReceived`, both after the
+    /// request map has been updated.
+    pub fn set_event_callback(&self, callback: EventCallback) {
+        *self.event_callback.write() = Some(callback);
+    }
+
+    /// Clear event callback
+    pub fn clear_event_callback(&self) {
+        *self.event_callback.write() = None;
+    }
+
+    /// Send an event to the client
+    fn send_event(&self, event_name: &str, params: Value) {
+        if let Some(ref callback) = *self.event_callback.read() {
+            callback(event_name, params);
+        }
+    }
+
+    /// Emit `Network.responseReceived` for a request with a stored response
+    fn emit_response_received(&self, request_id: &str) {
+        // Build params first so no map guard is held while the callback runs
+        let params = self
+            .request_map
+            .get(request_id)
+            .map(|request| response_received_params(request.value()));
+        if let Some(params) = params {
+            self.send_event("Network.responseReceived", params);
         }
     }
 
@@ -460,6 +1156,30 @@ impl NetworkDomain {
         }))
     }
 
+    /// Read a stored response body in chunks of about `chunk_size` bytes
+    ///
+    /// Lets callers process large bodies incrementally instead of building
+    /// one `getResponseBody` JSON string. Chunks split on character
+    /// boundaries, so one may run past `chunk_size` to finish a character.
+    /// Returns `None` if the request or its body is unknown.
+    pub fn response_body_chunks(&self, request_id: &str, chunk_size: usize) -> Option<Vec<String>> {
+        let request = self.request_map.get(request_id)?;
+        let mut rest = request.response_body.as_deref()?;
+        let chunk_size = chunk_size.max(1);
+
+        let mut chunks = Vec::with_capacity(rest.len().div_ceil(chunk_size));
+        while !rest.is_empty() {
+            let mut end = chunk_size.min(rest.len());
+            while !rest.is_char_boundary(end) {
+                end += 1;
+            }
+            let (chunk, tail) = rest.split_at(end);
+            chunks.push(chunk.to_string());
+            rest = tail;
+        }
+        Some(chunks)
+    }
+
     /// Enable request interception with specified patterns
     ///
     /// # Arguments
@@ -500,6 +1220,26 @@ impl NetworkDomain {
         Ok(json!({}))
     }
 
+    /// Find the first interception pattern matching a request
+    ///
+    /// # Arguments
+    /// * `url` - Request URL
+    /// * `resource_type` - Request resource type, if known
+    ///
+    /// # Returns
+    /// The first matching pattern, or None if the request is not intercepted
+    pub fn matches_interception(
+        &self,
+        url: &str,
+        resource_type: Option<&str>,
+    ) -> Option<InterceptionPattern> {
+        self.interception_patterns
+            .read()
+            .iter()
+            .find(|pattern| pattern.matches(url, resource_type))
+            .cloned()
+    }
+
     /// Track a network request
     ///
     /// # Arguments
@@ -536,6 +1276,9 @@ impl NetworkDomain {
 
         let mut request_info = RequestInfo::new(request_id.clone(), url, method);
         request_info.request_headers = headers;
+        if let Some(resource_type) = &resource_type {
+            request_info.priority = ResourcePriority::for_resource_type(resource_type);
+        }
         request_info.resource_type = resource_type;
 
         // Capture request body if within size limit
@@ -549,12 +1292,15 @@ impl NetworkDomain {
                     b.len(),
                     max_size
                 );
-                request_info.request_body = Some(b[..max_size].to_string());
+                request_info.request_body =
+                    Some(truncate_at_char_boundary(&b, max_size).to_string());
                 request_info.request_body_base64 = false;
             }
         }
 
+        let params = request_will_be_sent_params(&request_info);
         self.request_map.insert(request_id, request_info);
+        self.send_event("Network.requestWillBeSent", params);
     }
 
     /// Get request body for a given request
@@ -610,7 +1356,8 @@ impl NetworkDomain {
                     body.len(),
                     max_size
                 );
-                request.response_body = Some(body[..max_size].to_string());
+                request.response_body =
+                    Some(truncate_at_char_boundary(&body, max_size).to_string());
                 request.response_size = Some(body.len());
             }
             request.is_base64 = is_base64;
@@ -640,6 +1387,23 @@ impl NetworkDomain {
         is_base64: bool,
         from_cache: bool,
     ) {
+        if self.apply_response(&request_id, status_code, headers, body, is_base64, from_cache) {
+            self.emit_response_received(&request_id);
+        }
+    }
+
+    /// Record a response on a tracked request without emitting events
+    ///
+    /// Returns `false` if the request is unknown.
+    fn apply_response(
+        &self,
+        request_id: &str,
+        status_code: u16,
+        headers: HttpHeaders,
+        body: String,
+        is_base64: bool,
+        from_cache: bool,
+    ) -> bool {
         debug!(
             "Storing full response for request: {} (status: {})",
             request_id, status_code
@@ -647,7 +1411,7 @@ impl NetworkDomain {
 
         let max_size = self.max_response_body_size.load(Ordering::Relaxed) as usize;
 
-        if let Some(mut request) = self.request_map.get_mut(&request_id) {
+        if let Some(mut request) = self.request_map.get_mut(request_id) {
             request.status_code = Some(status_code);
             request.response_headers = headers;
             request.from_cache = from_cache;
@@ -668,16 +1432,78 @@ impl NetworkDomain {
                     "Response body exceeds size limit ({} > {}), truncating",
                     body_len, max_size
                 );
-                request.response_body = Some(body[..max_size].to_string());
+                request.response_body =
+                    Some(truncate_at_char_boundary(&body, max_size).to_string());
             }
             request.response_size = Some(body_len);
             request.is_base64 = is_base64;
+            true
         } else {
             warn!(
                 "Attempted to store response for unknown request: {}",
                 request_id
             );
+            false
+        }
+    }
+
+    /// Store a response whose body is encoded with `content_encoding`
+    ///
+    /// `gzip`, `deflate` and `br` bodies (and comma-separated combinations)
+    /// are decoded before storing, so `getResponseBody` returns the decoded
+    /// content. `response_size` is the decoded length and `compressed_size`
+    /// the encoded length. Decoded bodies that are not UTF-8 are stored
+    /// base64-encoded. Unknown encodings, or bodies that fail to decode, are
+    /// stored as the raw bytes base64-encoded.
+    ///
+    /// # Arguments
+    /// * `request_id` - Request identifier
+    /// * `status_code` - HTTP status code
+    /// * `headers` - Response headers
+    /// * `body` - Encoded response body
+    /// * `content_encoding` - Value of the `Content-Encoding` header
+    pub fn store_response_compressed(
+        &self,
+        request_id: String,
+        status_code: u16,
+        headers: HttpHeaders,
+        body: Vec<u8>,
+        content_encoding: &str,
+    ) {
+        let compressed_len = body.len();
+        let (decoded, compressed_size) = match decode_content(&body, content_encoding) {
+            Some(decoded) => (decoded, Some(compressed_len)),
+            None => {
+                warn!(
+                    "Cannot decode response body for {} (Content-Encoding: {}), storing raw bytes",
+                    request_id, content_encoding
+                );
+                (body, None)
+            }
+        };
+        let decoded_len = decoded.len();
+
+        let (text, is_base64) = match (compressed_size, String::from_utf8(decoded)) {
+            (Some(_), Ok(text)) => (text, false),
+            (_, Ok(text)) => (
+                base64::engine::general_purpose::STANDARD.encode(text.as_bytes()),
+                true,
+            ),
+            (_, Err(e)) => (
+                base64::engine::general_purpose::STANDARD.encode(e.into_bytes()),
+                true,
+            ),
+        };
+
+        if !self.apply_response(&request_id, status_code, headers, text, is_base64, false) {
+            return;
         }
+
+        if let Some(mut request) = self.request_map.get_mut(&request_id) {
+            request.response_size = Some(decoded_len);
+            request.compressed_size = compressed_size;
+        }
+        self.emit_response_received(&request_id);
     }
 
     /// Get all tracked requests (for Network Inspector)
@@ -685,7 +1511,18 @@ impl NetworkDomain {
         self.request_map.iter().map(|r| r.value().clone()).collect()
     }
 
-    /// Clear all tracked requests
+    /// Get tracked requests matching a filter
+    ///
+    /// An empty filter returns the same list as `get_all_requests`.
+    pub fn get_requests_filtered(&self, filter: RequestFilter) -> Vec<RequestInfo> {
+        self.request_map
+            .iter()
+            .filter(|r| filter.matches(r.value()))
+            .map(|r| r.value().clone())
+            .collect()
+    }
+
+    /// Clear all tracked requests
     pub fn clear_requests(&self) {
         debug!("Clearing all tracked requests");
         self.request_map.clear();
@@ -719,6 +1556,363 @@ impl NetworkDomain {
         self.interception_enabled.load(Ordering::SeqCst)
     }
 
+    /// Associate a tracked request with the frame that issued it
+    pub fn set_request_frame_id(&self, request_id: &str, frame_id: String) {
+        if let Some(mut request) = self.request_map.get_mut(request_id) {
+            request.frame_id = Some(frame_id);
+        } else {
+            warn!(
+                "Attempted to set frame for unknown request: {}",
+                request_id
+            );
+        }
+    }
+
+    /// Store the timing breakdown for a tracked request
+    pub fn set_request_timing(&self, request_id: &str, timing: TimingBreakdown) {
+        if let Some(mut request) = self.request_map.get_mut(request_id) {
+            request.timing = Some(timing);
+        } else {
+            warn!(
+                "Attempted to set timing for unknown request: {}",
+                request_id
+            );
+        }
+    }
+
+    /// Override the loading priority of a tracked request
+    pub fn set_request_priority(&self, request_id: &str, priority: ResourcePriority) {
+        if let Some(mut request) = self.request_map.get_mut(request_id) {
+            request.priority = priority;
+        } else {
+            warn!(
+                "Attempted to set priority for unknown request: {}",
+                request_id
+            );
+        }
+    }
+
+    /// CDP method: Network.getRequestTiming
+    pub async fn get_request_timing(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        let params = params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?;
+
+        let request_id = params
+            .get("requestId")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| CdpError::invalid_params("Missing requestId parameter"))?;
+
+        debug!("Network.getRequestTiming for request: {}", request_id);
+
+        let request = self.request_map.get(request_id).ok_or_else(|| {
+            CdpError::server_error(-32000, format!("Request not found: {}", request_id))
+        })?;
+
+        Ok(json!({
+            "requestId": request_id,
+            "timing": request.timing
+        }))
+    }
+
+    /// Get tracked requests matching a filter
+    ///
+    /// Returns the matching requests plus `totalCount`, the number of tracked
+    /// requests before filtering.
+    pub async fn get_filtered_requests(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        let filter: RequestFilter = match params {
+            Some(params) => serde_json::from_value(params)
+                .map_err(|e| CdpError::invalid_params(format!("Invalid filter: {}", e)))?,
+            None => RequestFilter::default(),
+        };
+
+        debug!("Network.getRequestsFiltered with filter: {:?}", filter);
+
+        let total_count = self.request_map.len();
+        let requests = self.get_requests_filtered(filter);
+
+        Ok(json!({
+            "requests": requests,
+            "totalCount": total_count
+        }))
+    }
+
+    /// Record what initiated a tracked request
+    pub fn set_initiator(&self, request_id: &str, initiator: Initiator) {
+        if let Some(mut request) = self.request_map.get_mut(request_id) {
+            request.initiator = Some(initiator);
+        } else {
+            warn!(
+                "Attempted to set initiator for unknown request: {}",
+                request_id
+            );
+        }
+    }
+
+    /// Group request ids by initiating script URL
+    ///
+    /// Parser- and preload-initiated requests are grouped under
+    /// `DOCUMENT_INITIATOR_KEY`. Requests without an initiator are omitted.
+    /// Ids within a group are ordered by start time.
+    pub fn requests_by_initiator(&self) -> HashMap<String, Vec<String>> {
+        let mut groups: HashMap<String, Vec<(f64, String)>> = HashMap::new();
+        for entry in self.request_map.iter() {
+            if let Some(initiator) = &entry.initiator {
+                groups
+                    .entry(initiator.group_key().to_string())
+                    .or_default()
+                    .push((entry.timestamp, entry.request_id.clone()));
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, mut requests)| {
+                requests.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+                (key, requests.into_iter().map(|(_, id)| id).collect())
+            })
+            .collect()
+    }
+
+    /// Store the cookies considered for a tracked request
+    ///
+    /// Each cookie carries the reasons it was blocked; an empty list means the
+    /// cookie was sent.
+    pub fn store_request_cookies(&self, request_id: &str, cookies: Vec<AssociatedCookie>) {
+        if let Some(mut request) = self.request_map.get_mut(request_id) {
+            request
+                .extra_info
+                .get_or_insert_with(RequestExtraInfo::default)
+                .associated_cookies = cookies;
+        } else {
+            warn!(
+                "Attempted to store cookies for unknown request: {}",
+                request_id
+            );
+        }
+    }
+
+    /// CDP method: Network.getAllCookies
+    pub async fn get_all_cookies(&self) -> Result<Value, CdpError> {
+        debug!("Network.getAllCookies ({} cookies)", self.cookie_store.len());
+        Ok(json!({
+            "cookies": self.cookie_store.all()
+        }))
+    }
+
+    /// CDP method: Network.clearBrowserCookies
+    pub async fn clear_browser_cookies(&self) -> Result<Value, CdpError> {
+        debug!("Network.clearBrowserCookies");
+        self.cookie_store.clear();
+        Ok(json!({}))
+    }
+
+    /// CDP method: Network.getRequestExtraInfo
+    pub async fn get_request_extra_info(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        let params = params.ok_or_else(|| CdpError::invalid_params("Missing parameters"))?;
+
+        let request_id = params
+            .get("requestId")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| CdpError::invalid_params("Missing requestId parameter"))?;
+
+        debug!("Network.getRequestExtraInfo for request: {}", request_id);
+
+        let request = self.request_map.get(request_id).ok_or_else(|| {
+            CdpError::server_error(-32000, format!("Request not found: {}", request_id))
+        })?;
+
+        let extra_info = request.extra_info.clone().unwrap_or_default();
+
+        Ok(json!({
+            "requestId": request_id,
+            "associatedCookies": extra_info.associated_cookies,
+            "headers": request.request_headers
+        }))
+    }
+
+    // =========================================================================
+    // HAR Export Methods
+    // =========================================================================
+
+    /// Export all tracked requests as a HAR 1.2 log
+    ///
+    /// Entries are ordered by start time. Requests without a response are
+    /// included with an empty response (status 0). Pages are derived from
+    /// the frames that issued requests.
+    pub fn export_har(&self) -> Value {
+        let mut requests = self.get_all_requests();
+        requests.sort_by(|a, b| {
+            a.timestamp
+                .total_cmp(&b.timestamp)
+                .then_with(|| a.request_id.cmp(&b.request_id))
+        });
+
+        let mut pages: Vec<Value> = Vec::new();
+        let mut seen_frames: Vec<&str> = Vec::new();
+        for request in &requests {
+            if let Some(frame_id) = request.frame_id.as_deref() {
+                if !seen_frames.contains(&frame_id) {
+                    seen_frames.push(frame_id);
+                    pages.push(json!({
+                        "startedDateTime": har_date_time(request.timestamp),
+                        "id": frame_id,
+                        "title": request.url,
+                        "pageTimings": {}
+                    }));
+                }
+            }
+        }
+
+        let entries: Vec<Value> = requests.iter().map(har_entry).collect();
+
+        json!({
+            "log": {
+                "version": HAR_VERSION,
+                "creator": {
+                    "name": "CortenBrowser DevTools",
+                    "version": env!("CARGO_PKG_VERSION")
+                },
+                "pages": pages,
+                "entries": entries
+            }
+        })
+    }
+
+    /// CDP method: Network.getHarLog
+    pub async fn get_har_log(&self) -> Result<Value, CdpError> {
+        debug!("Network.getHarLog ({} requests)", self.request_map.len());
+        Ok(self.export_har())
+    }
+
+    // =========================================================================
+    // State Persistence Methods
+    // =========================================================================
+
+    /// Serialize all tracked requests, WebSocket connections and caches
+    ///
+    /// The snapshot is tagged with `STATE_VERSION` so that `restore_state`
+    /// can reject snapshots written by a newer format.
+    pub fn serialize_state(&self) -> Value {
+        let mut requests = self.get_all_requests();
+        requests.sort_by(|a, b| {
+            a.timestamp
+                .total_cmp(&b.timestamp)
+                .then_with(|| a.request_id.cmp(&b.request_id))
+        });
+
+        let mut websockets = self.get_all_websocket_connections();
+        websockets.sort_by(|a, b| a.request_id.cmp(&b.request_id));
+
+        let mut caches: Vec<SavedCache> = self
+            .cache_info
+            .iter()
+            .map(|info| SavedCache {
+                info: info.value().clone(),
+                entries: self
+                    .cache_storage
+                    .get(info.key())
+                    .map(|entries| entries.value().clone())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        caches.sort_by(|a, b| a.info.cache_id.cmp(&b.info.cache_id));
+
+        let state = SavedState {
+            version: STATE_VERSION,
+            requests,
+            websockets,
+            caches,
+        };
+        serde_json::to_value(state).unwrap_or_else(|_| json!({ "version": STATE_VERSION }))
+    }
+
+    /// Replace the domain's tracked data with a snapshot from `serialize_state`
+    ///
+    /// Missing sections restore as empty. Snapshots with a version newer than
+    /// `STATE_VERSION` are rejected and leave the current state untouched.
+    pub fn restore_state(&self, state: Value) -> Result<(), CdpError> {
+        let state: SavedState = serde_json::from_value(state)
+            .map_err(|e| CdpError::invalid_params(format!("Invalid network state: {}", e)))?;
+
+        if state.version > STATE_VERSION {
+            return Err(CdpError::invalid_params(format!(
+                "Unsupported network state version {} (expected at most {})",
+                state.version, STATE_VERSION
+            )));
+        }
+
+        info!(
+            "Restoring network state: {} requests, {} websockets, {} caches",
+            state.requests.len(),
+            state.websockets.len(),
+            state.caches.len()
+        );
+
+        self.request_map.clear();
+        for request in state.requests {
+            self.request_map.insert(request.request_id.clone(), request);
+        }
+
+        self.websocket_connections.clear();
+        for connection in state.websockets {
+            self.websocket_connections
+                .insert(connection.request_id.clone(), connection);
+        }
+
+        self.cache_info.clear();
+        self.cache_storage.clear();
+        for cache in state.caches {
+            let cache_id = cache.info.cache_id.clone();
+            self.cache_info.insert(cache_id.clone(), cache.info);
+            self.cache_storage.insert(cache_id, cache.entries);
+        }
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Security Isolation Methods
+    // =========================================================================
+
+    /// Get the COEP/COOP/CORP status of a frame
+    ///
+    /// The status is derived from the response headers of the frame's most
+    /// recent `Document` request. When `frame_id` is `None`, the most recent
+    /// document request of any frame is used. Returns `None` if no matching
+    /// document request is tracked.
+    pub fn security_isolation_status(
+        &self,
+        frame_id: Option<&str>,
+    ) -> Option<SecurityIsolationStatus> {
+        self.request_map
+            .iter()
+            .filter(|r| r.resource_type.as_deref() == Some("Document"))
+            .filter(|r| frame_id.is_none() || r.frame_id.as_deref() == frame_id)
+            .max_by(|a, b| a.timestamp.total_cmp(&b.timestamp))
+            .map(|r| SecurityIsolationStatus::from_headers(&r.response_headers))
+    }
+
+    /// CDP method: Network.getSecurityIsolationStatus
+    pub async fn get_security_isolation_status(
+        &self,
+        params: Option<Value>,
+    ) -> Result<Value, CdpError> {
+        let frame_id = params
+            .as_ref()
+            .and_then(|p| p.get("frameId"))
+            .and_then(|v| v.as_str());
+
+        debug!("Network.getSecurityIsolationStatus for frame: {:?}", frame_id);
+
+        let status = self.security_isolation_status(frame_id).ok_or_else(|| {
+            CdpError::server_error(
+                -32000,
+                format!("No document request for frame: {}", frame_id.unwrap_or("(main)")),
+            )
+        })?;
+
+        Ok(json!({ "status": status }))
+    }
+
     // =========================================================================
     // Cache Inspection Methods (FEAT-030)
     // =========================================================================
@@ -965,8 +2159,9 @@ impl NetworkDomain {
             request_id, frame.opcode, frame.is_outgoing
         );
 
+        let max_frames = self.max_websocket_frames.load(Ordering::Relaxed);
         if let Some(mut conn) = self.websocket_connections.get_mut(request_id) {
-            conn.add_frame(frame);
+            conn.add_frame_capped(frame, max_frames);
         } else {
             warn!(
                 "Attempted to add frame to unknown WebSocket: {}",
@@ -991,7 +2186,7 @@ impl NetworkDomain {
     pub fn get_websocket_frames(&self, request_id: &str) -> Option<Vec<WebSocketFrame>> {
         self.websocket_connections
             .get(request_id)
-            .map(|conn| conn.frames.clone())
+            .map(|conn| conn.frames.iter().cloned().collect())
     }
 
     /// Get WebSocket connection info
@@ -1062,11 +2257,244 @@ impl NetworkDomain {
             "url": connection.url,
             "state": connection.state,
             "timestamp": connection.timestamp,
-            "frames": frames
+            "frames": frames,
+            "droppedFrameCount": connection.dropped_frame_count
         }))
     }
 }
 
+/// Longest prefix of `text` of at most `max_len` bytes that ends on a
+/// character boundary
+fn truncate_at_char_boundary(text: &str, max_len: usize) -> &str {
+    let mut end = max_len.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Decode a body according to a `Content-Encoding` header value
+///
+/// Encodings are undone in reverse order of application. Returns None for
+/// unknown encodings or corrupt bodies.
+fn decode_content(body: &[u8], content_encoding: &str) -> Option<Vec<u8>> {
+    let mut data = body.to_vec();
+
+    for encoding in content_encoding.rsplit(',') {
+        let mut decoded = Vec::new();
+        match encoding.trim().to_ascii_lowercase().as_str() {
+            "" | "identity" => continue,
+            "gzip" | "x-gzip" => {
+                flate2::read::MultiGzDecoder::new(data.as_slice())
+                    .read_to_end(&mut decoded)
+                    .ok()?;
+            }
+            "deflate" => {
+                // Servers send both zlib-wrapped and raw deflate streams
+                if flate2::read::ZlibDecoder::new(data.as_slice())
+                    .read_to_end(&mut decoded)
+                    .is_err()
+                {
+                    decoded.clear();
+                    flate2::read::DeflateDecoder::new(data.as_slice())
+                        .read_to_end(&mut decoded)
+                        .ok()?;
+                }
+            }
+            "br" => {
+                brotli::Decompressor::new(data.as_slice(), 4096)
+                    .read_to_end(&mut decoded)
+                    .ok()?;
+            }
+            _ => return None,
+        }
+        data = decoded;
+    }
+
+    Some(data)
+}
+
+/// Build `Network.requestWillBeSent` params for a tracked request
+fn request_will_be_sent_params(request: &RequestInfo) -> Value {
+    let mut cdp_request = json!({
+        "url": request.url,
+        "method": request.method,
+        "headers": request.request_headers,
+        "hasPostData": request.request_body.is_some(),
+        "initialPriority": request.priority
+    });
+    if let Some(body) = &request.request_body {
+        cdp_request["postData"] = json!(body);
+    }
+
+    let initiator = match &request.initiator {
+        Some(initiator) => json!(initiator),
+        None => json!({"type": "other"}),
+    };
+
+    let mut params = json!({
+        "requestId": request.request_id,
+        "loaderId": "",
+        "documentURL": request.url,
+        "request": cdp_request,
+        "timestamp": request.timestamp / 1000.0,
+        "wallTime": request.timestamp / 1000.0,
+        "initiator": initiator,
+        "type": request.resource_type.as_deref().unwrap_or("Other")
+    });
+    if let Some(frame_id) = &request.frame_id {
+        params["frameId"] = json!(frame_id);
+    }
+    params
+}
+
+/// Build `Network.responseReceived` params for a tracked request
+fn response_received_params(request: &RequestInfo) -> Value {
+    let timestamp = (request.timestamp + request.response_time.unwrap_or(0.0)) / 1000.0;
+    let encoded_length = request.compressed_size.or(request.response_size).unwrap_or(0);
+
+    let mut params = json!({
+        "requestId": request.request_id,
+        "loaderId": "",
+        "timestamp": timestamp,
+        "type": request.resource_type.as_deref().unwrap_or("Other"),
+        "response": {
+            "url": request.url,
+            "status": request.status_code.unwrap_or(0),
+            "statusText": "",
+            "headers": request.response_headers,
+            "mimeType": mime_type(&request.response_headers),
+            "fromDiskCache": request.from_cache,
+            // Service worker responses are not tracked yet, so cached means disk cache
+            "fromServiceWorker": false,
+            "encodedDataLength": encoded_length,
+            "timing": request.timing
+        }
+    });
+    if let Some(frame_id) = &request.frame_id {
+        params["frameId"] = json!(frame_id);
+    }
+    params
+}
+
+/// Build a HAR entry for a tracked request
+fn har_entry(request: &RequestInfo) -> Value {
+    let request_content_type = header_value(&request.request_headers, "content-type");
+    let response_content_type = header_value(&request.response_headers, "content-type");
+    let wait = request.response_time.unwrap_or(0.0);
+
+    let mut har_request = json!({
+        "method": request.method,
+        "url": request.url,
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": har_headers(&request.request_headers),
+        "queryString": har_query_string(&request.url),
+        "headersSize": -1,
+        "bodySize": request.request_body.as_ref().map(|b| b.len() as i64).unwrap_or(0)
+    });
+    if let Some(body) = &request.request_body {
+        har_request["postData"] = json!({
+            "mimeType": request_content_type.unwrap_or_default(),
+            "text": body
+        });
+    }
+
+    let mut content = json!({
+        "size": request.response_size.unwrap_or(0),
+        "mimeType": response_content_type.unwrap_or_default()
+    });
+    if let Some(body) = &request.response_body {
+        content["text"] = json!(body);
+        if request.is_base64 {
+            content["encoding"] = json!("base64");
+        }
+    }
+
+    let mut entry = json!({
+        "startedDateTime": har_date_time(request.timestamp),
+        "time": wait,
+        "request": har_request,
+        "response": {
+            "status": request.status_code.unwrap_or(0),
+            "statusText": "",
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": har_headers(&request.response_headers),
+            "content": content,
+            "redirectURL": header_value(&request.response_headers, "location").unwrap_or_default(),
+            "headersSize": -1,
+            "bodySize": request.response_size.map(|s| s as i64).unwrap_or(-1)
+        },
+        "cache": {},
+        "timings": {
+            "blocked": -1,
+            "dns": -1,
+            "connect": -1,
+            "send": 0,
+            "wait": wait,
+            "receive": 0,
+            "ssl": -1
+        }
+    });
+    if let Some(frame_id) = &request.frame_id {
+        entry["pageref"] = json!(frame_id);
+    }
+    entry["_priority"] = json!(request.priority);
+    entry
+}
+
+/// Headers as a HAR name/value list, sorted by name
+fn har_headers(headers: &HttpHeaders) -> Vec<Value> {
+    let mut sorted: Vec<(&String, &String)> = headers.iter().collect();
+    sorted.sort();
+    sorted
+        .into_iter()
+        .map(|(name, value)| json!({"name": name, "value": value}))
+        .collect()
+}
+
+/// Query parameters of a URL as a HAR name/value list
+fn har_query_string(url: &str) -> Vec<Value> {
+    let query = match url.split('#').next().and_then(|u| u.split_once('?')) {
+        Some((_, query)) => query,
+        None => return Vec::new(),
+    };
+
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            json!({"name": name, "value": value})
+        })
+        .collect()
+}
+
+/// Format milliseconds since the Unix epoch as an ISO 8601 UTC date-time
+fn har_date_time(timestamp_ms: f64) -> String {
+    let total_ms = timestamp_ms.max(0.0) as i64;
+    let millis = total_ms % 1000;
+    let secs = total_ms / 1000;
+    let (hour, minute, second) = ((secs / 3600) % 24, (secs / 60) % 60, secs % 60);
+
+    // Civil date from days since epoch (Howard Hinnant's algorithm)
+    let days = secs / 86_400 + 719_468;
+    let era = days / 146_097;
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year, month, day, hour, minute, second, millis
+    )
+}
+
 impl Default for NetworkDomain {
     fn default() -> Self {
         Self::new()
@@ -1098,6 +2526,13 @@ impl DomainHandler for NetworkDomain {
             "getResponseBody" => self.get_response_body(params).await,
             "getRequestPostData" => self.get_request_post_data(params).await,
             "setRequestInterception" => self.set_request_interception(params).await,
+            "getSecurityIsolationStatus" => self.get_security_isolation_status(params).await,
+            "getRequestExtraInfo" => self.get_request_extra_info(params).await,
+            "getHarLog" => self.get_har_log().await,
+            "getAllCookies" => self.get_all_cookies().await,
+            "clearBrowserCookies" => self.clear_browser_cookies().await,
+            "getRequestTiming" => self.get_request_timing(params).await,
+            "getRequestsFiltered" => self.get_filtered_requests(params).await,
 
             // Cache Storage methods (CacheStorage domain, often routed through Network)
             "requestCacheNames" => self.request_cache_names(params).await,
@@ -1666,6 +3101,41 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_websocket_frame_limit_evicts_oldest() {
+        let domain = NetworkDomain::with_websocket_frame_limit(1000);
+        domain.track_websocket("ws-busy".to_string(), "wss://example.com/feed".to_string());
+
+        for i in 0..1500 {
+            domain.add_websocket_text_frame("ws-busy", format!("frame-{}", i), false);
+        }
+
+        let frames = domain.get_websocket_frames("ws-busy").unwrap();
+        assert_eq!(frames.len(), 1000);
+        assert_eq!(frames[0].payload_data, "frame-500");
+        assert_eq!(frames[999].payload_data, "frame-1499");
+
+        let data = domain
+            .get_websocket_frame_data(Some(json!({"requestId": "ws-busy"})))
+            .await
+            .unwrap();
+        assert_eq!(data["droppedFrameCount"], 500);
+        assert_eq!(data["frames"].as_array().unwrap().len(), 1000);
+    }
+
+    #[tokio::test]
+    async fn test_websocket_default_frame_limit() {
+        let domain = NetworkDomain::new();
+        domain.track_websocket("ws-1".to_string(), "wss://example.com".to_string());
+        for i in 0..DEFAULT_MAX_WEBSOCKET_FRAMES + 1 {
+            domain.add_websocket_text_frame("ws-1", i.to_string(), true);
+        }
+
+        let conn = domain.get_websocket_connection("ws-1").unwrap();
+        assert_eq!(conn.frames.len(), DEFAULT_MAX_WEBSOCKET_FRAMES);
+        assert_eq!(conn.dropped_frame_count, 1);
+    }
+
     #[tokio::test]
     async fn test_request_post_data_not_found() {
         let domain = NetworkDomain::new();
@@ -1675,6 +3145,897 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // =========================================================================
+    // Request Extra Info Tests
+    // =========================================================================
+
+    fn cookie(name: &str, value: &str) -> NetworkCookie {
+        NetworkCookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: "example.com".to_string(),
+            path: "/".to_string(),
+            secure: false,
+            http_only: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_extra_info_reports_blocked_cookie() {
+        let domain = NetworkDomain::new();
+        domain.track_request(
+            "req-cookies".to_string(),
+            "http://example.com/".to_string(),
+            "GET".to_string(),
+        );
+
+        domain.store_request_cookies(
+            "req-cookies",
+            vec![
+                AssociatedCookie {
+                    cookie: cookie("session", "abc"),
+                    blocked_reasons: vec![],
+                },
+                AssociatedCookie {
+                    cookie: cookie("tracker", "xyz"),
+                    blocked_reasons: vec![CookieBlockedReason::SecureOnly],
+                },
+            ],
+        );
+
+        let result = domain
+            .handle_method("getRequestExtraInfo", Some(json!({"requestId": "req-cookies"})))
+            .await
+            .unwrap();
+
+        let cookies = result["associatedCookies"].as_array().unwrap();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0]["cookie"]["name"], "session");
+        assert_eq!(cookies[0]["blockedReasons"], json!([]));
+        assert_eq!(cookies[1]["cookie"]["name"], "tracker");
+        assert_eq!(cookies[1]["blockedReasons"], json!(["SecureOnly"]));
+    }
+
+    #[tokio::test]
+    async fn test_shared_cookie_store_with_storage_domain() {
+        let store = Arc::new(CookieStore::new());
+        let storage = console_storage::StorageDomain::with_cookie_store(Arc::clone(&store));
+        let domain = NetworkDomain::with_cookie_store(Arc::clone(&store));
+
+        storage
+            .handle_method(
+                "setCookie",
+                Some(json!({"name": "session", "value": "abc", "domain": "example.com"})),
+            )
+            .await
+            .unwrap();
+
+        let result = domain.handle_method("getAllCookies", None).await.unwrap();
+        assert_eq!(result["cookies"][0]["name"], "session");
+
+        domain.handle_method("clearBrowserCookies", None).await.unwrap();
+        let result = storage.handle_method("getCookies", None).await.unwrap();
+        assert_eq!(result["cookies"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_request_extra_info_unknown_request() {
+        let domain = NetworkDomain::new();
+        let result = domain
+            .get_request_extra_info(Some(json!({"requestId": "missing"})))
+            .await;
+        assert!(result.is_err());
+    }
+
+    // =========================================================================
+    // Request Timing Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_request_timing_round_trip() {
+        let domain = NetworkDomain::new();
+        track(&domain, "timed", "https://example.com/");
+
+        let timing = TimingBreakdown {
+            dns_start: Some(0.5),
+            dns_end: Some(12.0),
+            connect_start: Some(12.0),
+            connect_end: Some(40.0),
+            ssl_start: Some(20.0),
+            ssl_end: Some(40.0),
+            send_start: Some(40.5),
+            send_end: Some(41.0),
+            receive_headers_end: Some(95.25),
+        };
+        domain.set_request_timing("timed", timing.clone());
+
+        let result = domain
+            .handle_method("getRequestTiming", Some(json!({"requestId": "timed"})))
+            .await
+            .unwrap();
+        assert_eq!(result["timing"]["receiveHeadersEnd"], 95.25);
+        let parsed: TimingBreakdown = serde_json::from_value(result["timing"].clone()).unwrap();
+        assert_eq!(parsed, timing);
+    }
+
+    #[tokio::test]
+    async fn test_partial_request_timing_serializes_nulls() {
+        let domain = NetworkDomain::new();
+        track(&domain, "dns-only", "https://example.com/");
+        track(&domain, "untimed", "https://example.com/other");
+
+        domain.set_request_timing(
+            "dns-only",
+            TimingBreakdown {
+                dns_start: Some(1.0),
+                dns_end: Some(3.5),
+                ..Default::default()
+            },
+        );
+
+        let result = domain
+            .get_request_timing(Some(json!({"requestId": "dns-only"})))
+            .await
+            .unwrap();
+        assert_eq!(
+            result["timing"],
+            json!({
+                "dnsStart": 1.0,
+                "dnsEnd": 3.5,
+                "connectStart": null,
+                "connectEnd": null,
+                "sslStart": null,
+                "sslEnd": null,
+                "sendStart": null,
+                "sendEnd": null,
+                "receiveHeadersEnd": null
+            })
+        );
+
+        let result = domain
+            .get_request_timing(Some(json!({"requestId": "untimed"})))
+            .await
+            .unwrap();
+        assert!(result["timing"].is_null());
+    }
+
+    // =========================================================================
+    // Event Callback Tests
+    // =========================================================================
+
+    #[test]
+    fn test_event_callback_emits_request_and_response_events() {
+        let domain = Arc::new(NetworkDomain::new());
+        type Recorded = (String, Value, Vec<RequestInfo>);
+        let events: Arc<parking_lot::Mutex<Vec<Recorded>>> =
+            Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        // The callback snapshots the domain to verify state is already updated
+        let observed = Arc::clone(&domain);
+        let sink = Arc::clone(&events);
+        domain.set_event_callback(Arc::new(move |name: &str, params: Value| {
+            let snapshot = observed.get_all_requests();
+            sink.lock().push((name.to_string(), params, snapshot));
+        }));
+
+        let mut headers = HttpHeaders::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        domain.track_request_full(
+            "req-1".to_string(),
+            "https://example.com/api".to_string(),
+            "POST".to_string(),
+            HttpHeaders::new(),
+            Some("{}".to_string()),
+            Some("Fetch".to_string()),
+        );
+        domain.store_response_full(
+            "req-1".to_string(),
+            201,
+            headers,
+            "{\"ok\":true}".to_string(),
+            false,
+            false,
+        );
+
+        let events = events.lock();
+        assert_eq!(events.len(), 2);
+
+        let (name, params, snapshot) = &events[0];
+        assert_eq!(name, "Network.requestWillBeSent");
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(params["requestId"], "req-1");
+        assert_eq!(params["request"]["method"], "POST");
+        assert_eq!(params["request"]["postData"], "{}");
+        assert_eq!(params["type"], "Fetch");
+
+        let (name, params, snapshot) = &events[1];
+        assert_eq!(name, "Network.responseReceived");
+        assert_eq!(snapshot[0].status_code, Some(201));
+        assert_eq!(params["response"]["status"], 201);
+        assert_eq!(params["response"]["mimeType"], "application/json");
+        assert_eq!(params["response"]["encodedDataLength"], 11);
+    }
+
+    #[test]
+    fn test_response_received_parses_mime_type_and_cache_flags() {
+        let domain = NetworkDomain::new();
+        let events: Arc<parking_lot::Mutex<Vec<Value>>> =
+            Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        domain.set_event_callback(Arc::new(move |name: &str, params: Value| {
+            if name == "Network.responseReceived" {
+                sink.lock().push(params);
+            }
+        }));
+
+        let mut headers = HttpHeaders::new();
+        headers.insert("content-type".to_string(), "Text/HTML; charset=utf-8".to_string());
+        domain.track_request(
+            "req-1".to_string(),
+            "https://example.com/".to_string(),
+            "GET".to_string(),
+        );
+        domain.store_response_full(
+            "req-1".to_string(),
+            404,
+            headers,
+            "<h1>missing</h1>".to_string(),
+            false,
+            true,
+        );
+
+        let events = events.lock();
+        assert_eq!(events.len(), 1);
+        let response = &events[0]["response"];
+        assert_eq!(response["status"], 404);
+        assert_eq!(response["mimeType"], "text/html");
+        assert_eq!(response["headers"]["content-type"], "Text/HTML; charset=utf-8");
+        assert_eq!(response["fromDiskCache"], true);
+        assert_eq!(response["fromServiceWorker"], false);
+    }
+
+    #[test]
+    fn test_event_callback_skips_unknown_response_and_can_be_cleared() {
+        let domain = NetworkDomain::new();
+        let count = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&count);
+        domain.set_event_callback(Arc::new(move |_: &str, _: Value| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        domain.store_response_full(
+            "missing".to_string(),
+            200,
+            HttpHeaders::new(),
+            String::new(),
+            false,
+            false,
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        domain.track_request_full(
+            "req-1".to_string(),
+            "https://example.com/".to_string(),
+            "GET".to_string(),
+            HttpHeaders::new(),
+            None,
+            None,
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        domain.clear_event_callback();
+        domain.store_response_full(
+            "req-1".to_string(),
+            200,
+            HttpHeaders::new(),
+            String::new(),
+            false,
+            false,
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    // =========================================================================
+    // Request Filter Tests
+    // =========================================================================
+
+    fn track_filtered(
+        domain: &NetworkDomain,
+        id: &str,
+        url: &str,
+        resource_type: &str,
+        status: u16,
+        from_cache: bool,
+    ) {
+        domain.track_request_full(
+            id.to_string(),
+            url.to_string(),
+            "GET".to_string(),
+            HttpHeaders::new(),
+            None,
+            Some(resource_type.to_string()),
+        );
+        domain.store_response_full(
+            id.to_string(),
+            status,
+            HttpHeaders::new(),
+            String::new(),
+            false,
+            from_cache,
+        );
+    }
+
+    fn sorted_ids(requests: Vec<RequestInfo>) -> Vec<String> {
+        let mut ids: Vec<String> = requests.into_iter().map(|r| r.request_id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_requests_filtered_by_each_field() {
+        let domain = NetworkDomain::new();
+        track_filtered(&domain, "doc", "https://example.com/", "Document", 200, false);
+        track_filtered(&domain, "js", "https://cdn.example.com/app.js", "Script", 304, true);
+        track_filtered(&domain, "img", "https://cdn.example.com/logo.png", "Image", 404, false);
+        track(&domain, "pending", "https://example.com/api/poll");
+
+        assert_eq!(domain.get_requests_filtered(RequestFilter::default()).len(), 4);
+
+        let by_type = RequestFilter {
+            resource_type: Some("script".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(sorted_ids(domain.get_requests_filtered(by_type)), vec!["js"]);
+
+        let by_url = RequestFilter {
+            url_contains: Some("cdn.".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(sorted_ids(domain.get_requests_filtered(by_url)), vec!["img", "js"]);
+
+        let by_status = RequestFilter {
+            status_min: Some(300),
+            status_max: Some(399),
+            ..Default::default()
+        };
+        assert_eq!(sorted_ids(domain.get_requests_filtered(by_status)), vec!["js"]);
+
+        let not_cached = RequestFilter {
+            from_cache: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            sorted_ids(domain.get_requests_filtered(not_cached)),
+            vec!["doc", "img", "pending"]
+        );
+    }
+
+    fn priority_of(domain: &NetworkDomain, id: &str) -> ResourcePriority {
+        domain
+            .get_all_requests()
+            .into_iter()
+            .find(|r| r.request_id == id)
+            .map(|r| r.priority)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_priority_inferred_from_resource_type() {
+        let domain = NetworkDomain::new();
+        track_filtered(
+            &domain,
+            "img",
+            "https://example.com/logo.png",
+            "Image",
+            200,
+            false,
+        );
+        track_filtered(
+            &domain,
+            "doc",
+            "https://example.com/",
+            "Document",
+            200,
+            false,
+        );
+        track(&domain, "untyped", "https://example.com/api");
+
+        assert_eq!(priority_of(&domain, "img"), ResourcePriority::Low);
+        assert_eq!(priority_of(&domain, "doc"), ResourcePriority::VeryHigh);
+        assert_eq!(priority_of(&domain, "untyped"), ResourcePriority::Medium);
+    }
+
+    #[test]
+    fn test_explicit_priority_overrides_inferred() {
+        let domain = NetworkDomain::new();
+        track_filtered(
+            &domain,
+            "img",
+            "https://example.com/hero.png",
+            "Image",
+            200,
+            false,
+        );
+        domain.set_request_priority("img", ResourcePriority::High);
+
+        assert_eq!(priority_of(&domain, "img"), ResourcePriority::High);
+        let har = domain.export_har();
+        assert_eq!(har["log"]["entries"][0]["_priority"], "High");
+    }
+
+    #[tokio::test]
+    async fn test_get_filtered_requests_reports_total_count() {
+        let domain = NetworkDomain::new();
+        track_filtered(&domain, "ok", "https://example.com/a", "Fetch", 200, false);
+        track_filtered(&domain, "err", "https://example.com/b", "Fetch", 500, false);
+        track_filtered(&domain, "doc", "https://example.com/", "Document", 200, false);
+
+        let result = domain
+            .handle_method(
+                "getRequestsFiltered",
+                Some(json!({"resourceType": "Fetch", "statusMin": 400})),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["totalCount"], 3);
+        assert_eq!(result["requests"].as_array().unwrap().len(), 1);
+        assert_eq!(result["requests"][0]["request_id"], "err");
+
+        let result = domain.handle_method("getRequestsFiltered", None).await.unwrap();
+        assert_eq!(result["requests"].as_array().unwrap().len(), 3);
+
+        let result = domain
+            .handle_method("getRequestsFiltered", Some(json!({"statusMin": "high"})))
+            .await;
+        assert!(result.is_err());
+    }
+
+    // =========================================================================
+    // Interception Matching Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_matches_interception_wildcard_url() {
+        let domain = NetworkDomain::new();
+        domain
+            .set_request_interception(Some(json!({
+                "patterns": [{"urlPattern": "*.example.com/api/*"}]
+            })))
+            .await
+            .unwrap();
+
+        let matched = domain.matches_interception("https://v1.example.com/api/users", None);
+        assert_eq!(matched.unwrap().url_pattern.as_deref(), Some("*.example.com/api/*"));
+        assert!(domain.matches_interception("https://other.com", None).is_none());
+        assert!(domain
+            .matches_interception("https://v1.example.com/static/app.js", None)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_matches_interception_resource_type_and_order() {
+        let domain = NetworkDomain::new();
+        domain
+            .set_request_interception(Some(json!({
+                "patterns": [
+                    {"urlPattern": "https://cdn.test/img?.png", "resourceType": "Image"},
+                    {"urlPattern": "*"}
+                ]
+            })))
+            .await
+            .unwrap();
+
+        let image = domain.matches_interception("https://cdn.test/img1.png", Some("Image"));
+        assert_eq!(image.unwrap().resource_type.as_deref(), Some("Image"));
+
+        // Same URL with a different type falls through to the catch-all
+        let script = domain.matches_interception("https://cdn.test/img1.png", Some("Script"));
+        assert_eq!(script.unwrap().url_pattern.as_deref(), Some("*"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("a?c", "abc"));
+        assert!(!wildcard_match("a?c", "ac"));
+        assert!(wildcard_match("*.js", "https://x.test/a.b.js"));
+        assert!(!wildcard_match("*.js", "https://x.test/a.json"));
+        assert!(wildcard_match(r"literal\*", "literal*"));
+        assert!(!wildcard_match(r"literal\*", "literally"));
+    }
+
+    // =========================================================================
+    // Initiator Tests
+    // =========================================================================
+
+    fn track(domain: &NetworkDomain, request_id: &str, url: &str) {
+        domain.track_request(request_id.to_string(), url.to_string(), "GET".to_string());
+    }
+
+    #[test]
+    fn test_requests_grouped_by_script_initiator() {
+        let domain = NetworkDomain::new();
+        track(&domain, "req-1", "http://example.com/a.json");
+        track(&domain, "req-2", "http://example.com/b.json");
+        track(&domain, "req-3", "http://example.com/c.json");
+        track(&domain, "req-4", "http://example.com/untracked.png");
+
+        domain.set_initiator("req-1", Initiator::script("http://example.com/app.js", 10));
+        domain.set_initiator("req-2", Initiator::script("http://example.com/app.js", 42));
+        domain.set_initiator("req-3", Initiator::script("http://example.com/vendor.js", 1));
+
+        let groups = domain.requests_by_initiator();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["http://example.com/app.js"], vec!["req-1", "req-2"]);
+        assert_eq!(groups["http://example.com/vendor.js"], vec!["req-3"]);
+    }
+
+    #[test]
+    fn test_parser_initiated_request_grouped_under_document() {
+        let domain = NetworkDomain::new();
+        track(&domain, "req-style", "http://example.com/style.css");
+        track(&domain, "req-xhr", "http://example.com/data.json");
+
+        domain.set_initiator("req-style", Initiator::parser());
+        domain.set_initiator("req-xhr", Initiator::script("http://example.com/app.js", 3));
+
+        let groups = domain.requests_by_initiator();
+        assert_eq!(groups[DOCUMENT_INITIATOR_KEY], vec!["req-style"]);
+        assert_eq!(groups["http://example.com/app.js"], vec!["req-xhr"]);
+
+        let request = domain.request_map.get("req-xhr").unwrap();
+        let value = serde_json::to_value(request.initiator.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            value,
+            json!({"type": "script", "url": "http://example.com/app.js", "lineNumber": 3})
+        );
+    }
+
+    // =========================================================================
+    // Compressed Response Tests
+    // =========================================================================
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_store_response_compressed_gzip() {
+        let domain = NetworkDomain::new();
+        track(&domain, "gz-1", "http://example.com/data.json");
+
+        let text = r#"{"items": [1, 2, 3], "padding": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"}"#;
+        let compressed = gzip(text.as_bytes());
+        let compressed_len = compressed.len();
+        domain.store_response_compressed(
+            "gz-1".to_string(),
+            200,
+            HttpHeaders::new(),
+            compressed,
+            "gzip",
+        );
+
+        let result = domain
+            .get_response_body(Some(json!({"requestId": "gz-1"})))
+            .await
+            .unwrap();
+        assert_eq!(result["body"], text);
+        assert_eq!(result["base64Encoded"], false);
+
+        let request = domain.request_map.get("gz-1").unwrap();
+        assert_eq!(request.response_size, Some(text.len()));
+        assert_eq!(request.compressed_size, Some(compressed_len));
+    }
+
+    #[tokio::test]
+    async fn test_store_response_compressed_truncates_on_char_boundary() {
+        let domain = NetworkDomain::with_limits(10, 10);
+        track(&domain, "gz-2", "http://example.com/text.txt");
+
+        // The 10-byte limit falls inside the fifth "é"
+        let text = format!("a{}", "é".repeat(7));
+        domain.store_response_compressed(
+            "gz-2".to_string(),
+            200,
+            HttpHeaders::new(),
+            gzip(text.as_bytes()),
+            "gzip",
+        );
+
+        let result = domain
+            .get_response_body(Some(json!({"requestId": "gz-2"})))
+            .await
+            .unwrap();
+        assert_eq!(result["body"], "aéééé");
+        assert_eq!(result["base64Encoded"], false);
+
+        let request = domain.request_map.get("gz-2").unwrap();
+        assert_eq!(request.response_size, Some(text.len()));
+    }
+
+    #[test]
+    fn test_store_response_compressed_deflate_and_br() {
+        use std::io::Write;
+        let domain = NetworkDomain::new();
+        track(&domain, "df-1", "http://example.com/a.txt");
+        track(&domain, "br-1", "http://example.com/b.txt");
+
+        let mut zlib = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        zlib.write_all(b"deflated text").unwrap();
+        domain.store_response_compressed(
+            "df-1".to_string(),
+            200,
+            HttpHeaders::new(),
+            zlib.finish().unwrap(),
+            "deflate",
+        );
+
+        let mut br = Vec::new();
+        {
+            let mut writer = brotli::CompressorWriter::new(&mut br, 4096, 5, 22);
+            writer.write_all(b"brotli text").unwrap();
+        }
+        domain.store_response_compressed("br-1".to_string(), 200, HttpHeaders::new(), br, "BR");
+
+        let deflated = domain.request_map.get("df-1").unwrap();
+        assert_eq!(deflated.response_body.as_deref(), Some("deflated text"));
+        let brotli = domain.request_map.get("br-1").unwrap();
+        assert_eq!(brotli.response_body.as_deref(), Some("brotli text"));
+        assert!(!brotli.is_base64);
+    }
+
+    #[test]
+    fn test_store_response_compressed_unknown_encoding() {
+        let domain = NetworkDomain::new();
+        track(&domain, "zstd-1", "http://example.com/c.bin");
+
+        domain.store_response_compressed(
+            "zstd-1".to_string(),
+            200,
+            HttpHeaders::new(),
+            vec![0x28, 0xb5, 0x2f, 0xfd],
+            "zstd",
+        );
+
+        let request = domain.request_map.get("zstd-1").unwrap();
+        assert!(request.is_base64);
+        assert_eq!(request.response_body.as_deref(), Some("KLUv/Q=="));
+        assert_eq!(request.response_size, Some(4));
+        assert_eq!(request.compressed_size, None);
+    }
+
+    // =========================================================================
+    // HAR Export Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_export_har_maps_request_and_response() {
+        let domain = NetworkDomain::new();
+        let mut headers = HttpHeaders::new();
+        headers.insert("Accept".to_string(), "application/json".to_string());
+        domain.track_request_full(
+            "har-1".to_string(),
+            "http://example.com/api?page=2&q=rust".to_string(),
+            "GET".to_string(),
+            headers,
+            None,
+            Some("XHR".to_string()),
+        );
+        domain.set_request_frame_id("har-1", "frame-1".to_string());
+
+        let mut response_headers = HttpHeaders::new();
+        response_headers.insert("Content-Type".to_string(), "application/json".to_string());
+        domain.store_response_full(
+            "har-1".to_string(),
+            200,
+            response_headers,
+            r#"{"ok":true}"#.to_string(),
+            false,
+            false,
+        );
+
+        let har = domain.handle_method("getHarLog", None).await.unwrap();
+        let log = &har["log"];
+        assert_eq!(log["version"], "1.2");
+        assert!(log["creator"]["name"].is_string());
+        assert_eq!(log["pages"][0]["id"], "frame-1");
+
+        let entry = &log["entries"][0];
+        assert_eq!(entry["pageref"], "frame-1");
+        assert_eq!(entry["request"]["method"], "GET");
+        assert_eq!(entry["request"]["url"], "http://example.com/api?page=2&q=rust");
+        assert_eq!(
+            entry["request"]["headers"],
+            json!([{"name": "Accept", "value": "application/json"}])
+        );
+        assert_eq!(entry["request"]["queryString"][1], json!({"name": "q", "value": "rust"}));
+        assert_eq!(entry["response"]["status"], 200);
+        assert_eq!(entry["response"]["content"]["mimeType"], "application/json");
+        assert_eq!(entry["response"]["content"]["text"], r#"{"ok":true}"#);
+        assert!(entry["response"]["content"].get("encoding").is_none());
+        assert_eq!(entry["response"]["content"]["size"], 11);
+        assert!(entry["startedDateTime"].as_str().unwrap().ends_with('Z'));
+    }
+
+    #[test]
+    fn test_export_har_base64_and_pending_requests() {
+        let domain = NetworkDomain::new();
+        domain.track_request(
+            "har-img".to_string(),
+            "http://example.com/logo.png".to_string(),
+            "GET".to_string(),
+        );
+        domain.store_response_body("har-img".to_string(), "iVBORw0K".to_string(), true);
+        domain.track_request(
+            "har-pending".to_string(),
+            "http://example.com/slow".to_string(),
+            "GET".to_string(),
+        );
+
+        let har = domain.export_har();
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let image = entries.iter().find(|e| e["request"]["url"] == "http://example.com/logo.png");
+        let content = &image.unwrap()["response"]["content"];
+        assert_eq!(content["encoding"], "base64");
+        assert_eq!(content["text"], "iVBORw0K");
+
+        let pending = entries.iter().find(|e| e["request"]["url"] == "http://example.com/slow");
+        let response = &pending.unwrap()["response"];
+        assert_eq!(response["status"], 0);
+        assert_eq!(response["headers"], json!([]));
+        assert!(response["content"].get("text").is_none());
+        assert_eq!(response["bodySize"], -1);
+    }
+
+    #[test]
+    fn test_har_date_time() {
+        assert_eq!(har_date_time(0.0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(har_date_time(1_700_000_000_123.0), "2023-11-14T22:13:20.123Z");
+    }
+
+    // =========================================================================
+    // State Persistence Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_serialize_and_restore_state() {
+        let domain = NetworkDomain::new();
+        track(&domain, "req-1", "https://example.com/");
+        track(&domain, "req-2", "https://example.com/app.js");
+        domain.store_response_full(
+            "req-2".to_string(),
+            200,
+            HttpHeaders::new(),
+            "console.log(1)".to_string(),
+            false,
+            false,
+        );
+        domain.set_initiator("req-2", Initiator::parser());
+        domain.track_websocket("ws-1".to_string(), "wss://example.com/live".to_string());
+        domain.add_websocket_text_frame("ws-1", "hello".to_string(), true);
+        domain.add_cache(
+            "cache-1".to_string(),
+            "https://example.com".to_string(),
+            "v1".to_string(),
+        );
+
+        let state = domain.serialize_state();
+        assert_eq!(state["version"], STATE_VERSION);
+
+        let restored = NetworkDomain::new();
+        track(&restored, "stale", "https://stale.test/");
+        restored.restore_state(state).unwrap();
+
+        let mut original = domain.get_all_requests();
+        let mut loaded = restored.get_all_requests();
+        original.sort_by(|a, b| a.request_id.cmp(&b.request_id));
+        loaded.sort_by(|a, b| a.request_id.cmp(&b.request_id));
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&original).unwrap()
+        );
+        assert!(!restored.has_request("stale"));
+
+        assert_eq!(restored.get_websocket_frames("ws-1").unwrap().len(), 1);
+        let caches = restored
+            .request_cache_names(Some(json!({"securityOrigin": "https://example.com"})))
+            .await
+            .unwrap();
+        assert_eq!(caches["caches"][0]["cacheName"], "v1");
+    }
+
+    #[test]
+    fn test_restore_state_rejects_newer_version() {
+        let domain = NetworkDomain::new();
+        track(&domain, "kept", "https://example.com/");
+
+        let result = domain.restore_state(json!({"version": STATE_VERSION + 1, "requests": []}));
+        assert!(result.is_err());
+        assert!(domain.has_request("kept"));
+
+        // Missing sections restore as empty
+        domain.restore_state(json!({"version": STATE_VERSION})).unwrap();
+        assert!(domain.get_all_requests().is_empty());
+    }
+
+    // =========================================================================
+    // Security Isolation Tests
+    // =========================================================================
+
+    fn track_document(
+        domain: &NetworkDomain,
+        request_id: &str,
+        frame_id: &str,
+        headers: HttpHeaders,
+    ) {
+        domain.track_request_full(
+            request_id.to_string(),
+            "https://example.com/".to_string(),
+            "GET".to_string(),
+            HttpHeaders::new(),
+            None,
+            Some("Document".to_string()),
+        );
+        domain.set_request_frame_id(request_id, frame_id.to_string());
+        domain.store_response_full(
+            request_id.to_string(),
+            200,
+            headers,
+            "<html></html>".to_string(),
+            false,
+            false,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_security_isolation_status_from_headers() {
+        let domain = NetworkDomain::new();
+
+        let mut headers = HttpHeaders::new();
+        headers.insert("Cross-Origin-Embedder-Policy".to_string(), "require-corp".to_string());
+        headers.insert(
+            "cross-origin-opener-policy".to_string(),
+            "same-origin; report-to=\"coop-endpoint\"".to_string(),
+        );
+        track_document(&domain, "doc-1", "frame-1", headers);
+
+        let status = domain.security_isolation_status(Some("frame-1")).unwrap();
+        assert_eq!(status.coep.value, CrossOriginEmbedderPolicyValue::RequireCorp);
+        assert_eq!(status.coop.value, CrossOriginOpenerPolicyValue::SameOrigin);
+        assert_eq!(status.coop.reporting_endpoint.as_deref(), Some("coop-endpoint"));
+        assert_eq!(status.coop.report_only_value, CrossOriginOpenerPolicyValue::UnsafeNone);
+
+        let result = domain
+            .handle_method("getSecurityIsolationStatus", Some(json!({"frameId": "frame-1"})))
+            .await
+            .unwrap();
+        assert_eq!(result["status"]["coep"]["value"], "RequireCorp");
+        assert_eq!(result["status"]["coop"]["value"], "SameOrigin");
+        assert_eq!(result["status"]["coop"]["reportingEndpoint"], "coop-endpoint");
+    }
+
+    #[tokio::test]
+    async fn test_security_isolation_status_defaults() {
+        let domain = NetworkDomain::new();
+        track_document(&domain, "doc-2", "frame-2", HttpHeaders::new());
+
+        let status = domain.security_isolation_status(Some("frame-2")).unwrap();
+        assert_eq!(status, SecurityIsolationStatus::default());
+        assert_eq!(status.coep.value, CrossOriginEmbedderPolicyValue::None);
+        assert_eq!(status.coop.value, CrossOriginOpenerPolicyValue::UnsafeNone);
+        assert!(status.corp.is_none());
+
+        let params = json!({"frameId": "unknown-frame"});
+        let result = domain.get_security_isolation_status(Some(params)).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_handle_method_routing() {
         let domain = NetworkDomain::new();
@@ -92,6 +92,29 @@ async fn test_get_response_body_success() {
     assert_eq!(response["base64Encoded"], false);
 }
 
+#[test]
+fn test_response_body_chunks_reassemble_large_body() {
+    let domain = NetworkDomain::new();
+    let request_id = "large-body";
+    domain.track_request(
+        request_id.to_string(),
+        "https://example.com/dump.json".to_string(),
+        "GET".to_string(),
+    );
+
+    // Multi-byte characters straddle chunk boundaries
+    let body = "line of text é 🚀\n".repeat(200_000);
+    assert!(body.len() > 4 * 1024 * 1024);
+    domain.store_response_body(request_id.to_string(), body.clone(), false);
+
+    let chunks = domain.response_body_chunks(request_id, 64 * 1024).unwrap();
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 64 * 1024 + 3));
+    assert_eq!(chunks.concat(), body);
+
+    assert!(domain.response_body_chunks("unknown", 1024).is_none());
+}
+
 #[tokio::test]
 async fn test_get_response_body_base64() {
     // Test get_response_body for binary content (base64 encoded)
@@ -2,20 +2,36 @@
 //!
 //! Provides enhanced CPU profiling with sample-based profiling,
 //! call tree generation, and profile export capabilities.
-
-use parking_lot::RwLock;
-use std::collections::HashMap;
+//!
+//! Samples are normally fed with `add_sample`. When a `StackProvider` is
+//! registered, `start` also spawns a timer thread that captures a sample
+//! every sampling interval until `stop` or until every handle to the
+//! profiler is dropped.
+
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tracing::debug;
+use std::sync::{Arc, Weak};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
 
 use crate::types::{
     CallFrame, CallTreeNode, CpuSample, EnhancedProfileNode, ExportableProfile, PositionTickInfo,
+    Profile,
 };
 
+/// Source of call stacks for timer-driven sampling
+pub trait StackProvider: Send + Sync {
+    /// Capture the current call stack, outermost frame first
+    fn capture_stack(&self) -> Vec<CallFrame>;
+}
+
 /// CPU Profiler state and functionality
-#[derive(Debug)]
+///
+/// Clones share the same profiling state.
+#[derive(Clone)]
 pub struct CpuProfiler {
     /// Whether profiling is currently active
     profiling_active: Arc<AtomicBool>,
@@ -31,6 +47,53 @@ pub struct CpuProfiler {
     next_node_id: Arc<AtomicU32>,
     /// Profile title
     title: Arc<RwLock<Option<String>>>,
+    /// Stack provider for timer-driven sampling (opt-in)
+    stack_provider: Arc<RwLock<Option<Arc<dyn StackProvider>>>>,
+    /// Timer thread collecting samples while profiling
+    sampler_thread: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+/// Non-owning handle to a profiler's shared state
+///
+/// Held by the sampler thread so it doesn't keep a dropped profiler alive.
+struct WeakCpuProfiler {
+    profiling_active: Weak<AtomicBool>,
+    sampling_interval: Weak<AtomicU32>,
+    start_time: Weak<RwLock<f64>>,
+    samples: Weak<RwLock<Vec<CpuSample>>>,
+    nodes: Weak<RwLock<HashMap<u32, EnhancedProfileNode>>>,
+    next_node_id: Weak<AtomicU32>,
+    title: Weak<RwLock<Option<String>>>,
+    stack_provider: Weak<RwLock<Option<Arc<dyn StackProvider>>>>,
+    sampler_thread: Weak<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl WeakCpuProfiler {
+    /// Get a handle to the profiler, if any other handle is still alive
+    fn upgrade(&self) -> Option<CpuProfiler> {
+        Some(CpuProfiler {
+            profiling_active: self.profiling_active.upgrade()?,
+            sampling_interval: self.sampling_interval.upgrade()?,
+            start_time: self.start_time.upgrade()?,
+            samples: self.samples.upgrade()?,
+            nodes: self.nodes.upgrade()?,
+            next_node_id: self.next_node_id.upgrade()?,
+            title: self.title.upgrade()?,
+            stack_provider: self.stack_provider.upgrade()?,
+            sampler_thread: self.sampler_thread.upgrade()?,
+        })
+    }
+}
+
+impl fmt::Debug for CpuProfiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CpuProfiler")
+            .field("profiling_active", &self.is_profiling())
+            .field("sampling_interval", &self.get_sampling_interval())
+            .field("samples", &self.samples.read().len())
+            .field("timer_sampling", &self.stack_provider.read().is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl CpuProfiler {
@@ -44,6 +107,23 @@ impl CpuProfiler {
             nodes: Arc::new(RwLock::new(HashMap::new())),
             next_node_id: Arc::new(AtomicU32::new(1)),
             title: Arc::new(RwLock::new(None)),
+            stack_provider: Arc::new(RwLock::new(None)),
+            sampler_thread: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Non-owning handle to this profiler's state
+    fn downgrade(&self) -> WeakCpuProfiler {
+        WeakCpuProfiler {
+            profiling_active: Arc::downgrade(&self.profiling_active),
+            sampling_interval: Arc::downgrade(&self.sampling_interval),
+            start_time: Arc::downgrade(&self.start_time),
+            samples: Arc::downgrade(&self.samples),
+            nodes: Arc::downgrade(&self.nodes),
+            next_node_id: Arc::downgrade(&self.next_node_id),
+            title: Arc::downgrade(&self.title),
+            stack_provider: Arc::downgrade(&self.stack_provider),
+            sampler_thread: Arc::downgrade(&self.sampler_thread),
         }
     }
 
@@ -55,6 +135,19 @@ impl CpuProfiler {
             .unwrap_or(0.0)
     }
 
+    /// Time deltas between consecutive samples, the first relative to start
+    fn time_deltas(start_time: f64, samples: &[CpuSample]) -> Vec<u32> {
+        let mut previous = start_time;
+        samples
+            .iter()
+            .map(|sample| {
+                let delta = (sample.timestamp - previous).max(0.0) as u32;
+                previous = sample.timestamp;
+                delta
+            })
+            .collect()
+    }
+
     /// Check if profiling is active
     pub fn is_profiling(&self) -> bool {
         self.profiling_active.load(Ordering::SeqCst)
@@ -76,7 +169,22 @@ impl CpuProfiler {
         *self.title.write() = Some(title);
     }
 
+    /// Register a stack provider, enabling timer-driven sampling
+    ///
+    /// Takes effect on the next `start`.
+    pub fn set_stack_provider(&self, provider: Arc<dyn StackProvider>) {
+        *self.stack_provider.write() = Some(provider);
+    }
+
+    /// Remove the stack provider, returning to manual sampling
+    pub fn clear_stack_provider(&self) {
+        *self.stack_provider.write() = None;
+    }
+
     /// Start CPU profiling
+    ///
+    /// If a stack provider is registered, a timer thread samples it every
+    /// sampling interval until `stop` is called.
     pub fn start(&self) -> Result<(), String> {
         if self.profiling_active.load(Ordering::SeqCst) {
             return Err("Profiling already active".to_string());
@@ -109,9 +217,37 @@ impl CpuProfiler {
         *self.start_time.write() = Self::get_timestamp_micros();
         self.profiling_active.store(true, Ordering::SeqCst);
 
+        let provider = self.stack_provider.read().clone();
+        if let Some(provider) = provider {
+            self.spawn_sampler(provider);
+        }
+
         Ok(())
     }
 
+    /// Spawn the timer thread that samples `provider` while profiling
+    ///
+    /// The thread only holds the profiler between samples, so it exits once
+    /// profiling stops or every other handle is dropped.
+    fn spawn_sampler(&self, provider: Arc<dyn StackProvider>) {
+        let weak = self.downgrade();
+        let handle = std::thread::spawn(move || loop {
+            let interval = match weak.upgrade().filter(CpuProfiler::is_profiling) {
+                Some(profiler) => profiler.get_sampling_interval().max(1),
+                None => break,
+            };
+            std::thread::sleep(Duration::from_micros(interval as u64));
+            let Some(profiler) = weak.upgrade().filter(CpuProfiler::is_profiling) else {
+                break;
+            };
+            // May race with stop(); a rejected sample is simply dropped
+            let _ = profiler.add_sample(provider.capture_stack());
+        });
+
+        debug!("Started CPU profiler sampling thread");
+        *self.sampler_thread.lock() = Some(handle);
+    }
+
     /// Stop CPU profiling and return the profile
     pub fn stop(&self) -> Result<ExportableProfile, String> {
         if !self.profiling_active.load(Ordering::SeqCst) {
@@ -121,6 +257,13 @@ impl CpuProfiler {
         debug!("Stopping CPU profiler");
         self.profiling_active.store(false, Ordering::SeqCst);
 
+        let sampler = self.sampler_thread.lock().take();
+        if let Some(handle) = sampler {
+            if handle.join().is_err() {
+                warn!("CPU profiler sampling thread panicked");
+            }
+        }
+
         let start_time = *self.start_time.read();
         let end_time = Self::get_timestamp_micros();
         let samples = self.samples.read().clone();
@@ -133,10 +276,7 @@ impl CpuProfiler {
 
         // Extract sample data
         let sample_ids: Vec<u32> = samples.iter().map(|s| s.node_id).collect();
-        let time_deltas: Vec<u32> = samples
-            .windows(2)
-            .map(|w| (w[1].timestamp - w[0].timestamp) as u32)
-            .collect();
+        let time_deltas = Self::time_deltas(start_time, &samples);
 
         Ok(ExportableProfile {
             nodes: nodes_vec,
@@ -249,22 +389,15 @@ impl CpuProfiler {
     /// Generate a call tree from the current profile
     pub fn generate_call_tree(&self) -> CallTreeNode {
         let nodes = self.nodes.read();
-        let samples = self.samples.read();
-
-        // Calculate timing
-        let total_samples = samples.len() as f64;
         let sampling_interval = self.sampling_interval.load(Ordering::SeqCst) as f64;
-        let total_time = total_samples * sampling_interval;
 
         // Build tree recursively from root
-        self.build_call_tree_node(0, &nodes, total_time, sampling_interval)
+        Self::build_call_tree_node(0, &nodes, sampling_interval)
     }
 
     fn build_call_tree_node(
-        &self,
         node_id: u32,
         nodes: &HashMap<u32, EnhancedProfileNode>,
-        total_time: f64,
         sampling_interval: f64,
     ) -> CallTreeNode {
         let node = nodes.get(&node_id).cloned().unwrap_or_else(|| EnhancedProfileNode {
@@ -283,9 +416,7 @@ impl CpuProfiler {
             .children
             .unwrap_or_default()
             .iter()
-            .map(|&child_id| {
-                self.build_call_tree_node(child_id, nodes, total_time, sampling_interval)
-            })
+            .map(|&child_id| Self::build_call_tree_node(child_id, nodes, sampling_interval))
             .collect();
 
         let children_time: f64 = children.iter().map(|c| c.total_time).sum();
@@ -322,10 +453,7 @@ impl CpuProfiler {
         nodes_vec.sort_by_key(|n| n.id);
 
         let sample_ids: Vec<u32> = samples.iter().map(|s| s.node_id).collect();
-        let time_deltas: Vec<u32> = samples
-            .windows(2)
-            .map(|w| (w[1].timestamp - w[0].timestamp) as u32)
-            .collect();
+        let time_deltas = Self::time_deltas(start_time, &samples);
 
         let profile = ExportableProfile {
             nodes: nodes_vec,
@@ -359,7 +487,7 @@ impl CpuProfiler {
             })
             .filter(|(name, _)| !name.is_empty() && name != "(root)")
             .collect();
-        hot_functions.sort_by(|a, b| b.1.cmp(&a.1));
+        hot_functions.sort_by_key(|f| std::cmp::Reverse(f.1));
         hot_functions.truncate(10);
 
         ProfileStats {
@@ -390,6 +518,51 @@ pub struct ProfileStats {
     pub hot_functions: Vec<(String, u32)>,
 }
 
+impl ProfileStats {
+    /// Check a profile's internal consistency before export
+    ///
+    /// Every child and sample must reference an existing node, `samples` and
+    /// `timeDeltas` must be the same length, and some node must be the root
+    /// (not a child of any other node).
+    pub fn validate(&self, profile: &Profile) -> Result<(), String> {
+        let ids: HashSet<u32> = profile.nodes.iter().map(|n| n.id).collect();
+        if ids.len() != profile.nodes.len() {
+            return Err("Profile contains duplicate node ids".to_string());
+        }
+
+        let mut child_ids = HashSet::new();
+        for node in &profile.nodes {
+            for &child in node.children.iter().flatten() {
+                if !ids.contains(&child) {
+                    return Err(format!(
+                        "Node {} references missing child node {}",
+                        node.id, child
+                    ));
+                }
+                child_ids.insert(child);
+            }
+        }
+        if !ids.iter().any(|id| !child_ids.contains(id)) {
+            return Err("Profile has no root node".to_string());
+        }
+
+        let samples = profile.samples.as_deref().unwrap_or_default();
+        let time_deltas = profile.time_deltas.as_deref().unwrap_or_default();
+        if samples.len() != time_deltas.len() {
+            return Err(format!(
+                "Profile has {} samples but {} time deltas",
+                samples.len(),
+                time_deltas.len()
+            ));
+        }
+        if let Some((index, id)) = samples.iter().enumerate().find(|(_, id)| !ids.contains(id)) {
+            return Err(format!("Sample {} references unknown node {}", index, id));
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,11 +10,12 @@ use protocol_handler::DomainHandler;
 use serde_json::{json, Value};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 
 use crate::memory_profiler::MemoryProfiler;
-use crate::types::{HeapStatsUpdate, LastSeenObjectId, SamplingHeapProfile, SamplingHeapProfileNode};
+use crate::types::{
+    HeapStatsUpdate, LastSeenObjectId, RetainerPath, SamplingHeapProfile, SamplingHeapProfileNode,
+};
 
 /// Event callback type for heap profiler events
 pub type EventCallback = Arc<dyn Fn(&str, Value) + Send + Sync>;
@@ -69,12 +70,14 @@ impl HeapProfilerDomain {
         }
     }
 
-    /// Get current timestamp in microseconds
-    fn get_timestamp_micros() -> f64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_micros() as f64)
-            .unwrap_or(0.0)
+    /// Create a HeapProfilerDomain backed by a shared memory profiler
+    ///
+    /// Lets other domains, such as Runtime's `getHeapUsage`, read the same
+    /// allocation stats.
+    pub fn with_memory_profiler(memory_profiler: Arc<MemoryProfiler>) -> Self {
+        let mut domain = Self::new();
+        domain.memory_profiler = memory_profiler;
+        domain
     }
 
     /// Check if heap sampling is currently active
@@ -411,6 +414,35 @@ impl HeapProfilerDomain {
         }))
     }
 
+    /// Get the shortest retaining paths from a heap object to GC roots
+    ///
+    /// Uses the memory profiler's reference graph. A GC root yields an empty
+    /// list; unknown ids are an error.
+    pub fn get_retainers(&self, heap_object_id: &str) -> Result<Vec<RetainerPath>, CdpError> {
+        heap_object_id
+            .parse::<u64>()
+            .ok()
+            .and_then(|id| self.memory_profiler.get_retainers(id))
+            .ok_or_else(|| {
+                CdpError::server_error(-32000, format!("Heap object not found: {}", heap_object_id))
+            })
+    }
+
+    /// Handle the getRetainers method
+    fn handle_get_retainers(&self, params: Option<Value>) -> Result<Value, CdpError> {
+        debug!("HeapProfiler.getRetainers called");
+
+        let heap_object_id = params
+            .and_then(|p| {
+                p.get("heapObjectId")
+                    .and_then(|v| v.as_str().map(String::from))
+            })
+            .ok_or_else(|| CdpError::invalid_params("Missing heapObjectId parameter"))?;
+
+        let retainers = self.get_retainers(&heap_object_id)?;
+        Ok(json!({ "retainers": retainers }))
+    }
+
     /// Handle the getObjectByHeapObjectId method
     fn handle_get_object_by_heap_object_id(
         &self,
@@ -604,6 +636,7 @@ impl DomainHandler for HeapProfilerDomain {
             "startTrackingHeapObjects" => self.handle_start_tracking_heap_objects(params),
             "stopTrackingHeapObjects" => self.handle_stop_tracking_heap_objects(params),
             "addInspectedHeapObject" => self.handle_add_inspected_heap_object(params),
+            "getRetainers" => self.handle_get_retainers(params),
             _ => {
                 warn!("Unknown HeapProfiler method: {}", method);
                 Err(CdpError::method_not_found(format!(
@@ -18,7 +18,7 @@ mod profiler_domain;
 mod timeline_domain;
 mod types;
 
-pub use cpu_profiler::{CpuProfiler, ProfileStats};
+pub use cpu_profiler::{CpuProfiler, ProfileStats, StackProvider};
 pub use heap_profiler_domain::HeapProfilerDomain;
 pub use memory_profiler::{MemoryProfiler, MemoryStats};
 pub use profiler_domain::ProfilerDomain;
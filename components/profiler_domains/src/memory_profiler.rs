@@ -4,15 +4,17 @@
 //! basic leak detection capabilities.
 
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use runtime_debugger::{HeapUsage, MemoryProvider};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::debug;
 
 use crate::types::{
-    AllocationEntry, AllocationSummary, AllocationTrackingState, CallFrame, MemoryProfilerConfig,
-    MemorySnapshot, MemoryTimelineEntry, PotentialLeak,
+    AllocationEntry, AllocationSummary, AllocationTrackingState, CallFrame, HeapReferenceGraph,
+    MemoryProfilerConfig, MemorySnapshot, MemoryTimelineEntry, PotentialLeak, RetainerPath,
+    RetainerStep,
 };
 
 /// Memory Profiler for tracking allocations and detecting leaks
@@ -26,6 +28,8 @@ pub struct MemoryProfiler {
     next_alloc_id: Arc<AtomicU64>,
     /// Last timeline sample timestamp
     last_timeline_sample: Arc<RwLock<f64>>,
+    /// Object reference graph for retainer queries
+    graph: Arc<RwLock<HeapReferenceGraph>>,
 }
 
 impl MemoryProfiler {
@@ -36,6 +40,7 @@ impl MemoryProfiler {
             state: Arc::new(RwLock::new(AllocationTrackingState::default())),
             next_alloc_id: Arc::new(AtomicU64::new(1)),
             last_timeline_sample: Arc::new(RwLock::new(0.0)),
+            graph: Arc::new(RwLock::new(HeapReferenceGraph::default())),
         }
     }
 
@@ -84,6 +89,7 @@ impl MemoryProfiler {
             state.timeline.clear();
             state.total_allocated = 0;
             state.total_freed = 0;
+            state.peak_heap = 0;
         }
 
         self.next_alloc_id.store(1, Ordering::SeqCst);
@@ -148,6 +154,9 @@ impl MemoryProfiler {
             let mut state = self.state.write();
             state.allocations.insert(id, entry);
             state.total_allocated += size;
+            state.peak_heap = state
+                .peak_heap
+                .max(state.total_allocated - state.total_freed);
         }
 
         // Maybe record timeline entry
@@ -360,7 +369,7 @@ impl MemoryProfiler {
         }
 
         // Sort by leak score (highest first)
-        leaks.sort_by(|a, b| b.leak_score.cmp(&a.leak_score));
+        leaks.sort_by_key(|l| std::cmp::Reverse(l.leak_score));
 
         // Return top potential leaks
         leaks.truncate(50);
@@ -384,6 +393,7 @@ impl MemoryProfiler {
             total_allocated_bytes: state.total_allocated,
             total_freed_bytes: state.total_freed,
             current_heap_bytes: live_bytes,
+            heap_capacity_bytes: state.peak_heap,
             timeline_entries: state.timeline.len() as u64,
         }
     }
@@ -413,6 +423,80 @@ impl MemoryProfiler {
             .cloned()
             .collect()
     }
+
+    /// Register a heap object in the reference graph
+    pub fn add_heap_object(&self, id: u64, class_name: impl Into<String>) {
+        self.graph.write().class_names.insert(id, class_name.into());
+    }
+
+    /// Mark a registered heap object as a GC root
+    pub fn add_gc_root(&self, id: u64) {
+        self.graph.write().roots.insert(id);
+    }
+
+    /// Record that `from` holds `to` through the edge `edge_name`
+    pub fn add_reference(&self, from: u64, to: u64, edge_name: impl Into<String>) {
+        self.graph
+            .write()
+            .retainers
+            .entry(to)
+            .or_default()
+            .push((from, edge_name.into()));
+    }
+
+    /// Find the shortest retaining path from an object to each GC root
+    ///
+    /// Paths are ordered shortest first. A GC root has no retaining paths,
+    /// and neither does an object no root can reach. Returns `None` for an
+    /// unknown object.
+    pub fn get_retainers(&self, id: u64) -> Option<Vec<RetainerPath>> {
+        let graph = self.graph.read();
+        if !graph.class_names.contains_key(&id) {
+            return None;
+        }
+        if graph.roots.contains(&id) {
+            return Some(Vec::new());
+        }
+
+        // Breadth-first over incoming edges; `next_hop` points each visited
+        // retainer at the object it holds on the way back to `id`
+        let mut next_hop: HashMap<u64, (u64, &str)> = HashMap::new();
+        let mut visited = HashSet::from([id]);
+        let mut queue = VecDeque::from([id]);
+        let mut paths = Vec::new();
+
+        while let Some(current) = queue.pop_front() {
+            let incoming = match graph.retainers.get(&current) {
+                Some(incoming) => incoming,
+                None => continue,
+            };
+            for (retainer, edge_name) in incoming {
+                if !visited.insert(*retainer) {
+                    continue;
+                }
+                next_hop.insert(*retainer, (current, edge_name));
+
+                if graph.roots.contains(retainer) {
+                    let mut steps = Vec::new();
+                    let mut node = *retainer;
+                    while node != id {
+                        let (held, edge_name) = next_hop[&node];
+                        steps.push(RetainerStep {
+                            class_name: graph.class_names.get(&node).cloned().unwrap_or_default(),
+                            edge_name: edge_name.to_string(),
+                        });
+                        node = held;
+                    }
+                    steps.reverse();
+                    paths.push(RetainerPath { steps });
+                } else {
+                    queue.push_back(*retainer);
+                }
+            }
+        }
+
+        Some(paths)
+    }
 }
 
 impl Default for MemoryProfiler {
@@ -421,6 +505,19 @@ impl Default for MemoryProfiler {
     }
 }
 
+/// Lets a `RuntimeDomain` report tracked allocations through `getHeapUsage`
+///
+/// Live bytes are the used size and the heap's capacity is the total.
+impl MemoryProvider for MemoryProfiler {
+    fn heap_usage(&self) -> HeapUsage {
+        let stats = self.get_stats();
+        HeapUsage {
+            used_size: stats.current_heap_bytes,
+            total_size: stats.heap_capacity_bytes,
+        }
+    }
+}
+
 /// Memory statistics summary
 #[derive(Debug, Clone)]
 pub struct MemoryStats {
@@ -436,6 +533,8 @@ pub struct MemoryStats {
     pub total_freed_bytes: u64,
     /// Current heap size (live allocations)
     pub current_heap_bytes: u64,
+    /// Heap capacity: the largest live heap size reached while tracking
+    pub heap_capacity_bytes: u64,
     /// Number of timeline entries
     pub timeline_entries: u64,
 }
@@ -521,8 +620,10 @@ mod tests {
 
     #[test]
     fn test_potential_leak_detection() {
-        let mut config = MemoryProfilerConfig::default();
-        config.leak_threshold_age = 1.0; // Very short threshold for testing
+        let config = MemoryProfilerConfig {
+            leak_threshold_age: 1.0, // Very short threshold for testing
+            ..Default::default()
+        };
 
         let profiler = MemoryProfiler::with_config(config);
         profiler.start_tracking().unwrap();
@@ -570,8 +671,10 @@ mod tests {
 
     #[test]
     fn test_min_allocation_size_filter() {
-        let mut config = MemoryProfilerConfig::default();
-        config.min_allocation_size = 2048; // Only track allocations >= 2KB
+        let config = MemoryProfilerConfig {
+            min_allocation_size: 2048, // Only track allocations >= 2KB
+            ..Default::default()
+        };
 
         let profiler = MemoryProfiler::with_config(config);
         profiler.start_tracking().unwrap();
@@ -590,8 +693,10 @@ mod tests {
 
     #[test]
     fn test_memory_timeline() {
-        let mut config = MemoryProfilerConfig::default();
-        config.timeline_interval = 1.0; // 1 microsecond for testing
+        let config = MemoryProfilerConfig {
+            timeline_interval: 1.0, // 1 microsecond for testing
+            ..Default::default()
+        };
 
         let profiler = MemoryProfiler::with_config(config);
         profiler.start_tracking().unwrap();
@@ -86,11 +86,25 @@ impl ProfilerDomain {
     }
 
     /// Handle the disable method
+    ///
+    /// Any in-flight CPU profile or precise coverage session is stopped and its
+    /// data discarded, so a later enable starts from a clean state.
     fn handle_disable(&self) -> Result<Value, CdpError> {
         debug!("Profiler.disable called");
         self.enabled.store(false, Ordering::SeqCst);
-        self.profiling_active.store(false, Ordering::SeqCst);
-        self.coverage_active.store(false, Ordering::SeqCst);
+
+        if self.profiling_active.swap(false, Ordering::SeqCst) {
+            warn!("Profiler disabled while profiling was active; discarding profile");
+        }
+        if self.cpu_profiler.is_profiling() {
+            let _ = self.cpu_profiler.stop();
+        }
+
+        if self.coverage_active.swap(false, Ordering::SeqCst) {
+            warn!("Profiler disabled while precise coverage was active; discarding coverage");
+        }
+        self.coverage_data.write().clear();
+
         info!("Profiler domain disabled");
         Ok(json!({}))
     }
@@ -135,6 +149,13 @@ impl ProfilerDomain {
         debug!("Profiler.stop called");
 
         if !self.profiling_active.load(Ordering::SeqCst) {
+            if self.coverage_active.load(Ordering::SeqCst) {
+                // Don't let a stray stop look like it ended the coverage session
+                return Err(CdpError::new(
+                    -32600,
+                    "Profiling was not started; precise coverage is still active (use stopPreciseCoverage)",
+                ));
+            }
             return Err(CdpError::invalid_request());
         }
 
@@ -8,14 +8,16 @@ use cdp_types::CdpError;
 use parking_lot::RwLock;
 use protocol_handler::DomainHandler;
 use serde_json::{json, Value};
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, info, warn};
 
 use crate::types::{
-    FrameTiming, TimelineConfig, TimelineEvent, TimelineEventCategory,
-    TimelineMemorySnapshot, TimelineRecording,
+    FrameTiming, LayoutShift, LayoutShiftSource, TimelineConfig, TimelineEvent,
+    TimelineEventCategory, TimelineMemorySnapshot, TimelineRecording, TimelineScreenshot,
+    TimelineSummary,
 };
 
 /// TimelineDomain handles performance timeline recording
@@ -33,6 +35,10 @@ pub struct TimelineDomain {
     memory_snapshots: Arc<RwLock<Vec<TimelineMemorySnapshot>>>,
     /// Frame timing data
     frames: Arc<RwLock<Vec<FrameTiming>>>,
+    /// Filmstrip screenshots
+    screenshots: Arc<RwLock<VecDeque<TimelineScreenshot>>>,
+    /// Layout shift entries
+    layout_shifts: Arc<RwLock<Vec<LayoutShift>>>,
     /// Recording start time
     start_time: Arc<RwLock<f64>>,
     /// Last memory snapshot time
@@ -53,6 +59,8 @@ impl TimelineDomain {
             events: Arc::new(RwLock::new(Vec::new())),
             memory_snapshots: Arc::new(RwLock::new(Vec::new())),
             frames: Arc::new(RwLock::new(Vec::new())),
+            screenshots: Arc::new(RwLock::new(VecDeque::new())),
+            layout_shifts: Arc::new(RwLock::new(Vec::new())),
             start_time: Arc::new(RwLock::new(0.0)),
             last_memory_snapshot: Arc::new(RwLock::new(0.0)),
             event_counter: Arc::new(AtomicU64::new(0)),
@@ -123,12 +131,18 @@ impl TimelineDomain {
             if let Some(capture_memory) = p.get("includeCounters").and_then(|v| v.as_bool()) {
                 config.capture_memory = capture_memory;
             }
+
+            if let Some(max_screenshots) = p.get("maxScreenshots").and_then(|v| v.as_u64()) {
+                config.max_screenshots = max_screenshots as usize;
+            }
         }
 
         // Clear previous data
         self.events.write().clear();
         self.memory_snapshots.write().clear();
         self.frames.write().clear();
+        self.screenshots.write().clear();
+        self.layout_shifts.write().clear();
         self.event_counter.store(0, Ordering::SeqCst);
         self.frame_counter.store(0, Ordering::SeqCst);
 
@@ -161,6 +175,8 @@ impl TimelineDomain {
             events: self.events.read().clone(),
             memory_snapshots: self.memory_snapshots.read().clone(),
             frames: self.frames.read().clone(),
+            screenshots: self.screenshots.read().iter().cloned().collect(),
+            layout_shifts: self.layout_shifts.read().clone(),
         };
 
         info!("Timeline recording stopped with {} events", recording.events.len());
@@ -269,10 +285,7 @@ impl TimelineDomain {
         let frame_id = params
             .get("frameId")
             .and_then(|v| v.as_str())
-            .unwrap_or_else(|| {
-                // Generate a frame ID
-                "frame-auto"
-            })
+            .unwrap_or("frame-auto")
             .to_string();
 
         let now = Self::get_timestamp_micros();
@@ -329,6 +342,26 @@ impl TimelineDomain {
         Ok(json!({ "frames": frames }))
     }
 
+    /// Handle get screenshots
+    fn handle_get_screenshots(&self) -> Result<Value, CdpError> {
+        debug!("Timeline.getScreenshots called");
+
+        let screenshots: Vec<TimelineScreenshot> =
+            self.screenshots.read().iter().cloned().collect();
+        Ok(json!({ "screenshots": screenshots }))
+    }
+
+    /// Handle get layout shifts
+    fn handle_get_layout_shifts(&self) -> Result<Value, CdpError> {
+        debug!("Timeline.getLayoutShifts called");
+
+        let layout_shifts = self.layout_shifts.read().clone();
+        Ok(json!({
+            "layoutShifts": layout_shifts,
+            "cumulativeLayoutShift": self.cumulative_layout_shift()
+        }))
+    }
+
     /// Create a memory snapshot
     fn create_memory_snapshot(&self) -> TimelineMemorySnapshot {
         let timestamp = Self::get_timestamp_micros();
@@ -430,6 +463,60 @@ impl TimelineDomain {
         self.events.write().push(event);
     }
 
+    /// Record a filmstrip screenshot
+    ///
+    /// Once `max_screenshots` frames are retained, the oldest is evicted.
+    pub fn record_screenshot(&self, timestamp: f64, base64_data: impl Into<String>) {
+        if !self.is_recording() {
+            return;
+        }
+
+        let max_screenshots = self.config.read().max_screenshots;
+        if max_screenshots == 0 {
+            return;
+        }
+
+        let mut screenshots = self.screenshots.write();
+        while screenshots.len() >= max_screenshots {
+            screenshots.pop_front();
+        }
+        screenshots.push_back(TimelineScreenshot {
+            timestamp,
+            data: base64_data.into(),
+        });
+    }
+
+    /// Record a layout shift entry
+    pub fn record_layout_shift(
+        &self,
+        value: f64,
+        had_recent_input: bool,
+        sources: Vec<LayoutShiftSource>,
+    ) {
+        if !self.is_recording() {
+            return;
+        }
+
+        self.layout_shifts.write().push(LayoutShift {
+            timestamp: Self::get_timestamp_micros(),
+            value,
+            had_recent_input,
+            sources,
+        });
+    }
+
+    /// Cumulative Layout Shift of the recording
+    ///
+    /// Shifts that followed recent user input are excluded, as in Web Vitals.
+    pub fn cumulative_layout_shift(&self) -> f64 {
+        self.layout_shifts
+            .read()
+            .iter()
+            .filter(|shift| !shift.had_recent_input)
+            .map(|shift| shift.value)
+            .sum()
+    }
+
     /// Get the event count
     pub fn event_count(&self) -> usize {
         self.events.read().len()
@@ -444,6 +531,47 @@ impl TimelineDomain {
     pub fn memory_snapshot_count(&self) -> usize {
         self.memory_snapshots.read().len()
     }
+
+    /// Get the screenshot count
+    pub fn screenshot_count(&self) -> usize {
+        self.screenshots.read().len()
+    }
+
+    /// Summarize recorded events and frames
+    ///
+    /// Every category appears in the totals and counts, even with no events.
+    pub fn summary(&self) -> TimelineSummary {
+        let mut summary = TimelineSummary::default();
+        for category in [
+            TimelineEventCategory::Scripting,
+            TimelineEventCategory::Rendering,
+            TimelineEventCategory::Painting,
+            TimelineEventCategory::Loading,
+            TimelineEventCategory::Other,
+        ] {
+            summary.category_totals.insert(category, 0.0);
+            summary.category_counts.insert(category, 0);
+        }
+
+        let events = self.events.read();
+        for event in events.iter() {
+            *summary.category_totals.entry(event.category).or_insert(0.0) += event.duration;
+            *summary.category_counts.entry(event.category).or_insert(0) += 1;
+        }
+        summary.event_count = events.len();
+        drop(events);
+
+        let frames = self.frames.read();
+        summary.frame_count = frames.len();
+        if !frames.is_empty() {
+            let total: f64 = frames.iter().map(|f| f.duration).sum();
+            let dropped = frames.iter().filter(|f| f.dropped).count();
+            summary.average_frame_time = total / frames.len() as f64;
+            summary.dropped_frame_percentage = dropped as f64 * 100.0 / frames.len() as f64;
+        }
+
+        summary
+    }
 }
 
 impl Default for TimelineDomain {
@@ -472,6 +600,9 @@ impl DomainHandler for TimelineDomain {
             "getEvents" => self.handle_get_events(),
             "getMemorySnapshots" => self.handle_get_memory_snapshots(),
             "getFrames" => self.handle_get_frames(),
+            "getScreenshots" => self.handle_get_screenshots(),
+            "getLayoutShifts" => self.handle_get_layout_shifts(),
+            "getSummary" => Ok(json!({ "summary": self.summary() })),
             _ => {
                 warn!("Unknown Timeline method: {}", method);
                 Err(CdpError::method_not_found(format!("Timeline.{}", method)))
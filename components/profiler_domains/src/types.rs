@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // ============================================================================
 // Profiler Domain Types
@@ -411,18 +411,49 @@ pub struct AllocationTrackingState {
     pub total_allocated: u64,
     /// Total freed bytes
     pub total_freed: u64,
+    /// Largest live heap size reached (bytes)
+    pub peak_heap: u64,
     /// Timeline entries
     pub timeline: Vec<MemoryTimelineEntry>,
     /// Configuration
     pub config: MemoryProfilerConfig,
 }
 
+/// Object reference graph used to answer retainer queries
+#[derive(Debug, Clone, Default)]
+pub struct HeapReferenceGraph {
+    /// Class name of each known heap object
+    pub class_names: HashMap<u64, String>,
+    /// Objects that are GC roots
+    pub roots: HashSet<u64>,
+    /// Incoming references: target -> (retainer, edge name)
+    pub retainers: HashMap<u64, Vec<(u64, String)>>,
+}
+
+/// One hop of a retaining path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetainerStep {
+    /// Class of the retaining object
+    pub class_name: String,
+    /// Name of the edge holding the next object on the path
+    pub edge_name: String,
+}
+
+/// Retaining path from an object to a GC root, nearest retainer first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetainerPath {
+    /// Hops from the object's direct retainer up to the root
+    pub steps: Vec<RetainerStep>,
+}
+
 // ============================================================================
 // Timeline Domain Types (FEAT-034)
 // ============================================================================
 
 /// Event category for timeline recording
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TimelineEventCategory {
     /// Script execution events
@@ -434,15 +465,10 @@ pub enum TimelineEventCategory {
     /// Resource loading events
     Loading,
     /// Other/general events
+    #[default]
     Other,
 }
 
-impl Default for TimelineEventCategory {
-    fn default() -> Self {
-        Self::Other
-    }
-}
-
 impl std::fmt::Display for TimelineEventCategory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -598,6 +624,61 @@ impl FrameTiming {
     }
 }
 
+/// Aggregate statistics over recorded timeline data
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineSummary {
+    /// Total event duration per category, in microseconds
+    pub category_totals: HashMap<TimelineEventCategory, f64>,
+    /// Number of events per category
+    pub category_counts: HashMap<TimelineEventCategory, usize>,
+    /// Total number of recorded events
+    pub event_count: usize,
+    /// Number of recorded frames
+    pub frame_count: usize,
+    /// Mean frame duration (0 when no frames were recorded)
+    pub average_frame_time: f64,
+    /// Share of frames that were dropped, from 0 to 100
+    pub dropped_frame_percentage: f64,
+}
+
+/// Screenshot frame captured for the filmstrip
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineScreenshot {
+    /// Timestamp when the screenshot was captured
+    pub timestamp: f64,
+    /// Base64-encoded image data
+    pub data: String,
+}
+
+/// Element that moved during a layout shift (mirrors `LayoutShiftAttribution`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutShiftSource {
+    /// DOM node that moved, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<u32>,
+    /// Bounding rect before the shift as `[x, y, width, height]`
+    pub previous_rect: [f64; 4],
+    /// Bounding rect after the shift as `[x, y, width, height]`
+    pub current_rect: [f64; 4],
+}
+
+/// Layout shift entry contributing to Cumulative Layout Shift (CLS)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutShift {
+    /// Timestamp when the shift occurred
+    pub timestamp: f64,
+    /// Layout shift score
+    pub value: f64,
+    /// Whether the shift followed recent user input (excluded from CLS)
+    pub had_recent_input: bool,
+    /// Elements that moved
+    pub sources: Vec<LayoutShiftSource>,
+}
+
 /// Timeline recording configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -614,6 +695,8 @@ pub struct TimelineConfig {
     pub max_stack_depth: u32,
     /// Categories to record (empty = all)
     pub categories: Vec<TimelineEventCategory>,
+    /// Maximum screenshots retained for the filmstrip (oldest evicted first)
+    pub max_screenshots: usize,
 }
 
 impl Default for TimelineConfig {
@@ -625,6 +708,7 @@ impl Default for TimelineConfig {
             capture_stacks: true,
             max_stack_depth: 16,
             categories: vec![],
+            max_screenshots: 100,
         }
     }
 }
@@ -643,4 +727,10 @@ pub struct TimelineRecording {
     pub memory_snapshots: Vec<TimelineMemorySnapshot>,
     /// Frame timing information
     pub frames: Vec<FrameTiming>,
+    /// Filmstrip screenshots
+    #[serde(default)]
+    pub screenshots: Vec<TimelineScreenshot>,
+    /// Layout shift track
+    #[serde(default)]
+    pub layout_shifts: Vec<LayoutShift>,
 }
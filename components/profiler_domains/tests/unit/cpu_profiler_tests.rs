@@ -2,7 +2,10 @@
 //!
 //! These tests verify the enhanced CPU profiler implementation.
 
-use profiler_domains::{CallFrame, CpuProfiler, ProfileStats};
+use profiler_domains::{CallFrame, CpuProfiler, Profile, ProfileNode, StackProvider};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 #[test]
 fn test_cpu_profiler_creation() {
@@ -256,3 +259,176 @@ fn test_position_ticks() {
     assert_eq!(ticks[0].line, 42);
     assert_eq!(ticks[0].ticks, 5);
 }
+
+struct MockStackProvider {
+    captures: AtomicUsize,
+}
+
+impl StackProvider for MockStackProvider {
+    fn capture_stack(&self) -> Vec<CallFrame> {
+        self.captures.fetch_add(1, Ordering::SeqCst);
+        vec![CallFrame {
+            function_name: "tick".to_string(),
+            script_id: "1".to_string(),
+            url: "timer.js".to_string(),
+            line_number: 3,
+            column_number: 0,
+        }]
+    }
+}
+
+#[test]
+fn test_timer_sampling_collects_samples() {
+    let profiler = CpuProfiler::new();
+    let provider = Arc::new(MockStackProvider {
+        captures: AtomicUsize::new(0),
+    });
+    profiler.set_stack_provider(provider.clone());
+    profiler.set_sampling_interval(1000);
+
+    profiler.start().unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+    let profile = profiler.stop().unwrap();
+
+    let samples = profile.samples.unwrap();
+    assert!(samples.len() >= 3, "only {} samples", samples.len());
+    // A capture racing with stop() may be dropped
+    let captures = provider.captures.load(Ordering::SeqCst);
+    assert!(captures == samples.len() || captures == samples.len() + 1);
+    assert!(profile.nodes.iter().any(|n| n.call_frame.function_name == "tick"));
+
+    // No sampling after stop
+    std::thread::sleep(Duration::from_millis(5));
+    assert_eq!(provider.captures.load(Ordering::SeqCst), captures);
+}
+
+#[test]
+fn test_dropped_profiler_stops_timer_sampling() {
+    let profiler = CpuProfiler::new();
+    let provider = Arc::new(MockStackProvider {
+        captures: AtomicUsize::new(0),
+    });
+    profiler.set_stack_provider(provider.clone());
+    profiler.set_sampling_interval(1000);
+
+    profiler.start().unwrap();
+    std::thread::sleep(Duration::from_millis(10));
+    drop(profiler);
+
+    // The sampler thread exits and releases the profiler state with it
+    let deadline = std::time::Instant::now() + Duration::from_secs(1);
+    while Arc::strong_count(&provider) > 1 {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "sampler still running"
+        );
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    let captures = provider.captures.load(Ordering::SeqCst);
+    std::thread::sleep(Duration::from_millis(5));
+    assert_eq!(provider.captures.load(Ordering::SeqCst), captures);
+}
+
+#[test]
+fn test_no_timer_sampling_without_provider() {
+    let profiler = CpuProfiler::new();
+    profiler.set_sampling_interval(1000);
+
+    profiler.start().unwrap();
+    std::thread::sleep(Duration::from_millis(10));
+    let profile = profiler.stop().unwrap();
+
+    assert!(profile.samples.unwrap().is_empty());
+}
+
+fn node(id: u32, children: Option<Vec<u32>>) -> ProfileNode {
+    ProfileNode {
+        id,
+        call_frame: serde_json::json!({ "functionName": "f" }),
+        hit_count: Some(1),
+        children,
+        deopt_reason: None,
+        position_ticks: None,
+    }
+}
+
+fn well_formed_profile() -> Profile {
+    Profile {
+        nodes: vec![node(1, Some(vec![2, 3])), node(2, None), node(3, None)],
+        start_time: 0.0,
+        end_time: 300.0,
+        samples: Some(vec![2, 3, 2]),
+        time_deltas: Some(vec![100, 100, 100]),
+    }
+}
+
+#[test]
+fn test_validate_well_formed_profile() {
+    let stats = CpuProfiler::new().get_stats();
+    assert!(stats.validate(&well_formed_profile()).is_ok());
+}
+
+#[test]
+fn test_validate_rejects_dangling_child() {
+    let stats = CpuProfiler::new().get_stats();
+    let mut profile = well_formed_profile();
+    profile.nodes[0].children = Some(vec![2, 3, 9]);
+
+    let err = stats.validate(&profile).unwrap_err();
+    assert_eq!(err, "Node 1 references missing child node 9");
+}
+
+#[test]
+fn test_validate_rejects_mismatched_sample_and_delta_lengths() {
+    let stats = CpuProfiler::new().get_stats();
+    let mut profile = well_formed_profile();
+    profile.time_deltas = Some(vec![100, 100]);
+
+    let err = stats.validate(&profile).unwrap_err();
+    assert_eq!(err, "Profile has 3 samples but 2 time deltas");
+}
+
+#[test]
+fn test_validate_rejects_unknown_sample_id() {
+    let stats = CpuProfiler::new().get_stats();
+    let mut profile = well_formed_profile();
+    profile.samples = Some(vec![2, 7, 2]);
+
+    let err = stats.validate(&profile).unwrap_err();
+    assert_eq!(err, "Sample 1 references unknown node 7");
+}
+
+#[test]
+fn test_validate_rejects_missing_root() {
+    let stats = CpuProfiler::new().get_stats();
+    let mut profile = well_formed_profile();
+    profile.nodes[1].children = Some(vec![1]);
+
+    assert_eq!(
+        stats.validate(&profile).unwrap_err(),
+        "Profile has no root node"
+    );
+}
+
+#[test]
+fn test_stop_produces_one_time_delta_per_sample() {
+    let profiler = CpuProfiler::new();
+    profiler.start().unwrap();
+    for _ in 0..3 {
+        profiler
+            .add_sample(vec![CallFrame {
+                function_name: "work".to_string(),
+                script_id: "1".to_string(),
+                url: "work.js".to_string(),
+                line_number: 1,
+                column_number: 0,
+            }])
+            .unwrap();
+    }
+    let profile = profiler.stop().unwrap();
+
+    assert_eq!(
+        profile.samples.as_ref().unwrap().len(),
+        profile.time_deltas.as_ref().unwrap().len()
+    );
+}
@@ -2,7 +2,9 @@
 //!
 //! These tests verify the HeapProfilerDomain implementation following TDD principles.
 
-use profiler_domains::{HeapProfilerDomain, SamplingHeapProfile, SamplingHeapProfileNode};
+use profiler_domains::{
+    HeapProfilerDomain, RetainerStep, SamplingHeapProfile, SamplingHeapProfileNode,
+};
 use protocol_handler::DomainHandler;
 use serde_json::json;
 
@@ -288,3 +290,76 @@ fn test_sampling_heap_profile_serialization() {
     assert!(json["head"]["children"].is_array());
     assert_eq!(json["head"]["children"][0]["selfSize"], 512);
 }
+
+/// Window (root) -> App.state -> Store.items -> leaf
+fn retainer_graph() -> HeapProfilerDomain {
+    let heap_profiler = HeapProfilerDomain::new();
+    let profiler = heap_profiler.memory_profiler();
+    profiler.add_heap_object(1, "Window");
+    profiler.add_heap_object(2, "App");
+    profiler.add_heap_object(3, "Store");
+    profiler.add_heap_object(4, "Array");
+    profiler.add_gc_root(1);
+    profiler.add_reference(1, 2, "app");
+    profiler.add_reference(2, 3, "state");
+    profiler.add_reference(3, 4, "items");
+    heap_profiler
+}
+
+#[test]
+fn test_get_retainers_reports_path_to_root() {
+    let heap_profiler = retainer_graph();
+
+    let paths = heap_profiler.get_retainers("4").unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(
+        paths[0].steps,
+        vec![
+            RetainerStep {
+                class_name: "Store".to_string(),
+                edge_name: "items".to_string(),
+            },
+            RetainerStep {
+                class_name: "App".to_string(),
+                edge_name: "state".to_string(),
+            },
+            RetainerStep {
+                class_name: "Window".to_string(),
+                edge_name: "app".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_get_retainers_prefers_shortest_path() {
+    let heap_profiler = retainer_graph();
+    // A shortcut from the root makes the leaf reachable in two hops
+    let profiler = heap_profiler.memory_profiler();
+    profiler.add_heap_object(5, "Cache");
+    profiler.add_reference(1, 5, "cache");
+    profiler.add_reference(5, 4, "entry");
+
+    let paths = heap_profiler.get_retainers("4").unwrap();
+    assert_eq!(paths.len(), 1);
+    let classes: Vec<&str> = paths[0]
+        .steps
+        .iter()
+        .map(|s| s.class_name.as_str())
+        .collect();
+    assert_eq!(classes, vec!["Cache", "Window"]);
+}
+
+#[tokio::test]
+async fn test_get_retainers_root_and_unknown() {
+    let heap_profiler = retainer_graph();
+    assert!(heap_profiler.get_retainers("1").unwrap().is_empty());
+    assert!(heap_profiler.get_retainers("99").is_err());
+
+    let result = heap_profiler
+        .handle_method("getRetainers", Some(json!({ "heapObjectId": "3" })))
+        .await
+        .unwrap();
+    assert_eq!(result["retainers"][0]["steps"][0]["className"], "App");
+    assert_eq!(result["retainers"][0]["steps"][0]["edgeName"], "state");
+}
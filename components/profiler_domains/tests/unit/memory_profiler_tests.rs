@@ -3,7 +3,7 @@
 //! These tests verify the memory profiler implementation with allocation tracking
 //! and leak detection capabilities.
 
-use profiler_domains::{CallFrame, MemoryProfiler, MemoryProfilerConfig, MemoryStats};
+use profiler_domains::{CallFrame, MemoryProfiler, MemoryProfilerConfig};
 
 #[test]
 fn test_memory_profiler_creation() {
@@ -149,8 +149,10 @@ fn test_allocation_summaries_by_call_site() {
 
 #[test]
 fn test_min_allocation_size_filter() {
-    let mut config = MemoryProfilerConfig::default();
-    config.min_allocation_size = 2048;
+    let config = MemoryProfilerConfig {
+        min_allocation_size: 2048,
+        ..Default::default()
+    };
 
     let profiler = MemoryProfiler::with_config(config);
     profiler.start_tracking().unwrap();
@@ -169,8 +171,10 @@ fn test_min_allocation_size_filter() {
 
 #[test]
 fn test_potential_leak_detection() {
-    let mut config = MemoryProfilerConfig::default();
-    config.leak_threshold_age = 1.0; // Very short for testing
+    let config = MemoryProfilerConfig {
+        leak_threshold_age: 1.0, // Very short for testing
+        ..Default::default()
+    };
 
     let profiler = MemoryProfiler::with_config(config);
     profiler.start_tracking().unwrap();
@@ -191,8 +195,10 @@ fn test_potential_leak_detection() {
 
 #[test]
 fn test_memory_timeline() {
-    let mut config = MemoryProfilerConfig::default();
-    config.timeline_interval = 1.0; // 1 microsecond
+    let config = MemoryProfilerConfig {
+        timeline_interval: 1.0, // 1 microsecond
+        ..Default::default()
+    };
 
     let profiler = MemoryProfiler::with_config(config);
     profiler.start_tracking().unwrap();
@@ -237,9 +243,11 @@ fn test_force_gc() {
 fn test_config_modification() {
     let profiler = MemoryProfiler::new();
 
-    let mut new_config = MemoryProfilerConfig::default();
-    new_config.max_stack_depth = 8;
-    new_config.min_allocation_size = 4096;
+    let new_config = MemoryProfilerConfig {
+        max_stack_depth: 8,
+        min_allocation_size: 4096,
+        ..Default::default()
+    };
 
     profiler.set_config(new_config.clone());
 
@@ -250,8 +258,10 @@ fn test_config_modification() {
 
 #[test]
 fn test_stack_trace_truncation() {
-    let mut config = MemoryProfilerConfig::default();
-    config.max_stack_depth = 2;
+    let config = MemoryProfilerConfig {
+        max_stack_depth: 2,
+        ..Default::default()
+    };
 
     let profiler = MemoryProfiler::with_config(config);
     profiler.start_tracking().unwrap();
@@ -275,8 +285,10 @@ fn test_stack_trace_truncation() {
 
 #[test]
 fn test_disable_stack_traces() {
-    let mut config = MemoryProfilerConfig::default();
-    config.capture_stack_traces = false;
+    let config = MemoryProfilerConfig {
+        capture_stack_traces: false,
+        ..Default::default()
+    };
 
     let profiler = MemoryProfiler::with_config(config);
     profiler.start_tracking().unwrap();
@@ -301,7 +313,7 @@ fn test_memory_stats() {
     profiler.start_tracking().unwrap();
 
     let id1 = profiler.record_allocation(1024, vec![]).unwrap();
-    let id2 = profiler.record_allocation(2048, vec![]).unwrap();
+    let _id2 = profiler.record_allocation(2048, vec![]).unwrap();
     profiler.record_deallocation(id1).unwrap();
 
     let stats = profiler.get_stats();
@@ -311,4 +323,26 @@ fn test_memory_stats() {
     assert_eq!(stats.total_allocated_bytes, 3072);
     assert_eq!(stats.total_freed_bytes, 1024);
     assert_eq!(stats.current_heap_bytes, 2048);
+    assert_eq!(stats.heap_capacity_bytes, 3072);
+}
+
+#[tokio::test]
+async fn test_runtime_heap_usage_from_memory_profiler() {
+    use protocol_handler::DomainHandler;
+    use runtime_debugger::RuntimeDomain;
+    use std::sync::Arc;
+
+    let profiler = Arc::new(MemoryProfiler::new());
+    profiler.start_tracking().unwrap();
+    let id = profiler.record_allocation(1024, vec![]).unwrap();
+    profiler.record_deallocation(id).unwrap();
+    profiler.record_allocation(4096, vec![]).unwrap();
+
+    let runtime = RuntimeDomain::new();
+    runtime.set_memory_provider(profiler);
+
+    let result = runtime.handle_method("getHeapUsage", None).await.unwrap();
+    // Capacity, not the 5120 bytes ever allocated
+    assert_eq!(result["usedSize"], 4096);
+    assert_eq!(result["totalSize"], 4096);
 }
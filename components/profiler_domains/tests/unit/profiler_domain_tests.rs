@@ -69,6 +69,53 @@ async fn test_profiler_stop() {
     assert!(!profiler.is_profiling());
 }
 
+#[tokio::test]
+async fn test_profiler_stop_with_only_coverage_active() {
+    let profiler = ProfilerDomain::new();
+
+    let _ = profiler.handle_method("enable", None).await;
+    let _ = profiler.handle_method("startPreciseCoverage", None).await;
+
+    // stop without start is rejected and leaves coverage running
+    let err = profiler.handle_method("stop", None).await.unwrap_err();
+    assert_eq!(err.code, -32600);
+    assert!(err.message.contains("precise coverage is still active"));
+    assert!(profiler.is_coverage_active());
+
+    let result = profiler.handle_method("takePreciseCoverage", None).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_profiler_disable_while_everything_active() {
+    let profiler = ProfilerDomain::new();
+
+    let _ = profiler.handle_method("enable", None).await;
+    let _ = profiler.handle_method("start", None).await;
+    let _ = profiler.handle_method("startPreciseCoverage", None).await;
+    assert!(profiler.is_profiling());
+    assert!(profiler.cpu_profiler().is_profiling());
+
+    let result = profiler.handle_method("disable", None).await;
+    assert_eq!(result.unwrap(), json!({}));
+
+    assert!(!profiler.is_profiling());
+    assert!(!profiler.is_coverage_active());
+    assert!(!profiler.cpu_profiler().is_profiling());
+
+    // Nothing left to stop or take
+    assert!(profiler.handle_method("stop", None).await.is_err());
+    assert!(profiler
+        .handle_method("takePreciseCoverage", None)
+        .await
+        .is_err());
+
+    // Re-enabling allows a fresh profiling session
+    let _ = profiler.handle_method("enable", None).await;
+    assert!(profiler.handle_method("start", None).await.is_ok());
+    assert!(profiler.handle_method("stop", None).await.is_ok());
+}
+
 #[tokio::test]
 async fn test_profiler_start_precise_coverage() {
     let profiler = ProfilerDomain::new();
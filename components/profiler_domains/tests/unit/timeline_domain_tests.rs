@@ -1,8 +1,8 @@
 //! Unit tests for TimelineDomain (FEAT-034)
 
 use profiler_domains::{
-    FrameTiming, TimelineConfig, TimelineDomain, TimelineEvent, TimelineEventCategory,
-    TimelineMemorySnapshot, TimelineRecording,
+    FrameTiming, LayoutShiftSource, TimelineConfig, TimelineDomain, TimelineEvent,
+    TimelineEventCategory, TimelineMemorySnapshot, TimelineRecording,
 };
 use protocol_handler::DomainHandler;
 use serde_json::json;
@@ -416,6 +416,108 @@ async fn test_helper_methods_do_nothing_when_not_recording() {
     assert_eq!(domain.event_count(), 0);
 }
 
+// ============================================================================
+// Screenshot Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_record_screenshots_in_recording() {
+    let domain = TimelineDomain::new();
+    domain.handle_method("enable", None).await.unwrap();
+    domain.handle_method("start", None).await.unwrap();
+
+    domain.record_screenshot(1000.0, "aW1hZ2Ux");
+    domain.record_screenshot(2000.0, "aW1hZ2Uy");
+    domain.record_screenshot(3000.0, "aW1hZ2Uz");
+    assert_eq!(domain.screenshot_count(), 3);
+
+    let result = domain.handle_method("getScreenshots", None).await.unwrap();
+    let screenshots = result["screenshots"].as_array().unwrap();
+    assert_eq!(screenshots.len(), 3);
+    assert_eq!(screenshots[0]["timestamp"], 1000.0);
+    assert_eq!(screenshots[0]["data"], "aW1hZ2Ux");
+    assert_eq!(screenshots[2]["data"], "aW1hZ2Uz");
+
+    let result = domain.handle_method("stop", None).await.unwrap();
+    let recording: TimelineRecording = serde_json::from_value(result["timeline"].clone()).unwrap();
+    assert_eq!(recording.screenshots.len(), 3);
+    assert_eq!(recording.screenshots[1].timestamp, 2000.0);
+    assert_eq!(recording.screenshots[1].data, "aW1hZ2Uy");
+}
+
+#[tokio::test]
+async fn test_screenshot_cap_evicts_oldest() {
+    let domain = TimelineDomain::new();
+    domain.handle_method("enable", None).await.unwrap();
+    domain
+        .handle_method("start", Some(json!({ "maxScreenshots": 2 })))
+        .await
+        .unwrap();
+
+    domain.record_screenshot(1000.0, "aW1hZ2Ux");
+    domain.record_screenshot(2000.0, "aW1hZ2Uy");
+    domain.record_screenshot(3000.0, "aW1hZ2Uz");
+    assert_eq!(domain.screenshot_count(), 2);
+
+    let result = domain.handle_method("getScreenshots", None).await.unwrap();
+    let screenshots = result["screenshots"].as_array().unwrap();
+    assert_eq!(screenshots[0]["data"], "aW1hZ2Uy");
+    assert_eq!(screenshots[1]["data"], "aW1hZ2Uz");
+}
+
+#[tokio::test]
+async fn test_record_screenshot_when_not_recording() {
+    let domain = TimelineDomain::new();
+    domain.handle_method("enable", None).await.unwrap();
+
+    domain.record_screenshot(1000.0, "aW1hZ2Ux");
+    assert_eq!(domain.screenshot_count(), 0);
+}
+
+// ============================================================================
+// Layout Shift Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_cumulative_layout_shift_excludes_recent_input() {
+    let domain = TimelineDomain::new();
+    domain.handle_method("enable", None).await.unwrap();
+    domain.handle_method("start", None).await.unwrap();
+
+    let source = LayoutShiftSource {
+        node_id: Some(42),
+        previous_rect: [0.0, 100.0, 300.0, 50.0],
+        current_rect: [0.0, 180.0, 300.0, 50.0],
+    };
+    domain.record_layout_shift(0.15, false, vec![source]);
+    domain.record_layout_shift(0.4, true, vec![]);
+
+    assert!((domain.cumulative_layout_shift() - 0.15).abs() < f64::EPSILON);
+
+    let result = domain.handle_method("getLayoutShifts", None).await.unwrap();
+    assert_eq!(result["layoutShifts"].as_array().unwrap().len(), 2);
+    assert_eq!(result["cumulativeLayoutShift"], 0.15);
+
+    let result = domain.handle_method("stop", None).await.unwrap();
+    let shifts = result["timeline"]["layoutShifts"].as_array().unwrap();
+    assert_eq!(shifts.len(), 2);
+    assert_eq!(shifts[0]["hadRecentInput"], false);
+    assert_eq!(shifts[0]["sources"][0]["nodeId"], 42);
+    assert_eq!(shifts[1]["hadRecentInput"], true);
+
+    let recording: TimelineRecording = serde_json::from_value(result["timeline"].clone()).unwrap();
+    assert_eq!(recording.layout_shifts[1].value, 0.4);
+}
+
+#[tokio::test]
+async fn test_layout_shift_when_not_recording() {
+    let domain = TimelineDomain::new();
+    domain.handle_method("enable", None).await.unwrap();
+
+    domain.record_layout_shift(0.2, false, vec![]);
+    assert_eq!(domain.cumulative_layout_shift(), 0.0);
+}
+
 // ============================================================================
 // Unknown Method Test
 // ============================================================================
@@ -515,6 +617,7 @@ fn test_timeline_config_default() {
     assert!(config.capture_stacks);
     assert_eq!(config.max_stack_depth, 16);
     assert!(config.categories.is_empty());
+    assert_eq!(config.max_screenshots, 100);
 }
 
 #[test]
@@ -572,3 +675,71 @@ fn test_timeline_memory_snapshot_serialization() {
     assert_eq!(deserialized.timestamp, 1000.0);
     assert_eq!(deserialized.js_heap_size_used, 50_000_000);
 }
+
+// ============================================================================
+// Summary Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_summary_category_totals_and_dropped_frames() {
+    let domain = TimelineDomain::new();
+    domain.handle_method("enable", None).await.unwrap();
+    domain.handle_method("start", None).await.unwrap();
+
+    domain.record_scripting_event("FunctionCall", 100.0, None);
+    domain.record_scripting_event("EvaluateScript", 50.0, None);
+    domain.record_rendering_event("Layout", 30.0, None);
+
+    for (i, dropped) in [false, false, false, true].iter().enumerate() {
+        let start = i as f64 * 20.0;
+        domain
+            .handle_method(
+                "recordFrame",
+                Some(json!({
+                    "frameId": format!("frame-{}", i),
+                    "startTime": start,
+                    "endTime": start + 16.0,
+                    "dropped": dropped
+                })),
+            )
+            .await
+            .unwrap();
+    }
+
+    let summary = domain.summary();
+    assert_eq!(summary.category_totals[&TimelineEventCategory::Scripting], 150.0);
+    assert_eq!(summary.category_totals[&TimelineEventCategory::Rendering], 30.0);
+    assert_eq!(summary.category_totals[&TimelineEventCategory::Painting], 0.0);
+    assert_eq!(summary.category_counts[&TimelineEventCategory::Scripting], 2);
+    assert_eq!(summary.event_count, 3);
+    assert_eq!(summary.frame_count, 4);
+    assert_eq!(summary.average_frame_time, 16.0);
+    assert_eq!(summary.dropped_frame_percentage, 25.0);
+
+    let result = domain.handle_method("getSummary", None).await.unwrap();
+    assert_eq!(result["summary"]["categoryTotals"]["scripting"], 150.0);
+    assert_eq!(result["summary"]["droppedFramePercentage"], 25.0);
+}
+
+#[test]
+fn test_summary_empty() {
+    let summary = TimelineDomain::new().summary();
+    assert_eq!(summary.event_count, 0);
+    assert_eq!(summary.frame_count, 0);
+    assert_eq!(summary.average_frame_time, 0.0);
+    assert_eq!(summary.dropped_frame_percentage, 0.0);
+}
+
+#[test]
+fn test_recording_without_filmstrip_or_layout_shifts_deserializes() {
+    let recording: TimelineRecording = serde_json::from_value(json!({
+        "startTime": 0.0,
+        "endTime": 100.0,
+        "events": [],
+        "memorySnapshots": [],
+        "frames": []
+    }))
+    .unwrap();
+    assert!(recording.screenshots.is_empty());
+    assert!(recording.layout_shifts.is_empty());
+}
@@ -8,19 +8,29 @@
 //! - **FEAT-041**: Message Batching - Batch CDP messages for efficiency
 
 pub mod batching;
+pub mod policy;
 pub mod validation;
 
 use async_trait::async_trait;
-use cdp_types::{CdpError, CdpRequest, CdpResponse};
+use cdp_types::{CdpError, CdpEvent, CdpRequest, CdpResponse};
 use dashmap::DashMap;
+use futures::FutureExt;
+use parking_lot::RwLock;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use tracing::{debug, error, warn};
 
+pub use policy::MethodPolicy;
+
 // Re-export validation types
 pub use validation::{
-    validate_cdp_request, validate_cdp_request_detailed, validate_method_name,
+    json_nesting_depth, validate_cdp_request, validate_cdp_request_detailed, validate_method_name,
     MessageValidator, MessageValidatorConfig, ValidatedRequest, ValidationResult,
+    DEFAULT_MAX_PARAMS_DEPTH,
 };
 
 // Re-export batching types
@@ -28,6 +38,42 @@ pub use batching::{
     AsyncMessageBatcher, BatchConfig, BatchStats, BatchedEvent, EventBatch, MessageBatcher,
 };
 
+/// Result of a domain method together with events it triggered
+///
+/// Events are delivered to the client after the method's response, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandlerOutcome {
+    /// The method's return value
+    pub result: Value,
+    /// Events to emit after the response has been sent
+    pub events: Vec<CdpEvent>,
+}
+
+impl HandlerOutcome {
+    /// Create an outcome with no follow-up events
+    pub fn new(result: Value) -> Self {
+        Self {
+            result,
+            events: Vec::new(),
+        }
+    }
+
+    /// Add a follow-up event
+    pub fn with_event(mut self, method: impl Into<String>, params: Value) -> Self {
+        self.events.push(CdpEvent {
+            method: method.into(),
+            params,
+        });
+        self
+    }
+}
+
+impl From<Value> for HandlerOutcome {
+    fn from(result: Value) -> Self {
+        Self::new(result)
+    }
+}
+
 /// Trait that all domain handlers must implement
 ///
 /// Domain handlers provide implementations for specific CDP domains (e.g., DOM, Network, Runtime).
@@ -46,15 +92,89 @@ pub trait DomainHandler: Send + Sync {
     /// # Returns
     /// Result containing the method's return value or a CDP error
     async fn handle_method(&self, method: &str, params: Option<Value>) -> Result<Value, CdpError>;
+
+    /// Handle a method call that may also trigger events
+    ///
+    /// The default implementation delegates to `handle_method` and emits no
+    /// events. Override this for methods such as `Debugger.enable` that return
+    /// a result and then report existing state through events.
+    async fn handle_method_with_events(
+        &self,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<HandlerOutcome, CdpError> {
+        self.handle_method(method, params)
+            .await
+            .map(HandlerOutcome::from)
+    }
+
+    /// Methods this domain handles, without the domain prefix
+    ///
+    /// Used for introspection only. Defaults to `supported_methods`; override
+    /// this instead when method names are not `'static`.
+    fn methods(&self) -> Vec<&str> {
+        self.supported_methods()
+    }
+
+    /// Statically known methods this domain handles, for feature detection
+    ///
+    /// The default is empty, meaning the domain does not advertise its methods.
+    fn supported_methods(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+}
+
+/// Synthetic domain answering introspection requests such as
+/// `CDP.getSupportedMethods`, unless a handler registers under the same name
+pub const INTROSPECTION_DOMAIN: &str = "CDP";
+
+/// Hook run around every routed request
+///
+/// Middleware is invoked in registration order. `before` sees the request
+/// before it reaches a domain handler; returning `Err` short-circuits routing
+/// and the error becomes the response. `after` sees the handler's result,
+/// only for requests that passed every `before`.
+#[async_trait]
+pub trait ProtocolMiddleware: Send + Sync {
+    /// Inspect a request before it is routed
+    async fn before(&self, req: &CdpRequest) -> Result<(), CdpError>;
+
+    /// Observe the result of a routed request
+    async fn after(&self, req: &CdpRequest, res: &Result<Value, CdpError>);
+}
+
+/// Key for a session-scoped domain handler: (session ID, domain name)
+type SessionDomainKey = (String, String);
+
+/// A parsed request along with the flat-session `sessionId` it targets
+struct ParsedRequest {
+    request: CdpRequest,
+    session_id: Option<String>,
 }
 
 /// Main protocol handler that routes CDP messages to appropriate domain handlers
 ///
 /// The ProtocolHandler maintains a registry of domain handlers and routes incoming
 /// CDP requests to the appropriate handler based on the method name.
+///
+/// In flat-session mode a request carries a `sessionId`; it is routed to a
+/// handler registered for that session if there is one, otherwise to the
+/// global handler for the domain. Responses and events echo the `sessionId`.
 pub struct ProtocolHandler {
     /// Registry of domain handlers, keyed by domain name
     domains: Arc<DashMap<String, Arc<dyn DomainHandler>>>,
+    /// Domain handlers registered for a single session
+    session_domains: Arc<DashMap<SessionDomainKey, Arc<dyn DomainHandler>>>,
+    /// Middleware run around routing, in registration order
+    middleware: Arc<RwLock<Vec<Arc<dyn ProtocolMiddleware>>>>,
+    /// Restricts which methods are dispatched (no restriction when unset)
+    method_policy: Arc<RwLock<Option<MethodPolicy>>>,
+    /// Deepest params nesting accepted before a request is rejected
+    max_params_depth: Arc<AtomicUsize>,
+    /// Number of messages handled (including malformed ones)
+    requests_handled: Arc<AtomicU64>,
+    /// Number of events emitted to clients
+    events_emitted: Arc<AtomicU64>,
 }
 
 impl ProtocolHandler {
@@ -69,9 +189,36 @@ impl ProtocolHandler {
     pub fn new() -> Self {
         Self {
             domains: Arc::new(DashMap::new()),
+            session_domains: Arc::new(DashMap::new()),
+            middleware: Arc::new(RwLock::new(Vec::new())),
+            method_policy: Arc::new(RwLock::new(None)),
+            max_params_depth: Arc::new(AtomicUsize::new(DEFAULT_MAX_PARAMS_DEPTH)),
+            requests_handled: Arc::new(AtomicU64::new(0)),
+            events_emitted: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Number of messages handled since creation or the last reset
+    pub fn requests_handled(&self) -> u64 {
+        self.requests_handled.load(Ordering::Relaxed)
+    }
+
+    /// Number of events emitted since creation or the last reset
+    pub fn events_emitted(&self) -> u64 {
+        self.events_emitted.load(Ordering::Relaxed)
+    }
+
+    /// Zero the request and event counters
+    pub fn reset_counters(&self) {
+        self.requests_handled.store(0, Ordering::Relaxed);
+        self.events_emitted.store(0, Ordering::Relaxed);
+    }
+
+    /// Record that an event was emitted to clients
+    pub fn record_event(&self) {
+        self.events_emitted.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Register a domain handler
     ///
     /// # Arguments
@@ -101,6 +248,84 @@ impl ProtocolHandler {
         self.domains.remove(domain_name).map(|(_, v)| v)
     }
 
+    /// Restrict which methods are dispatched
+    ///
+    /// Blocked methods fail with `CdpError::method_blocked` (-32001) without
+    /// reaching a domain handler.
+    pub fn set_method_policy(&self, policy: MethodPolicy) {
+        *self.method_policy.write() = Some(policy);
+    }
+
+    /// Remove the method policy, allowing every method again
+    pub fn clear_method_policy(&self) {
+        *self.method_policy.write() = None;
+    }
+
+    /// Limit how deeply request params may nest
+    ///
+    /// Over-deep requests fail with -32600 before reaching a handler. Parsing
+    /// recurses once per level, so very large limits give up the stack
+    /// overflow protection this provides.
+    pub fn set_max_params_depth(&self, depth: usize) {
+        self.max_params_depth.store(depth, Ordering::Relaxed);
+    }
+
+    /// Deepest params nesting currently accepted
+    pub fn max_params_depth(&self) -> usize {
+        self.max_params_depth.load(Ordering::Relaxed)
+    }
+
+    /// Append a middleware; it runs after every middleware added before it
+    pub fn add_middleware(&self, middleware: Arc<dyn ProtocolMiddleware>) {
+        self.middleware.write().push(middleware);
+    }
+
+    /// Register a domain handler that only serves requests for `session_id`
+    ///
+    /// Takes precedence over a globally registered handler for the same domain.
+    pub fn register_domain_for_session(
+        &self,
+        session_id: impl Into<String>,
+        handler: Arc<dyn DomainHandler>,
+    ) {
+        let session_id = session_id.into();
+        let name = handler.name().to_string();
+        debug!("Registering domain handler {} for session {}", name, session_id);
+        self.session_domains.insert((session_id, name), handler);
+    }
+
+    /// Remove every domain handler registered for `session_id`
+    ///
+    /// # Returns
+    /// The number of handlers removed
+    pub fn unregister_session(&self, session_id: &str) -> usize {
+        let before = self.session_domains.len();
+        self.session_domains.retain(|(session, _), _| session != session_id);
+        before - self.session_domains.len()
+    }
+
+    /// Names of globally registered domains, sorted
+    pub fn registered_domain_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.domains.iter().map(|e| e.key().clone()).collect();
+        names.sort();
+        names
+    }
+
+    /// Map each registered domain to the methods it advertises
+    ///
+    /// Method lists come from `DomainHandler::methods` and are sorted.
+    pub fn describe(&self) -> HashMap<String, Vec<String>> {
+        self.domains
+            .iter()
+            .map(|entry| {
+                let mut methods: Vec<String> =
+                    entry.value().methods().into_iter().map(str::to_string).collect();
+                methods.sort();
+                (entry.key().clone(), methods)
+            })
+            .collect()
+    }
+
     /// Handle an incoming CDP message
     ///
     /// Parses the message, validates it, routes it to the appropriate domain handler,
@@ -116,48 +341,178 @@ impl ProtocolHandler {
     /// ```ignore
     /// let response = handler.handle_message(r#"{"id": 1, "method": "DOM.getDocument"}"#).await;
     /// ```
+    ///
+    /// Events returned alongside the result are dropped; use
+    /// `handle_message_with_sink` to deliver them.
     pub async fn handle_message(&self, message: &str) -> String {
+        let (response, events, _) = self.process_message(message).await;
+        if !events.is_empty() {
+            debug!("Dropping {} events (no event sink)", events.len());
+        }
+        response
+    }
+
+    /// Handle an incoming CDP message and send the output to `sink`
+    ///
+    /// The response is sent first, followed by any events the handler
+    /// returned, in order. Each delivered event is counted in `events_emitted`.
+    pub async fn handle_message_with_sink(
+        &self,
+        message: &str,
+        sink: &mpsc::UnboundedSender<String>,
+    ) {
+        let (response, events, session_id) = self.process_message(message).await;
+        if sink.send(response).is_err() {
+            warn!("Event sink closed before response was sent");
+            return;
+        }
+
+        for event in events {
+            let serialized = match to_string_with_session(&event, session_id.as_deref()) {
+                Ok(s) => s,
+                Err(e) => {
+                    error!("Failed to serialize event {}: {}", event.method, e);
+                    continue;
+                }
+            };
+            if sink.send(serialized).is_err() {
+                warn!("Event sink closed while sending events");
+                return;
+            }
+            self.record_event();
+        }
+    }
+
+    /// Parse, route and build the response for a message
+    ///
+    /// Also returns the request's `sessionId` so events can be tagged with it.
+    async fn process_message(&self, message: &str) -> (String, Vec<CdpEvent>, Option<String>) {
+        self.requests_handled.fetch_add(1, Ordering::Relaxed);
+
         // Parse the message
-        let request = match self.parse_request(message) {
+        let ParsedRequest {
+            request,
+            session_id,
+        } = match self.parse_request(message) {
             Ok(req) => req,
             Err(error) => {
-                return self.create_error_response(None, error);
+                return (self.create_error_response(None, error, None), Vec::new(), None);
             }
         };
 
         let request_id = request.id;
+        let session = session_id.as_deref();
 
         // Validate and route the request
-        match self.route_request(&request).await {
-            Ok(result) => self.create_success_response(request_id, result),
-            Err(error) => self.create_error_response(Some(request_id), error),
+        match self.route_with_middleware(&request, session).await {
+            Ok(outcome) => (
+                self.create_success_response(request_id, outcome.result, session),
+                outcome.events,
+                session_id,
+            ),
+            Err(error) => (
+                self.create_error_response(Some(request_id), error, session),
+                Vec::new(),
+                session_id,
+            ),
         }
     }
 
-    /// Parse a JSON string into a CDP request
-    fn parse_request(&self, message: &str) -> Result<CdpRequest, CdpError> {
+    /// Parse a JSON string into a CDP request and its optional `sessionId`
+    fn parse_request(&self, message: &str) -> Result<ParsedRequest, CdpError> {
+        // Reject over-deep params before the recursive parser sees them; the
+        // request object adds one level around params
+        let max_depth = self.max_params_depth();
+        if validation::json_nesting_depth(message) > max_depth + 1 {
+            warn!("Rejected request nested deeper than {}", max_depth);
+            return Err(validation::params_depth_error(max_depth));
+        }
+
         // First, try to parse as generic JSON to distinguish parse errors from invalid requests
-        let _json_check: Value = serde_json::from_str(message).map_err(|e| {
+        let json: Value = validation::parse_unbounded(message).map_err(|e| {
             error!("Invalid JSON: {}", e);
             CdpError::parse_error()
         })?;
 
+        let session_id = json
+            .get("sessionId")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
         // Then try to parse as CdpRequest
         // If this fails, it's because the JSON is valid but doesn't match the request schema
-        serde_json::from_str::<CdpRequest>(message).map_err(|e| {
+        let request = serde_json::from_value::<CdpRequest>(json).map_err(|e| {
             error!("Invalid CDP request structure: {}", e);
             CdpError::invalid_request()
+        })?;
+
+        Ok(ParsedRequest {
+            request,
+            session_id,
         })
     }
 
+    /// Route a request, running middleware before and after the handler
+    async fn route_with_middleware(
+        &self,
+        request: &CdpRequest,
+        session_id: Option<&str>,
+    ) -> Result<HandlerOutcome, CdpError> {
+        // Snapshot so middleware can be added while requests are in flight
+        let middleware = self.middleware.read().clone();
+        if middleware.is_empty() {
+            return self.route_request(request, session_id).await;
+        }
+
+        for mw in &middleware {
+            mw.before(request).await?;
+        }
+
+        let outcome = self.route_request(request, session_id).await;
+        let result = outcome
+            .as_ref()
+            .map(|o| o.result.clone())
+            .map_err(Clone::clone);
+        for mw in &middleware {
+            mw.after(request, &result).await;
+        }
+        outcome
+    }
+
+    /// Look up the handler for a domain, preferring one scoped to the session
+    fn find_handler(
+        &self,
+        domain_name: &str,
+        session_id: Option<&str>,
+    ) -> Option<Arc<dyn DomainHandler>> {
+        if let Some(session_id) = session_id {
+            let key = (session_id.to_string(), domain_name.to_string());
+            if let Some(handler) = self.session_domains.get(&key) {
+                return Some(handler.clone());
+            }
+        }
+        self.domains.get(domain_name).map(|h| h.clone())
+    }
+
     /// Validate and route a request to the appropriate domain handler
-    async fn route_request(&self, request: &CdpRequest) -> Result<Value, CdpError> {
+    async fn route_request(
+        &self,
+        request: &CdpRequest,
+        session_id: Option<&str>,
+    ) -> Result<HandlerOutcome, CdpError> {
         // Validate the request has required fields
         if request.method.is_empty() {
             warn!("Request missing method field");
             return Err(CdpError::invalid_request());
         }
 
+        if let Some(policy) = self.method_policy.read().as_ref() {
+            if !policy.permits(&request.method) {
+                warn!("Method blocked by policy: {}", request.method);
+                return Err(CdpError::method_blocked(&request.method));
+            }
+        }
+
         // Parse the method into domain and method name
         let (domain_name, method_name) = self.parse_method(&request.method)?;
 
@@ -167,19 +522,40 @@ impl ProtocolHandler {
         );
 
         // Look up the domain handler
-        let handler = self
-            .domains
-            .get(domain_name)
-            .ok_or_else(|| {
-                warn!("Domain not found: {}", domain_name);
-                CdpError::method_not_found(&request.method)
-            })?
-            .clone();
-
-        // Call the domain handler
-        handler
-            .handle_method(method_name, request.params.clone())
+        let handler = self.find_handler(domain_name, session_id);
+        if handler.is_none() && domain_name == INTROSPECTION_DOMAIN {
+            return self.handle_introspection(&request.method, method_name);
+        }
+        let handler = handler.ok_or_else(|| {
+            warn!("Domain not found: {}", domain_name);
+            CdpError::method_not_found(&request.method)
+        })?;
+
+        // Call the domain handler, turning a panic into an internal error so
+        // one faulty handler can't take the session down with it
+        AssertUnwindSafe(handler.handle_method_with_events(method_name, request.params.clone()))
+            .catch_unwind()
             .await
+            .unwrap_or_else(|_| {
+                error!("Handler for {} panicked", request.method);
+                Err(CdpError::internal_error("Internal error while handling request"))
+            })
+    }
+
+    /// Answer a request to the synthetic introspection domain
+    fn handle_introspection(
+        &self,
+        method: &str,
+        method_name: &str,
+    ) -> Result<HandlerOutcome, CdpError> {
+        match method_name {
+            "getSupportedMethods" => {
+                let domains = serde_json::to_value(self.describe())
+                    .map_err(|e| CdpError::internal_error(e.to_string()))?;
+                Ok(HandlerOutcome::new(serde_json::json!({ "domains": domains })))
+            }
+            _ => Err(CdpError::method_not_found(method)),
+        }
     }
 
     /// Parse a method string into domain name and method name
@@ -196,32 +572,38 @@ impl ProtocolHandler {
         Ok((parts[0], parts[1]))
     }
 
-    /// Create a success response
-    fn create_success_response(&self, id: u64, result: Value) -> String {
+    /// Create a success response, echoing the request's `sessionId`
+    fn create_success_response(&self, id: u64, result: Value, session_id: Option<&str>) -> String {
         let response = CdpResponse {
             id,
             result: Some(result),
             error: None,
         };
 
-        serde_json::to_string(&response).unwrap_or_else(|e| {
+        to_string_with_session(&response, session_id).unwrap_or_else(|e| {
             error!("Failed to serialize response: {}", e);
             self.create_error_response(
                 Some(id),
                 CdpError::internal_error("Failed to serialize response"),
+                session_id,
             )
         })
     }
 
-    /// Create an error response
-    fn create_error_response(&self, id: Option<u64>, error: CdpError) -> String {
+    /// Create an error response, echoing the request's `sessionId`
+    fn create_error_response(
+        &self,
+        id: Option<u64>,
+        error: CdpError,
+        session_id: Option<&str>,
+    ) -> String {
         let response = CdpResponse {
             id: id.unwrap_or(0),
             result: None,
             error: Some(error),
         };
 
-        serde_json::to_string(&response).unwrap_or_else(|e| {
+        to_string_with_session(&response, session_id).unwrap_or_else(|e| {
             error!("Failed to serialize error response: {}", e);
             // Fallback to a minimal error response
             format!(
@@ -232,6 +614,22 @@ impl ProtocolHandler {
     }
 }
 
+/// Serialize a response or event, adding `sessionId` when there is one
+fn to_string_with_session<T: serde::Serialize>(
+    message: &T,
+    session_id: Option<&str>,
+) -> serde_json::Result<String> {
+    let session_id = match session_id {
+        Some(session_id) => session_id,
+        None => return serde_json::to_string(message),
+    };
+    let mut value = serde_json::to_value(message)?;
+    if let Value::Object(map) = &mut value {
+        map.insert("sessionId".to_string(), Value::String(session_id.to_string()));
+    }
+    serde_json::to_string(&value)
+}
+
 impl Default for ProtocolHandler {
     fn default() -> Self {
         Self::new()
@@ -276,6 +674,61 @@ mod tests {
                 ))),
             }
         }
+
+        fn methods(&self) -> Vec<&str> {
+            vec!["test", "echo"]
+        }
+    }
+
+    // Mock domain that reports which instance served the request
+    struct TaggedDomainHandler {
+        name: &'static str,
+        tag: &'static str,
+    }
+
+    #[async_trait]
+    impl DomainHandler for TaggedDomainHandler {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn handle_method(
+            &self,
+            _method: &str,
+            _params: Option<Value>,
+        ) -> Result<Value, CdpError> {
+            Ok(json!({ "tag": self.tag }))
+        }
+    }
+
+    // Mock domain whose enable method reports existing scripts via an event
+    struct EventingDomainHandler;
+
+    #[async_trait]
+    impl DomainHandler for EventingDomainHandler {
+        fn name(&self) -> &str {
+            "Eventing"
+        }
+
+        async fn handle_method(
+            &self,
+            method: &str,
+            _params: Option<Value>,
+        ) -> Result<Value, CdpError> {
+            Err(CdpError::method_not_found(format!("Eventing.{}", method)))
+        }
+
+        async fn handle_method_with_events(
+            &self,
+            method: &str,
+            params: Option<Value>,
+        ) -> Result<HandlerOutcome, CdpError> {
+            match method {
+                "enable" => Ok(HandlerOutcome::new(json!({"debuggerId": "d1"}))
+                    .with_event("Eventing.scriptParsed", json!({"scriptId": "1"}))),
+                _ => self.handle_method(method, params).await.map(Into::into),
+            }
+        }
     }
 
     #[tokio::test]
@@ -307,6 +760,18 @@ mod tests {
         assert_eq!(handler.domains.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_describe_lists_domain_methods() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(TestDomainHandler::new("Test")));
+        handler.register_domain(Arc::new(EventingDomainHandler));
+
+        let description = handler.describe();
+        assert_eq!(description["Test"], vec!["echo", "test"]);
+        // Domains that do not override `methods` advertise nothing
+        assert!(description["Eventing"].is_empty());
+    }
+
     #[tokio::test]
     async fn test_parse_method() {
         let handler = ProtocolHandler::new();
@@ -381,6 +846,26 @@ mod tests {
         assert_eq!(response_json["error"]["code"], -32601);
     }
 
+    #[tokio::test]
+    async fn test_request_and_event_counters() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(TestDomainHandler::new("Test")));
+        assert_eq!(handler.requests_handled(), 0);
+
+        handler
+            .handle_message(r#"{"id": 1, "method": "Test.test"}"#)
+            .await;
+        handler.handle_message("not json").await;
+        assert_eq!(handler.requests_handled(), 2);
+
+        handler.record_event();
+        assert_eq!(handler.events_emitted(), 1);
+
+        handler.reset_counters();
+        assert_eq!(handler.requests_handled(), 0);
+        assert_eq!(handler.events_emitted(), 0);
+    }
+
     #[tokio::test]
     async fn test_handle_message_parse_error() {
         let handler = ProtocolHandler::new();
@@ -392,6 +877,50 @@ mod tests {
         assert_eq!(response_json["error"]["code"], -32700);
     }
 
+    fn nested_params(depth: usize) -> String {
+        // `params` itself is the outermost level
+        format!(
+            r#"{{"id": 7, "method": "Test.test", "params": {}{}}}"#,
+            r#"{"a": "#.repeat(depth - 1) + "{}",
+            "}".repeat(depth - 1)
+        )
+    }
+
+    #[tokio::test]
+    async fn test_params_depth_limit() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(TestDomainHandler::new("Test")));
+        assert_eq!(handler.max_params_depth(), DEFAULT_MAX_PARAMS_DEPTH);
+
+        let response = handler
+            .handle_message(&nested_params(DEFAULT_MAX_PARAMS_DEPTH))
+            .await;
+        let response_json: Value = serde_json::from_str(&response).unwrap();
+        assert!(response_json["error"].is_null(), "{}", response);
+
+        let response = handler
+            .handle_message(&nested_params(DEFAULT_MAX_PARAMS_DEPTH + 1))
+            .await;
+        let response_json: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response_json["error"]["code"], -32600);
+        assert_eq!(
+            response_json["error"]["data"]["details"],
+            format!(
+                "Params nesting depth exceeds maximum of {}",
+                DEFAULT_MAX_PARAMS_DEPTH
+            )
+        );
+
+        // Brackets inside strings don't count, and the limit is configurable
+        handler.set_max_params_depth(2);
+        let request = json!({"id": 8, "method": "Test.echo", "params": {"text": "[[[{{{"}});
+        let response = handler.handle_message(&request.to_string()).await;
+        assert!(serde_json::from_str::<Value>(&response).unwrap()["error"].is_null());
+        let response = handler.handle_message(&nested_params(3)).await;
+        let response_json: Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response_json["error"]["code"], -32600);
+    }
+
     #[tokio::test]
     async fn test_handle_message_invalid_method_format() {
         let handler = ProtocolHandler::new();
@@ -408,4 +937,332 @@ mod tests {
         assert!(response_json["error"].is_object());
         assert_eq!(response_json["error"]["code"], -32600);
     }
+
+    #[tokio::test]
+    async fn test_handler_outcome_events_follow_result() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(EventingDomainHandler));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        handler
+            .handle_message_with_sink(r#"{"id": 7, "method": "Eventing.enable"}"#, &tx)
+            .await;
+
+        let response: Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["result"]["debuggerId"], "d1");
+
+        let event: Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(event["method"], "Eventing.scriptParsed");
+        assert_eq!(event["params"]["scriptId"], "1");
+
+        assert!(rx.try_recv().is_err());
+        assert_eq!(handler.events_emitted(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_default_handler_outcome_has_no_events() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(TestDomainHandler::new("Test")));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        handler
+            .handle_message_with_sink(r#"{"id": 1, "method": "Test.test"}"#, &tx)
+            .await;
+
+        assert!(rx.try_recv().is_ok());
+        assert!(rx.try_recv().is_err());
+        assert_eq!(handler.events_emitted(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_session_scoped_routing() {
+        let handler = ProtocolHandler::new();
+        let dom = |tag| Arc::new(TaggedDomainHandler { name: "DOM", tag });
+        handler.register_domain(dom("global"));
+        handler.register_domain_for_session("session-a", dom("a"));
+        handler.register_domain_for_session("session-b", dom("b"));
+
+        for (session, tag) in [("session-a", "a"), ("session-b", "b"), ("session-c", "global")] {
+            let request = json!({"id": 1, "method": "DOM.getDocument", "sessionId": session});
+            let response: Value =
+                serde_json::from_str(&handler.handle_message(&request.to_string()).await).unwrap();
+            assert_eq!(response["result"]["tag"], tag);
+            assert_eq!(response["sessionId"], session);
+        }
+
+        // Requests without a sessionId use the global handler and omit it
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 2, "method": "DOM.getDocument"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["result"]["tag"], "global");
+        assert!(response.get("sessionId").is_none());
+
+        assert_eq!(handler.unregister_session("session-a"), 1);
+        let request = json!({"id": 3, "method": "DOM.getDocument", "sessionId": "session-a"});
+        let response: Value =
+            serde_json::from_str(&handler.handle_message(&request.to_string()).await).unwrap();
+        assert_eq!(response["result"]["tag"], "global");
+    }
+
+    #[tokio::test]
+    async fn test_session_id_echoed_on_errors_and_events() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain_for_session("s1", Arc::new(EventingDomainHandler));
+
+        let request = json!({"id": 4, "method": "Missing.method", "sessionId": "s1"});
+        let response: Value =
+            serde_json::from_str(&handler.handle_message(&request.to_string()).await).unwrap();
+        assert_eq!(response["error"]["code"], -32601);
+        assert_eq!(response["sessionId"], "s1");
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let request = json!({"id": 5, "method": "Eventing.enable", "sessionId": "s1"});
+        handler
+            .handle_message_with_sink(&request.to_string(), &tx)
+            .await;
+        let _response = rx.try_recv().unwrap();
+        let event: Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(event["method"], "Eventing.scriptParsed");
+        assert_eq!(event["sessionId"], "s1");
+    }
+
+    // Middleware that rejects listed methods and records what it saw
+    struct BlockingMiddleware {
+        blocked: Vec<&'static str>,
+        seen: parking_lot::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ProtocolMiddleware for BlockingMiddleware {
+        async fn before(&self, req: &CdpRequest) -> Result<(), CdpError> {
+            self.seen.lock().push(format!("before {}", req.method));
+            if self.blocked.contains(&req.method.as_str()) {
+                return Err(CdpError::server_error(-32000, format!("{} is blocked", req.method)));
+            }
+            Ok(())
+        }
+
+        async fn after(&self, req: &CdpRequest, res: &Result<Value, CdpError>) {
+            self.seen
+                .lock()
+                .push(format!("after {} ok={}", req.method, res.is_ok()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_rejects_blocked_methods() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(TestDomainHandler::new("Test")));
+        let middleware = Arc::new(BlockingMiddleware {
+            blocked: vec!["Test.echo"],
+            seen: parking_lot::Mutex::new(Vec::new()),
+        });
+        handler.add_middleware(middleware.clone());
+
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 1, "method": "Test.echo", "params": {"a": 1}}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["error"]["code"], -32000);
+        assert!(response.get("result").is_none());
+
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 2, "method": "Test.test"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["result"]["success"], true);
+
+        assert_eq!(
+            *middleware.seen.lock(),
+            vec!["before Test.echo", "before Test.test", "after Test.test ok=true"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_runs_in_order_and_sees_errors() {
+        let handler = ProtocolHandler::new();
+        let first = Arc::new(BlockingMiddleware {
+            blocked: vec![],
+            seen: parking_lot::Mutex::new(Vec::new()),
+        });
+        let second = Arc::new(BlockingMiddleware {
+            blocked: vec!["Missing.method"],
+            seen: parking_lot::Mutex::new(Vec::new()),
+        });
+        handler.add_middleware(first.clone());
+        handler.add_middleware(second.clone());
+
+        handler
+            .handle_message(r#"{"id": 1, "method": "Other.method"}"#)
+            .await;
+        assert_eq!(
+            *first.seen.lock(),
+            vec!["before Other.method", "after Other.method ok=false"]
+        );
+
+        // A rejection by the second middleware skips every `after`
+        handler
+            .handle_message(r#"{"id": 2, "method": "Missing.method"}"#)
+            .await;
+        assert_eq!(first.seen.lock().len(), 3);
+        assert_eq!(second.seen.lock().last().unwrap(), "before Missing.method");
+    }
+
+    #[tokio::test]
+    async fn test_method_policy_deny_list() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(TaggedDomainHandler { name: "Runtime", tag: "rt" }));
+        handler.register_domain(Arc::new(TaggedDomainHandler { name: "DOM", tag: "dom" }));
+        handler.set_method_policy(MethodPolicy::deny(["Runtime.evaluate"]));
+
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 1, "method": "Runtime.evaluate"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], -32001);
+        assert_eq!(response["error"]["message"], "Method blocked by policy");
+
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 2, "method": "DOM.getDocument"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["result"]["tag"], "dom");
+
+        handler.set_method_policy(MethodPolicy::deny(["Runtime.*"]));
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 3, "method": "Runtime.getProperties"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], -32001);
+
+        handler.clear_method_policy();
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 4, "method": "Runtime.evaluate"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["result"]["tag"], "rt");
+    }
+
+    // Mock domain advertising its methods through `supported_methods`
+    struct AdvertisingDomainHandler;
+
+    #[async_trait]
+    impl DomainHandler for AdvertisingDomainHandler {
+        fn name(&self) -> &str {
+            "Advertising"
+        }
+
+        async fn handle_method(
+            &self,
+            method: &str,
+            _params: Option<Value>,
+        ) -> Result<Value, CdpError> {
+            Err(CdpError::method_not_found(method))
+        }
+
+        fn supported_methods(&self) -> Vec<&'static str> {
+            vec!["enable", "disable"]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_domain_names() {
+        let handler = ProtocolHandler::new();
+        assert!(handler.registered_domain_names().is_empty());
+
+        handler.register_domain(Arc::new(TestDomainHandler::new("Test")));
+        handler.register_domain(Arc::new(AdvertisingDomainHandler));
+        assert_eq!(handler.registered_domain_names(), vec!["Advertising", "Test"]);
+    }
+
+    #[tokio::test]
+    async fn test_cdp_get_supported_methods() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(AdvertisingDomainHandler));
+        handler.register_domain(Arc::new(EventingDomainHandler));
+
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 1, "method": "CDP.getSupportedMethods"}"#)
+                .await,
+        )
+        .unwrap();
+        let domains = &response["result"]["domains"];
+        assert_eq!(domains["Advertising"], json!(["disable", "enable"]));
+        assert_eq!(domains["Eventing"], json!([]));
+
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 2, "method": "CDP.unknown"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], -32601);
+    }
+
+    // Mock domain whose handler panics
+    struct PanickingDomainHandler;
+
+    #[async_trait]
+    impl DomainHandler for PanickingDomainHandler {
+        fn name(&self) -> &str {
+            "Panicking"
+        }
+
+        async fn handle_method(
+            &self,
+            method: &str,
+            _params: Option<Value>,
+        ) -> Result<Value, CdpError> {
+            match method {
+                "explode" => panic!("handler bug"),
+                _ => Ok(json!({})),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handler_panic_becomes_internal_error() {
+        let handler = ProtocolHandler::new();
+        handler.register_domain(Arc::new(PanickingDomainHandler));
+
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 1, "method": "Panicking.explode"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["error"]["code"], -32603);
+        assert!(!response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("handler bug"));
+
+        // The handler keeps serving requests afterwards
+        let response: Value = serde_json::from_str(
+            &handler
+                .handle_message(r#"{"id": 2, "method": "Panicking.ok"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["result"], json!({}));
+    }
 }
@@ -0,0 +1,87 @@
+//! Method allow/deny policy
+//!
+//! Restricts which CDP methods a `ProtocolHandler` dispatches. Entries are
+//! full method names (`Runtime.evaluate`), domain wildcards (`Runtime.*`) or
+//! `*` for every method.
+
+use std::collections::HashSet;
+
+/// Which CDP methods may be called
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MethodPolicy {
+    /// Only methods matching an entry may be called
+    AllowList(HashSet<String>),
+    /// Methods matching an entry are blocked; everything else may be called
+    DenyList(HashSet<String>),
+}
+
+impl MethodPolicy {
+    /// Build an allow-list policy from method patterns
+    pub fn allow<I, S>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::AllowList(entries.into_iter().map(Into::into).collect())
+    }
+
+    /// Build a deny-list policy from method patterns
+    pub fn deny<I, S>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::DenyList(entries.into_iter().map(Into::into).collect())
+    }
+
+    /// Whether `method` (in `Domain.method` form) may be dispatched
+    pub fn permits(&self, method: &str) -> bool {
+        match self {
+            Self::AllowList(entries) => matches_any(entries, method),
+            Self::DenyList(entries) => !matches_any(entries, method),
+        }
+    }
+}
+
+/// Whether any entry names `method`, directly or through a wildcard
+fn matches_any(entries: &HashSet<String>, method: &str) -> bool {
+    if entries.contains(method) || entries.contains("*") {
+        return true;
+    }
+    match method.split_once('.') {
+        Some((domain, _)) => entries.contains(&format!("{}.*", domain)),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deny_list() {
+        let policy = MethodPolicy::deny(["Runtime.evaluate"]);
+        assert!(!policy.permits("Runtime.evaluate"));
+        assert!(policy.permits("Runtime.getProperties"));
+        assert!(policy.permits("DOM.getDocument"));
+    }
+
+    #[test]
+    fn test_allow_list() {
+        let policy = MethodPolicy::allow(["DOM.getDocument"]);
+        assert!(policy.permits("DOM.getDocument"));
+        assert!(!policy.permits("DOM.removeNode"));
+    }
+
+    #[test]
+    fn test_domain_wildcards() {
+        let policy = MethodPolicy::deny(["Runtime.*"]);
+        assert!(!policy.permits("Runtime.evaluate"));
+        assert!(!policy.permits("Runtime.callFunctionOn"));
+        assert!(policy.permits("RuntimeExtra.evaluate"));
+        assert!(policy.permits("DOM.getDocument"));
+
+        let policy = MethodPolicy::allow(["*"]);
+        assert!(policy.permits("Anything.goes"));
+    }
+}
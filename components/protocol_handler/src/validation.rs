@@ -4,9 +4,13 @@
 //! messages, ensuring they conform to the protocol specification.
 
 use cdp_types::CdpError;
+use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashSet;
 
+/// Default maximum nesting depth of request params
+pub const DEFAULT_MAX_PARAMS_DEPTH: usize = 256;
+
 /// Known CDP domains for validation
 static KNOWN_DOMAINS: &[&str] = &[
     "Browser",
@@ -62,6 +66,8 @@ pub struct MessageValidatorConfig {
     pub max_method_length: usize,
     /// Maximum params size in bytes (when serialized)
     pub max_params_size: usize,
+    /// Maximum nesting depth of params (each object or array is one level)
+    pub max_params_depth: usize,
     /// Whether to enforce known domains only
     pub enforce_known_domains: bool,
     /// Whether to allow empty params
@@ -75,6 +81,7 @@ impl Default for MessageValidatorConfig {
         Self {
             max_method_length: 256,
             max_params_size: 10 * 1024 * 1024, // 10MB
+            max_params_depth: DEFAULT_MAX_PARAMS_DEPTH,
             enforce_known_domains: false,
             allow_empty_params: true,
             custom_domains: vec![],
@@ -144,7 +151,11 @@ impl MessageValidator {
 
     /// Validate a raw JSON string as a CDP request
     pub fn validate_request_str(&self, json_str: &str) -> Result<ValidatedRequest, CdpError> {
-        let json: Value = serde_json::from_str(json_str).map_err(|e| {
+        // The request object adds one level around params
+        if json_nesting_depth(json_str) > self.config.max_params_depth + 1 {
+            return Err(params_depth_error(self.config.max_params_depth));
+        }
+        let json: Value = parse_unbounded(json_str).map_err(|e| {
             CdpError::with_data(
                 -32700,
                 "Parse error",
@@ -344,6 +355,9 @@ impl MessageValidator {
                         }),
                     ));
                 }
+                if json_nesting_depth(&params_str) > self.config.max_params_depth {
+                    return Err(params_depth_error(self.config.max_params_depth));
+                }
                 Ok(Some(params.clone()))
             }
             Value::Null => {
@@ -397,6 +411,62 @@ pub struct ValidatedRequest {
     pub params: Option<Value>,
 }
 
+/// Deepest nesting of objects and arrays in a JSON text
+///
+/// Scans without recursing, so it is safe on arbitrarily deep input.
+/// Brackets inside strings are ignored.
+pub fn json_nesting_depth(text: &str) -> usize {
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for byte in text.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    max_depth
+}
+
+/// Error for params nested deeper than `max_depth`
+pub(crate) fn params_depth_error(max_depth: usize) -> CdpError {
+    CdpError::with_data(
+        -32600,
+        "Invalid Request",
+        serde_json::json!({
+            "details": format!("Params nesting depth exceeds maximum of {}", max_depth)
+        }),
+    )
+}
+
+/// Parse JSON without serde_json's built-in recursion limit
+///
+/// Only call after checking `json_nesting_depth`; the parser recurses once
+/// per level.
+pub(crate) fn parse_unbounded(text: &str) -> serde_json::Result<Value> {
+    let mut deserializer = serde_json::Deserializer::from_str(text);
+    deserializer.disable_recursion_limit();
+    let value = Value::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
 /// Quick validation function for CDP requests
 pub fn validate_cdp_request(json_str: &str) -> Result<ValidatedRequest, CdpError> {
     let validator = MessageValidator::default();
@@ -752,6 +822,45 @@ mod tests {
         assert!(validator.validate_request(&long).is_err());
     }
 
+    #[test]
+    fn test_max_params_depth() {
+        let validator = MessageValidator::new(MessageValidatorConfig {
+            max_params_depth: 3,
+            ..Default::default()
+        });
+
+        let at_limit = json!({"id": 1, "method": "DOM.setAttribute", "params": {"a": [{}]}});
+        assert!(validator.validate_request(&at_limit).is_ok());
+        assert!(validator
+            .validate_request_str(&at_limit.to_string())
+            .is_ok());
+
+        let too_deep = json!({"id": 1, "method": "DOM.setAttribute", "params": {"a": [{"b": []}]}});
+        let error = validator.validate_request(&too_deep).unwrap_err();
+        assert_eq!(error.code, -32600);
+        let error = validator
+            .validate_request_str(&too_deep.to_string())
+            .unwrap_err();
+        assert_eq!(error.code, -32600);
+
+        // Far beyond serde_json's own recursion limit, still a clean rejection
+        let deep = format!(
+            r#"{{"id": 1, "method": "DOM.x", "params": {}}}"#,
+            "[".repeat(100_000)
+        );
+        let error = MessageValidator::default()
+            .validate_request_str(&deep)
+            .unwrap_err();
+        assert_eq!(error.code, -32600);
+    }
+
+    #[test]
+    fn test_json_nesting_depth() {
+        assert_eq!(json_nesting_depth("1"), 0);
+        assert_eq!(json_nesting_depth(r#"{"a": [1, {"b": {}}]}"#), 4);
+        assert_eq!(json_nesting_depth(r#"{"a": "[[{\"{"}"#), 1);
+    }
+
     #[test]
     fn test_disallow_empty_params() {
         let config = MessageValidatorConfig {
@@ -43,8 +43,9 @@ impl DomainHandler for MockDomainHandler {
 
 #[tokio::test]
 async fn test_protocol_handler_creation() {
-    let _handler = ProtocolHandler::new();
-    assert!(true, "ProtocolHandler should be created successfully");
+    let handler = ProtocolHandler::new();
+    let response = handler.handle_message("not json").await;
+    assert!(response.contains("-32700"));
 }
 
 #[tokio::test]
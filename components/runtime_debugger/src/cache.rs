@@ -2,7 +2,9 @@
 //!
 //! Implements caching for RemoteObject references with:
 //! - LRU (Least Recently Used) eviction policy
+//! - Optional TTL-based expiry
 //! - Object group management
+//! - Bulk release by execution context
 //! - Configurable cache size
 //! - Thread-safe access
 
@@ -30,6 +32,8 @@ pub struct CacheEntry {
     pub value: Value,
     /// Object group this entry belongs to
     pub group: Option<String>,
+    /// Execution context the object lives in (if known)
+    pub execution_context_id: Option<u32>,
     /// When this entry was created
     pub created_at: Instant,
     /// When this entry was last accessed
@@ -46,6 +50,7 @@ impl CacheEntry {
             object,
             value,
             group,
+            execution_context_id: None,
             created_at: now,
             last_accessed: now,
             access_count: 1,
@@ -69,8 +74,8 @@ impl CacheEntry {
 pub struct CacheConfig {
     /// Maximum number of entries in the cache
     pub max_size: usize,
-    /// Time-to-live for cached entries
-    pub ttl: Duration,
+    /// Time-to-live for cached entries (`None` keeps entries until evicted)
+    pub ttl: Option<Duration>,
     /// Whether to track access statistics
     pub track_stats: bool,
 }
@@ -79,7 +84,7 @@ impl Default for CacheConfig {
     fn default() -> Self {
         Self {
             max_size: DEFAULT_MAX_SIZE,
-            ttl: Duration::from_secs(DEFAULT_TTL_SECS),
+            ttl: Some(Duration::from_secs(DEFAULT_TTL_SECS)),
             track_stats: true,
         }
     }
@@ -94,8 +99,8 @@ pub struct CacheStats {
     pub misses: u64,
     /// Number of entries evicted
     pub evictions: u64,
-    /// Number of entries expired
-    pub expirations: u64,
+    /// Number of entries purged for outliving the TTL
+    pub expired_count: u64,
     /// Current cache size
     pub size: usize,
 }
@@ -155,14 +160,33 @@ impl RemoteObjectCache {
 
     /// Insert an object into the cache
     pub fn insert(&self, object: RemoteObject, value: Value, group: Option<String>) -> Option<RemoteObjectId> {
-        let object_id = object.object_id.clone()?;
+        let entry = CacheEntry::new(object, value, group);
+        self.insert_entry(entry)
+    }
+
+    /// Insert an object belonging to an execution context
+    ///
+    /// Entries inserted this way are dropped by `release_context`.
+    pub fn insert_in_context(
+        &self,
+        object: RemoteObject,
+        value: Value,
+        group: Option<String>,
+        execution_context_id: u32,
+    ) -> Option<RemoteObjectId> {
+        let mut entry = CacheEntry::new(object, value, group);
+        entry.execution_context_id = Some(execution_context_id);
+        self.insert_entry(entry)
+    }
+
+    /// Insert a prepared entry, evicting first if at capacity
+    fn insert_entry(&self, entry: CacheEntry) -> Option<RemoteObjectId> {
+        let object_id = entry.object.object_id.clone()?;
         let id_str = object_id.0.clone();
+        let group = entry.group.clone();
 
         debug!("Caching object: {}", id_str);
 
-        // Create cache entry
-        let entry = CacheEntry::new(object, value, group.clone());
-
         // Evict if necessary
         self.evict_if_needed();
 
@@ -185,7 +209,7 @@ impl RemoteObjectCache {
             let mut groups = self.groups.write();
             groups
                 .entry(group_name)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(id_str);
         }
 
@@ -206,13 +230,13 @@ impl RemoteObjectCache {
         {
             let entries = self.entries.read();
             if let Some(entry) = entries.get(id_str) {
-                if entry.is_expired(self.config.ttl) {
+                if self.is_stale(entry) {
                     drop(entries);
                     self.remove(object_id);
                     self.misses.fetch_add(1, Ordering::Relaxed);
                     {
                         let mut stats = self.stats.write();
-                        stats.expirations += 1;
+                        stats.expired_count += 1;
                         stats.misses = self.misses.load(Ordering::Relaxed);
                     }
                     return None;
@@ -253,7 +277,7 @@ impl RemoteObjectCache {
     pub fn contains(&self, object_id: &RemoteObjectId) -> bool {
         let entries = self.entries.read();
         if let Some(entry) = entries.get(&object_id.0) {
-            !entry.is_expired(self.config.ttl)
+            !self.is_stale(entry)
         } else {
             false
         }
@@ -326,6 +350,31 @@ impl RemoteObjectCache {
         released
     }
 
+    /// Release every object belonging to an execution context
+    ///
+    /// Used when a context is destroyed, e.g. on navigation. Surviving
+    /// entries keep their LRU order. Returns the number of entries removed.
+    pub fn release_context(&self, execution_context_id: u32) -> usize {
+        debug!(
+            "Releasing objects for execution context {}",
+            execution_context_id
+        );
+
+        let object_ids: Vec<String> = {
+            let entries = self.entries.read();
+            entries
+                .iter()
+                .filter(|(_, entry)| entry.execution_context_id == Some(execution_context_id))
+                .map(|(id, _)| id.clone())
+                .collect()
+        };
+
+        object_ids
+            .into_iter()
+            .filter_map(|id_str| self.remove(&RemoteObjectId(id_str)))
+            .count()
+    }
+
     /// Clear the entire cache
     pub fn clear(&self) {
         debug!("Clearing cache");
@@ -362,6 +411,11 @@ impl RemoteObjectCache {
         self.entries.read().len()
     }
 
+    /// Snapshot of every entry currently held, in no particular order
+    pub fn entries(&self) -> Vec<CacheEntry> {
+        self.entries.read().values().cloned().collect()
+    }
+
     /// Check if the cache is empty
     pub fn is_empty(&self) -> bool {
         self.entries.read().is_empty()
@@ -381,6 +435,11 @@ impl RemoteObjectCache {
         self.groups.read().keys().cloned().collect()
     }
 
+    /// Whether an entry has outlived the configured TTL
+    fn is_stale(&self, entry: &CacheEntry) -> bool {
+        self.config.ttl.is_some_and(|ttl| entry.is_expired(ttl))
+    }
+
     /// Evict entries if cache is at capacity
     fn evict_if_needed(&self) {
         let current_size = self.entries.read().len();
@@ -403,7 +462,7 @@ impl RemoteObjectCache {
             let entries = self.entries.read();
             entries
                 .iter()
-                .filter(|(_, entry)| entry.is_expired(self.config.ttl))
+                .filter(|(_, entry)| self.is_stale(entry))
                 .map(|(id, _)| id.clone())
                 .collect()
         };
@@ -413,7 +472,7 @@ impl RemoteObjectCache {
             self.remove(&object_id);
             {
                 let mut stats = self.stats.write();
-                stats.expirations += 1;
+                stats.expired_count += 1;
             }
         }
     }
@@ -481,7 +540,7 @@ mod tests {
     fn test_cache_with_config() {
         let config = CacheConfig {
             max_size: 100,
-            ttl: Duration::from_secs(60),
+            ttl: Some(Duration::from_secs(60)),
             track_stats: true,
         };
         let cache = RemoteObjectCache::with_config(config);
@@ -590,11 +649,44 @@ mod tests {
         assert!(cache.get_group("group-a").is_empty());
     }
 
+    #[test]
+    fn test_cache_release_context() {
+        let config = CacheConfig {
+            max_size: 4,
+            ..Default::default()
+        };
+        let cache = RemoteObjectCache::with_config(config);
+
+        cache.insert_in_context(make_remote_object("obj-0"), json!({}), None, 1);
+        cache.insert_in_context(make_remote_object("obj-1"), json!({}), None, 2);
+        cache.insert_in_context(
+            make_remote_object("obj-2"),
+            json!({}),
+            Some("g".to_string()),
+            1,
+        );
+        cache.insert(make_remote_object("obj-3"), json!({}), None);
+
+        assert_eq!(cache.release_context(1), 2);
+        assert_eq!(cache.release_context(1), 0);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().size, 2);
+        assert!(cache.get_group("g").is_empty());
+        assert!(!cache.contains(&RemoteObjectId("obj-0".to_string())));
+
+        // Survivors keep their order: obj-1 is still least recently used
+        for i in 4..7 {
+            cache.insert(make_remote_object(&format!("obj-{}", i)), json!({}), None);
+        }
+        assert!(!cache.contains(&RemoteObjectId("obj-1".to_string())));
+        assert!(cache.contains(&RemoteObjectId("obj-3".to_string())));
+    }
+
     #[test]
     fn test_cache_lru_eviction() {
         let config = CacheConfig {
             max_size: 3,
-            ttl: Duration::from_secs(300),
+            ttl: Some(Duration::from_secs(300)),
             track_stats: true,
         };
         let cache = RemoteObjectCache::with_config(config);
@@ -667,6 +759,48 @@ mod tests {
         // Can't easily test true expiration without waiting, but the logic is tested
     }
 
+    #[test]
+    fn test_cache_ttl_expiry() {
+        let config = CacheConfig {
+            ttl: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+        let cache = RemoteObjectCache::with_config(config);
+
+        cache.insert(
+            make_remote_object("obj-old"),
+            json!({}),
+            Some("group".to_string()),
+        );
+        std::thread::sleep(Duration::from_millis(60));
+        cache.insert(make_remote_object("obj-new"), json!({}), None);
+
+        assert!(cache.get(&RemoteObjectId("obj-old".to_string())).is_none());
+        assert!(cache.get(&RemoteObjectId("obj-new".to_string())).is_some());
+
+        let stats = cache.stats();
+        assert_eq!(stats.expired_count, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get_group("group").is_empty());
+    }
+
+    #[test]
+    fn test_cache_without_ttl_never_expires() {
+        let config = CacheConfig {
+            ttl: None,
+            ..Default::default()
+        };
+        let cache = RemoteObjectCache::with_config(config);
+
+        cache.insert(make_remote_object("obj-1"), json!({}), None);
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert_eq!(cache.gc(), 0);
+        assert!(cache.get(&RemoteObjectId("obj-1".to_string())).is_some());
+        assert_eq!(cache.stats().expired_count, 0);
+    }
+
     #[test]
     fn test_cache_stats_hit_rate() {
         let stats = CacheStats {
@@ -20,9 +20,12 @@ pub use debugger::DebuggerDomain;
 pub use preview::{PreviewConfig, PreviewGenerator};
 pub use repl::{
     CompletionItem, CompletionKind, HistoryEntry, ReplEvaluateOptions, ReplEvaluateResult,
-    ReplSession,
+    ReplEvaluation, ReplSession,
+};
+pub use runtime::{
+    ConsoleDiscardCallback, ExceptionFormatter, HeapUsage, MemoryProvider, RuntimeDomain,
+    ScriptEvaluator,
 };
-pub use runtime::RuntimeDomain;
 
 use thiserror::Error;
 
@@ -57,6 +60,18 @@ pub enum RuntimeDebuggerError {
     #[error("Debugger not paused")]
     DebuggerNotPaused,
 
+    /// Evaluation aborted by Runtime.terminateExecution
+    #[error("Execution was terminated")]
+    ExecutionTerminated,
+
+    /// Evaluation exceeded its timeout
+    #[error("Evaluation timed out")]
+    EvaluationTimedOut,
+
+    /// The evaluated script threw
+    #[error("Uncaught exception: {}", .0.text)]
+    Exception(Box<cdp_types::domains::runtime::ExceptionDetails>),
+
     /// Serialization error
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
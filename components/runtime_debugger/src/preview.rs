@@ -4,19 +4,26 @@
 //! - Arrays with element previews
 //! - Objects with property previews
 //! - Special types (Date, Map, Set, RegExp, etc.)
+//! - Map and Set entry previews, and typed arrays labelled by class
 //! - Nested structures with configurable depth limits
 
 use cdp_types::domains::runtime::{
-    ObjectPreview, PropertyPreview, RemoteObject, RemoteObjectSubtype, RemoteObjectType,
+    EntryPreview, ObjectPreview, PropertyPreview, RemoteObject, RemoteObjectSubtype,
+    RemoteObjectType,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Configuration for object preview generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PreviewConfig {
     /// Maximum depth for nested object previews
+    ///
+    /// Object and array properties are expanded into a `value_preview` up to
+    /// this many levels; deeper ones are summarized and their parent preview
+    /// is marked `overflow`.
     pub max_depth: u32,
     /// Maximum number of properties to include in preview
     pub max_properties: usize,
@@ -43,19 +50,33 @@ impl Default for PreviewConfig {
 /// Object preview generator
 pub struct PreviewGenerator {
     config: PreviewConfig,
+    /// Set by `Runtime.setCustomObjectFormatterEnabled`; gates custom formatter hooks
+    custom_formatters_enabled: AtomicBool,
 }
 
 impl PreviewGenerator {
     /// Create a new preview generator with default config
     pub fn new() -> Self {
-        Self {
-            config: PreviewConfig::default(),
-        }
+        Self::with_config(PreviewConfig::default())
     }
 
     /// Create a new preview generator with custom config
     pub fn with_config(config: PreviewConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            custom_formatters_enabled: AtomicBool::new(false),
+        }
+    }
+
+    /// Allow or disallow custom object formatter hooks
+    pub fn set_custom_formatters_enabled(&self, enabled: bool) {
+        self.custom_formatters_enabled
+            .store(enabled, Ordering::SeqCst);
+    }
+
+    /// Whether custom object formatter hooks may run
+    pub fn custom_formatters_enabled(&self) -> bool {
+        self.custom_formatters_enabled.load(Ordering::SeqCst)
     }
 
     /// Generate preview for a RemoteObject
@@ -115,7 +136,7 @@ impl PreviewGenerator {
                 Some(self.generate_promise_preview(obj))
             }
             Some(RemoteObjectSubtype::Typedarray) => {
-                self.generate_typed_array_preview(value, depth)
+                self.generate_typed_array_preview(obj, value, depth)
             }
             Some(RemoteObjectSubtype::Null) => None,
             _ => self.generate_plain_object_preview(value, depth),
@@ -127,6 +148,7 @@ impl PreviewGenerator {
         let obj = value.as_object()?;
         let mut properties = Vec::new();
         let mut count = 0;
+        let mut collapsed = false;
 
         for (key, val) in obj.iter() {
             if count >= self.config.max_properties {
@@ -137,6 +159,7 @@ impl PreviewGenerator {
                 continue;
             }
 
+            collapsed |= self.is_collapsed(val, depth + 1);
             properties.push(self.value_to_property_preview(key, val, depth + 1));
             count += 1;
         }
@@ -145,8 +168,9 @@ impl PreviewGenerator {
             object_type: RemoteObjectType::Object,
             subtype: None,
             description: Some(format!("Object {{{}}}", obj.len())),
-            overflow: obj.len() > self.config.max_properties,
+            overflow: obj.len() > self.config.max_properties || collapsed,
             properties,
+            entries: None,
         })
     }
 
@@ -154,12 +178,14 @@ impl PreviewGenerator {
     fn generate_array_preview(&self, value: &Value, depth: u32) -> Option<ObjectPreview> {
         let arr = value.as_array()?;
         let mut properties = Vec::new();
+        let mut collapsed = false;
 
         for (i, val) in arr.iter().enumerate() {
             if i >= self.config.max_properties {
                 break;
             }
 
+            collapsed |= self.is_collapsed(val, depth + 1);
             properties.push(self.value_to_property_preview(&i.to_string(), val, depth + 1));
         }
 
@@ -167,8 +193,9 @@ impl PreviewGenerator {
             object_type: RemoteObjectType::Object,
             subtype: Some(RemoteObjectSubtype::Array),
             description: Some(format!("Array({})", arr.len())),
-            overflow: arr.len() > self.config.max_properties,
+            overflow: arr.len() > self.config.max_properties || collapsed,
             properties,
+            entries: None,
         })
     }
 
@@ -180,6 +207,7 @@ impl PreviewGenerator {
             description: obj.description.clone(),
             overflow: false,
             properties: vec![],
+            entries: None,
         }
     }
 
@@ -191,14 +219,18 @@ impl PreviewGenerator {
             description: obj.description.clone(),
             overflow: false,
             properties: vec![],
+            entries: None,
         }
     }
 
     /// Generate preview for a Map
-    fn generate_map_preview(&self, value: &Value, _depth: u32) -> Option<ObjectPreview> {
+    ///
+    /// Each `[key, value]` pair gets an entry with key and value sub-previews.
+    fn generate_map_preview(&self, value: &Value, depth: u32) -> Option<ObjectPreview> {
         // For maps represented as arrays of [key, value] pairs
         let entries = value.get("entries").and_then(|e| e.as_array())?;
         let mut properties = Vec::new();
+        let mut entry_previews = Vec::new();
 
         for (i, entry) in entries.iter().enumerate() {
             if i >= self.config.max_properties {
@@ -214,6 +246,11 @@ impl PreviewGenerator {
                         property_type: self.value_to_type(&arr[1]),
                         value: Some(val_str),
                         subtype: self.value_to_subtype(&arr[1]),
+                        value_preview: None,
+                    });
+                    entry_previews.push(EntryPreview {
+                        key: Some(self.value_to_entry_preview(&arr[0], depth + 1)),
+                        value: self.value_to_entry_preview(&arr[1], depth + 1),
                     });
                 }
             }
@@ -225,29 +262,40 @@ impl PreviewGenerator {
             description: Some(format!("Map({})", entries.len())),
             overflow: entries.len() > self.config.max_properties,
             properties,
+            entries: Some(entry_previews),
         })
     }
 
     /// Generate preview for a Set
+    ///
+    /// Each value gets a value-only entry.
     fn generate_set_preview(&self, value: &Value, depth: u32) -> Option<ObjectPreview> {
         // For sets represented as arrays of values
         let values = value.get("values").and_then(|v| v.as_array())?;
         let mut properties = Vec::new();
+        let mut entry_previews = Vec::new();
+        let mut collapsed = false;
 
         for (i, val) in values.iter().enumerate() {
             if i >= self.config.max_properties {
                 break;
             }
 
+            collapsed |= self.is_collapsed(val, depth + 1);
             properties.push(self.value_to_property_preview(&i.to_string(), val, depth + 1));
+            entry_previews.push(EntryPreview {
+                key: None,
+                value: self.value_to_entry_preview(val, depth + 1),
+            });
         }
 
         Some(ObjectPreview {
             object_type: RemoteObjectType::Object,
             subtype: Some(RemoteObjectSubtype::Set),
             description: Some(format!("Set({})", values.len())),
-            overflow: values.len() > self.config.max_properties,
+            overflow: values.len() > self.config.max_properties || collapsed,
             properties,
+            entries: Some(entry_previews),
         })
     }
 
@@ -262,6 +310,7 @@ impl PreviewGenerator {
                 property_type: RemoteObjectType::String,
                 value: Some(desc.clone()),
                 subtype: None,
+                value_preview: None,
             });
         }
 
@@ -271,6 +320,7 @@ impl PreviewGenerator {
             description: obj.description.clone(),
             overflow: false,
             properties,
+            entries: None,
         }
     }
 
@@ -287,15 +337,38 @@ impl PreviewGenerator {
                     property_type: RemoteObjectType::String,
                     value: Some("pending".to_string()),
                     subtype: None,
+                    value_preview: None,
                 },
             ],
+            entries: None,
         }
     }
 
     /// Generate preview for a TypedArray
-    fn generate_typed_array_preview(&self, value: &Value, _depth: u32) -> Option<ObjectPreview> {
+    ///
+    /// The preview is labelled with the array's class, e.g. `Int8Array(4)`,
+    /// and a leading `[[Int8Array]]` internal property holding the element
+    /// values as a compact list. Elements past `max_properties` are omitted.
+    fn generate_typed_array_preview(
+        &self,
+        obj: &RemoteObject,
+        value: &Value,
+        _depth: u32,
+    ) -> Option<ObjectPreview> {
         let arr = value.as_array()?;
-        let mut properties = Vec::new();
+        let class_name = obj.class_name.as_deref().unwrap_or("TypedArray");
+        let shown = arr.len().min(self.config.max_properties);
+        let mut compact: Vec<String> = arr[..shown].iter().map(|v| v.to_string()).collect();
+        if arr.len() > shown {
+            compact.push("...".to_string());
+        }
+        let mut properties = vec![PropertyPreview {
+            name: format!("[[{}]]", class_name),
+            property_type: RemoteObjectType::Object,
+            value: Some(format!("[{}]", compact.join(", "))),
+            subtype: Some(RemoteObjectSubtype::Typedarray),
+            value_preview: None,
+        }];
 
         for (i, val) in arr.iter().enumerate() {
             if i >= self.config.max_properties {
@@ -307,15 +380,17 @@ impl PreviewGenerator {
                 property_type: RemoteObjectType::Number,
                 value: val.as_f64().map(|n| n.to_string()),
                 subtype: None,
+                value_preview: None,
             });
         }
 
         Some(ObjectPreview {
             object_type: RemoteObjectType::Object,
             subtype: Some(RemoteObjectSubtype::Typedarray),
-            description: Some(format!("TypedArray({})", arr.len())),
+            description: Some(format!("{}({})", class_name, arr.len())),
             overflow: arr.len() > self.config.max_properties,
             properties,
+            entries: None,
         })
     }
 
@@ -327,28 +402,65 @@ impl PreviewGenerator {
             description: obj.description.clone(),
             overflow: false,
             properties: vec![],
+            entries: None,
         }
     }
 
     /// Convert a Value to a PropertyPreview
+    ///
+    /// Objects and arrays above `max_depth` get a nested `value_preview`;
+    /// at the limit they are only summarized.
     fn value_to_property_preview(
         &self,
         name: &str,
         value: &Value,
         depth: u32,
     ) -> PropertyPreview {
-        let value_str = if depth < self.config.max_depth {
-            Some(self.value_to_short_string(value))
-        } else {
-            Some("...".to_string())
-        };
-
         PropertyPreview {
             name: name.to_string(),
             property_type: self.value_to_type(value),
-            value: value_str,
+            value: Some(self.value_to_short_string(value)),
             subtype: self.value_to_subtype(value),
+            value_preview: self.generate_nested_preview(value, depth).map(Box::new),
+        }
+    }
+
+    /// Preview of a Map or Set entry's key or value
+    ///
+    /// Objects and arrays are expanded like nested properties; anything else
+    /// becomes a preview carrying just its short description.
+    fn value_to_entry_preview(&self, value: &Value, depth: u32) -> ObjectPreview {
+        self.generate_nested_preview(value, depth)
+            .unwrap_or_else(|| ObjectPreview {
+                object_type: self.value_to_type(value),
+                subtype: self.value_to_subtype(value),
+                description: Some(self.value_to_short_string(value)),
+                overflow: self.is_collapsed(value, depth),
+                properties: vec![],
+                entries: None,
+            })
+    }
+
+    /// Preview of an object or array value above `max_depth`
+    fn generate_nested_preview(&self, value: &Value, depth: u32) -> Option<ObjectPreview> {
+        if depth >= self.config.max_depth {
+            return None;
         }
+        match value {
+            Value::Array(_) => self.generate_array_preview(value, depth),
+            Value::Object(_) => self.generate_plain_object_preview(value, depth),
+            _ => None,
+        }
+    }
+
+    /// Whether a non-empty object or array at `depth` is summarized, not expanded
+    fn is_collapsed(&self, value: &Value, depth: u32) -> bool {
+        let non_empty = match value {
+            Value::Array(arr) => !arr.is_empty(),
+            Value::Object(obj) => !obj.is_empty(),
+            _ => false,
+        };
+        non_empty && depth >= self.config.max_depth
     }
 
     /// Convert a Value to a short string representation
@@ -538,6 +650,58 @@ mod tests {
         assert_eq!(preview.description, Some("Array(10)".to_string()));
     }
 
+    #[test]
+    fn test_nested_preview_respects_max_depth() {
+        let gen = PreviewGenerator::with_config(PreviewConfig {
+            max_depth: 2,
+            ..Default::default()
+        });
+        let obj = RemoteObject {
+            object_type: RemoteObjectType::Object,
+            subtype: None,
+            class_name: Some("Object".to_string()),
+            value: None,
+            unserializable_value: None,
+            description: Some("Object".to_string()),
+            object_id: None,
+            preview: None,
+        };
+        let value = json!({"one": {"two": {"three": {"four": 4}}}, "n": 1});
+
+        let level1 = gen.generate_preview(&obj, &value).unwrap();
+        assert!(!level1.overflow);
+        let one = level1.properties.iter().find(|p| p.name == "one").unwrap();
+        let level2 = one.value_preview.as_ref().expect("level 2 is expanded");
+        assert_eq!(level2.properties[0].name, "two");
+
+        // Level 3 is summarized and the level that cut it short overflows
+        let two = &level2.properties[0];
+        assert!(two.value_preview.is_none());
+        assert_eq!(two.value.as_deref(), Some("{...} (1 keys)"));
+        assert!(level2.overflow);
+
+        let n = level1.properties.iter().find(|p| p.name == "n").unwrap();
+        assert_eq!(n.value.as_deref(), Some("1"));
+        assert!(n.value_preview.is_none());
+    }
+
+    #[test]
+    fn test_nested_arrays_honor_element_cap() {
+        let gen = PreviewGenerator::new();
+        let value = json!([[1, 2, 3, 4, 5, 6, 7], [[1, 2, 3, 4, 5, 6]]]);
+
+        let level1 = gen.generate_array_preview(&value, 0).unwrap();
+        let level2 = level1.properties[0].value_preview.as_ref().unwrap();
+        assert_eq!(level2.properties.len(), 5);
+        assert!(level2.overflow);
+
+        let inner = level1.properties[1].value_preview.as_ref().unwrap();
+        let level3 = inner.properties[0].value_preview.as_ref().unwrap();
+        assert_eq!(level3.properties.len(), 5);
+        assert!(level3.overflow);
+        assert!(level3.properties.iter().all(|p| p.value_preview.is_none()));
+    }
+
     #[test]
     fn test_generate_date_preview() {
         let gen = PreviewGenerator::new();
@@ -596,6 +760,85 @@ mod tests {
         assert_eq!(preview.description, Some("Promise".to_string()));
     }
 
+    fn make_object(subtype: RemoteObjectSubtype, class_name: &str) -> RemoteObject {
+        RemoteObject {
+            object_type: RemoteObjectType::Object,
+            subtype: Some(subtype),
+            class_name: Some(class_name.to_string()),
+            value: None,
+            unserializable_value: None,
+            description: None,
+            object_id: None,
+            preview: None,
+        }
+    }
+
+    #[test]
+    fn test_map_preview_entries() {
+        let gen = PreviewGenerator::new();
+        let obj = make_object(RemoteObjectSubtype::Map, "Map");
+        let value = json!({"entries": [["a", 1], [{"id": 2}, [true]]]});
+
+        let preview = gen.generate_preview(&obj, &value).unwrap();
+        assert_eq!(preview.description, Some("Map(2)".to_string()));
+        let entries = preview.entries.unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let key = entries[0].key.as_ref().unwrap();
+        assert_eq!(key.object_type, RemoteObjectType::String);
+        assert_eq!(key.description, Some("\"a\"".to_string()));
+        assert_eq!(entries[0].value.description, Some("1".to_string()));
+
+        // Object keys and values get their own sub-previews
+        let key = entries[1].key.as_ref().unwrap();
+        assert_eq!(key.properties[0].name, "id");
+        assert_eq!(entries[1].value.subtype, Some(RemoteObjectSubtype::Array));
+        assert_eq!(
+            entries[1].value.properties[0].value,
+            Some("true".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_preview_entries_respect_limit() {
+        let gen = PreviewGenerator::new();
+        let obj = make_object(RemoteObjectSubtype::Set, "Set");
+        let value = json!({"values": [1, 2, 3, 4, 5, 6, 7]});
+
+        let preview = gen.generate_preview(&obj, &value).unwrap();
+        assert_eq!(preview.description, Some("Set(7)".to_string()));
+        assert!(preview.overflow);
+        let entries = preview.entries.unwrap();
+        assert_eq!(entries.len(), 5);
+        assert!(entries.iter().all(|entry| entry.key.is_none()));
+        assert_eq!(entries[4].value.description, Some("5".to_string()));
+        assert_eq!(entries[4].value.object_type, RemoteObjectType::Number);
+    }
+
+    #[test]
+    fn test_typed_array_preview() {
+        let gen = PreviewGenerator::with_config(PreviewConfig {
+            max_properties: 3,
+            ..Default::default()
+        });
+        let obj = make_object(RemoteObjectSubtype::Typedarray, "Int8Array");
+
+        let preview = gen.generate_preview(&obj, &json!([1, -2, 3, 4])).unwrap();
+        assert_eq!(preview.subtype, Some(RemoteObjectSubtype::Typedarray));
+        assert_eq!(preview.description, Some("Int8Array(4)".to_string()));
+        assert!(preview.overflow);
+        assert!(preview.entries.is_none());
+
+        let label = &preview.properties[0];
+        assert_eq!(label.name, "[[Int8Array]]");
+        assert_eq!(label.value, Some("[1, -2, 3, ...]".to_string()));
+        let elements: Vec<_> = preview.properties[1..]
+            .iter()
+            .map(|p| (p.name.as_str(), p.value.as_deref().unwrap()))
+            .collect();
+        assert_eq!(elements, vec![("0", "1"), ("1", "-2"), ("2", "3")]);
+    }
+
     #[test]
     fn test_generate_function_preview() {
         let gen = PreviewGenerator::new();
@@ -14,13 +14,15 @@ use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::VecDeque;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::debug;
 
+use crate::runtime::MOCK_LOOP_LIMIT;
 use crate::{Result, RuntimeDebuggerError};
 
-/// Maximum number of history entries to keep
+/// Default maximum number of history entries to keep
 const MAX_HISTORY_SIZE: usize = 1000;
 
 /// Default preview depth for objects
@@ -34,6 +36,8 @@ const MAX_PREVIEW_PROPERTIES: usize = 5;
 pub struct ReplSession {
     /// Command history
     history: Arc<RwLock<VecDeque<HistoryEntry>>>,
+    /// Maximum number of history entries kept (oldest evicted first)
+    history_limit: Arc<AtomicUsize>,
     /// Current multi-line buffer
     multiline_buffer: Arc<RwLock<String>>,
     /// Whether we're in multi-line mode
@@ -72,6 +76,9 @@ pub struct CompletionItem {
     pub kind: CompletionKind,
     /// Documentation (if available)
     pub documentation: Option<String>,
+    /// Ranking score from `ReplSession::complete` (higher ranks first)
+    #[serde(default)]
+    pub score: f32,
 }
 
 /// Types of auto-completion items
@@ -107,12 +114,45 @@ pub struct ReplEvaluateOptions {
     /// Disable breaks during evaluation
     #[serde(default)]
     pub disable_breaks: bool,
+    /// Terminate the evaluation after this many milliseconds (may be fractional)
+    #[serde(default, rename = "timeout")]
+    pub timeout_ms: Option<f64>,
+}
+
+impl ReplEvaluateOptions {
+    /// The evaluation timeout, if one was given
+    ///
+    /// Negative or non-finite values are ignored.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms
+            .and_then(|ms| Duration::try_from_secs_f64(ms / 1000.0).ok())
+    }
+}
+
+/// Outcome of feeding one line of input to the REPL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplEvaluateResult {
+    /// The (possibly multi-line) input was complete and has been evaluated
+    Evaluated(Box<ReplEvaluation>),
+    /// The input is incomplete; it was buffered and the next call continues it
+    NeedMoreInput,
+}
+
+impl ReplEvaluateResult {
+    /// The evaluation, or `None` if more input is needed
+    pub fn evaluation(self) -> Option<ReplEvaluation> {
+        match self {
+            ReplEvaluateResult::Evaluated(evaluation) => Some(*evaluation),
+            ReplEvaluateResult::NeedMoreInput => None,
+        }
+    }
 }
 
 /// REPL evaluation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ReplEvaluateResult {
+pub struct ReplEvaluation {
     /// The evaluated result as a RemoteObject
     pub result: RemoteObject,
     /// Whether the expression had side effects
@@ -129,6 +169,7 @@ impl ReplSession {
     pub fn new() -> Self {
         Self {
             history: Arc::new(RwLock::new(VecDeque::with_capacity(MAX_HISTORY_SIZE))),
+            history_limit: Arc::new(AtomicUsize::new(MAX_HISTORY_SIZE)),
             multiline_buffer: Arc::new(RwLock::new(String::new())),
             in_multiline: Arc::new(RwLock::new(false)),
             session_id: uuid::Uuid::new_v4().to_string(),
@@ -143,6 +184,9 @@ impl ReplSession {
     }
 
     /// Evaluate an expression in REPL mode
+    ///
+    /// Incomplete input (see `is_input_complete`) is buffered and reported as
+    /// `NeedMoreInput`; later calls append to it until it is complete.
     pub fn evaluate(
         &self,
         expression: &str,
@@ -166,9 +210,7 @@ impl ReplSession {
                 buffer.clear();
                 expr
             } else {
-                return Err(RuntimeDebuggerError::EvaluationError(
-                    "MULTILINE_CONTINUE".to_string(),
-                ));
+                return Ok(ReplEvaluateResult::NeedMoreInput);
             }
         } else if !Self::is_expression_complete(expression) {
             // Start multi-line mode
@@ -177,9 +219,7 @@ impl ReplSession {
             buffer.clear();
             buffer.push_str(expression);
             buffer.push('\n');
-            return Err(RuntimeDebuggerError::EvaluationError(
-                "MULTILINE_CONTINUE".to_string(),
-            ));
+            return Ok(ReplEvaluateResult::NeedMoreInput);
         } else {
             expression.to_string()
         };
@@ -191,8 +231,9 @@ impl ReplSession {
             full_expression.clone()
         };
 
-        // Mock evaluation
-        let (result, had_side_effects) = self.mock_evaluate(&transformed, options)?;
+        // Mock evaluation, bounded by the requested timeout
+        let deadline = options.timeout().map(|timeout| Instant::now() + timeout);
+        let (result, had_side_effects) = self.mock_evaluate(&transformed, options, deadline)?;
 
         // Add to history
         let history_entry = HistoryEntry {
@@ -213,61 +254,94 @@ impl ReplSession {
             None
         };
 
-        Ok(ReplEvaluateResult {
+        Ok(ReplEvaluateResult::Evaluated(Box::new(ReplEvaluation {
             result,
             had_side_effects,
             repl_mode: options.repl_mode,
             completion_hints,
-        })
+        })))
+    }
+
+    /// Check whether `input` is complete or needs more lines
+    ///
+    /// Input is incomplete while it has unclosed braces, brackets or
+    /// parentheses, or an unterminated string, template literal or block
+    /// comment. Delimiters inside strings and comments are ignored.
+    pub fn is_input_complete(&self, input: &str) -> bool {
+        Self::is_expression_complete(input)
     }
 
     /// Check if an expression is syntactically complete
+    ///
+    /// A stray closing delimiter counts as complete so that evaluation can
+    /// report the syntax error instead of waiting for more input.
     fn is_expression_complete(expression: &str) -> bool {
-        let expr = expression.trim();
-
-        // Check for unclosed brackets/braces/parentheses
-        let mut brace_count = 0i32;
-        let mut bracket_count = 0i32;
-        let mut paren_count = 0i32;
-        let mut in_string = false;
-        let mut string_char = ' ';
-        let mut escape_next = false;
-
-        for ch in expr.chars() {
-            if escape_next {
-                escape_next = false;
-                continue;
-            }
-
-            if ch == '\\' {
-                escape_next = true;
-                continue;
-            }
-
-            if in_string {
-                if ch == string_char {
-                    in_string = false;
+        // Expected closing delimiters, innermost last; '`' marks template text
+        let mut closers: Vec<char> = Vec::new();
+        let mut chars = expression.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            if closers.last() == Some(&'`') {
+                match ch {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '`' => {
+                        closers.pop();
+                    }
+                    '$' if chars.peek() == Some(&'{') => {
+                        chars.next();
+                        closers.push('}');
+                    }
+                    _ => {}
                 }
                 continue;
             }
 
             match ch {
-                '"' | '\'' | '`' => {
-                    in_string = true;
-                    string_char = ch;
+                '"' | '\'' => loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            chars.next();
+                        }
+                        Some(c) if c == ch => break,
+                        Some(_) => {}
+                        None => return false,
+                    }
+                },
+                '`' => closers.push('`'),
+                '/' if chars.peek() == Some(&'/') => {
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                '/' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    let mut prev = ' ';
+                    loop {
+                        match chars.next() {
+                            Some('/') if prev == '*' => break,
+                            Some(c) => prev = c,
+                            None => return false,
+                        }
+                    }
+                }
+                '{' => closers.push('}'),
+                '[' => closers.push(']'),
+                '(' => closers.push(')'),
+                '}' | ']' | ')' => {
+                    if closers.last() != Some(&ch) {
+                        return true;
+                    }
+                    closers.pop();
                 }
-                '{' => brace_count += 1,
-                '}' => brace_count -= 1,
-                '[' => bracket_count += 1,
-                ']' => bracket_count -= 1,
-                '(' => paren_count += 1,
-                ')' => paren_count -= 1,
                 _ => {}
             }
         }
 
-        // Expression is complete if all brackets are balanced and we're not in a string
-        !in_string && brace_count == 0 && bracket_count == 0 && paren_count == 0
+        closers.is_empty()
     }
 
     /// Transform expression for REPL mode
@@ -300,6 +374,7 @@ impl ReplSession {
         &self,
         expression: &str,
         options: &ReplEvaluateOptions,
+        deadline: Option<Instant>,
     ) -> Result<(RemoteObject, bool)> {
         let expr = expression.trim();
         let preview_depth = options.preview_depth.unwrap_or(DEFAULT_PREVIEW_DEPTH);
@@ -322,6 +397,7 @@ impl ReplSession {
 
         // Simple mock evaluation for common expressions
         let (result, side_effects) = match expr {
+            "while (true) {}" | "for (;;) {}" => return Self::mock_infinite_loop(deadline),
             "42" => (
                 RemoteObject {
                     object_type: RemoteObjectType::Number,
@@ -492,6 +568,7 @@ impl ReplSession {
                                 description: Some(format!("Date {}", timestamp)),
                                 overflow: false,
                                 properties: vec![],
+                                entries: None,
                             })
                         } else {
                             None
@@ -516,6 +593,7 @@ impl ReplSession {
                             description: Some("Map(0)".to_string()),
                             overflow: false,
                             properties: vec![],
+                            entries: None,
                         })
                     } else {
                         None
@@ -539,6 +617,7 @@ impl ReplSession {
                             description: Some("Set(0)".to_string()),
                             overflow: false,
                             properties: vec![],
+                            entries: None,
                         })
                     } else {
                         None
@@ -574,6 +653,20 @@ impl ReplSession {
         Ok((result, side_effects))
     }
 
+    /// Run until the deadline passes, as an infinite loop would
+    fn mock_infinite_loop(deadline: Option<Instant>) -> Result<(RemoteObject, bool)> {
+        let started = Instant::now();
+        while started.elapsed() < MOCK_LOOP_LIMIT {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(RuntimeDebuggerError::EvaluationTimedOut);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        Err(RuntimeDebuggerError::EvaluationError(
+            "Mock evaluator loop limit exceeded".to_string(),
+        ))
+    }
+
     /// Convert a JSON Value to a RemoteObject with optional preview
     fn value_to_remote_object(
         &self,
@@ -682,6 +775,7 @@ impl ReplSession {
             description: Some(format!("Array({})", arr.len())),
             overflow: arr.len() > MAX_PREVIEW_PROPERTIES,
             properties,
+            entries: None,
         }
     }
 
@@ -703,6 +797,7 @@ impl ReplSession {
             description: Some(format!("Object {{{}}}", obj.len())),
             overflow: obj.len() > MAX_PREVIEW_PROPERTIES,
             properties,
+            entries: None,
         }
     }
 
@@ -714,49 +809,73 @@ impl ReplSession {
                 property_type: RemoteObjectType::Object,
                 value: Some("null".to_string()),
                 subtype: Some(RemoteObjectSubtype::Null),
+                value_preview: None,
             },
             Value::Bool(b) => PropertyPreview {
                 name: name.to_string(),
                 property_type: RemoteObjectType::Boolean,
                 value: Some(b.to_string()),
                 subtype: None,
+                value_preview: None,
             },
             Value::Number(n) => PropertyPreview {
                 name: name.to_string(),
                 property_type: RemoteObjectType::Number,
                 value: Some(n.to_string()),
                 subtype: None,
+                value_preview: None,
             },
             Value::String(s) => PropertyPreview {
                 name: name.to_string(),
                 property_type: RemoteObjectType::String,
                 value: Some(format!("\"{}\"", s)),
                 subtype: None,
+                value_preview: None,
             },
             Value::Array(arr) => PropertyPreview {
                 name: name.to_string(),
                 property_type: RemoteObjectType::Object,
                 value: Some(format!("Array({})", arr.len())),
                 subtype: Some(RemoteObjectSubtype::Array),
+                value_preview: None,
             },
             Value::Object(obj) => PropertyPreview {
                 name: name.to_string(),
                 property_type: RemoteObjectType::Object,
                 value: Some(format!("{{...}} ({} keys)", obj.len())),
                 subtype: None,
+                value_preview: None,
             },
         }
     }
 
     /// Add entry to history
     fn add_to_history(&self, entry: HistoryEntry) {
+        let limit = self.history_limit();
         let mut history = self.history.write();
-        if history.len() >= MAX_HISTORY_SIZE {
+        while history.len() >= limit {
             history.pop_front();
         }
         history.push_back(entry);
     }
 
+    /// Maximum number of history entries kept
+    pub fn history_limit(&self) -> usize {
+        self.history_limit.load(Ordering::SeqCst)
+    }
+
+    /// Cap the history at `limit` entries, evicting the oldest beyond it
+    ///
+    /// A limit of zero is treated as one.
+    pub fn set_history_limit(&self, limit: usize) {
+        let limit = limit.max(1);
+        self.history_limit.store(limit, Ordering::SeqCst);
+        let mut history = self.history.write();
+        while history.len() > limit {
+            history.pop_front();
+        }
+    }
+
     /// Get command history
     pub fn get_history(&self, count: Option<usize>) -> Vec<HistoryEntry> {
         let history = self.history.read();
@@ -769,6 +888,57 @@ impl ReplSession {
         self.history.write().clear();
     }
 
+    /// Find history entries whose expression contains `substring`, newest first
+    ///
+    /// Backs a Ctrl+R style reverse search; an empty substring matches all.
+    pub fn search_history(&self, substring: &str) -> Vec<HistoryEntry> {
+        self.history
+            .read()
+            .iter()
+            .rev()
+            .filter(|entry| entry.expression.contains(substring))
+            .cloned()
+            .collect()
+    }
+
+    /// Export history as line-delimited JSON, oldest first
+    pub fn export_history(&self) -> String {
+        self.history
+            .read()
+            .iter()
+            .filter_map(|entry| serde_json::to_string(entry).ok())
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    /// Append history exported by `export_history`
+    ///
+    /// Blank lines are skipped. Nothing is imported if any line is invalid.
+    /// The history limit still applies, so only the most recent entries are
+    /// kept. Returns the number of entries read.
+    pub fn import_history(&self, data: &str) -> Result<usize> {
+        let entries = data
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str::<HistoryEntry>(line).map_err(|e| {
+                    RuntimeDebuggerError::InvalidParams(format!(
+                        "Invalid history entry on line {}: {}",
+                        index + 1,
+                        e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let count = entries.len();
+        for entry in entries {
+            self.add_to_history(entry);
+        }
+        Ok(count)
+    }
+
     /// Get auto-completion suggestions for a partial expression
     pub fn get_completions(&self, partial: &str) -> Vec<CompletionItem> {
         let cache = self.completion_cache.read();
@@ -781,6 +951,89 @@ impl ReplSession {
             .collect()
     }
 
+    /// Ranked completions for the identifier or member expression at `cursor`
+    ///
+    /// Identifiers declared in the current input (or pending multi-line
+    /// buffer) rank first, then those declared earlier in the session, then
+    /// globals. Within a tier, and for property completions such as
+    /// `JSON.pa`, candidates covering more of their text with the typed
+    /// prefix rank higher. `cursor` is a byte offset, clamped to the input.
+    pub fn complete(&self, input: &str, cursor: usize) -> Vec<CompletionItem> {
+        let mut cursor = cursor.min(input.len());
+        while !input.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+        let before = &input[..cursor];
+        let token_start = before
+            .rfind(|c: char| !(is_identifier_char(c) || c == '.'))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let token = &before[token_start..];
+        if token.is_empty() {
+            return Vec::new();
+        }
+
+        let mut items: Vec<CompletionItem> = Vec::new();
+        let mut push = |item: CompletionItem| {
+            if !items.iter().any(|existing| existing.text == item.text) {
+                items.push(item);
+            }
+        };
+
+        if token.contains('.') {
+            let token_lower = token.to_lowercase();
+            for item in self.completion_cache.read().iter() {
+                if item.text.to_lowercase().starts_with(&token_lower) {
+                    push(CompletionItem {
+                        score: prefix_score(token, &item.text),
+                        ..item.clone()
+                    });
+                }
+            }
+        } else {
+            let pending = self.multiline_buffer.read().clone();
+            let local = declared_identifiers(&format!("{}\n{}", pending, &before[..token_start]));
+            let session: Vec<String> = self
+                .history
+                .read()
+                .iter()
+                .flat_map(|entry| declared_identifiers(&entry.expression))
+                .collect();
+            let scoped = [
+                (local, 3.0, "Local variable"),
+                (session, 2.0, "Session variable"),
+            ];
+            for (names, tier, documentation) in scoped {
+                for name in names.into_iter().filter(|n| n.starts_with(token)) {
+                    push(CompletionItem {
+                        score: tier + prefix_score(token, &name),
+                        label: name.clone(),
+                        text: name,
+                        kind: CompletionKind::Variable,
+                        documentation: Some(documentation.to_string()),
+                    });
+                }
+            }
+
+            let token_lower = token.to_lowercase();
+            for item in self.completion_cache.read().iter() {
+                if !item.text.contains('.') && item.text.to_lowercase().starts_with(&token_lower) {
+                    push(CompletionItem {
+                        score: 1.0 + prefix_score(token, &item.text),
+                        ..item.clone()
+                    });
+                }
+            }
+        }
+
+        items.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.text.cmp(&b.text))
+        });
+        items
+    }
+
     /// Generate completion hints based on an evaluation result
     fn generate_completion_hints(&self, result: &RemoteObject) -> Vec<CompletionItem> {
         let mut hints = Vec::new();
@@ -830,78 +1083,105 @@ impl ReplSession {
                 label: "console".to_string(),
                 kind: CompletionKind::Variable,
                 documentation: Some("Console API for logging".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "console.log".to_string(),
                 label: "console.log()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Log message to console".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "console.error".to_string(),
                 label: "console.error()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Log error to console".to_string()),
+                score: 0.0,
+            },
+            CompletionItem {
+                text: "document".to_string(),
+                label: "document".to_string(),
+                kind: CompletionKind::Variable,
+                documentation: Some("The current page's Document".to_string()),
+                score: 0.0,
+            },
+            CompletionItem {
+                text: "window".to_string(),
+                label: "window".to_string(),
+                kind: CompletionKind::Variable,
+                documentation: Some("The global Window object".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "JSON".to_string(),
                 label: "JSON".to_string(),
                 kind: CompletionKind::Class,
                 documentation: Some("JSON parsing and stringification".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "JSON.parse".to_string(),
                 label: "JSON.parse()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Parse JSON string".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "JSON.stringify".to_string(),
                 label: "JSON.stringify()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Convert to JSON string".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "Math".to_string(),
                 label: "Math".to_string(),
                 kind: CompletionKind::Class,
                 documentation: Some("Mathematical functions".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "Date".to_string(),
                 label: "Date".to_string(),
                 kind: CompletionKind::Class,
                 documentation: Some("Date and time".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "Array".to_string(),
                 label: "Array".to_string(),
                 kind: CompletionKind::Class,
                 documentation: Some("Array constructor".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "Object".to_string(),
                 label: "Object".to_string(),
                 kind: CompletionKind::Class,
                 documentation: Some("Object constructor".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "let".to_string(),
                 label: "let".to_string(),
                 kind: CompletionKind::Keyword,
                 documentation: Some("Declare block-scoped variable".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "const".to_string(),
                 label: "const".to_string(),
                 kind: CompletionKind::Keyword,
                 documentation: Some("Declare constant".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: "function".to_string(),
                 label: "function".to_string(),
                 kind: CompletionKind::Keyword,
                 documentation: Some("Declare function".to_string()),
+                score: 0.0,
             },
         ]
     }
@@ -914,30 +1194,35 @@ impl ReplSession {
                 label: ".length".to_string(),
                 kind: CompletionKind::Property,
                 documentation: Some("String length".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".split".to_string(),
                 label: ".split()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Split string".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".trim".to_string(),
                 label: ".trim()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Trim whitespace".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".toUpperCase".to_string(),
                 label: ".toUpperCase()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Convert to uppercase".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".toLowerCase".to_string(),
                 label: ".toLowerCase()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Convert to lowercase".to_string()),
+                score: 0.0,
             },
         ]
     }
@@ -950,12 +1235,14 @@ impl ReplSession {
                 label: ".toFixed()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Format with fixed decimals".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".toString".to_string(),
                 label: ".toString()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Convert to string".to_string()),
+                score: 0.0,
             },
         ]
     }
@@ -968,36 +1255,42 @@ impl ReplSession {
                 label: ".length".to_string(),
                 kind: CompletionKind::Property,
                 documentation: Some("Array length".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".push".to_string(),
                 label: ".push()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Add to end".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".pop".to_string(),
                 label: ".pop()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Remove from end".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".map".to_string(),
                 label: ".map()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Map elements".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".filter".to_string(),
                 label: ".filter()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Filter elements".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".reduce".to_string(),
                 label: ".reduce()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Reduce to single value".to_string()),
+                score: 0.0,
             },
         ]
     }
@@ -1010,18 +1303,21 @@ impl ReplSession {
                 label: "Object.keys()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Get object keys".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".values".to_string(),
                 label: "Object.values()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Get object values".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".entries".to_string(),
                 label: "Object.entries()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Get key-value pairs".to_string()),
+                score: 0.0,
             },
         ]
     }
@@ -1034,30 +1330,35 @@ impl ReplSession {
                 label: ".size".to_string(),
                 kind: CompletionKind::Property,
                 documentation: Some("Map size".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".get".to_string(),
                 label: ".get()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Get value by key".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".set".to_string(),
                 label: ".set()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Set key-value pair".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".has".to_string(),
                 label: ".has()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Check if key exists".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".delete".to_string(),
                 label: ".delete()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Delete by key".to_string()),
+                score: 0.0,
             },
         ]
     }
@@ -1070,24 +1371,28 @@ impl ReplSession {
                 label: ".size".to_string(),
                 kind: CompletionKind::Property,
                 documentation: Some("Set size".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".add".to_string(),
                 label: ".add()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Add value".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".has".to_string(),
                 label: ".has()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Check if value exists".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".delete".to_string(),
                 label: ".delete()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Delete value".to_string()),
+                score: 0.0,
             },
         ]
     }
@@ -1100,24 +1405,28 @@ impl ReplSession {
                 label: ".getTime()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Get timestamp".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".toISOString".to_string(),
                 label: ".toISOString()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Get ISO string".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".getFullYear".to_string(),
                 label: ".getFullYear()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Get year".to_string()),
+                score: 0.0,
             },
             CompletionItem {
                 text: ".getMonth".to_string(),
                 label: ".getMonth()".to_string(),
                 kind: CompletionKind::Method,
                 documentation: Some("Get month (0-11)".to_string()),
+                score: 0.0,
             },
         ]
     }
@@ -1134,6 +1443,37 @@ impl ReplSession {
     }
 }
 
+/// Whether `c` can appear in a JavaScript identifier
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Names declared with `let`, `const`, `var`, `function` or `class` in `source`
+fn declared_identifiers(source: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut previous = "";
+    for word in source
+        .split(|c: char| !is_identifier_char(c))
+        .filter(|w| !w.is_empty())
+    {
+        if matches!(previous, "let" | "const" | "var" | "function" | "class")
+            && !names.iter().any(|n| n == word)
+        {
+            names.push(word.to_string());
+        }
+        previous = word;
+    }
+    names
+}
+
+/// Fraction of `candidate` covered by the typed `prefix`, in `0.0..=1.0`
+fn prefix_score(prefix: &str, candidate: &str) -> f32 {
+    if candidate.is_empty() {
+        return 0.0;
+    }
+    prefix.len().min(candidate.len()) as f32 / candidate.len() as f32
+}
+
 impl Default for ReplSession {
     fn default() -> Self {
         Self::new()
@@ -1159,7 +1499,7 @@ mod tests {
         let result = session.evaluate("42", &options);
         assert!(result.is_ok());
 
-        let result = result.unwrap();
+        let result = result.unwrap().evaluation().unwrap();
         assert_eq!(result.result.object_type, RemoteObjectType::Number);
         assert_eq!(result.result.value, Some(json!(42)));
     }
@@ -1172,7 +1512,7 @@ mod tests {
         let result = session.evaluate("\"hello\"", &options);
         assert!(result.is_ok());
 
-        let result = result.unwrap();
+        let result = result.unwrap().evaluation().unwrap();
         assert_eq!(result.result.object_type, RemoteObjectType::String);
         assert_eq!(result.result.value, Some(json!("hello")));
     }
@@ -1188,7 +1528,7 @@ mod tests {
         let result = session.evaluate(r#"{"a": 1, "b": 2}"#, &options);
         assert!(result.is_ok());
 
-        let result = result.unwrap();
+        let result = result.unwrap().evaluation().unwrap();
         assert_eq!(result.result.object_type, RemoteObjectType::Object);
         assert!(result.result.object_id.is_some());
         assert!(result.result.preview.is_some());
@@ -1205,7 +1545,7 @@ mod tests {
         let result = session.evaluate("[1, 2, 3]", &options);
         assert!(result.is_ok());
 
-        let result = result.unwrap();
+        let result = result.unwrap().evaluation().unwrap();
         assert_eq!(result.result.object_type, RemoteObjectType::Object);
         assert_eq!(result.result.subtype, Some(RemoteObjectSubtype::Array));
         assert!(result.result.preview.is_some());
@@ -1227,7 +1567,29 @@ mod tests {
         // Note: Real JS engine would handle {a: 1}, mock requires JSON
         let result = session.evaluate(r#"({"a": 1})"#, &options);
         assert!(result.is_ok());
-        assert!(result.unwrap().repl_mode);
+        assert!(result.unwrap().evaluation().unwrap().repl_mode);
+    }
+
+    #[test]
+    fn test_repl_evaluate_honors_timeout() {
+        let session = ReplSession::new();
+        let options: ReplEvaluateOptions =
+            serde_json::from_value(json!({ "timeout": 15.5 })).unwrap();
+        assert_eq!(options.timeout(), Some(Duration::from_micros(15_500)));
+
+        let started = Instant::now();
+        let result = session.evaluate("while (true) {}", &options);
+        assert!(matches!(
+            result,
+            Err(RuntimeDebuggerError::EvaluationTimedOut)
+        ));
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let negative = ReplEvaluateOptions {
+            timeout_ms: Some(-1.0),
+            ..Default::default()
+        };
+        assert_eq!(negative.timeout(), None);
     }
 
     #[test]
@@ -1256,6 +1618,106 @@ mod tests {
         assert_eq!(session.get_history(None).len(), 0);
     }
 
+    #[test]
+    fn test_search_history_newest_first() {
+        let session = ReplSession::new();
+        let options = ReplEvaluateOptions::default();
+
+        session.evaluate("[1, 2, 3]", &options).unwrap();
+        session.evaluate("42", &options).unwrap();
+        session.evaluate("[4, 5]", &options).unwrap();
+
+        let matches = session.search_history("[");
+        let expressions: Vec<&str> = matches.iter().map(|e| e.expression.as_str()).collect();
+        assert_eq!(expressions, vec!["[4, 5]", "[1, 2, 3]"]);
+        assert!(session.search_history("missing").is_empty());
+    }
+
+    #[test]
+    fn test_history_export_import_round_trip() {
+        let session = ReplSession::new();
+        let options = ReplEvaluateOptions::default();
+        session.evaluate("42", &options).unwrap();
+        session.evaluate("true", &options).unwrap();
+
+        let exported = session.export_history();
+        assert_eq!(exported.lines().count(), 2);
+
+        let restored = ReplSession::new();
+        assert_eq!(restored.import_history(&exported).unwrap(), 2);
+        let history = restored.get_history(None);
+        assert_eq!(history[0].expression, "true");
+        assert_eq!(history[1].expression, "42");
+
+        assert!(restored.import_history("not json").is_err());
+        assert_eq!(restored.get_history(None).len(), 2);
+    }
+
+    #[test]
+    fn test_history_limit_keeps_most_recent() {
+        let session = ReplSession::new();
+        let options = ReplEvaluateOptions::default();
+        for expression in ["1", "2", "3"] {
+            session.evaluate(expression, &options).unwrap();
+        }
+        let exported = session.export_history();
+
+        let limited = ReplSession::new();
+        limited.set_history_limit(2);
+        assert_eq!(limited.import_history(&exported).unwrap(), 3);
+        let expressions: Vec<String> = limited
+            .get_history(None)
+            .into_iter()
+            .map(|e| e.expression)
+            .collect();
+        assert_eq!(expressions, vec!["3", "2"]);
+
+        session.set_history_limit(1);
+        assert_eq!(session.get_history(None).len(), 1);
+        assert_eq!(session.history_limit(), 1);
+    }
+
+    #[test]
+    fn test_complete_ranks_locals_above_globals() {
+        let session = ReplSession::new();
+
+        let input = "const docData = load(); doc";
+        let completions = session.complete(input, input.len());
+        let texts: Vec<&str> = completions.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["docData", "document"]);
+        assert!(completions[0].score > completions[1].score);
+
+        let completions = session.complete("doc", 3);
+        assert_eq!(completions[0].text, "document");
+    }
+
+    #[test]
+    fn test_complete_session_declarations_rank_above_globals() {
+        let session = ReplSession::new();
+        session
+            .import_history(r#"{"expression":"let docs = []","timestamp":0,"success":true,"result_preview":null}"#)
+            .unwrap();
+
+        let completions = session.complete("doc", 3);
+        assert_eq!(completions[0].text, "docs");
+        assert_eq!(completions[1].text, "document");
+    }
+
+    #[test]
+    fn test_complete_properties_rank_by_prefix_length() {
+        let session = ReplSession::new();
+
+        let completions = session.complete("JSON.", 5);
+        let texts: Vec<&str> = completions.iter().map(|c| c.text.as_str()).collect();
+        // "JSON." covers more of "JSON.parse" than of "JSON.stringify"
+        assert_eq!(texts, vec!["JSON.parse", "JSON.stringify"]);
+
+        let completions = session.complete("x = JSON.st", 11);
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].text, "JSON.stringify");
+        assert!(session.complete("", 0).is_empty());
+    }
+
     #[test]
     fn test_repl_completions() {
         let session = ReplSession::new();
@@ -1285,8 +1747,8 @@ mod tests {
         let options = ReplEvaluateOptions::default();
 
         // Start incomplete expression
-        let result = session.evaluate("{", &options);
-        assert!(result.is_err());
+        let result = session.evaluate("{", &options).unwrap();
+        assert!(matches!(result, ReplEvaluateResult::NeedMoreInput));
         assert!(session.is_multiline());
 
         // Cancel multiline
@@ -1300,21 +1762,30 @@ mod tests {
         let options = ReplEvaluateOptions::default();
 
         // NaN
-        let result = session.evaluate("NaN", &options).unwrap();
-        assert_eq!(
-            result.result.unserializable_value,
-            Some("NaN".to_string())
-        );
+        let result = session
+            .evaluate("NaN", &options)
+            .unwrap()
+            .evaluation()
+            .unwrap();
+        assert_eq!(result.result.unserializable_value, Some("NaN".to_string()));
 
         // Infinity
-        let result = session.evaluate("Infinity", &options).unwrap();
+        let result = session
+            .evaluate("Infinity", &options)
+            .unwrap()
+            .evaluation()
+            .unwrap();
         assert_eq!(
             result.result.unserializable_value,
             Some("Infinity".to_string())
         );
 
         // undefined
-        let result = session.evaluate("undefined", &options).unwrap();
+        let result = session
+            .evaluate("undefined", &options)
+            .unwrap()
+            .evaluation()
+            .unwrap();
         assert_eq!(result.result.object_type, RemoteObjectType::Undefined);
     }
 
@@ -1326,7 +1797,11 @@ mod tests {
             ..Default::default()
         };
 
-        let result = session.evaluate("new Date()", &options).unwrap();
+        let result = session
+            .evaluate("new Date()", &options)
+            .unwrap()
+            .evaluation()
+            .unwrap();
         assert_eq!(result.result.subtype, Some(RemoteObjectSubtype::Date));
         assert!(result.result.preview.is_some());
     }
@@ -1339,7 +1814,11 @@ mod tests {
             ..Default::default()
         };
 
-        let result = session.evaluate("new Map()", &options).unwrap();
+        let result = session
+            .evaluate("new Map()", &options)
+            .unwrap()
+            .evaluation()
+            .unwrap();
         assert_eq!(result.result.subtype, Some(RemoteObjectSubtype::Map));
     }
 
@@ -1351,7 +1830,11 @@ mod tests {
             ..Default::default()
         };
 
-        let result = session.evaluate("new Set()", &options).unwrap();
+        let result = session
+            .evaluate("new Set()", &options)
+            .unwrap()
+            .evaluation()
+            .unwrap();
         assert_eq!(result.result.subtype, Some(RemoteObjectSubtype::Set));
     }
 
@@ -1360,7 +1843,11 @@ mod tests {
         let session = ReplSession::new();
         let options = ReplEvaluateOptions::default();
 
-        let result = session.evaluate("console.log", &options).unwrap();
+        let result = session
+            .evaluate("console.log", &options)
+            .unwrap()
+            .evaluation()
+            .unwrap();
         assert_eq!(result.result.object_type, RemoteObjectType::Function);
         assert!(result.result.object_id.is_some());
     }
@@ -1373,7 +1860,11 @@ mod tests {
             ..Default::default()
         };
 
-        let result = session.evaluate("[1, 2, 3]", &options).unwrap();
+        let result = session
+            .evaluate("[1, 2, 3]", &options)
+            .unwrap()
+            .evaluation()
+            .unwrap();
         assert!(result.completion_hints.is_some());
 
         let hints = result.completion_hints.unwrap();
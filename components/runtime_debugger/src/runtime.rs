@@ -9,33 +9,250 @@ use dashmap::DashMap;
 use parking_lot::RwLock;
 use protocol_handler::DomainHandler;
 use serde_json::{json, Value};
-use std::sync::atomic::AtomicU32;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, warn};
 use uuid::Uuid;
 
+use crate::cache::{CacheConfig, RemoteObjectCache};
+use crate::preview::PreviewGenerator;
+use crate::repl::ReplEvaluateOptions;
 use crate::{Result, RuntimeDebuggerError};
 
+/// Exception text reported for evaluations aborted by `terminateExecution`
+pub const EXECUTION_TERMINATED: &str = "Execution was terminated";
+
+/// Exception text reported for evaluations that exceed their timeout
+pub const EVALUATION_TIMED_OUT: &str = "Evaluation timed out";
+
+/// Execution context evaluations run in unless the client names another
+pub const DEFAULT_EXECUTION_CONTEXT_ID: u32 = 1;
+
+/// Upper bound on how long the mock evaluator runs an infinite loop
+pub(crate) const MOCK_LOOP_LIMIT: Duration = Duration::from_secs(10);
+
+/// Heap usage as reported by `Runtime.getHeapUsage`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeapUsage {
+    /// Bytes currently in use
+    pub used_size: u64,
+    /// Bytes reserved for the heap; never less than `used_size`
+    pub total_size: u64,
+}
+
+/// Source of heap usage figures for `Runtime.getHeapUsage`
+pub trait MemoryProvider: Send + Sync {
+    /// Report the current heap usage
+    fn heap_usage(&self) -> HeapUsage;
+}
+
+/// JavaScript engine hook used by `evaluate`
+///
+/// Without an evaluator the domain falls back to its built-in mock.
+pub trait ScriptEvaluator: Send + Sync {
+    /// Evaluate an expression to a JSON value
+    fn evaluate(&self, expression: &str) -> Result<Value>;
+
+    /// Ask a running evaluation to stop as soon as possible
+    fn terminate(&self) {}
+}
+
+/// Rewrites exceptions thrown by `evaluate` before they reach the client
+///
+/// A debugger bridge implements this to source-map exception locations and
+/// stack frames.
+pub trait ExceptionFormatter: Send + Sync {
+    /// Format raw exception details for display
+    fn format_exception(&self, raw: ExceptionDetails) -> ExceptionDetails;
+}
+
+/// Callback that clears the console buffer for `discardConsoleEntries`
+pub type ConsoleDiscardCallback = Arc<dyn Fn() + Send + Sync>;
+
+/// Running evaluations by id, each with its own cancel flag
+type RunningEvaluations = DashMap<u64, Arc<AtomicBool>>;
+
+/// Cancel flag of one running evaluation, unregistered on drop
+struct EvaluationHandle {
+    id: u64,
+    cancel: Arc<AtomicBool>,
+    running: Arc<RunningEvaluations>,
+}
+
+impl Drop for EvaluationHandle {
+    fn drop(&mut self) {
+        self.running.remove(&self.id);
+    }
+}
+
 /// Runtime domain handler for JavaScript execution
 pub struct RuntimeDomain {
-    /// Map of remote object IDs to their values
-    object_map: Arc<DashMap<RemoteObjectId, RemoteObject>>,
     /// Execution context counter (for future use)
     _context_counter: Arc<AtomicU32>,
     /// Enabled state
     enabled: Arc<RwLock<bool>>,
+    /// Cancel flags of in-flight evaluations, set by terminateExecution
+    running_evaluations: Arc<RunningEvaluations>,
+    /// Source of evaluation ids
+    next_evaluation_id: Arc<AtomicU64>,
+    /// Whether the target is paused at startup waiting for the debugger
+    waiting_for_debugger: Arc<AtomicBool>,
+    /// Identifier of the isolate backing this domain, fixed at creation
+    isolate_id: String,
+    /// Engine used for evaluation (opt-in)
+    evaluator: Arc<RwLock<Option<Arc<dyn ScriptEvaluator>>>>,
+    /// Memory provider backing getHeapUsage (opt-in)
+    memory_provider: Arc<RwLock<Option<Arc<dyn MemoryProvider>>>>,
+    /// Formatter applied to thrown exceptions (opt-in)
+    exception_formatter: Arc<RwLock<Option<Arc<dyn ExceptionFormatter>>>>,
+    /// Clears the console buffer on discardConsoleEntries (opt-in)
+    console_discard_callback: Arc<RwLock<Option<ConsoleDiscardCallback>>>,
+    /// Preview generator for remote objects
+    preview_generator: Arc<PreviewGenerator>,
+    /// Remote objects handed out to the client, tracked by group and
+    /// execution context
+    object_cache: Arc<RemoteObjectCache>,
 }
 
 impl RuntimeDomain {
     /// Create a new RuntimeDomain
     pub fn new() -> Self {
         Self {
-            object_map: Arc::new(DashMap::new()),
             _context_counter: Arc::new(AtomicU32::new(1)),
             enabled: Arc::new(RwLock::new(false)),
+            running_evaluations: Arc::new(DashMap::new()),
+            next_evaluation_id: Arc::new(AtomicU64::new(1)),
+            waiting_for_debugger: Arc::new(AtomicBool::new(false)),
+            isolate_id: Uuid::new_v4().simple().to_string(),
+            evaluator: Arc::new(RwLock::new(None)),
+            memory_provider: Arc::new(RwLock::new(None)),
+            exception_formatter: Arc::new(RwLock::new(None)),
+            console_discard_callback: Arc::new(RwLock::new(None)),
+            preview_generator: Arc::new(PreviewGenerator::new()),
+            // Objects stay valid until released, so only the size bound applies
+            object_cache: Arc::new(RemoteObjectCache::with_config(CacheConfig {
+                ttl: None,
+                ..CacheConfig::default()
+            })),
+        }
+    }
+
+    /// Get the store holding this domain's remote objects
+    pub fn object_cache(&self) -> &Arc<RemoteObjectCache> {
+        &self.object_cache
+    }
+
+    /// Drop every cached object belonging to a destroyed execution context
+    ///
+    /// Call on navigation. Returns the number of objects released.
+    pub fn release_execution_context(&self, execution_context_id: u32) -> usize {
+        debug!("Releasing execution context {}", execution_context_id);
+        self.object_cache.release_context(execution_context_id)
+    }
+
+    /// Register the callback that clears the console on discardConsoleEntries
+    pub fn set_console_discard_callback(&self, callback: ConsoleDiscardCallback) {
+        *self.console_discard_callback.write() = Some(callback);
+    }
+
+    /// Remove the console discard callback
+    pub fn clear_console_discard_callback(&self) {
+        *self.console_discard_callback.write() = None;
+    }
+
+    /// Discard collected console entries via the registered callback
+    ///
+    /// A no-op when no callback is registered.
+    pub fn discard_console_entries(&self) {
+        let callback = self.console_discard_callback.read().clone();
+        if let Some(callback) = callback {
+            callback();
         }
     }
 
+    /// Get the preview generator used for remote objects
+    pub fn preview_generator(&self) -> &PreviewGenerator {
+        &self.preview_generator
+    }
+
+    /// Register the engine used for evaluation
+    pub fn set_evaluator(&self, evaluator: Arc<dyn ScriptEvaluator>) {
+        *self.evaluator.write() = Some(evaluator);
+    }
+
+    /// Remove the evaluator, returning to the built-in mock
+    pub fn clear_evaluator(&self) {
+        *self.evaluator.write() = None;
+    }
+
+    /// Register the memory provider used by getHeapUsage
+    pub fn set_memory_provider(&self, provider: Arc<dyn MemoryProvider>) {
+        *self.memory_provider.write() = Some(provider);
+    }
+
+    /// Remove the memory provider, returning to the built-in estimate
+    pub fn clear_memory_provider(&self) {
+        *self.memory_provider.write() = None;
+    }
+
+    /// Register the formatter applied to exceptions thrown by evaluate
+    pub fn set_exception_formatter(&self, formatter: Arc<dyn ExceptionFormatter>) {
+        *self.exception_formatter.write() = Some(formatter);
+    }
+
+    /// Remove the exception formatter, reporting exceptions as thrown
+    pub fn clear_exception_formatter(&self) {
+        *self.exception_formatter.write() = None;
+    }
+
+    /// Format exception details with the registered formatter, if any
+    ///
+    /// Without a formatter the details are returned unchanged.
+    pub fn format_exception(&self, raw: ExceptionDetails) -> ExceptionDetails {
+        let formatter = self.exception_formatter.read().clone();
+        match formatter {
+            Some(formatter) => formatter.format_exception(raw),
+            None => raw,
+        }
+    }
+
+    /// Current heap usage
+    ///
+    /// Reads from the registered memory provider, clamping so `used_size`
+    /// never exceeds `total_size`. Without one, usage is estimated from the
+    /// cached remote objects.
+    pub fn get_heap_usage(&self) -> HeapUsage {
+        let provider = self.memory_provider.read().clone();
+        match provider {
+            Some(provider) => {
+                let usage = provider.heap_usage();
+                HeapUsage {
+                    used_size: usage.used_size,
+                    total_size: usage.total_size.max(usage.used_size),
+                }
+            }
+            None => {
+                let used_size = self
+                    .object_cache
+                    .entries()
+                    .iter()
+                    .map(|entry| serde_json::to_vec(&entry.object).map_or(0, |v| v.len() as u64))
+                    .sum();
+                HeapUsage {
+                    used_size,
+                    total_size: used_size,
+                }
+            }
+        }
+    }
+
+    /// Stable identifier of the isolate backing this domain
+    pub fn isolate_id(&self) -> &str {
+        &self.isolate_id
+    }
+
     /// Enable the Runtime domain
     pub fn enable(&self) {
         *self.enabled.write() = true;
@@ -45,7 +262,7 @@ impl RuntimeDomain {
     /// Disable the Runtime domain
     pub fn disable(&self) {
         *self.enabled.write() = false;
-        self.object_map.clear();
+        self.object_cache.clear();
         debug!("Runtime domain disabled");
     }
 
@@ -54,20 +271,203 @@ impl RuntimeDomain {
         *self.enabled.read()
     }
 
-    /// Evaluate JavaScript expression
+    /// Evaluate JavaScript expression on the calling thread
+    ///
+    /// Blocks until the evaluation finishes; async callers should use
+    /// `evaluate_with_timeout`. If `terminate_execution` is called while the
+    /// evaluation runs, it returns early with `EXECUTION_TERMINATED`
+    /// exception details.
     pub fn evaluate(&self, expression: &str) -> Result<EvaluateResponse> {
         debug!("Evaluating expression: {}", expression);
 
-        // Mock JavaScript evaluation for now
-        let result = self.mock_evaluate(expression)?;
-        let remote_object = self.create_remote_object(result);
+        let evaluation = self.begin_evaluation();
+        let evaluator = self.evaluator.read().clone();
+        let result = Self::run_evaluation(evaluator.as_deref(), expression, &evaluation.cancel);
+        self.finish_evaluation(result, DEFAULT_EXECUTION_CONTEXT_ID, None)
+    }
+
+    /// Evaluate JavaScript expression, giving up after `timeout`
+    ///
+    /// On timeout the evaluator is terminated and the response carries
+    /// `EVALUATION_TIMED_OUT` exception details.
+    pub async fn evaluate_with_timeout(
+        &self,
+        expression: &str,
+        timeout: Option<Duration>,
+    ) -> Result<EvaluateResponse> {
+        self.evaluate_in_context(expression, DEFAULT_EXECUTION_CONTEXT_ID, None, timeout)
+            .await
+    }
+
+    /// Evaluate JavaScript expression in an execution context
+    ///
+    /// The evaluation runs on the blocking pool so the caller's runtime stays
+    /// free to handle `terminateExecution`. Objects in the result belong to
+    /// `execution_context_id` and, if given, `object_group`, and are released
+    /// along with either.
+    pub async fn evaluate_in_context(
+        &self,
+        expression: &str,
+        execution_context_id: u32,
+        object_group: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<EvaluateResponse> {
+        debug!(
+            "Evaluating expression in context {}: {}",
+            execution_context_id, expression
+        );
+
+        // The task owns the handle, so the evaluation stays registered until
+        // it actually stops, even after a timeout abandons it
+        let evaluation = self.begin_evaluation();
+        let cancel = Arc::clone(&evaluation.cancel);
+        let evaluator = self.evaluator.read().clone();
+        let expression = expression.to_string();
+        let task = tokio::task::spawn_blocking(move || {
+            Self::run_evaluation(evaluator.as_deref(), &expression, &evaluation.cancel)
+        });
+
+        let joined = match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, task).await {
+                Ok(joined) => joined,
+                Err(_) => {
+                    warn!("Evaluation exceeded {:?}, terminating", timeout);
+                    // Only this evaluation's flag; the abandoned task sees it
+                    cancel.store(true, Ordering::SeqCst);
+                    let evaluator = self.evaluator.read().clone();
+                    if let Some(evaluator) = evaluator {
+                        evaluator.terminate();
+                    }
+                    return Ok(Self::aborted_response(EVALUATION_TIMED_OUT));
+                }
+            },
+            None => task.await,
+        };
+        let result = joined.map_err(|e| {
+            RuntimeDebuggerError::EvaluationError(format!(
+                "Evaluator stopped without a result: {}",
+                e
+            ))
+        })?;
+
+        self.finish_evaluation(result, execution_context_id, object_group.map(String::from))
+    }
+
+    /// Register a cancel flag for a new evaluation
+    fn begin_evaluation(&self) -> EvaluationHandle {
+        let id = self.next_evaluation_id.fetch_add(1, Ordering::SeqCst);
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.running_evaluations.insert(id, Arc::clone(&cancel));
+        EvaluationHandle {
+            id,
+            cancel,
+            running: Arc::clone(&self.running_evaluations),
+        }
+    }
+
+    /// Run an expression on the evaluator, or the mock without one
+    fn run_evaluation(
+        evaluator: Option<&dyn ScriptEvaluator>,
+        expression: &str,
+        cancel: &AtomicBool,
+    ) -> Result<Value> {
+        match evaluator {
+            Some(evaluator) => evaluator.evaluate(expression),
+            None => Self::mock_evaluate(expression, cancel),
+        }
+    }
+
+    /// Turn an evaluation result into the response sent to the client
+    fn finish_evaluation(
+        &self,
+        result: Result<Value>,
+        execution_context_id: u32,
+        object_group: Option<String>,
+    ) -> Result<EvaluateResponse> {
+        let result = match result {
+            Err(RuntimeDebuggerError::ExecutionTerminated) => {
+                return Ok(Self::aborted_response(EXECUTION_TERMINATED));
+            }
+            Err(RuntimeDebuggerError::EvaluationTimedOut) => {
+                return Ok(Self::aborted_response(EVALUATION_TIMED_OUT));
+            }
+            Err(RuntimeDebuggerError::Exception(details)) => {
+                return Ok(Self::exception_response(self.format_exception(*details)));
+            }
+            other => other?,
+        };
 
         Ok(EvaluateResponse {
-            result: remote_object,
+            result: self.create_remote_object(result, execution_context_id, object_group),
             exception_details: None,
         })
     }
 
+    /// Abort every evaluation currently running, if any
+    pub fn terminate_execution(&self) {
+        debug!("Terminating execution");
+        for evaluation in self.running_evaluations.iter() {
+            evaluation.value().store(true, Ordering::SeqCst);
+        }
+        let evaluator = self.evaluator.read().clone();
+        if let Some(evaluator) = evaluator {
+            evaluator.terminate();
+        }
+    }
+
+    /// Mark the target as paused at startup until the debugger attaches
+    pub fn wait_for_debugger(&self) {
+        debug!("Waiting for debugger");
+        self.waiting_for_debugger.store(true, Ordering::SeqCst);
+    }
+
+    /// Check if the target is waiting for the debugger
+    pub fn is_waiting_for_debugger(&self) -> bool {
+        self.waiting_for_debugger.load(Ordering::SeqCst)
+    }
+
+    /// Release a target waiting for the debugger
+    ///
+    /// Returns `true` if the target was waiting; otherwise this is a no-op.
+    pub fn run_if_waiting_for_debugger(&self) -> bool {
+        let was_waiting = self.waiting_for_debugger.swap(false, Ordering::SeqCst);
+        if was_waiting {
+            debug!("Released target waiting for debugger");
+        }
+        was_waiting
+    }
+
+    /// Response for an evaluation that was terminated or timed out
+    fn aborted_response(text: &str) -> EvaluateResponse {
+        Self::exception_response(ExceptionDetails {
+            exception_id: 0,
+            text: text.to_string(),
+            line_number: 0,
+            column_number: 0,
+            script_id: None,
+            url: None,
+            stack_trace: None,
+            exception: None,
+        })
+    }
+
+    /// Response for an evaluation that threw; the result is the thrown value
+    fn exception_response(details: ExceptionDetails) -> EvaluateResponse {
+        EvaluateResponse {
+            result: details.exception.clone().unwrap_or(RemoteObject {
+                object_type: RemoteObjectType::Undefined,
+                subtype: None,
+                class_name: None,
+                value: None,
+                unserializable_value: None,
+                description: None,
+                object_id: None,
+                preview: None,
+            }),
+            exception_details: Some(details),
+        }
+    }
+
     /// Call function on remote object
     pub fn call_function_on(
         &self,
@@ -80,34 +480,41 @@ impl RuntimeDomain {
         );
 
         // Get the object
-        let _obj = self
-            .object_map
+        let target = self
+            .object_cache
             .get(object_id)
             .ok_or_else(|| RuntimeDebuggerError::ObjectNotFound(object_id.0.clone()))?;
 
-        // Mock function call for now
-        let result = self.mock_evaluate(function_declaration)?;
-        Ok(self.create_remote_object(result))
+        // Mock function call for now; the result lives alongside its target
+        let evaluation = self.begin_evaluation();
+        let result = Self::mock_evaluate(function_declaration, &evaluation.cancel)?;
+        Ok(self.create_remote_object(
+            result,
+            target
+                .execution_context_id
+                .unwrap_or(DEFAULT_EXECUTION_CONTEXT_ID),
+            target.group,
+        ))
     }
 
     /// Get properties of remote object
     pub fn get_properties(&self, object_id: &RemoteObjectId) -> Result<Vec<PropertyDescriptor>> {
         debug!("Getting properties for object {:?}", object_id);
 
-        let obj = self
-            .object_map
+        let entry = self
+            .object_cache
             .get(object_id)
             .ok_or_else(|| RuntimeDebuggerError::ObjectNotFound(object_id.0.clone()))?;
 
         // For mock implementation, return basic properties
-        Ok(self.mock_get_properties(&obj))
+        Ok(self.mock_get_properties(&entry.object))
     }
 
     /// Release remote object
     pub fn release_object(&self, object_id: &RemoteObjectId) -> Result<()> {
         debug!("Releasing object {:?}", object_id);
 
-        self.object_map
+        self.object_cache
             .remove(object_id)
             .ok_or_else(|| RuntimeDebuggerError::ObjectNotFound(object_id.0.clone()))?;
 
@@ -117,11 +524,19 @@ impl RuntimeDomain {
     /// Release all remote objects
     pub fn release_all_objects(&self) {
         debug!("Releasing all remote objects");
-        self.object_map.clear();
+        self.object_cache.clear();
     }
 
     /// Create a remote object from a JSON value
-    fn create_remote_object(&self, value: Value) -> RemoteObject {
+    ///
+    /// Objects and arrays get an object ID and are stored under the given
+    /// execution context and group.
+    fn create_remote_object(
+        &self,
+        value: Value,
+        execution_context_id: u32,
+        object_group: Option<String>,
+    ) -> RemoteObject {
         match value {
             Value::Null => RemoteObject {
                 object_type: RemoteObjectType::Object,
@@ -188,14 +603,19 @@ impl RuntimeDomain {
                 };
 
                 // Store the object for later retrieval
-                self.object_map.insert(object_id, remote_obj.clone());
+                self.object_cache.insert_in_context(
+                    remote_obj.clone(),
+                    value,
+                    object_group,
+                    execution_context_id,
+                );
                 remote_obj
             }
         }
     }
 
     /// Mock JavaScript evaluation (to be replaced with real JS engine)
-    fn mock_evaluate(&self, expression: &str) -> Result<Value> {
+    fn mock_evaluate(expression: &str, cancel: &AtomicBool) -> Result<Value> {
         let expr = expression.trim();
 
         // Try to parse as JSON first (handles objects and arrays)
@@ -212,6 +632,7 @@ impl RuntimeDomain {
             s if s.starts_with('"') && s.ends_with('"') => Ok(json!(s[1..s.len() - 1].to_string())),
             "1 + 1" => Ok(json!(2)),
             "2 * 3" => Ok(json!(6)),
+            "while (true) {}" | "for (;;) {}" => Self::mock_infinite_loop(cancel),
             _ => Err(RuntimeDebuggerError::EvaluationError(format!(
                 "Mock evaluator cannot handle: {}",
                 expr
@@ -219,6 +640,20 @@ impl RuntimeDomain {
         }
     }
 
+    /// Run until terminated, as an infinite loop would
+    fn mock_infinite_loop(cancel: &AtomicBool) -> Result<Value> {
+        let started = Instant::now();
+        while started.elapsed() < MOCK_LOOP_LIMIT {
+            if cancel.load(Ordering::SeqCst) {
+                return Err(RuntimeDebuggerError::ExecutionTerminated);
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        Err(RuntimeDebuggerError::EvaluationError(
+            "Mock evaluator loop limit exceeded".to_string(),
+        ))
+    }
+
     /// Mock property descriptor generation
     fn mock_get_properties(&self, _obj: &RemoteObject) -> Vec<PropertyDescriptor> {
         // For testing, return empty vector
@@ -239,6 +674,24 @@ impl DomainHandler for RuntimeDomain {
         "Runtime"
     }
 
+    fn supported_methods(&self) -> Vec<&'static str> {
+        vec![
+            "enable",
+            "disable",
+            "evaluate",
+            "callFunctionOn",
+            "getProperties",
+            "releaseObject",
+            "terminateExecution",
+            "runIfWaitingForDebugger",
+            "getHeapUsage",
+            "getIsolateId",
+            "releaseObjectGroup",
+            "discardConsoleEntries",
+            "setCustomObjectFormatterEnabled",
+        ]
+    }
+
     async fn handle_method(
         &self,
         method: &str,
@@ -259,9 +712,17 @@ impl DomainHandler for RuntimeDomain {
                     .get("expression")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| CdpError::invalid_params("Missing expression"))?;
+                let options: ReplEvaluateOptions = serde_json::from_value(params.clone())
+                    .map_err(|e| CdpError::invalid_params(e.to_string()))?;
+                let context_id = params
+                    .get("contextId")
+                    .and_then(|v| v.as_u64())
+                    .map_or(DEFAULT_EXECUTION_CONTEXT_ID, |id| id as u32);
+                let object_group = params.get("objectGroup").and_then(|v| v.as_str());
 
                 let response = self
-                    .evaluate(expression)
+                    .evaluate_in_context(expression, context_id, object_group, options.timeout())
+                    .await
                     .map_err(|e| CdpError::internal_error(e.to_string()))?;
 
                 Ok(serde_json::to_value(response)
@@ -314,9 +775,39 @@ impl DomainHandler for RuntimeDomain {
 
                 Ok(json!({}))
             }
+            "terminateExecution" => {
+                self.terminate_execution();
+                Ok(json!({}))
+            }
+            "runIfWaitingForDebugger" => {
+                self.run_if_waiting_for_debugger();
+                Ok(json!({}))
+            }
+            "getHeapUsage" => Ok(serde_json::to_value(self.get_heap_usage())
+                .map_err(|e| CdpError::internal_error(e.to_string()))?),
+            "getIsolateId" => Ok(json!({ "id": self.isolate_id() })),
             "releaseObjectGroup" => {
-                // For mock implementation, just release all objects
-                self.release_all_objects();
+                if let Some(group) = params
+                    .as_ref()
+                    .and_then(|p| p.get("objectGroup"))
+                    .and_then(|v| v.as_str())
+                {
+                    self.object_cache.release_group(group);
+                }
+                Ok(json!({}))
+            }
+            "discardConsoleEntries" => {
+                self.discard_console_entries();
+                Ok(json!({}))
+            }
+            "setCustomObjectFormatterEnabled" => {
+                let enabled = params
+                    .as_ref()
+                    .and_then(|p| p.get("enabled"))
+                    .and_then(|v| v.as_bool())
+                    .ok_or_else(|| CdpError::invalid_params("Missing enabled"))?;
+                self.preview_generator
+                    .set_custom_formatters_enabled(enabled);
                 Ok(json!({}))
             }
             _ => {
@@ -342,7 +833,45 @@ mod tests {
     fn test_runtime_domain_new() {
         let runtime = RuntimeDomain::new();
         assert!(!runtime.is_enabled());
-        assert_eq!(runtime.object_map.len(), 0);
+        assert_eq!(runtime.object_cache.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_heap_usage() {
+        let runtime = RuntimeDomain::new();
+        runtime.enable();
+        runtime.evaluate("[1, 2, 3]").unwrap();
+
+        let result = runtime.handle_method("getHeapUsage", None).await.unwrap();
+        let used = result["usedSize"].as_u64().unwrap();
+        let total = result["totalSize"].as_u64().unwrap();
+        assert!(used <= total);
+
+        struct FixedProvider;
+        impl MemoryProvider for FixedProvider {
+            fn heap_usage(&self) -> HeapUsage {
+                HeapUsage {
+                    used_size: 2048,
+                    total_size: 1024,
+                }
+            }
+        }
+        runtime.set_memory_provider(Arc::new(FixedProvider));
+        let usage = runtime.get_heap_usage();
+        assert_eq!(usage.used_size, 2048);
+        assert_eq!(usage.total_size, 2048);
+    }
+
+    #[tokio::test]
+    async fn test_get_isolate_id_is_stable() {
+        let runtime = RuntimeDomain::new();
+        let first = runtime.handle_method("getIsolateId", None).await.unwrap();
+        let second = runtime.handle_method("getIsolateId", None).await.unwrap();
+
+        let id = first["id"].as_str().unwrap();
+        assert!(!id.is_empty());
+        assert_eq!(first, second);
+        assert_ne!(id, RuntimeDomain::new().isolate_id());
     }
 
     #[test]
@@ -479,12 +1008,45 @@ mod tests {
         let response = runtime.evaluate(r#"{"a": 1}"#).unwrap();
         let object_id = response.result.object_id.clone().unwrap();
 
-        assert_eq!(runtime.object_map.len(), 1);
+        assert_eq!(runtime.object_cache.len(), 1);
 
         // Release it
         let result = runtime.release_object(&object_id);
         assert!(result.is_ok());
-        assert_eq!(runtime.object_map.len(), 0);
+        assert_eq!(runtime.object_cache.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_release_execution_context() {
+        let runtime = RuntimeDomain::new();
+        let evaluate = |params: Value| runtime.handle_method("evaluate", Some(params));
+        evaluate(json!({ "expression": "{}" })).await.unwrap();
+        evaluate(json!({ "expression": "[]", "contextId": 2, "objectGroup": "console" }))
+            .await
+            .unwrap();
+        let kept = evaluate(json!({ "expression": "{}", "contextId": 2 }))
+            .await
+            .unwrap();
+
+        let cache = runtime.object_cache();
+        assert_eq!(cache.len(), 3);
+        assert_eq!(
+            runtime.release_execution_context(DEFAULT_EXECUTION_CONTEXT_ID),
+            1
+        );
+        assert_eq!(cache.len(), 2);
+
+        runtime
+            .handle_method(
+                "releaseObjectGroup",
+                Some(json!({ "objectGroup": "console" })),
+            )
+            .await
+            .unwrap();
+        let kept_id = RemoteObjectId(kept["result"]["objectId"].as_str().unwrap().to_string());
+        assert!(runtime.get_properties(&kept_id).is_ok());
+        assert_eq!(runtime.release_execution_context(2), 1);
+        assert!(cache.is_empty());
     }
 
     #[test]
@@ -497,11 +1059,11 @@ mod tests {
         let _ = runtime.evaluate("[1, 2, 3]").unwrap();
         let _ = runtime.evaluate(r#"{"b": 2}"#).unwrap();
 
-        assert_eq!(runtime.object_map.len(), 3);
+        assert_eq!(runtime.object_cache.len(), 3);
 
         // Release all
         runtime.release_all_objects();
-        assert_eq!(runtime.object_map.len(), 0);
+        assert_eq!(runtime.object_cache.len(), 0);
     }
 
     #[tokio::test]
@@ -545,6 +1107,64 @@ mod tests {
         assert_eq!(response.result.object_type, RemoteObjectType::Number);
     }
 
+    /// Evaluator that throws an error with the given text
+    struct ThrowingEvaluator(&'static str);
+
+    impl ScriptEvaluator for ThrowingEvaluator {
+        fn evaluate(&self, _expression: &str) -> Result<Value> {
+            Err(RuntimeDebuggerError::Exception(Box::new(
+                ExceptionDetails {
+                    exception_id: 1,
+                    text: self.0.to_string(),
+                    line_number: 3,
+                    column_number: 7,
+                    script_id: Some("42".to_string()),
+                    url: Some("file:///app.js".to_string()),
+                    stack_trace: None,
+                    exception: None,
+                },
+            )))
+        }
+    }
+
+    /// Formatter that rewrites the reported url
+    struct RenamingFormatter;
+
+    impl ExceptionFormatter for RenamingFormatter {
+        fn format_exception(&self, raw: ExceptionDetails) -> ExceptionDetails {
+            ExceptionDetails {
+                url: Some("src/app.ts".to_string()),
+                ..raw
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_exception_goes_through_formatter() {
+        let runtime = RuntimeDomain::new();
+        runtime.enable();
+        runtime.set_evaluator(Arc::new(ThrowingEvaluator("Uncaught Error: boom")));
+
+        let evaluate = || runtime.handle_method("evaluate", Some(json!({ "expression": "f()" })));
+        let response: EvaluateResponse = serde_json::from_value(evaluate().await.unwrap()).unwrap();
+        let details = response.exception_details.unwrap();
+        assert_eq!(details.text, "Uncaught Error: boom");
+        assert_eq!(details.url.as_deref(), Some("file:///app.js"));
+
+        runtime.set_exception_formatter(Arc::new(RenamingFormatter));
+        let response: EvaluateResponse = serde_json::from_value(evaluate().await.unwrap()).unwrap();
+        let details = response.exception_details.unwrap();
+        assert_eq!(details.url.as_deref(), Some("src/app.ts"));
+        assert_eq!(details.line_number, 3);
+
+        runtime.clear_exception_formatter();
+        let response: EvaluateResponse = serde_json::from_value(evaluate().await.unwrap()).unwrap();
+        assert_eq!(
+            response.exception_details.unwrap().url.as_deref(),
+            Some("file:///app.js")
+        );
+    }
+
     #[tokio::test]
     async fn test_domain_handler_unknown_method() {
         let runtime = RuntimeDomain::new();
@@ -552,4 +1172,231 @@ mod tests {
         let result = runtime.handle_method("unknownMethod", None).await;
         assert!(result.is_err());
     }
+
+    /// Evaluator that sleeps for `delay` unless terminated first
+    struct SleepingEvaluator {
+        delay: Duration,
+        terminated: AtomicBool,
+    }
+
+    impl SleepingEvaluator {
+        fn new(delay: Duration) -> Arc<Self> {
+            Arc::new(Self {
+                delay,
+                terminated: AtomicBool::new(false),
+            })
+        }
+    }
+
+    impl ScriptEvaluator for SleepingEvaluator {
+        fn evaluate(&self, _expression: &str) -> Result<Value> {
+            let started = Instant::now();
+            while started.elapsed() < self.delay {
+                if self.terminated.load(Ordering::SeqCst) {
+                    return Err(RuntimeDebuggerError::ExecutionTerminated);
+                }
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            Ok(json!("done"))
+        }
+
+        fn terminate(&self) {
+            self.terminated.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_within_timeout_succeeds() {
+        let runtime = RuntimeDomain::new();
+        let evaluator = SleepingEvaluator::new(Duration::from_millis(10));
+        runtime.set_evaluator(evaluator.clone());
+
+        let response = runtime
+            .evaluate_with_timeout("work()", Some(Duration::from_secs(5)))
+            .await
+            .unwrap();
+        assert!(response.exception_details.is_none());
+        assert_eq!(response.result.value, Some(json!("done")));
+        assert!(!evaluator.terminated.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_over_timeout_terminates_evaluator() {
+        let runtime = RuntimeDomain::new();
+        let evaluator = SleepingEvaluator::new(Duration::from_secs(10));
+        runtime.set_evaluator(evaluator.clone());
+
+        let started = Instant::now();
+        let response = runtime
+            .evaluate_with_timeout("work()", Some(Duration::from_millis(20)))
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(response.result.object_type, RemoteObjectType::Undefined);
+        assert_eq!(
+            response.exception_details.unwrap().text,
+            EVALUATION_TIMED_OUT
+        );
+        assert!(evaluator.terminated.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_domain_handler_evaluate_timeout() {
+        let runtime = RuntimeDomain::new();
+
+        let params = json!({ "expression": "while (true) {}", "timeout": 20.5 });
+        let result = runtime
+            .handle_method("evaluate", Some(params))
+            .await
+            .unwrap();
+        assert_eq!(result["exceptionDetails"]["text"], EVALUATION_TIMED_OUT);
+
+        let params = json!({ "expression": "42", "timeout": 1000 });
+        let result = runtime
+            .handle_method("evaluate", Some(params))
+            .await
+            .unwrap();
+        assert_eq!(result["result"]["value"], 42);
+        assert!(result.get("exceptionDetails").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_terminate_execution_reaches_evaluate_on_current_thread() {
+        let runtime = Arc::new(RuntimeDomain::new());
+
+        let evaluating = Arc::clone(&runtime);
+        let evaluation = tokio::spawn(async move {
+            let params = json!({ "expression": "while (true) {}" });
+            evaluating.handle_method("evaluate", Some(params)).await
+        });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let started = Instant::now();
+        runtime
+            .handle_method("terminateExecution", None)
+            .await
+            .unwrap();
+
+        let result = evaluation.await.unwrap().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(result["exceptionDetails"]["text"], EXECUTION_TERMINATED);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_evaluation_keeps_its_own_cancel_flag() {
+        let runtime = RuntimeDomain::new();
+
+        let response = runtime
+            .evaluate_with_timeout("while (true) {}", Some(Duration::from_millis(10)))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.exception_details.unwrap().text,
+            EVALUATION_TIMED_OUT
+        );
+
+        // The abandoned loop stops on its own flag and unregisters
+        let started = Instant::now();
+        while !runtime.running_evaluations.is_empty() {
+            assert!(started.elapsed() < Duration::from_secs(1));
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        assert!(runtime.evaluate("42").unwrap().exception_details.is_none());
+    }
+
+    #[test]
+    fn test_terminate_execution_aborts_long_evaluation() {
+        let runtime = Arc::new(RuntimeDomain::new());
+        runtime.enable();
+
+        let evaluating = Arc::clone(&runtime);
+        let handle = std::thread::spawn(move || evaluating.evaluate("while (true) {}"));
+
+        std::thread::sleep(Duration::from_millis(20));
+        let started = Instant::now();
+        runtime.terminate_execution();
+
+        let response = handle.join().unwrap().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(response.result.object_type, RemoteObjectType::Undefined);
+        assert_eq!(response.exception_details.unwrap().text, EXECUTION_TERMINATED);
+
+        // A stale terminate request doesn't affect the next evaluation
+        runtime.terminate_execution();
+        let response = runtime.evaluate("42").unwrap();
+        assert!(response.exception_details.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_if_waiting_for_debugger() {
+        let runtime = RuntimeDomain::new();
+
+        // No-op when not waiting
+        assert!(!runtime.run_if_waiting_for_debugger());
+        assert!(runtime
+            .handle_method("runIfWaitingForDebugger", None)
+            .await
+            .is_ok());
+
+        runtime.wait_for_debugger();
+        assert!(runtime.is_waiting_for_debugger());
+
+        runtime
+            .handle_method("runIfWaitingForDebugger", None)
+            .await
+            .unwrap();
+        assert!(!runtime.is_waiting_for_debugger());
+    }
+
+    #[tokio::test]
+    async fn test_set_custom_object_formatter_enabled() {
+        let runtime = RuntimeDomain::new();
+        assert!(!runtime.preview_generator().custom_formatters_enabled());
+
+        runtime
+            .handle_method(
+                "setCustomObjectFormatterEnabled",
+                Some(json!({ "enabled": true })),
+            )
+            .await
+            .unwrap();
+        assert!(runtime.preview_generator().custom_formatters_enabled());
+
+        runtime
+            .handle_method(
+                "setCustomObjectFormatterEnabled",
+                Some(json!({ "enabled": false })),
+            )
+            .await
+            .unwrap();
+        assert!(!runtime.preview_generator().custom_formatters_enabled());
+
+        let result = runtime
+            .handle_method("setCustomObjectFormatterEnabled", None)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_discard_console_entries_invokes_callback() {
+        let runtime = RuntimeDomain::new();
+
+        // No callback registered: still succeeds
+        runtime
+            .handle_method("discardConsoleEntries", None)
+            .await
+            .unwrap();
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&calls);
+        runtime.set_console_discard_callback(Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        runtime
+            .handle_method("discardConsoleEntries", None)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
@@ -3,7 +3,7 @@
 //! Tests the protocol handler routing and domain registration functionality.
 
 use profiler_domains::{HeapProfilerDomain, ProfilerDomain, TimelineDomain};
-use protocol_handler::{DomainHandler, ProtocolHandler};
+use protocol_handler::ProtocolHandler;
 use serde_json::{json, Value};
 use std::sync::Arc;
 